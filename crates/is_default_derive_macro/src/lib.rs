@@ -6,9 +6,47 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parse_macro_input};
+use syn::{Data, DeriveInput, Field, Fields, parse_macro_input};
 
-#[proc_macro_derive(IsDefault)]
+/// Derive macro for `spk_schema_foundation::IsDefault`.
+///
+/// A struct is considered default when all of its fields are. Fields
+/// that should not be considered, such as a cache, can be marked with
+/// `#[is_default(skip)]` and do not need to implement `IsDefault`.
+///
+/// # Example
+///
+/// ```
+/// use spk_schema_foundation::IsDefault;
+///
+/// struct Level(u32);
+///
+/// impl IsDefault for Level {
+///     fn is_default(&self) -> bool {
+///         self.0 == 0
+///     }
+/// }
+///
+/// #[derive(is_default_derive_macro::IsDefault)]
+/// struct Settings {
+///     level: Level,
+///     #[is_default(skip)]
+///     cache: Vec<String>,
+/// }
+///
+/// let settings = Settings {
+///     level: Level(0),
+///     cache: vec!["cached".to_string()],
+/// };
+/// assert!(settings.is_default());
+///
+/// let settings = Settings {
+///     level: Level(1),
+///     cache: Vec::new(),
+/// };
+/// assert!(!settings.is_default());
+/// ```
+#[proc_macro_derive(IsDefault, attributes(is_default))]
 pub fn derive_is_default(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
@@ -23,8 +61,17 @@ pub fn derive_is_default(input: TokenStream) -> TokenStream {
         Data::Struct(data_struct) => {
             match data_struct.fields {
                 Fields::Named(fields_named) => {
+                    let mut checked_fields = Vec::new();
+                    for field in fields_named.named.iter() {
+                        match is_skipped(field) {
+                            Ok(true) => continue,
+                            Ok(false) => checked_fields.push(field),
+                            Err(err) => return err.to_compile_error().into(),
+                        }
+                    }
+
                     // Generate code that calls `IsDefault::is_default` on each field
-                    let field_checks = fields_named.named.iter().map(|field| {
+                    let field_checks = checked_fields.into_iter().map(|field| {
                         let field_name = &field.ident;
                         quote! {
                             spk_schema_foundation::IsDefault::is_default(&self.#field_name)
@@ -72,3 +119,21 @@ pub fn derive_is_default(input: TokenStream) -> TokenStream {
     // Return the generated code
     TokenStream::from(expanded)
 }
+
+/// True if the field has an `#[is_default(skip)]` attribute.
+fn is_skipped(field: &Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("is_default") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                return Ok(());
+            }
+            Err(meta.error("unsupported is_default attribute, expected `skip`"))
+        })?;
+    }
+    Ok(skip)
+}
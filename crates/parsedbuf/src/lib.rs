@@ -28,10 +28,57 @@ pub use paste;
 /// assert!(matches!(Integer::new("blue"), Err(_)));
 /// assert!(matches!(Integer::new("25"), Ok(_)));
 /// assert_eq!(Integer::new("25").unwrap(), "25");
+///
+/// // well-known values can be wrapped without allocating, and always
+/// // refer to the given static string
+/// static ANSWER: &str = "42";
+/// for _ in 0..1000 {
+///     let answer = unsafe { Integer::from_static(ANSWER) };
+///     assert!(std::ptr::eq(answer.as_str(), ANSWER));
+/// }
+/// ```
+///
+/// Types that users may type with inconsistent casing can instead be
+/// generated with a leading `case_insensitive` flag. These compare equal
+/// to any [`str`] that matches when ignoring ascii case, and have an
+/// `eq_ignore_ascii_case` method for comparing with other strings.
+///
+/// Only comparisons with strings ignore case. Comparisons between two
+/// instances, along with [`Ord`] and [`Hash`], remain case-sensitive so
+/// that these types behave correctly as keys in maps and sets. Two
+/// values that compare equal to the same string may still be different
+/// keys.
+///
+/// ```
+/// use std::hash::{BuildHasher, RandomState};
+///
+/// #[derive(Debug)]
+/// pub struct ParseError(&'static str);
+///
+/// parsedbuf::parsed!(case_insensitive, Word, ParseError);
+///
+/// impl Word {
+///     fn validate(candidate: &str) -> Result<(), ParseError> {
+///         if !candidate.chars().all(|c| c.is_ascii_alphabetic()) {
+///             Err(ParseError("expected all letters"))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// let upper = Word::new("ABC").unwrap();
+/// let lower = Word::new("abc").unwrap();
+/// assert!(upper.eq_ignore_ascii_case("abc"));
+/// assert_eq!(upper, "abc");
+/// assert_ne!(upper, lower);
+///
+/// let state = RandomState::new();
+/// assert_ne!(state.hash_one(upper), state.hash_one(lower));
 /// ```
 #[macro_export]
 macro_rules! parsed {
-    ($type_name:ident, $owned_type_name:ident, $parse_error:ty, $what:tt) => {
+    (@common $type_name:ident, $owned_type_name:ident, $parse_error:ty, $what:tt) => {
         $crate::paste::paste! {
             #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
             #[doc = "A borrowed, immutable, and validated " $what " string"]
@@ -90,6 +137,28 @@ macro_rules! parsed {
                 }
             }
 
+            $crate::paste::paste! {
+                #[doc = "Wrap a static str as a `" $type_name "` without any allocation"]
+                #[doc = ""]
+                #[doc = "This is intended for well-known values that are used often,"]
+                #[doc = "and always refers to the given static string."]
+                #[doc = ""]
+                #[doc = "# Safety:"]
+                #[doc = ""]
+                #[doc = "The argument is only validated in debug builds, where this"]
+                #[doc = "will panic if it is not valid. Release builds skip validation"]
+                #[doc = "so the argument must be known to be valid."]
+                pub unsafe fn from_static(inner: &'static str) -> &'static Self {
+                    debug_assert!(
+                        Self::validate(inner).is_ok(),
+                        "invalid {} string: {inner:?}",
+                        stringify!($what)
+                    );
+                    // Safety: the caller has promised that this is valid
+                    unsafe { Self::from_str(inner) }
+                }
+            }
+
             pub fn is_empty(&self) -> bool {
                 self.0.is_empty()
             }
@@ -159,18 +228,6 @@ macro_rules! parsed {
             }
         }
 
-        impl std::cmp::PartialEq<str> for $type_name {
-            fn eq(&self, other: &str) -> bool {
-                self.as_str() == other
-            }
-        }
-
-        impl std::cmp::PartialEq<str> for $owned_type_name {
-            fn eq(&self, other: &str) -> bool {
-                &**self == other
-            }
-        }
-
         impl std::convert::AsRef<$type_name> for $type_name {
             fn as_ref(&self) -> &$type_name {
                 self
@@ -215,18 +272,6 @@ macro_rules! parsed {
             }
         }
 
-        impl std::cmp::PartialEq<&str> for $type_name {
-            fn eq(&self, other: &&str) -> bool {
-                self.as_str() == *other
-            }
-        }
-
-        impl std::cmp::PartialEq<&str> for $owned_type_name {
-            fn eq(&self, other: &&str) -> bool {
-                &**self == other
-            }
-        }
-
         impl std::convert::From<&$type_name> for $owned_type_name {
             fn from(name: &$type_name) -> Self {
                 name.to_owned()
@@ -291,6 +336,75 @@ macro_rules! parsed {
             }
         }
     };
+    (case_insensitive, $type_name:ident, $owned_type_name:ident, $parse_error:ty, $what:tt) => {
+        $crate::parsed!(@common $type_name, $owned_type_name, $parse_error, $what);
+
+        impl $type_name {
+            /// Check if this is equal to the given string, ignoring ascii case.
+            pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+                self.0.eq_ignore_ascii_case(other)
+            }
+        }
+
+        impl std::cmp::PartialEq<str> for $type_name {
+            fn eq(&self, other: &str) -> bool {
+                self.eq_ignore_ascii_case(other)
+            }
+        }
+
+        impl std::cmp::PartialEq<str> for $owned_type_name {
+            fn eq(&self, other: &str) -> bool {
+                self.eq_ignore_ascii_case(other)
+            }
+        }
+
+        impl std::cmp::PartialEq<&str> for $type_name {
+            fn eq(&self, other: &&str) -> bool {
+                self.eq_ignore_ascii_case(other)
+            }
+        }
+
+        impl std::cmp::PartialEq<&str> for $owned_type_name {
+            fn eq(&self, other: &&str) -> bool {
+                self.eq_ignore_ascii_case(other)
+            }
+        }
+    };
+    (case_insensitive, $type_name:ident, $parse_error:ty, $what:tt) => {
+        $crate::paste::paste! {
+            $crate::parsed!(case_insensitive, $type_name, [<$type_name Buf>], $parse_error, $what);
+        }
+    };
+    (case_insensitive, $type_name:ident, $parse_error:ty) => {
+        $crate::parsed!(case_insensitive, $type_name, $parse_error, $type_name);
+    };
+    ($type_name:ident, $owned_type_name:ident, $parse_error:ty, $what:tt) => {
+        $crate::parsed!(@common $type_name, $owned_type_name, $parse_error, $what);
+
+        impl std::cmp::PartialEq<str> for $type_name {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl std::cmp::PartialEq<str> for $owned_type_name {
+            fn eq(&self, other: &str) -> bool {
+                &**self == other
+            }
+        }
+
+        impl std::cmp::PartialEq<&str> for $type_name {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl std::cmp::PartialEq<&str> for $owned_type_name {
+            fn eq(&self, other: &&str) -> bool {
+                &**self == other
+            }
+        }
+    };
     ($type_name:ident, $parse_error:ty, $what:tt) => {
         $crate::paste::paste! {
             $crate::parsed!($type_name, [<$type_name Buf>], $parse_error, $what);
@@ -4,16 +4,26 @@
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::LitStr;
+use syn::{LitInt, LitStr};
+
+/// The tick interval used for bars that do not specify one.
+const DEFAULT_TICK_MS: u64 = 100;
 
 /// Derive macro for generating boilerplate [`Default`] and [`Drop`] impls
 /// for a struct with [`indicatif::ProgressBar`] fields.
 ///
+/// A `hidden()` constructor is also generated which creates the same
+/// bars without ever drawing them.
+///
 /// The struct is required to have one or more fields of type [`indicatif::ProgressBar`].
 /// Each progress bar field requires a `#[progress_bar]` attribute with a `message`
 /// argument. A `template` argument is also required either at the struct level or
 /// the field level.
 ///
+/// Each bar ticks every 100ms by default. This can be changed with a
+/// `tick_ms` argument, either at the struct level to apply to every bar
+/// or at the field level for a single bar.
+///
 /// # Example
 ///
 /// ```
@@ -27,6 +37,40 @@ use syn::LitStr;
 ///     widgets: indicatif::ProgressBar,
 /// }
 /// ```
+///
+/// With a different tick interval for each bar:
+///
+/// ```
+/// use progress_bar_derive_macro::ProgressBar;
+/// #[derive(ProgressBar)]
+/// #[progress_bar(
+///     template = "      {spinner} {msg:<16.green} [{bar:40.cyan/dim}] {pos:>8}/{len:6}",
+///     tick_ms = 250
+/// )]
+/// struct MyStruct {
+///     #[progress_bar(message = "processing widgets")]
+///     widgets: indicatif::ProgressBar,
+///     #[progress_bar(message = "processing gadgets", tick_ms = 1000)]
+///     gadgets: indicatif::ProgressBar,
+/// }
+/// ```
+///
+/// When the bars should not be shown:
+///
+/// ```
+/// use progress_bar_derive_macro::ProgressBar;
+/// #[derive(ProgressBar)]
+/// struct MyStruct {
+///     #[progress_bar(
+///         message = "processing widgets",
+///         template = "      {spinner} {msg:<16.green} [{bar:40.cyan/dim}] {pos:>8}/{len:6}"
+///     )]
+///     widgets: indicatif::ProgressBar,
+/// }
+///
+/// let bars = MyStruct::hidden();
+/// assert!(bars.widgets.is_hidden());
+/// ```
 #[proc_macro_derive(ProgressBar, attributes(progress_bar))]
 pub fn proc_macro_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
@@ -38,9 +82,11 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
 
     let mut progress_bar_field_names = Vec::new();
     let mut bars = Vec::new();
+    let mut ticks = Vec::new();
 
     if let syn::Data::Struct(s) = &ast.data {
         let mut template = None;
+        let mut default_tick_ms = DEFAULT_TICK_MS;
 
         for attr in &ast.attrs {
             if !attr.path().is_ident("progress_bar") {
@@ -54,6 +100,12 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                     template = Some(s.value());
                     return Ok(());
                 }
+                if meta.path.is_ident("tick_ms") {
+                    let value = meta.value()?;
+                    let n: LitInt = value.parse()?;
+                    default_tick_ms = n.base10_parse()?;
+                    return Ok(());
+                }
                 Ok(())
             }) {
                 return err.to_compile_error().into();
@@ -72,6 +124,7 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                 }
 
                 let mut message = None;
+                let mut tick_ms = default_tick_ms;
 
                 for attr in &field.attrs {
                     if !attr.path().is_ident("progress_bar") {
@@ -91,6 +144,12 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                             template = Some(s.value());
                             return Ok(());
                         }
+                        if meta.path.is_ident("tick_ms") {
+                            let value = meta.value()?;
+                            let n: LitInt = value.parse()?;
+                            tick_ms = n.base10_parse()?;
+                            return Ok(());
+                        }
                         Ok(())
                     }) {
                         return err.to_compile_error().into();
@@ -124,13 +183,14 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                         .tick_strings(TICK_STRINGS)
                         .progress_chars(PROGRESS_CHARS);
                     let #ident = bars.add(
-                        indicatif::ProgressBar::new(0)
+                        new_bar()
                             .with_style(#ident_style)
                             .with_message(#message),
                     );
                 });
 
                 progress_bar_field_names.push(quote! { #ident });
+                ticks.push(quote! { #tick_ms });
             }
         }
     };
@@ -142,8 +202,28 @@ fn impl_proc_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                 static PROGRESS_CHARS: &str = "=>-";
 
                 let bars = indicatif::MultiProgress::new();
+                let new_bar = || indicatif::ProgressBar::new(0);
+                #(#bars)*
+                #(#progress_bar_field_names.enable_steady_tick(std::time::Duration::from_millis(#ticks));)*
+                Self {
+                    #(#progress_bar_field_names,)*
+                }
+            }
+        }
+
+        impl #name {
+            /// Create the same progress bars, but without drawing
+            /// them, eg: for when there is no terminal to draw to.
+            #[allow(dead_code)]
+            pub fn hidden() -> Self {
+                static TICK_STRINGS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+                static PROGRESS_CHARS: &str = "=>-";
+
+                let bars = indicatif::MultiProgress::with_draw_target(
+                    indicatif::ProgressDrawTarget::hidden(),
+                );
+                let new_bar = indicatif::ProgressBar::hidden;
                 #(#bars)*
-                #(#progress_bar_field_names.enable_steady_tick(std::time::Duration::from_millis(100));)*
                 Self {
                     #(#progress_bar_field_names,)*
                 }
@@ -64,11 +64,11 @@ pub struct CmdClean {
     prune_repeated_keep: Option<NonZero<u64>>,
 
     /// Prune tags older that the given age (eg: 1y, 8w, 10d, 3h, 4m, 8s)
-    #[clap(long = "prune-if-older-than", group = "repo_data", value_parser = age_to_date)]
+    #[clap(long = "prune-if-older-than", group = "repo_data", value_parser = cli::age_to_date)]
     prune_if_older_than: Option<DateTime<Utc>>,
 
     /// Always keep data newer than the given age (eg: 1y, 8w, 10d, 3h, 4m, 8s)
-    #[clap(long = "keep-if-newer-than", group = "repo_data", value_parser = age_to_date)]
+    #[clap(long = "keep-if-newer-than", group = "repo_data", value_parser = cli::age_to_date)]
     keep_if_newer_than: Option<DateTime<Utc>>,
 
     /// Prune tags if there are more than this number in a stream
@@ -79,6 +79,12 @@ pub struct CmdClean {
     #[clap(long = "keep-if-less-than", group = "repo_data")]
     keep_if_less_than: Option<u64>,
 
+    /// Remove objects older than the given age (eg: 1y, 8w, 10d, 3h, 4m, 8s)
+    /// even if they are still tagged. Objects used by a stored runtime are
+    /// never removed this way.
+    #[clap(long = "older-than", group = "repo_data", value_parser = cli::age_to_date)]
+    older_than: Option<DateTime<Utc>>,
+
     /// Do not remove proxies for users that have no additional
     /// hard links.
     ///
@@ -188,6 +194,7 @@ impl CmdClean {
             .with_keep_tags_newer_than(self.keep_if_newer_than)
             .with_prune_tags_if_version_more_than(self.prune_if_more_than)
             .with_keep_tags_if_version_less_than(self.keep_if_less_than)
+            .with_prune_objects_older_than(self.older_than)
             .with_remove_proxies_with_no_links(!self.keep_proxies_with_no_links)
             .with_removal_concurrency(self.max_removal_concurrency)
             .with_discover_concurrency(self.max_discover_concurrency)
@@ -218,6 +225,7 @@ impl CmdClean {
             pruned_tags,
             visited_objects,
             removed_objects,
+            expired_objects,
             visited_payloads,
             removed_payloads,
             visited_renders,
@@ -245,6 +253,13 @@ impl CmdClean {
             "{visited_objects:>12} objects visited  [{:>6} {removed}]",
             removed_objects.len()
         );
+        if self.older_than.is_some() {
+            println!(
+                "{:>30}[{:>6} {removed}]",
+                "objects past age cutoff ",
+                expired_objects.len()
+            );
+        }
         println!(
             "{visited_payloads:>12} payloads visited [{:>6} {removed}]",
             removed_payloads.len()
@@ -266,23 +281,3 @@ impl CmdClean {
         Ok(0)
     }
 }
-
-fn age_to_date(age: &str) -> Result<DateTime<Utc>> {
-    let (num, postfix) = age.split_at(age.len() - 1);
-    let num: i64 = num
-        .parse()
-        .map_err(|err| spfs::Error::from(format!("{err:?}")))?;
-    if num < 0 {
-        miette::bail!("provided age must be greater than zero: '{age}'");
-    }
-
-    match postfix {
-        "y" => Ok(Utc::now() - chrono::Duration::weeks(num * 52)),
-        "w" => Ok(Utc::now() - chrono::Duration::weeks(num)),
-        "d" => Ok(Utc::now() - chrono::Duration::days(num)),
-        "h" => Ok(Utc::now() - chrono::Duration::hours(num)),
-        "m" => Ok(Utc::now() - chrono::Duration::minutes(num)),
-        "s" => Ok(Utc::now() - chrono::Duration::seconds(num)),
-        _ => miette::bail!("Unknown age postfix: '{postfix}', must be one of y, w, d, h, m, s"),
-    }
-}
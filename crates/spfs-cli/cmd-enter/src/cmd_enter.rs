@@ -81,6 +81,11 @@ pub struct RemountArgs {
     /// Remount the overlay filesystem, don't enter a new namespace
     #[clap(id = "remount", long = "remount")]
     enabled: bool,
+
+    /// Re-apply the current environment after remounting, in case any
+    /// variables were changed by the remount
+    #[clap(long, requires = "remount")]
+    preserve_env: bool,
 }
 
 fn parse_env_key_value(s: &str) -> Result<EnvKeyValue> {
@@ -216,10 +221,16 @@ impl CmdEnter {
             Ok(None)
         } else if self.remount.enabled {
             let start_time = Instant::now();
-            // Safety: the responsibility of the caller.
-            let render_summary = unsafe {
-                spfs::reinitialize_runtime(&config.filesystem.overlayfs_options, &mut runtime)
-                    .await?
+            let overlayfs_options = &config.filesystem.overlayfs_options;
+            let render_summary = if self.remount.preserve_env {
+                // Safety: the responsibility of the caller.
+                unsafe {
+                    spfs::reinitialize_runtime_preserving_env(overlayfs_options, &mut runtime)
+                        .await?
+                }
+            } else {
+                // Safety: the responsibility of the caller.
+                unsafe { spfs::reinitialize_runtime(overlayfs_options, &mut runtime).await? }
             };
             // Safety: the responsibility of the caller.
             unsafe {
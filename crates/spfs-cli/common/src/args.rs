@@ -9,6 +9,7 @@ use std::sync::Arc;
 #[cfg(feature = "sentry")]
 use std::sync::Mutex;
 
+use chrono::{DateTime, Utc};
 use miette::{Error, IntoDiagnostic, Result, WrapErr};
 #[cfg(feature = "sentry")]
 use once_cell::sync::OnceCell;
@@ -708,6 +709,30 @@ macro_rules! handle_result {
     }};
 }
 
+/// Parse a relative age (eg: 1y, 8w, 10d, 3h, 4m, 8s) into the
+/// date time that is that far in the past.
+///
+/// Intended for use as a clap value parser.
+pub fn age_to_date(age: &str) -> Result<DateTime<Utc>> {
+    let (num, postfix) = age.split_at(age.len() - 1);
+    let num: i64 = num
+        .parse()
+        .map_err(|err| spfs::Error::from(format!("{err:?}")))?;
+    if num < 0 {
+        miette::bail!("provided age must be greater than zero: '{age}'");
+    }
+
+    match postfix {
+        "y" => Ok(Utc::now() - chrono::Duration::weeks(num * 52)),
+        "w" => Ok(Utc::now() - chrono::Duration::weeks(num)),
+        "d" => Ok(Utc::now() - chrono::Duration::days(num)),
+        "h" => Ok(Utc::now() - chrono::Duration::hours(num)),
+        "m" => Ok(Utc::now() - chrono::Duration::minutes(num)),
+        "s" => Ok(Utc::now() - chrono::Duration::seconds(num)),
+        _ => miette::bail!("Unknown age postfix: '{postfix}', must be one of y, w, d, h, m, s"),
+    }
+}
+
 pub fn capture_if_relevant(err: &Error) {
     match err.root_cause().downcast_ref::<spfs::Error>() {
         Some(spfs::Error::NoActiveRuntime) => (),
@@ -21,6 +21,7 @@ pub use args::{
     Render,
     Repositories,
     Sync,
+    age_to_date,
     capture_if_relevant,
 };
 #[cfg(feature = "sentry")]
@@ -81,6 +81,15 @@ pub struct CmdCommit {
     /// Allow committing an empty layer or platform
     #[clap(long, hide = true)]
     allow_empty: bool,
+
+    /// Preserve the modification times of committed files
+    ///
+    /// Modification times are normally discarded so that the same
+    /// content always produces the same layer. When given, they are
+    /// stored and restored on render instead, meaning that otherwise
+    /// identical commits will no longer share a digest.
+    #[clap(long)]
+    preserve_mtimes: bool,
 }
 
 impl CmdCommit {
@@ -93,7 +102,8 @@ impl CmdCommit {
                 .with_reporter(spfs::commit::ConsoleCommitReporter::default())
                 .with_max_concurrent_branches(self.max_concurrent_branches)
                 .with_max_concurrent_blobs(self.max_concurrent_blobs)
-                .with_allow_empty(self.allow_empty);
+                .with_allow_empty(self.allow_empty)
+                .with_preserve_mtimes(self.preserve_mtimes);
             if self.hash_while_committing {
                 let committer = committer
                     .with_blob_hasher(spfs::commit::WriteToRepositoryBlobHasher { repo: &repo });
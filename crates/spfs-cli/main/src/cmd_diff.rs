@@ -15,11 +15,22 @@ pub struct CmdDiff {
     /// The tag or id to diff the base against, defaults to the contents of the spfs filesystem
     #[clap(value_name = "TO")]
     top: Option<String>,
+
+    /// Show files that were moved without changing their content as
+    /// renamed, rather than as being removed and added
+    ///
+    /// Files are matched only by their content, so unrelated files
+    /// with identical content may also be shown as renamed.
+    #[clap(long)]
+    renames: bool,
 }
 
 impl CmdDiff {
     pub async fn run(&mut self, _config: &spfs::Config) -> Result<i32> {
-        let diffs = spfs::diff(self.base.as_ref(), self.top.as_ref()).await?;
+        let mut diffs = spfs::diff(self.base.as_ref(), self.top.as_ref()).await?;
+        if self.renames {
+            diffs = spfs::tracking::detect_renames(diffs);
+        }
         let out = spfs::io::format_changes(diffs.iter());
         if out.trim().is_empty() {
             tracing::info!("no changes");
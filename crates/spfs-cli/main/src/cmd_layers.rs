@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::collections::HashSet;
+
 use clap::Args;
 use miette::Result;
 use spfs::io::{self, DigestFormat};
@@ -22,6 +24,10 @@ pub struct CmdLayers {
     /// Also find and report any tags that point to each layer, implies --short
     #[clap(long)]
     tags: bool,
+
+    /// Only list the layers that are used by the given tag or reference
+    #[clap(long, value_name = "TAG")]
+    referenced_by: Option<String>,
 }
 
 impl CmdLayers {
@@ -29,6 +35,15 @@ impl CmdLayers {
         let repo =
             spfs::config::open_repository_from_string(config, self.repos.remote.as_ref()).await?;
 
+        if let Some(reference) = &self.referenced_by {
+            for (digest, object) in find_referenced_objects(&repo, reference).await? {
+                if object.kind() == spfs::graph::ObjectKind::Layer {
+                    println!("{}", self.format_digest(digest, &repo).await?);
+                }
+            }
+            return Ok(0);
+        }
+
         let mut layers = repo.iter_layers();
         while let Some(layer) = layers.next().await {
             let (digest, _) = layer?;
@@ -52,3 +67,27 @@ impl CmdLayers {
         .map_err(|err| err.into())
     }
 }
+
+/// Find all of the platforms and layers that are used by the given reference,
+/// in the order that they are discovered from the top down.
+pub(crate) async fn find_referenced_objects(
+    repo: &spfs::storage::RepositoryHandle,
+    reference: &str,
+) -> Result<Vec<(spfs::encoding::Digest, spfs::graph::Object)>> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![repo.resolve_ref(reference).await?];
+    while let Some(digest) = to_visit.pop() {
+        if !visited.insert(digest) {
+            continue;
+        }
+        let object = repo.read_object(digest).await?;
+        // layers can only be referenced by platforms, so there is
+        // no need to walk any further into the object graph
+        if let spfs::graph::object::Enum::Platform(platform) = object.to_enum() {
+            to_visit.extend(platform.iter_bottom_up().copied());
+        }
+        found.push((digest, object));
+    }
+    Ok(found)
+}
@@ -5,8 +5,9 @@
 use chrono::Local;
 use clap::Args;
 use colored::*;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use miette::Result;
+use spfs::io::Pluralize;
 use spfs::prelude::*;
 use spfs::{self};
 use spfs_cli_common as cli;
@@ -17,6 +18,11 @@ pub struct CmdLog {
     #[clap(flatten)]
     pub(crate) repos: cli::Repositories,
 
+    /// Show the history as a graph of how each version was built
+    /// on top of the layers of an older one, oldest first
+    #[clap(long)]
+    graph: bool,
+
     /// The tag to show history of
     tag: String,
 }
@@ -27,19 +33,116 @@ impl CmdLog {
             spfs::config::open_repository_from_string(config, self.repos.remote.as_ref()).await?;
 
         let tag = spfs::tracking::TagSpec::parse(&self.tag)?;
+        if self.graph {
+            return self.print_graph(&repo, &tag).await;
+        }
         let mut tag_stream = repo.read_tag(&tag).await?.enumerate();
         while let Some((i, tag)) = tag_stream.next().await {
             let tag = tag?;
             let spec = spfs::tracking::build_tag_spec(tag.org(), tag.name(), i as u64)?;
-            let spec_str = spec.to_string();
-            println!(
-                "{} {} {} {}",
-                tag.target.to_string()[..10].yellow(),
-                spec_str.bold(),
-                tag.user.bright_blue(),
-                tag.time.with_timezone(&Local).to_string().green(),
-            );
+            println!("{}", format_entry(&spec, &tag));
+        }
+        Ok(0)
+    }
+
+    async fn print_graph(
+        &self,
+        repo: &spfs::storage::RepositoryHandle,
+        tag: &spfs::tracking::TagSpec,
+    ) -> Result<i32> {
+        let history: Vec<_> = repo.read_tag(tag).await?.try_collect().await?;
+        let mut layers = Vec::with_capacity(history.len());
+        for tag in history.iter() {
+            layers.push(resolve_layer_digests(repo, tag.target).await);
+        }
+
+        // each version is related to the older version with the most
+        // layers that its own layers were built on top of, if any
+        let mut roots = Vec::new();
+        let mut children = vec![Vec::new(); history.len()];
+        let mut lines = Vec::with_capacity(history.len());
+        for (i, tag) in history.iter().enumerate() {
+            let spec = spfs::tracking::build_tag_spec(tag.org(), tag.name(), i as u64)?;
+            let mut line = format_entry(&spec, tag);
+            let parent = (i + 1..history.len())
+                .filter(|j| layers[i].starts_with(&layers[*j]))
+                .max_by_key(|j| (layers[*j].len(), std::cmp::Reverse(*j)));
+            match parent {
+                Some(parent) => {
+                    let added = layers[i].len() - layers[parent].len();
+                    if added == 0 {
+                        line.push_str(&" (same layers)".dimmed().to_string());
+                    } else {
+                        let text = format!(" (+{added} {})", "layer".pluralize(added));
+                        line.push_str(&text.dimmed().to_string());
+                    }
+                    children[parent].push(i);
+                }
+                None => roots.push(i),
+            }
+            lines.push(line);
+        }
+
+        // versions are indexed from newest to oldest, and the
+        // graph is shown from the oldest version
+        roots.reverse();
+        for child in children.iter_mut() {
+            child.reverse();
+        }
+        for (i, root) in roots.into_iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            print_graph_branch(&lines, &children, root, "");
         }
         Ok(0)
     }
 }
+
+fn format_entry(spec: &spfs::tracking::TagSpec, tag: &spfs::tracking::Tag) -> String {
+    format!(
+        "{} {} {} {}",
+        tag.target.to_string()[..10].yellow(),
+        spec.to_string().bold(),
+        tag.user.bright_blue(),
+        tag.time.with_timezone(&Local).to_string().green(),
+    )
+}
+
+/// The digests of all layers used by the given target, or the target
+/// itself if it cannot be resolved into layers
+async fn resolve_layer_digests(
+    repo: &spfs::storage::RepositoryHandle,
+    target: spfs::encoding::Digest,
+) -> Vec<spfs::encoding::Digest> {
+    let stack = spfs::graph::Stack::from(target);
+    match spfs::resolve_stack_to_layers(&stack, Some(repo)).await {
+        Ok(layers) => layers
+            .iter()
+            .map(|layer| layer.digest())
+            .collect::<spfs::Result<_>>()
+            .unwrap_or_else(|_| vec![target]),
+        Err(err) => {
+            tracing::debug!("failed to resolve layers of {target}: {err}");
+            vec![target]
+        }
+    }
+}
+
+/// Print the given version and all of those that were built on top of it.
+///
+/// The newest child of each version continues in the same column, with
+/// any others being shown as branches off to the side.
+fn print_graph_branch(lines: &[String], children: &[Vec<usize>], mut node: usize, prefix: &str) {
+    loop {
+        println!("{prefix}* {}", lines[node]);
+        let Some((newest, others)) = children[node].split_last() else {
+            return;
+        };
+        for other in others {
+            println!("{prefix}|\\");
+            print_graph_branch(lines, children, *other, &format!("{prefix}| "));
+        }
+        node = *newest;
+    }
+}
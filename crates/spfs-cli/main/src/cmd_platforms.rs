@@ -9,6 +9,8 @@ use spfs::prelude::*;
 use spfs_cli_common as cli;
 use tokio_stream::StreamExt;
 
+use super::cmd_layers::find_referenced_objects;
+
 /// List all platforms in an spfs repository
 #[derive(Debug, Args)]
 pub struct CmdPlatforms {
@@ -22,6 +24,10 @@ pub struct CmdPlatforms {
     /// Also find and report any tags that point to each platform, implies --short
     #[clap(long)]
     tags: bool,
+
+    /// Only list the platforms that are used by the given tag or reference
+    #[clap(long, value_name = "TAG")]
+    referenced_by: Option<String>,
 }
 
 impl CmdPlatforms {
@@ -29,6 +35,15 @@ impl CmdPlatforms {
         let repo =
             spfs::config::open_repository_from_string(config, self.repos.remote.as_ref()).await?;
 
+        if let Some(reference) = &self.referenced_by {
+            for (digest, object) in find_referenced_objects(&repo, reference).await? {
+                if object.kind() == spfs::graph::ObjectKind::Platform {
+                    println!("{}", self.format_digest(digest, &repo).await?);
+                }
+            }
+            return Ok(0);
+        }
+
         let mut platforms = repo.iter_platforms();
         while let Some(platform) = platforms.next().await {
             let (digest, _) = platform?;
@@ -6,6 +6,16 @@ use clap::Args;
 use miette::{Context, IntoDiagnostic, Result};
 use spfs_cli_common as cli;
 
+/// Options for the output of runtime info
+#[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
+pub enum InfoFormat {
+    /// The complete stored state of the runtime (default)
+    #[default]
+    Full,
+    /// A stable summary of the runtime, for use by other tools
+    Json,
+}
+
 /// Show the complete state of a runtime
 #[derive(Debug, Args)]
 pub struct CmdRuntimeInfo {
@@ -15,6 +25,10 @@ pub struct CmdRuntimeInfo {
     #[clap(flatten)]
     annotation: cli::AnnotationViewing,
 
+    /// The format to print the runtime information in
+    #[clap(long, value_enum, default_value_t)]
+    format: InfoFormat,
+
     /// The name/id of the runtime to remove
     #[clap(env = "SPFS_RUNTIME")]
     name: String,
@@ -37,8 +51,29 @@ impl CmdRuntimeInfo {
             return Ok(0);
         }
 
-        serde_json::to_writer_pretty(std::io::stdout(), runtime.data())
-            .into_diagnostic()
+        let res = match self.format {
+            InfoFormat::Full => serde_json::to_writer_pretty(std::io::stdout(), runtime.data()),
+            InfoFormat::Json => {
+                // the fields here are relied upon by other tools
+                // and should only be added to, not changed
+                let summary = serde_json::json!({
+                    "name": runtime.name(),
+                    "stack": runtime
+                        .status
+                        .stack
+                        .iter_bottom_up()
+                        .map(|digest| digest.to_string())
+                        .collect::<Vec<_>>(),
+                    "durable": runtime.is_durable(),
+                    "editable": runtime.status.editable,
+                    "running": runtime.status.running,
+                    "owner_pid": runtime.status.owner,
+                    "monitor_pid": runtime.status.monitor,
+                });
+                serde_json::to_writer_pretty(std::io::stdout(), &summary)
+            }
+        };
+        res.into_diagnostic()
             .wrap_err("Failed to generate json output")?;
         println!(); // the trailing new line is nice for interactive shells
 
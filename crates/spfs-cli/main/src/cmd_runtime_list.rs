@@ -114,7 +114,7 @@ impl CmdRuntimeList {
                         };
 
                         message = format!(
-                            "{message:37}\trunning={}\tpid={:<7}\teditable={}\tdurable={}\tstatus={process_status}",
+                            "{message:37}\trunning={}\tpid={:<7}\teditable={}\tdurable={}\tstatus={process_status}\torphaned={}",
                             runtime.status.running,
                             runtime
                                 .status
@@ -123,6 +123,7 @@ impl CmdRuntimeList {
                                 .unwrap_or_else(|| "unknown".to_string()),
                             runtime.status.editable,
                             runtime.is_durable(),
+                            runtime.is_orphaned(),
                         )
                     }
                     println!("{message}");
@@ -2,16 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use clap::Args;
 use miette::Result;
 #[cfg(unix)]
 use procfs::Current;
+use spfs::runtime::PruneProtection;
 use spfs_cli_common as cli;
 use tokio_stream::StreamExt;
 
-use super::cmd_runtime_remove::is_monitor_running;
-
 /// Find and remove runtimes from the repository based on a pruning strategy
 #[derive(Debug, Args)]
 pub struct CmdRuntimePrune {
@@ -35,6 +34,25 @@ pub struct CmdRuntimePrune {
     /// Remove runtimes started before last reboot
     #[clap(long)]
     from_before_boot: bool,
+
+    /// Remove runtimes started on this machine whose monitor
+    /// process is no longer running
+    #[clap(long)]
+    orphaned: bool,
+
+    /// Remove runtimes created longer ago than the given age (eg: 1y, 8w, 10d, 3h, 4m, 8s)
+    #[clap(long, value_parser = cli::age_to_date)]
+    older_than: Option<DateTime<Utc>>,
+
+    /// Only remove runtimes with a name that matches this glob pattern (eg: 'ci-*')
+    ///
+    /// This limits the runtimes selected by any of the other pruning strategies
+    #[clap(long, value_name = "PATTERN")]
+    name: Option<glob::Pattern>,
+
+    /// Allow durable runtimes to be removed, normally they will not be removed
+    #[clap(long)]
+    remove_durable: bool,
 }
 
 impl CmdRuntimePrune {
@@ -48,12 +66,16 @@ impl CmdRuntimePrune {
         };
 
         // TODO: Clap 4.x AppGroup supports grouping flags better.
-        if !self.from_before_boot {
+        if !self.from_before_boot && !self.orphaned && self.older_than.is_none() {
             tracing::info!("No pruning strategy selected.");
             return Ok(1);
         }
 
         let default_author = spfs::runtime::Author::default();
+        let active_runtime = spfs::active_runtime()
+            .await
+            .ok()
+            .map(|rt| rt.name().to_owned());
 
         #[cfg(unix)]
         let boot_time = match procfs::Uptime::current() {
@@ -79,17 +101,52 @@ impl CmdRuntimePrune {
         while let Some(runtime) = runtimes.next().await {
             match runtime {
                 Ok(runtime) => {
-                    if runtime.author.created >= boot_time {
-                        // This runtime is newer than the system boot time.
+                    let is_from_before_boot =
+                        self.from_before_boot && runtime.author.created < boot_time;
+                    let is_orphaned = self.orphaned && runtime.is_orphaned();
+                    let is_too_old = self
+                        .older_than
+                        .is_some_and(|older_than| runtime.author.created < older_than);
+                    if !is_from_before_boot && !is_orphaned && !is_too_old {
                         tracing::debug!(
                             created = ?runtime.author.created,
                             ?boot_time,
-                            "Skipping runtime created since boot: {name}",
+                            "Skipping runtime not selected for pruning: {name}",
                             name = runtime.name()
                         );
                         continue;
                     }
 
+                    if let Some(pattern) = &self.name
+                        && !pattern.matches(runtime.name())
+                    {
+                        tracing::debug!(
+                            "Skipping runtime that does not match the name pattern: {name}",
+                            name = runtime.name()
+                        );
+                        continue;
+                    }
+
+                    match runtime.prune_protection(active_runtime.as_deref(), self.remove_durable) {
+                        Some(PruneProtection::Active) => {
+                            tracing::info!(
+                                "Won't delete {}, it is the active runtime",
+                                runtime.name()
+                            );
+                            continue;
+                        }
+                        Some(PruneProtection::Durable) => {
+                            // Durable runtimes are not considered trash
+                            // runtimes, they are not suitable for pruning.
+                            tracing::info!(
+                                "Won't delete {}, the runtime is durable. Use --remove-durable or `spk runtime rm` to delete it.",
+                                runtime.name()
+                            );
+                            continue;
+                        }
+                        None => {}
+                    }
+
                     let is_same_author = runtime.author.user_name == default_author.user_name;
                     if !self.ignore_user && !is_same_author {
                         tracing::info!(
@@ -110,7 +167,10 @@ impl CmdRuntimePrune {
                         continue;
                     }
 
-                    if !self.ignore_monitor && is_same_host && is_monitor_running(&runtime) {
+                    if !self.ignore_monitor
+                        && is_same_host
+                        && spfs::monitor::is_monitor_running(&runtime)
+                    {
                         tracing::info!(
                             "Won't delete, the monitor process appears to still be running",
                         );
@@ -121,16 +181,6 @@ impl CmdRuntimePrune {
                         continue;
                     }
 
-                    if runtime.is_durable() {
-                        // Durable runtimes are not considered trash
-                        // runtimes, they are not suitable for pruning.
-                        tracing::info!(
-                            "Won't delete {}, the runtime is durable. Use `spk runtime rm` to delete it.",
-                            runtime.name()
-                        );
-                        continue;
-                    }
-
                     if let Err(err) = runtime_storage.remove_runtime(runtime.name()).await {
                         tracing::error!(
                             "Failed to remove runtime {name}: {err}",
@@ -139,7 +189,7 @@ impl CmdRuntimePrune {
                         continue;
                     }
 
-                    tracing::info!("Pruned runtime {name}", name = runtime.name());
+                    println!("Pruned runtime {name}", name = runtime.name());
                 }
                 Err(err) => {
                     tracing::error!("Failed to read runtime: {}", err);
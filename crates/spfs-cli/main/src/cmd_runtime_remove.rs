@@ -76,7 +76,7 @@ impl CmdRuntimeRemove {
                 return Ok(1);
             }
 
-            if !self.ignore_monitor && is_same_host && is_monitor_running(&runtime) {
+            if !self.ignore_monitor && is_same_host && spfs::monitor::is_monitor_running(&runtime) {
                 tracing::error!("Won't delete, the monitor process appears to still be running",);
                 tracing::error!(" > terminating the command should trigger the cleanup process");
                 tracing::error!(" > use --ignore-monitor to ignore this error");
@@ -96,37 +96,3 @@ impl CmdRuntimeRemove {
         Ok(0)
     }
 }
-
-pub(crate) fn is_monitor_running(rt: &spfs::runtime::Runtime) -> bool {
-    if let Some(pid) = rt.status.monitor {
-        // we are blatantly ignoring the fact that this pid might
-        // have been reused and is not the monitor anymore. Given
-        // that there will always be a race condition to this effect
-        // even if we did try to check the command line args for this
-        // process. So we stick on the extra conservative side
-        is_process_running(pid)
-    } else {
-        false
-    }
-}
-
-#[cfg(unix)]
-fn is_process_running(pid: u32) -> bool {
-    // sending a null signal to the pid just allows us to check
-    // if the process actually exists without affecting it
-    let pid = nix::unistd::Pid::from_raw(pid as i32);
-    nix::sys::signal::kill(pid, None).is_ok()
-}
-
-#[cfg(windows)]
-fn is_process_running(pid: u32) -> bool {
-    // PROCESS_SYNCHRONIZE seems like the most limited access we can request,
-    // which simply allows us to wait on the PID
-    let access = windows::Win32::System::Threading::PROCESS_SYNCHRONIZE;
-    let result = unsafe { windows::Win32::System::Threading::OpenProcess(access, false, pid) };
-    let Ok(handle) = result else {
-        return false;
-    };
-    let _ = unsafe { windows::Win32::Foundation::CloseHandle(handle) };
-    true
-}
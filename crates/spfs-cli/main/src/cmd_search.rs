@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::collections::HashSet;
+
 use clap::Args;
 use miette::Result;
 use spfs::prelude::*;
@@ -9,11 +11,22 @@ use spfs_cli_common as cli;
 use tokio_stream::StreamExt;
 
 /// Search for available tags by substring
+///
+/// By default, the local repository and all configured remotes are
+/// searched, and each matching tag is reported once no matter how many
+/// repositories contain it.
 #[derive(Debug, Args)]
 pub struct CmdSearch {
     #[clap(flatten)]
     pub(crate) repos: cli::Repositories,
 
+    /// Also show the name of the repository that each tag was found in
+    ///
+    /// When a tag exists in more than one repository, the first one
+    /// searched is shown (local, then remotes by name)
+    #[clap(long)]
+    show_repo: bool,
+
     /// The search term/substring to look for
     #[clap(value_name = "TERM")]
     term: String,
@@ -21,24 +34,44 @@ pub struct CmdSearch {
 
 impl CmdSearch {
     pub async fn run(&mut self, config: &spfs::Config) -> Result<i32> {
-        let mut repos = Vec::with_capacity(config.remote.len());
-        for name in config.list_remote_names() {
-            let remote = match config.get_remote(&name).await {
-                Ok(remote) => remote,
-                Err(err) => {
-                    tracing::warn!(remote = %name, "failed to load remote repository");
-                    tracing::debug!(" > {:?}", err);
-                    continue;
-                }
-            };
-            repos.push(remote);
+        let mut repos = Vec::with_capacity(config.remote.len() + 1);
+        if let Some(name) = &self.repos.remote {
+            repos.push((name.clone(), config.get_remote(name).await?));
+        } else {
+            let mut names = config.list_remote_names();
+            names.sort();
+            for name in names {
+                let remote = match config.get_remote(&name).await {
+                    Ok(remote) => remote,
+                    Err(err) => {
+                        tracing::warn!(remote = %name, "failed to load remote repository");
+                        tracing::debug!(" > {:?}", err);
+                        continue;
+                    }
+                };
+                repos.push((name, remote));
+            }
+            repos.insert(
+                0,
+                (
+                    "local".to_string(),
+                    config.get_local_repository().await?.into(),
+                ),
+            );
         }
-        repos.insert(0, config.get_local_repository().await?.into());
-        for repo in repos.into_iter() {
+
+        let mut reported = HashSet::new();
+        for (name, repo) in repos.into_iter() {
             let mut tag_streams = repo.iter_tags();
             while let Some(tag) = tag_streams.next().await {
                 let (tag, _) = tag?;
-                if tag.to_string().contains(&self.term) {
+                let found = tag.to_string();
+                if !found.contains(&self.term) || !reported.insert(found) {
+                    continue;
+                }
+                if self.show_repo {
+                    println!("{tag:?}\t{name}");
+                } else {
                     println!("{tag:?}");
                 }
             }
@@ -7,11 +7,19 @@ use std::os::unix::prelude::PermissionsExt;
 use std::path::PathBuf;
 
 use clap::Args;
-use miette::Result;
+use miette::{Context, IntoDiagnostic, Result, bail};
 use spfs::Error;
 use spfs::prelude::*;
 use spfs::tracking::BlobReadExt;
 use spfs_cli_common as cli;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+#[cfg(test)]
+#[path = "./cmd_write_test.rs"]
+mod cmd_write_test;
+#[cfg(test)]
+#[path = "./fixtures.rs"]
+mod fixtures;
 
 /// Store an arbitrary blob of data in spfs
 #[derive(Debug, Args)]
@@ -27,8 +35,18 @@ pub struct CmdWrite {
     pub(crate) repos: cli::Repositories,
 
     /// Store the contents of this file instead of reading from stdin
-    #[clap(long, short)]
+    #[clap(long, short, conflicts_with = "objects")]
     file: Option<PathBuf>,
+
+    /// Import a stream of encoded objects from stdin instead of
+    /// storing a single blob of data
+    ///
+    /// Each object in the stream is written as its digest, followed by
+    /// the length of the encoded object as an unsigned 64-bit integer and
+    /// then the encoded object itself. Every object is verified against
+    /// its digest before being stored. Tags are not supported in this mode.
+    #[clap(long, conflicts_with = "tags")]
+    objects: bool,
 }
 
 impl CmdWrite {
@@ -36,6 +54,13 @@ impl CmdWrite {
         let repo =
             spfs::config::open_repository_from_string(config, self.repos.remote.as_ref()).await?;
 
+        if self.objects {
+            let count =
+                write_object_stream(&repo, tokio::io::BufReader::new(tokio::io::stdin())).await?;
+            tracing::info!(%count, "imported objects");
+            return Ok(0);
+        }
+
         let reader: std::pin::Pin<Box<dyn spfs::tracking::BlobRead>> = match &self.file {
             Some(file) => {
                 let handle = tokio::fs::File::open(&file)
@@ -73,3 +98,68 @@ impl CmdWrite {
         Ok(0)
     }
 }
+
+/// Read a stream of length-prefixed objects, storing each one
+/// into the given repository and returning the number of
+/// objects that were read.
+async fn write_object_stream(
+    repo: &spfs::storage::RepositoryHandle,
+    mut reader: impl AsyncBufRead + Unpin,
+) -> Result<usize> {
+    let mut count = 0;
+    let mut offset = 0;
+    loop {
+        let at_end = reader
+            .fill_buf()
+            .await
+            .into_diagnostic()
+            .wrap_err("Failed to read object stream")?
+            .is_empty();
+        if at_end {
+            break;
+        }
+        let position = || format!("object #{} at byte offset {offset}", count + 1);
+        let mut digest = spfs::encoding::NULL_DIGEST;
+        reader
+            .read_exact(&mut digest)
+            .await
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read digest of {}", position()))?;
+        let expected = spfs::encoding::Digest::from_bytes(&digest)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read digest of {}", position()))?;
+        let length = reader
+            .read_u64()
+            .await
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read length of {}", position()))?;
+        let mut buf = Vec::new();
+        (&mut reader)
+            .take(length)
+            .read_to_end(&mut buf)
+            .await
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read {}", position()))?;
+        if (buf.len() as u64) < length {
+            bail!(
+                "Unexpected end of stream in {}: expected {length} bytes, got {}",
+                position(),
+                buf.len()
+            );
+        }
+        let object = spfs::graph::Object::new(buf)
+            .wrap_err_with(|| format!("Failed to decode {}", position()))?;
+        let actual = object.digest()?;
+        if actual != expected {
+            bail!(
+                "Digest mismatch for {}: expected {expected}, got {actual}",
+                position()
+            );
+        }
+        repo.write_object(&object).await?;
+        tracing::debug!(digest = %actual, kind = ?object.kind(), "imported");
+        count += 1;
+        offset += spfs::encoding::DIGEST_SIZE as u64 + 8 + length;
+    }
+    Ok(count)
+}
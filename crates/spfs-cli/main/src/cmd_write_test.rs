@@ -0,0 +1,78 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use spfs::prelude::*;
+
+use super::fixtures::*;
+use super::write_object_stream;
+
+/// Append an object to the stream, in the format read by `--objects`
+fn push_object(stream: &mut Vec<u8>, object: &spfs::graph::Object) {
+    let mut encoded = Vec::new();
+    object.encode(&mut encoded).unwrap();
+    spfs::encoding::write_digest(&mut *stream, &object.digest().unwrap()).unwrap();
+    spfs::encoding::write_uint64(&mut *stream, encoded.len() as u64).unwrap();
+    stream.extend(encoded);
+}
+
+async fn make_repo(tmpdir: &tempfile::TempDir) -> spfs::storage::RepositoryHandle {
+    spfs::storage::fs::MaybeOpenFsRepository::create(tmpdir.path().join("repo"))
+        .await
+        .unwrap()
+        .into()
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_write_object_stream(tmpdir: tempfile::TempDir) {
+    let repo = make_repo(&tmpdir).await;
+    let first = spfs::graph::Layer::new(spfs::encoding::EMPTY_DIGEST.into()).into_object();
+    let second = spfs::graph::Layer::new(spfs::encoding::NULL_DIGEST.into()).into_object();
+    let mut stream = Vec::new();
+    push_object(&mut stream, &first);
+    push_object(&mut stream, &second);
+
+    let count = write_object_stream(&repo, stream.as_slice()).await.unwrap();
+    assert_eq!(count, 2);
+    assert!(repo.has_object(first.digest().unwrap()).await);
+    assert!(repo.has_object(second.digest().unwrap()).await);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_write_object_stream_digest_mismatch(tmpdir: tempfile::TempDir) {
+    let repo = make_repo(&tmpdir).await;
+    let object = spfs::graph::Layer::new(spfs::encoding::EMPTY_DIGEST.into()).into_object();
+    let mut encoded = Vec::new();
+    object.encode(&mut encoded).unwrap();
+    let mut stream = Vec::new();
+    spfs::encoding::write_digest(&mut stream, &spfs::encoding::NULL_DIGEST.into()).unwrap();
+    spfs::encoding::write_uint64(&mut stream, encoded.len() as u64).unwrap();
+    stream.extend(encoded);
+
+    let err = write_object_stream(&repo, stream.as_slice())
+        .await
+        .expect_err("a mismatched digest should not be imported");
+    assert!(err.to_string().contains("Digest mismatch"), "{err:?}");
+    assert!(!repo.has_object(object.digest().unwrap()).await);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_write_object_stream_truncated(tmpdir: tempfile::TempDir) {
+    let repo = make_repo(&tmpdir).await;
+    let object = spfs::graph::Layer::new(spfs::encoding::EMPTY_DIGEST.into()).into_object();
+    let mut stream = Vec::new();
+    push_object(&mut stream, &object);
+    stream.truncate(stream.len() - 1);
+
+    let err = write_object_stream(&repo, stream.as_slice())
+        .await
+        .expect_err("a truncated stream should fail");
+    assert!(
+        err.to_string().contains("Unexpected end of stream"),
+        "{err:?}"
+    );
+}
@@ -10,6 +10,10 @@ use crate::{DIGEST_SIZE, Digest, Error, NULL_DIGEST, Result};
 const INT64_SIZE: usize = std::mem::size_of::<u64>();
 const INT8_SIZE: usize = std::mem::size_of::<u8>();
 
+/// The longest string that is accepted when decoding objects
+/// that may have come from an untrusted source.
+pub const MAX_DECODED_STRING_LENGTH: usize = 4096;
+
 #[cfg(test)]
 #[path = "./binary_test.rs"]
 mod binary_test;
@@ -112,7 +116,21 @@ pub fn write_string(mut writer: impl Write, string: &str) -> Result<()> {
 }
 
 /// Read a string from the given binary stream.
+///
+/// The string is read until its terminating null character no matter
+/// how long it is, and so this should only be used for trusted data.
+/// See [`read_string_with_limit`] for reading from untrusted sources.
 pub fn read_string(reader: &mut impl BufRead) -> Result<String> {
+    read_string_with_limit(reader, usize::MAX)
+}
+
+/// Read a string from the given binary stream, failing if it
+/// is longer than the given number of bytes.
+///
+/// This avoids buffering an arbitrary amount of data when the
+/// terminating null character is missing or very far away.
+pub fn read_string_with_limit(reader: &mut impl BufRead, limit: usize) -> Result<String> {
+    let mut length = 0_usize;
     let mut r = Vec::with_capacity(
         // most strings are short enough that they are expected
         // to be fully read in one iteration, but we can get
@@ -123,6 +141,9 @@ pub fn read_string(reader: &mut impl BufRead) -> Result<String> {
         let buf = reader.fill_buf().map_err(Error::FailedRead)?;
         match buf.iter().position(|&c| c == 0) {
             Some(index) => {
+                if length.saturating_add(index) > limit {
+                    return Err(Error::StringTooLong { limit });
+                }
                 r.push(
                     std::str::from_utf8(&buf[..index])
                         .map_err(Error::InvalidStringEncoding)?
@@ -137,6 +158,10 @@ pub fn read_string(reader: &mut impl BufRead) -> Result<String> {
                         std::io::ErrorKind::UnexpectedEof,
                     )));
                 }
+                length = length.saturating_add(buf.len());
+                if length > limit {
+                    return Err(Error::StringTooLong { limit });
+                }
                 r.push(
                     std::str::from_utf8(buf)
                         .map_err(Error::InvalidStringEncoding)?
@@ -8,7 +8,15 @@ use rand::Rng;
 use rand::distributions::{Alphanumeric, DistString};
 use rstest::rstest;
 
-use super::{consume_header, read_int, read_string, write_header, write_int, write_string};
+use super::{
+    consume_header,
+    read_int,
+    read_string,
+    read_string_with_limit,
+    write_header,
+    write_int,
+    write_string,
+};
 
 fn assert_read_content(stream: &mut impl Read, expected: &[u8]) {
     let mut buf: Vec<u8> = Vec::new();
@@ -181,3 +189,14 @@ fn test_read_string_normal() {
     let r = read_string(&mut ts);
     assert!(matches!(r, Ok(s) if s == test_string2));
 }
+
+#[rstest]
+#[case::within_one_buffer(64)]
+#[case::across_buffers(4)]
+fn test_read_string_with_limit(#[case] buffer_size: usize) {
+    let mut ts = TestStream::new(vec!["short", "much too long"], buffer_size);
+    let r = read_string_with_limit(&mut ts, 5);
+    assert!(matches!(r, Ok(s) if s == "short"));
+    let r = read_string_with_limit(&mut ts, 5);
+    assert!(matches!(r, Err(crate::Error::StringTooLong { limit: 5 })));
+}
@@ -37,6 +37,13 @@ pub enum Error {
     #[error("Cannot encode string with null character")]
     StringHasNull,
 
+    /// A string being decoded was longer than the allowed limit
+    #[error("String exceeds the maximum allowed length of {limit} bytes")]
+    StringTooLong {
+        /// The maximum number of bytes that were allowed
+        limit: usize,
+    },
+
     /// The header in a byte stream was not as expected
     #[error("Invalid header: wanted '{wanted:?}', got '{got:?}'")]
     InvalidHeader {
@@ -17,10 +17,12 @@ mod error;
 mod hash;
 
 pub use binary::{
+    MAX_DECODED_STRING_LENGTH,
     consume_header,
     read_digest,
     read_int,
     read_string,
+    read_string_with_limit,
     read_uint8,
     read_uint64,
     write_digest,
@@ -32,7 +34,14 @@ pub use binary::{
 };
 pub use error::{Error, Result};
 pub use hash::{Decodable, Digestible, Encodable, Hasher, PartialDigest};
-pub use spfs_proto::{DIGEST_SIZE, Digest, EMPTY_DIGEST, NULL_DIGEST, parse_digest};
+pub use spfs_proto::{
+    DIGEST_SIZE,
+    Digest,
+    EMPTY_DIGEST,
+    NULL_DIGEST,
+    parse_digest,
+    parse_multibase_digest,
+};
 
 /// # Encoding Prelude
 ///
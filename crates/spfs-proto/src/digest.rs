@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use data_encoding::BASE32;
+use data_encoding::{BASE32, BASE32_NOPAD};
 use miette::Diagnostic;
 
 use super::Digest;
@@ -185,6 +185,25 @@ pub fn parse_digest(digest_str: impl AsRef<str>) -> Result<Digest> {
     Digest::from_bytes(digest_bytes.as_slice())
 }
 
+/// Parse a string-encoded digest that may also use a multibase base32 encoding.
+///
+/// Multibase digests are identified by a leading `b` or `B` and are
+/// base32 encoded without any padding, ignoring case. Anything else is
+/// parsed the same as [`parse_digest`], which can be distinguished
+/// because those digests are always padded.
+pub fn parse_multibase_digest(digest_str: impl AsRef<str>) -> Result<Digest> {
+    let digest_str = digest_str.as_ref();
+    match digest_str.strip_prefix(['b', 'B']) {
+        Some(encoded) if !encoded.ends_with('=') => {
+            let digest_bytes = BASE32_NOPAD
+                .decode(encoded.to_ascii_uppercase().as_bytes())
+                .map_err(Error::InvalidDigestEncoding)?;
+            Digest::from_bytes(digest_bytes.as_slice())
+        }
+        _ => parse_digest(digest_str),
+    }
+}
+
 /// A specialized result for digest-related operations
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -33,3 +33,35 @@ fn digest_debug_shows_base32_string() {
         "Digest(\"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA====\")"
     );
 }
+
+#[rstest]
+fn test_parse_multibase_digest() {
+    use crate::{EMPTY_DIGEST, parse_digest, parse_multibase_digest};
+    let expected = Digest::from_bytes(&EMPTY_DIGEST).unwrap();
+    let encoded = data_encoding::BASE32_NOPAD.encode(&EMPTY_DIGEST);
+
+    let upper = format!("B{encoded}");
+    assert_eq!(parse_multibase_digest(upper).unwrap(), expected);
+    let lower = format!("b{}", encoded.to_ascii_lowercase());
+    assert_eq!(parse_multibase_digest(lower).unwrap(), expected);
+    assert_eq!(
+        parse_multibase_digest(expected.to_string()).unwrap(),
+        expected,
+        "standard digests should still be accepted"
+    );
+    assert!(
+        parse_digest(format!("B{encoded}")).is_err(),
+        "the default parser should not accept multibase digests"
+    );
+}
+
+#[rstest]
+fn test_parse_multibase_digest_starting_with_b() {
+    use crate::parse_multibase_digest;
+    // a standard digest whose encoding happens to
+    // start with the multibase prefix character
+    let digest = Digest::from_bytes(&[0x08; crate::DIGEST_SIZE]).unwrap();
+    let encoded = digest.to_string();
+    assert!(encoded.starts_with('B'));
+    assert_eq!(parse_multibase_digest(encoded).unwrap(), digest);
+}
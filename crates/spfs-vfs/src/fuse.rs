@@ -129,6 +129,8 @@ impl Filesystem {
             entries,
             user_data: _,
             legacy_size,
+            xattrs,
+            mtime,
         } = entry;
 
         let inode = self.allocate_inode();
@@ -143,6 +145,8 @@ impl Filesystem {
             entries,
             user_data: inode,
             legacy_size,
+            xattrs,
+            mtime,
         });
         self.inodes.insert(inode, Arc::clone(&entry));
         entry
@@ -198,9 +202,10 @@ impl Filesystem {
             gid: self.opts.gid.as_raw(),
             blocks: (size / Self::BLOCK_SIZE as u64) + 1,
             // Use the time of the filesystem creation as the times here so
-            // that the filesystem appears to be static and unchanging.
+            // that the filesystem appears to be static and unchanging,
+            // unless the original modification time was preserved.
             atime: self.fs_creation_time,
-            mtime: self.fs_creation_time,
+            mtime: entry.mtime.unwrap_or(self.fs_creation_time),
             ctime: self.fs_creation_time,
             crtime: self.fs_creation_time,
             kind,
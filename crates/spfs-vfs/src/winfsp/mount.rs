@@ -132,6 +132,8 @@ impl Mount {
             entries,
             user_data: _,
             legacy_size,
+            xattrs,
+            mtime,
         } = entry;
 
         let inode = self.allocate_inode();
@@ -146,6 +148,8 @@ impl Mount {
             entries,
             user_data: inode,
             legacy_size,
+            xattrs,
+            mtime,
         });
         self.inodes.insert(inode, Arc::clone(&entry));
         entry
@@ -19,7 +19,7 @@ use progress_bar_derive_macro::ProgressBar;
 use super::prune::PruneParameters;
 use crate::io::Pluralize;
 use crate::prelude::*;
-use crate::runtime::makedirs_with_perms;
+use crate::runtime::{is_runtime_tag, makedirs_with_perms};
 use crate::storage::fs::FsRepositoryOps;
 use crate::storage::{TagNamespace, TagNamespaceBuf};
 use crate::{Digest, Error, Result, encoding, graph, storage, tracking};
@@ -44,8 +44,10 @@ where
     removal_concurrency: usize,
     discover_concurrency: usize,
     attached: DashSet<encoding::Digest>,
+    expired: DashSet<encoding::Digest>,
     dry_run: bool,
     must_be_older_than: DateTime<Utc>,
+    prune_objects_older_than: Option<DateTime<Utc>>,
     prune_all_tag_namespaces: bool,
     prune_repeated_tags: Option<NonZero<u64>>,
     prune_params: PruneParameters,
@@ -68,8 +70,10 @@ impl<'repo> Cleaner<'repo, SilentCleanReporter> {
             discover_concurrency: Self::DEFAULT_DISCOVER_CONCURRENCY,
             tag_stream_concurrency: Self::DEFAULT_TAG_STREAM_CONCURRENCY,
             attached: Default::default(),
+            expired: Default::default(),
             dry_run: false,
             must_be_older_than: Utc::now(),
+            prune_objects_older_than: None,
             prune_all_tag_namespaces: false,
             prune_repeated_tags: None,
             prune_params: Default::default(),
@@ -89,8 +93,10 @@ where
             repo: self.repo,
             reporter,
             attached: self.attached,
+            expired: self.expired,
             dry_run: self.dry_run,
             must_be_older_than: self.must_be_older_than,
+            prune_objects_older_than: self.prune_objects_older_than,
             prune_all_tag_namespaces: self.prune_all_tag_namespaces,
             prune_repeated_tags: self.prune_repeated_tags,
             prune_params: self.prune_params,
@@ -158,6 +164,22 @@ where
         self
     }
 
+    /// Remove any objects that are older than this date time, even if
+    /// they are still connected to a tag.
+    ///
+    /// This is in addition to the removal of detached objects, and is
+    /// intended for repositories where older tags are known to be stale.
+    /// Objects that are connected to a stored runtime are never removed
+    /// in this way, and the required age of [`Self::with_required_age`]
+    /// is still respected.
+    pub fn with_prune_objects_older_than(
+        mut self,
+        prune_objects_older_than: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.prune_objects_older_than = prune_objects_older_than;
+        self
+    }
+
     /// When walking tags, whether to prune tags in all tag namespaces or only
     /// the tag namespace configured on the repository.
     pub fn with_prune_all_tag_namespaces(mut self, prune_all_tag_namespaces: bool) -> Self {
@@ -279,6 +301,7 @@ where
                     let _ = writeln!(&mut out, " - the tag's version is less than {v}",);
                 }
             }
+            let _ = writeln!(&mut out, " - tags that store a runtime are never pruned");
             let _ = writeln!(
                 &mut out,
                 " - otherwise, {find} all the objects and payloads connected to it",
@@ -290,6 +313,17 @@ where
             );
         }
 
+        if let Some(dt) = self.prune_objects_older_than {
+            let _ = writeln!(
+                &mut out,
+                "Then, {find} all of the objects connected to a runtime"
+            );
+            let _ = writeln!(
+                &mut out,
+                " - {remove} any other object created before {}, even if connected to a tag",
+                dt.min(self.must_be_older_than).with_timezone(&Local)
+            );
+        }
         let _ = writeln!(
             &mut out,
             "Then, {scan} all of the objects in the repository"
@@ -378,10 +412,15 @@ where
             return Ok(result);
         }
 
-        // Safety: both of these functions require that the repository is fully
+        // Safety: all of these functions require that the repository is fully
         // walked and all attached objects discovered. See the above block which
         // checks for this before proceeding
         unsafe {
+            if let Some(cutoff) = self.prune_objects_older_than {
+                // expired objects are removed first so that any payloads
+                // and renders associated with them can also be cleaned
+                result += self.remove_expired_objects(cutoff).await?;
+            }
             // because we don't yet know if some detached objects will be
             // kept due to age, we cannot process these two steps in parallel
             result += self.remove_unvisited_objects_and_payloads().await?;
@@ -447,6 +486,11 @@ where
                 (None, None) => true,
                 _ => false,
             };
+        // Runtimes are stored as tags, and must remain roots of the
+        // repository for as long as the runtime exists. Durable runtimes
+        // in particular can be much older than any pruning parameters, but
+        // still rely on all of their layers being available.
+        let should_prune_this_namespace = should_prune_this_namespace && !is_runtime_tag(&tag_spec);
 
         let history = self
             .repo
@@ -539,6 +583,89 @@ where
         Ok(result)
     }
 
+    /// Find all of the objects that are connected to a runtime
+    /// stored in the repository.
+    async fn find_runtime_objects(&self) -> Result<HashSet<encoding::Digest>> {
+        let mut to_visit = Vec::new();
+        // runtime tags are always kept out of any tag namespace
+        let mut tag_streams = self.repo.iter_tag_streams_in_namespace(None);
+        while let Some((spec, _stream)) = tag_streams.try_next().await? {
+            if !is_runtime_tag(&spec) {
+                continue;
+            }
+            let mut history = self.repo.read_tag_in_namespace(None, &spec).await?;
+            while let Some(tag) = history.try_next().await? {
+                to_visit.push(tag.target);
+            }
+        }
+        drop(tag_streams);
+
+        let mut found = HashSet::new();
+        while let Some(digest) = to_visit.pop() {
+            if !found.insert(digest) {
+                continue;
+            }
+            match self.repo.read_object(digest).await {
+                Ok(obj) => to_visit.extend(obj.child_objects()),
+                Err(Error::UnknownObject(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(found)
+    }
+
+    /// Remove any attached objects that were created before the given
+    /// cutoff, except for those connected to a runtime.
+    ///
+    /// # Safety
+    /// This function should only be called once the discovery of all attached
+    /// objects has completed successfully and with no errors. Otherwise, it may
+    /// remove data that is still being used by a runtime
+    async unsafe fn remove_expired_objects(&self, cutoff: DateTime<Utc>) -> Result<CleanResult> {
+        let mut result = CleanResult::default();
+        let cutoff = cutoff.min(self.must_be_older_than);
+        let runtime_objects = self.find_runtime_objects().await?;
+        let candidates = self
+            .attached
+            .iter()
+            .map(|digest| *digest)
+            .filter(|digest| !runtime_objects.contains(digest))
+            .collect::<Vec<_>>();
+        let mut stream = futures::stream::iter(candidates)
+            .map(|digest| async move {
+                let object = match self.repo.read_object(digest).await {
+                    Ok(object) => object,
+                    // the object may have already been removed, and
+                    // payloads are cleaned along with their blob
+                    Err(Error::UnknownObject(_)) => return Ok(None),
+                    Err(err) => return Err(err),
+                };
+                if self.dry_run {
+                    return Ok(Some((digest, object)));
+                }
+                let removed = match self.repo.remove_object_if_older_than(cutoff, digest).await {
+                    Err(Error::UnknownObject(_)) => true,
+                    res => res?,
+                };
+                Ok(removed.then_some((digest, object)))
+            })
+            .buffer_unordered(self.removal_concurrency)
+            .try_filter_map(|removed| ready(Ok(removed)))
+            .boxed();
+        while let Some((digest, object)) = stream.try_next().await? {
+            // expired objects are no longer considered attached so
+            // that any associated payloads and renders are also removed
+            self.attached.remove(&digest);
+            self.expired.insert(digest);
+            result.removed_objects.insert(digest);
+            result.expired_objects.insert(digest);
+            self.reporter.object_removed(&object);
+        }
+        drop(stream);
+
+        Ok(result)
+    }
+
     /// # Safety
     /// This function should only be called once the discovery of all attached
     /// objects has completed successfully and with no errors. Otherwise, it may
@@ -548,8 +675,11 @@ where
         let mut stream = self
             .repo
             .iter_objects()
-            // we have no interest in removing attached items
-            .try_filter(|(digest, _object)| ready(!self.attached.contains(digest)))
+            // we have no interest in removing attached items, and
+            // expired objects have already been removed
+            .try_filter(|(digest, _object)| {
+                ready(!self.attached.contains(digest) && !self.expired.contains(digest))
+            })
             // we have already visited all attached objects
             // but also want to report these ones
             .and_then(|obj| {
@@ -853,6 +983,9 @@ pub struct CleanResult {
     pub visited_objects: u64,
     /// The objects removed
     pub removed_objects: HashSet<encoding::Digest>,
+    /// The objects removed because of their age, even though they
+    /// were still connected to a tag (also included in `removed_objects`)
+    pub expired_objects: HashSet<encoding::Digest>,
 
     /// The number of payloads visited when walking the database
     pub visited_payloads: u64,
@@ -900,6 +1033,7 @@ impl std::ops::AddAssign for CleanResult {
             pruned_tags,
             visited_objects,
             removed_objects,
+            expired_objects,
             visited_payloads,
             removed_payloads,
             visited_renders,
@@ -926,6 +1060,7 @@ impl std::ops::AddAssign for CleanResult {
         self.visited_tags += visited_tags;
         self.visited_objects += visited_objects;
         self.removed_objects.extend(removed_objects);
+        self.expired_objects.extend(expired_objects);
         self.visited_payloads += visited_payloads;
         self.removed_payloads.extend(removed_payloads);
         self.visited_renders += visited_renders;
@@ -185,6 +185,190 @@ async fn test_clean_untagged_objects(#[future] tmprepo: TempRepo, tmpdir: tempfi
     }
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_clean_objects_older_than_keeps_runtimes(
+    #[future] tmprepo: TempRepo,
+    tmpdir: tempfile::TempDir,
+) {
+    init_logging();
+    let tmprepo = tmprepo.await;
+
+    // Group 1: tagged objects that have gone stale
+    let data_dir_1 = tmpdir.path().join("data");
+    ensure(data_dir_1.join("dir/dir/test.file"), "1 hello");
+    ensure(data_dir_1.join("dir/dir/test.file2"), "1 hello, world");
+
+    let manifest1 = crate::Committer::new(&tmprepo)
+        .commit_dir(data_dir_1.as_path())
+        .await
+        .unwrap();
+    let layer1 = tmprepo
+        .create_layer(&manifest1.to_graph_manifest())
+        .await
+        .unwrap();
+    let tag = tracking::TagSpec::parse("stale_manifest").unwrap();
+    tmprepo
+        .push_tag(&tag, &layer1.digest().unwrap())
+        .await
+        .unwrap();
+
+    // Group 2: objects of equal age that are used by a runtime
+    let data_dir_2 = tmpdir.path().join("data2");
+    ensure(data_dir_2.join("dir/dir/test.file"), "2 hello");
+    ensure(data_dir_2.join("dir/dir/test.file2"), "2 hello, world");
+
+    let manifest2 = crate::Committer::new(&tmprepo)
+        .commit_dir(data_dir_2.as_path())
+        .await
+        .unwrap();
+    let layer2 = tmprepo
+        .create_layer(&manifest2.to_graph_manifest())
+        .await
+        .unwrap();
+    let tag = tracking::TagSpec::parse("spfs/runtimes/data/test-runtime").unwrap();
+    tmprepo
+        .push_tag(&tag, &layer2.digest().unwrap())
+        .await
+        .unwrap();
+
+    // Ensure the cutoff is a measurable amount of time after
+    // all of the objects were created.
+    sleep(Duration::from_millis(250)).await;
+    let cutoff = Utc::now();
+
+    let cleaner = Cleaner::new(&tmprepo)
+        .with_reporter(TracingCleanReporter)
+        .with_required_age_cutoff(cutoff)
+        .with_prune_objects_older_than(Some(cutoff));
+    let result = cleaner
+        .prune_all_tags_and_clean()
+        .await
+        .expect("failed to clean objects");
+    println!("{result:#?}");
+
+    assert!(
+        result.expired_objects.contains(&layer1.digest().unwrap()),
+        "old layer should be reported as removed, even though it is tagged"
+    );
+    assert!(
+        matches!(
+            tmprepo.read_object(layer1.digest().unwrap()).await,
+            Err(Error::UnknownObject(_))
+        ),
+        "old layer should be removed, even though it is tagged"
+    );
+    for node in manifest1.walk() {
+        if !node.entry.kind.is_blob() {
+            continue;
+        }
+        assert!(
+            matches!(
+                tmprepo.open_payload(node.entry.object).await,
+                Err(Error::UnknownObject(_))
+            ),
+            "expected payload to be cleaned but it was not: {:?}",
+            node.entry.object
+        );
+    }
+
+    tmprepo
+        .read_object(layer2.digest().unwrap())
+        .await
+        .expect("expected runtime layer not to be cleaned");
+    for node in manifest2.walk() {
+        if !node.entry.kind.is_blob() {
+            continue;
+        }
+        tmprepo
+            .open_payload(node.entry.object)
+            .await
+            .expect("expected runtime payload not to be cleaned");
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_clean_keeps_durable_runtime_layers(tmpdir: tempfile::TempDir) {
+    init_logging();
+    let root = tmpdir.path().join("repo");
+    let tmprepo = storage::RepositoryHandle::from(
+        storage::fs::MaybeOpenFsRepository::create(&root)
+            .await
+            .unwrap(),
+    );
+    let runtime_storage = crate::runtime::Storage::new(
+        storage::fs::MaybeOpenFsRepository::open(&root)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+
+    let data_dir = tmpdir.path().join("data");
+    ensure(data_dir.join("dir/dir/test.file"), "durable hello");
+    ensure(data_dir.join("dir/dir/test.file2"), "durable hello, world");
+
+    let manifest = crate::Committer::new(&tmprepo)
+        .commit_dir(data_dir.as_path())
+        .await
+        .unwrap();
+    let layer = tmprepo
+        .create_layer(&manifest.to_graph_manifest())
+        .await
+        .unwrap();
+
+    let mut runtime = runtime_storage
+        .create_named_runtime("durable-runtime", true, Vec::new())
+        .await
+        .unwrap();
+    runtime.push_digest(layer.digest().unwrap());
+    runtime.save_state_to_storage().await.unwrap();
+
+    // Ensure the cutoff is a measurable amount of time after
+    // the runtime was saved.
+    sleep(Duration::from_millis(250)).await;
+    let cutoff = Utc::now();
+
+    // Prune every tag that can be pruned, which should still
+    // leave the runtime and all of its data intact
+    let cleaner = Cleaner::new(&tmprepo)
+        .with_reporter(TracingCleanReporter)
+        .with_required_age_cutoff(cutoff)
+        .with_prune_tags_older_than(Some(cutoff))
+        .with_prune_all_tag_namespaces(true);
+    let result = cleaner
+        .prune_all_tags_and_clean()
+        .await
+        .expect("failed to clean objects");
+    println!("{result:#?}");
+
+    assert!(
+        result.into_all_tags().is_empty(),
+        "runtime tags should never be pruned"
+    );
+    let runtime = runtime_storage
+        .read_runtime("durable-runtime")
+        .await
+        .expect("durable runtime should still exist");
+    assert_eq!(
+        runtime.status.stack.to_top_down(),
+        vec![layer.digest().unwrap()]
+    );
+    tmprepo
+        .read_object(layer.digest().unwrap())
+        .await
+        .expect("expected durable runtime layer not to be cleaned");
+    for node in manifest.walk() {
+        if !node.entry.kind.is_blob() {
+            continue;
+        }
+        tmprepo
+            .open_payload(node.entry.object)
+            .await
+            .expect("expected durable runtime payload not to be cleaned");
+    }
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_clean_on_repo_with_tag_namespace(
@@ -131,6 +131,22 @@ where
         self
     }
 
+    /// Preserve the modification time of committed files and directories.
+    ///
+    /// When enabled, the original modification times are stored in the
+    /// committed manifest and restored when it is rendered, for tools
+    /// that embed build times or compare file freshness. This comes at
+    /// the cost of reproducibility: committing identical content at
+    /// different times will produce different manifests and layers.
+    /// Modification times can only be stored when objects are written
+    /// using the flatbuffers encoding format.
+    ///
+    /// Defaults to false.
+    pub fn with_preserve_mtimes(mut self, preserve_mtimes: bool) -> Self {
+        self.builder = self.builder.with_preserve_mtimes(preserve_mtimes);
+        self
+    }
+
     /// Use the given [`BlobHasher`] when building the manifest.
     ///
     /// See [`InMemoryBlobHasher`] and [`WriteToRepositoryBlobHasher`] for
@@ -772,6 +772,16 @@ impl Config {
         }
     }
 
+    /// Get the address of a remote repository by name, without opening it.
+    pub fn get_remote_address<S: AsRef<str>>(&self, remote_name: S) -> Result<url::Url> {
+        match self.remote.get(remote_name.as_ref()) {
+            Some(remote) => remote.to_address(),
+            None => Err(crate::Error::UnknownRemoteName(
+                remote_name.as_ref().to_owned(),
+            )),
+        }
+    }
+
     /// Get a remote repository by name.
     pub async fn get_remote<S: AsRef<str>>(
         &self,
@@ -54,6 +54,19 @@ async fn test_config_get_remote() {
     assert!(repo.is_ok());
 }
 
+#[rstest]
+fn test_config_get_remote_address() {
+    let config: Config =
+        serde_json::from_str(r#"{"remote": { "origin": { "address": "http://myaddress" } } }"#)
+            .unwrap();
+    let address = config.get_remote_address("origin").unwrap();
+    assert_eq!(address.as_str(), "http://myaddress/");
+    let err = config
+        .get_remote_address("unknown")
+        .expect_err("should fail for an unknown remote");
+    assert!(matches!(err, crate::Error::UnknownRemoteName(_)));
+}
+
 #[rstest]
 #[case(
     r#"
@@ -19,6 +19,34 @@ pub async fn diff(
     base: Option<&String>,
     top: Option<&String>,
 ) -> Result<Vec<tracking::Diff<(), ()>>> {
+    let (base_manifest, top_manifest) = compute_base_and_top_manifests(base, top).await?;
+
+    tracing::debug!("computing diffs");
+    Ok(tracking::compute_diff(&base_manifest, &top_manifest))
+}
+
+/// Return a summary of the changes going from 'base' to 'top'.
+///
+/// This is much cheaper than [`diff`] when only the kind of change
+/// matters, as it stops as soon as any content is found to differ.
+/// The arguments are the same as for [`diff`].
+pub async fn diff_summary(
+    base: Option<&String>,
+    top: Option<&String>,
+) -> Result<tracking::DiffSummary> {
+    let (base_manifest, top_manifest) = compute_base_and_top_manifests(base, top).await?;
+
+    tracing::debug!("computing diff summary");
+    Ok(tracking::compute_diff_summary(
+        &base_manifest,
+        &top_manifest,
+    ))
+}
+
+async fn compute_base_and_top_manifests(
+    base: Option<&String>,
+    top: Option<&String>,
+) -> Result<(tracking::Manifest, tracking::Manifest)> {
     let base_manifest = match base {
         None => {
             tracing::debug!("computing runtime manifest as base");
@@ -41,9 +69,7 @@ pub async fn diff(
             compute_manifest(top).await?
         }
     };
-
-    tracing::debug!("computing diffs");
-    Ok(tracking::compute_diff(&base_manifest, &top_manifest))
+    Ok((base_manifest, top_manifest))
 }
 
 /// Build a manifest of the current set of changes
@@ -66,6 +66,15 @@ pub enum Error {
     Utf8Error(#[from] Utf8Error),
     #[error("Error communicating with the server: {0:?}")]
     Tonic(Box<tonic::Status>),
+    /// Denotes a message received from the server that is missing
+    /// required data or is otherwise malformed
+    #[error("Invalid rpc message, {message}.{field}: {reason}")]
+    #[diagnostic(code("spfs::invalid_rpc_message"))]
+    InvalidRpcMessage {
+        message: &'static str,
+        field: &'static str,
+        reason: String,
+    },
     #[error(transparent)]
     TokioJoinError(#[from] tokio::task::JoinError),
     #[error("Failed to spawn {0}")]
@@ -74,6 +83,17 @@ pub enum Error {
     /// Denotes a missing object or one that is not present in the database.
     #[error("Unknown Object: {0}")]
     UnknownObject(encoding::Digest),
+    /// Denotes an object that refers back to one of its own ancestors,
+    /// which can only happen if the database has been corrupted.
+    #[error("Object {parent} refers back to its ancestor {digest}, the repository may be corrupt")]
+    #[diagnostic(
+        code("spfs::object_cycle"),
+        help("Run 'spfs check' to find and repair any corrupt objects")
+    )]
+    ObjectCycle {
+        digest: encoding::Digest,
+        parent: encoding::Digest,
+    },
     /// Denotes an object missing its payload.
     #[error("Object {0} missing payload: {1}")]
     ObjectMissingPayload(crate::graph::Object, encoding::Digest),
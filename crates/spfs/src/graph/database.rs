@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Poll;
 
 use chrono::{DateTime, Utc};
@@ -12,15 +13,46 @@ use futures::{Future, Stream, StreamExt, TryStreamExt};
 use super::{FlatObject, Object, ObjectProto};
 use crate::{Error, Result, encoding};
 
-/// Walks an object tree depth-first starting at some root digest
+#[cfg(test)]
+#[path = "./database_test.rs"]
+mod database_test;
+
+/// The chain of objects that led to some object being visited
+/// while walking a database, used to identify reference cycles.
+struct WalkPath {
+    digest: encoding::Digest,
+    parent: Option<Arc<WalkPath>>,
+}
+
+impl WalkPath {
+    fn contains(&self, digest: &encoding::Digest) -> bool {
+        let mut current = Some(self);
+        while let Some(path) = current {
+            if &path.digest == digest {
+                return true;
+            }
+            current = path.parent.as_deref();
+        }
+        false
+    }
+}
+
+/// Walks an object tree breadth-first starting at some root digest
+///
+/// Each object is only visited once, even if it is referenced by
+/// multiple parents, so that a corrupt database cannot cause the walk
+/// to continue forever. Since objects are content-addressed, an object
+/// that refers back to one of the objects that led to it can only be
+/// the result of corruption and is reported as an [`Error::ObjectCycle`].
 #[allow(clippy::type_complexity)]
 pub struct DatabaseWalker<'db> {
     db: &'db dyn DatabaseView,
     next: Option<(
-        encoding::Digest,
+        Arc<WalkPath>,
         Pin<Box<dyn Future<Output = Result<Object>> + Send + 'db>>,
     )>,
-    queue: VecDeque<encoding::Digest>,
+    queue: VecDeque<Arc<WalkPath>>,
+    visited: HashSet<encoding::Digest>,
 }
 
 impl<'db> DatabaseWalker<'db> {
@@ -31,11 +63,15 @@ impl<'db> DatabaseWalker<'db> {
     /// The same as [`DatabaseView::read_object`]
     pub fn new(db: &'db dyn DatabaseView, root: encoding::Digest) -> Self {
         let mut queue = VecDeque::new();
-        queue.push_back(root);
+        queue.push_back(Arc::new(WalkPath {
+            digest: root,
+            parent: None,
+        }));
         DatabaseWalker {
             db,
             queue,
             next: None,
+            visited: HashSet::from([root]),
         }
     }
 }
@@ -47,25 +83,43 @@ impl Stream for DatabaseWalker<'_> {
         mut self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let (digest, mut current_future) = match self.next.take() {
+        let (path, mut current_future) = match self.next.take() {
             Some(f) => f,
             None => match self.queue.pop_front() {
                 None => return Poll::Ready(None),
-                Some(digest) => (digest, self.db.read_object(digest)),
+                Some(path) => {
+                    let future = self.db.read_object(path.digest);
+                    (path, future)
+                }
             },
         };
 
         match Pin::new(&mut current_future).poll(cx) {
             Poll::Pending => {
-                self.next = Some((digest, current_future));
+                self.next = Some((path, current_future));
                 Poll::Pending
             }
             Poll::Ready(obj) => Poll::Ready(match obj {
                 Ok(obj) => {
                     for digest in obj.child_objects() {
-                        self.queue.push_back(digest);
+                        if path.contains(&digest) {
+                            // stop walking entirely, since the cycle
+                            // would otherwise be reported repeatedly
+                            self.queue.clear();
+                            return Poll::Ready(Some(Err(Error::ObjectCycle {
+                                digest,
+                                parent: path.digest,
+                            })));
+                        }
+                        if !self.visited.insert(digest) {
+                            continue;
+                        }
+                        self.queue.push_back(Arc::new(WalkPath {
+                            digest,
+                            parent: Some(Arc::clone(&path)),
+                        }));
                     }
-                    Some(Ok((digest, obj)))
+                    Some(Ok((path.digest, obj)))
                 }
                 Err(err) => Some(Err(err)),
             }),
@@ -91,7 +145,20 @@ impl<'db> DatabaseIterator<'db> {
     /// # Errors
     /// The same as [`DatabaseView::read_object`]
     pub fn new(db: &'db dyn DatabaseView) -> Self {
-        let iter = db.find_digests(crate::graph::DigestSearchCriteria::All);
+        Self::with_criteria(db, crate::graph::DigestSearchCriteria::All)
+    }
+
+    /// Create an iterator that yields only the objects whose digest
+    /// starts with the given prefix from the given database.
+    ///
+    /// # Errors
+    /// The same as [`DatabaseView::read_object`]
+    pub fn with_prefix(db: &'db dyn DatabaseView, prefix: encoding::PartialDigest) -> Self {
+        Self::with_criteria(db, crate::graph::DigestSearchCriteria::StartsWith(prefix))
+    }
+
+    fn with_criteria(db: &'db dyn DatabaseView, criteria: DigestSearchCriteria) -> Self {
+        let iter = db.find_digests(criteria);
         DatabaseIterator {
             db,
             inner: iter,
@@ -158,6 +225,13 @@ pub trait DatabaseView: Sync + Send {
     /// Iterate all the object in this database.
     fn iter_objects(&self) -> DatabaseIterator<'_>;
 
+    /// Iterate all the objects in this database whose digest
+    /// starts with the given prefix.
+    ///
+    /// Objects are streamed as they are found, and implementations
+    /// should avoid visiting objects that cannot match the prefix.
+    fn iter_objects_with_prefix(&self, prefix: encoding::PartialDigest) -> DatabaseIterator<'_>;
+
     /// Walk all objects connected to the given root object.
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> DatabaseWalker<'db>;
 
@@ -243,6 +317,10 @@ impl<T: DatabaseView> DatabaseView for &T {
         DatabaseView::iter_objects(&**self)
     }
 
+    fn iter_objects_with_prefix(&self, prefix: encoding::PartialDigest) -> DatabaseIterator<'_> {
+        DatabaseView::iter_objects_with_prefix(&**self, prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> DatabaseWalker<'db> {
         DatabaseView::walk_objects(&**self, root)
     }
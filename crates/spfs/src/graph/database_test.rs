@@ -0,0 +1,82 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use futures::TryStreamExt;
+use rstest::rstest;
+
+use crate::fixtures::*;
+use crate::prelude::*;
+use crate::{Error, graph};
+
+#[rstest]
+#[tokio::test]
+async fn test_walk_objects_visits_each_once() {
+    init_logging();
+    let tmpdir = tmpdir();
+    let repo = crate::storage::fs::OpenFsRepository::create(tmpdir.path().join("repo"))
+        .await
+        .unwrap();
+
+    let manifest = graph::Manifest::default();
+    repo.write_object(&manifest).await.unwrap();
+    let first = graph::Layer::new(manifest.digest().unwrap());
+    let second = graph::Layer::new_with_manifest_and_annotations(
+        manifest.digest().unwrap(),
+        vec![("key", graph::AnnotationValue::string("value"))],
+    );
+    repo.write_object(&first).await.unwrap();
+    repo.write_object(&second).await.unwrap();
+    let platform = graph::Platform::from(
+        [first.digest().unwrap(), second.digest().unwrap()]
+            .into_iter()
+            .collect::<graph::Stack>(),
+    );
+    repo.write_object(&platform).await.unwrap();
+
+    let visited: Vec<_> = repo
+        .walk_objects(&platform.digest().unwrap())
+        .map_ok(|(digest, _)| digest)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        visited.len(),
+        4,
+        "the shared manifest should only be visited once: {visited:?}"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_walk_objects_reports_cycle() {
+    init_logging();
+    let tmpdir = tmpdir();
+    let repo = crate::storage::fs::OpenFsRepository::create(tmpdir.path().join("repo"))
+        .await
+        .unwrap();
+
+    // a layer whose manifest is missing, and then deliberately
+    // corrupt the database by storing the layer itself in place
+    // of that manifest so that the layer refers to itself
+    let missing = random_digest();
+    let layer = graph::Layer::new(missing);
+    repo.write_object(&layer).await.unwrap();
+    let mut encoded = Vec::new();
+    layer.encode(&mut encoded).unwrap();
+    let corrupt_path = repo.objects.build_digest_path(&missing);
+    repo.objects.ensure_base_dir(&corrupt_path).unwrap();
+    std::fs::write(&corrupt_path, encoded).unwrap();
+
+    let result: crate::Result<Vec<_>> = repo
+        .walk_objects(&layer.digest().unwrap())
+        .try_collect()
+        .await;
+    match result {
+        Err(Error::ObjectCycle { digest, parent }) => {
+            assert_eq!(digest, missing);
+            assert_eq!(parent, missing);
+        }
+        other => panic!("expected a cycle to be reported, got {other:?}"),
+    }
+}
@@ -13,6 +13,27 @@ use crate::{Result, encoding, tracking};
 #[path = "./entry_test.rs"]
 mod entry_test;
 
+/// Precedes the extended attributes of an entry in its digest encoding
+const XATTRS_MARKER: u8 = 1;
+/// Precedes the modification time of an entry in its digest encoding
+const MTIME_MARKER: u8 = 2;
+
+fn mtime_to_nanos(mtime: std::time::SystemTime) -> i64 {
+    match mtime.duration_since(std::time::UNIX_EPOCH) {
+        Ok(since) => since.as_nanos() as i64,
+        Err(err) => -(err.duration().as_nanos() as i64),
+    }
+}
+
+fn mtime_from_nanos(nanos: i64) -> std::time::SystemTime {
+    let offset = std::time::Duration::from_nanos(nanos.unsigned_abs());
+    if nanos < 0 {
+        std::time::UNIX_EPOCH - offset
+    } else {
+        std::time::UNIX_EPOCH + offset
+    }
+}
+
 /// Entry represents one item in the file system, such as
 /// a file or directory.
 #[derive(Copy, Clone)]
@@ -26,6 +47,10 @@ impl std::fmt::Debug for Entry<'_> {
             .field("mode", &self.mode())
             .field("size", &self.size())
             .field("object", self.object())
+            .field(
+                "xattrs",
+                &self.xattrs().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -47,6 +72,50 @@ impl<'buf> Entry<'buf> {
         size: u64,
         object: &encoding::Digest,
     ) -> flatbuffers::WIPOffset<spfs_proto::Entry<'fbb>> {
+        Self::build_with_metadata(
+            builder,
+            name,
+            kind,
+            mode,
+            size,
+            object,
+            &tracking::Xattrs::new(),
+            None,
+        )
+    }
+
+    /// Construct a valid entry from its component parts,
+    /// including any extended attributes and modification time
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_with_metadata<'fbb>(
+        builder: &mut flatbuffers::FlatBufferBuilder<'fbb>,
+        name: &str,
+        kind: tracking::EntryKind,
+        mode: u32,
+        size: u64,
+        object: &encoding::Digest,
+        xattrs: &tracking::Xattrs,
+        mtime: Option<std::time::SystemTime>,
+    ) -> flatbuffers::WIPOffset<spfs_proto::Entry<'fbb>> {
+        // entries without any metadata leave the fields unset entirely
+        // so that their encoding is the same as before they existed
+        let xattrs = (!xattrs.is_empty()).then(|| {
+            let xattrs = xattrs
+                .iter()
+                .map(|(name, value)| {
+                    let name = builder.create_string(name);
+                    let value = builder.create_vector(value);
+                    spfs_proto::Xattr::create(
+                        builder,
+                        &spfs_proto::XattrArgs {
+                            name: Some(name),
+                            value: Some(value),
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+            builder.create_vector(&xattrs)
+        });
         let name = builder.create_string(name);
         spfs_proto::Entry::create(
             builder,
@@ -56,6 +125,8 @@ impl<'buf> Entry<'buf> {
                 mode,
                 size_: size,
                 object: Some(object),
+                xattrs,
+                mtime_ns: mtime.map(mtime_to_nanos).unwrap_or_default(),
             },
         )
     }
@@ -65,13 +136,15 @@ impl<'buf> Entry<'buf> {
         name: &str,
         entry: &tracking::Entry<T>,
     ) -> flatbuffers::WIPOffset<spfs_proto::Entry<'fbb>> {
-        Self::build(
+        Self::build_with_metadata(
             builder,
             name,
             entry.kind,
             entry.mode,
             entry.size_for_legacy_encode(),
             &entry.object,
+            &entry.xattrs,
+            entry.mtime,
         )
     }
 
@@ -107,6 +180,23 @@ impl<'buf> Entry<'buf> {
         self.0.object()
     }
 
+    /// The extended attributes of this entry, sorted by name
+    pub fn xattrs(&self) -> impl Iterator<Item = (&'buf str, &'buf [u8])> {
+        self.0
+            .xattrs()
+            .into_iter()
+            .flatten()
+            .map(|xattr| (xattr.name(), xattr.value().bytes()))
+    }
+
+    /// The preserved modification time of this entry, if any
+    pub fn mtime(&self) -> Option<std::time::SystemTime> {
+        match self.0.mtime_ns() {
+            0 => None,
+            nanos => Some(mtime_from_nanos(nanos)),
+        }
+    }
+
     #[inline]
     pub fn is_symlink(&self) -> bool {
         unix_mode::is_symlink(self.mode())
@@ -174,7 +264,7 @@ impl encoding::Digestible for Entry<'_> {
 
     fn digest(&self) -> std::result::Result<spfs_proto::Digest, Self::Error> {
         let mut hasher = encoding::Hasher::new_sync();
-        self.legacy_encode(&mut hasher)?;
+        self.digest_encode(&mut hasher)?;
         Ok(hasher.digest())
     }
 }
@@ -185,7 +275,27 @@ impl Entry<'_> {
         self.kind().encode(&mut *writer)?;
         encoding::write_uint64(&mut *writer, self.mode() as u64)?;
         encoding::write_uint64(&mut *writer, self.size_for_legacy_encode())?;
-        encoding::write_string(writer, self.name())?;
+        encoding::write_string(&mut *writer, self.name())?;
+        // additional metadata is only included when present, each
+        // with a distinct marker, so that the digest of all other
+        // entries remains unchanged
+        let xattrs: Vec<_> = self.xattrs().collect();
+        if !xattrs.is_empty() {
+            encoding::write_uint8(&mut *writer, XATTRS_MARKER)?;
+            encoding::write_uint64(&mut *writer, xattrs.len() as u64)?;
+            for (name, value) in xattrs {
+                encoding::write_string(&mut *writer, name)?;
+                encoding::write_uint64(&mut *writer, value.len() as u64)?;
+                writer
+                    .write_all(value)
+                    .map_err(encoding::Error::FailedWrite)?;
+            }
+        }
+        let mtime_ns = self.0.mtime_ns();
+        if mtime_ns != 0 {
+            encoding::write_uint8(&mut *writer, MTIME_MARKER)?;
+            encoding::write_int(&mut *writer, mtime_ns)?;
+        }
         Ok(())
     }
 
@@ -207,7 +317,7 @@ impl Entry<'_> {
         let mut kind = tracking::EntryKind::decode(&mut *reader)?;
         let mode = encoding::read_uint64(&mut *reader)? as u32;
         let size = encoding::read_uint64(&mut *reader)?;
-        let name = encoding::read_string(reader)?;
+        let name = encoding::read_string_with_limit(reader, encoding::MAX_DECODED_STRING_LENGTH)?;
         if kind.is_blob() {
             kind = tracking::EntryKind::Blob(size);
         }
@@ -246,6 +356,8 @@ impl EntryBuf {
                         }
                     },
                     name: Some(name),
+                    xattrs: None,
+                    mtime_ns: 0,
                 },
             );
             builder.finish_minimal(e);
@@ -277,6 +389,8 @@ impl EntryBuf {
                         }
                     },
                     name: Some(name),
+                    xattrs: None,
+                    mtime_ns: 0,
                 },
             );
             builder.finish_minimal(e);
@@ -7,7 +7,7 @@ use std::io::BufRead;
 
 use spfs_proto::ManifestArgs;
 
-use super::object::HeaderBuilder;
+use super::object::{EncodingFormat, HeaderBuilder};
 use super::{Entry, ObjectKind, Tree};
 use crate::prelude::*;
 use crate::{Result, encoding, tracking};
@@ -128,6 +128,11 @@ impl Manifest {
                     object: *entry.object(),
                     user_data: (),
                     legacy_size: entry.size_for_legacy_encode(),
+                    xattrs: entry
+                        .xattrs()
+                        .map(|(name, value)| (name.to_owned(), value.to_vec()))
+                        .collect(),
+                    mtime: entry.mtime(),
                 };
                 if entry.kind().is_tree() {
                     new_entry.object = encoding::NULL_DIGEST.into();
@@ -202,12 +207,24 @@ impl ManifestBuilder {
     /// Build a manifest that contains `source` as the root
     /// entry. If `source` is not a tree, an empty manifest is
     /// returned.
+    ///
+    /// Extended attributes and modification times are only stored
+    /// when the manifest uses the flatbuffers encoding format, since
+    /// the legacy format has no way to represent them.
     pub fn build<T>(&self, source: &tracking::Entry<T>) -> Manifest
     where
         T: std::cmp::Eq + std::cmp::PartialEq,
     {
+        let include_metadata = self.header.encoding_format() != EncodingFormat::Legacy;
         super::BUILDER.with_borrow_mut(|builder| {
-            let trees = Self::build_from_entry(builder, source);
+            let mut dropped_metadata = 0;
+            let trees =
+                Self::build_from_entry(builder, source, include_metadata, &mut dropped_metadata);
+            if dropped_metadata > 0 {
+                tracing::warn!(
+                    "Extended attributes or modification times of {dropped_metadata} entries were not stored, they require the flatbuffers encoding format"
+                );
+            }
             let trees = builder.create_vector(&trees);
             let manifest =
                 spfs_proto::Manifest::create(builder, &ManifestArgs { trees: Some(trees) });
@@ -287,6 +304,8 @@ impl ManifestBuilder {
     fn build_from_entry<'buf, T>(
         builder: &mut flatbuffers::FlatBufferBuilder<'buf>,
         source: &tracking::Entry<T>,
+        include_metadata: bool,
+        dropped_metadata: &mut usize,
     ) -> Vec<flatbuffers::WIPOffset<spfs_proto::Tree<'buf>>>
     where
         T: std::cmp::Eq + std::cmp::PartialEq,
@@ -298,10 +317,24 @@ impl ManifestBuilder {
         let mut sub_manifests = Vec::new();
         entries.sort_unstable();
 
+        let no_xattrs = tracking::Xattrs::new();
         for node in entries {
+            let (xattrs, mtime) = if include_metadata {
+                (&node.entry.xattrs, node.entry.mtime)
+            } else {
+                if !node.entry.xattrs.is_empty() || node.entry.mtime.is_some() {
+                    *dropped_metadata += 1;
+                }
+                (&no_xattrs, None)
+            };
             let converted = match node.entry.kind {
                 tracking::EntryKind::Tree => {
-                    let sub = Self::build_from_entry(builder, node.entry);
+                    let sub = Self::build_from_entry(
+                        builder,
+                        node.entry,
+                        include_metadata,
+                        dropped_metadata,
+                    );
                     let first_offset = sub.first().expect("should always have a root entry");
                     let wip_data = builder.unfinished_data();
                     // WIPOffset is relative to the end of the buffer
@@ -315,16 +348,27 @@ impl ManifestBuilder {
                         .digest()
                         .expect("entry should have a valid digest");
                     sub_manifests.push(sub);
-                    Entry::build(
+                    Entry::build_with_metadata(
                         builder,
                         node.path.as_str(),
                         node.entry.kind,
                         node.entry.mode,
                         node.entry.size_for_legacy_encode(),
                         &sub_root_digest,
+                        xattrs,
+                        mtime,
                     )
                 }
-                _ => Entry::from(builder, node.path.as_str(), node.entry),
+                _ => Entry::build_with_metadata(
+                    builder,
+                    node.path.as_str(),
+                    node.entry.kind,
+                    node.entry.mode,
+                    node.entry.size_for_legacy_encode(),
+                    &node.entry.object,
+                    xattrs,
+                    mtime,
+                ),
             };
             roots.push(converted);
         }
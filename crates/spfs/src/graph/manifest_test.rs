@@ -5,6 +5,8 @@
 use rstest::rstest;
 
 use super::Manifest;
+use crate::graph::object::EncodingFormat;
+use crate::prelude::*;
 use crate::tracking::{self, EntryKind};
 
 #[rstest]
@@ -36,3 +38,48 @@ fn test_manifest_from_tracking_manifest() {
 
     assert!(tm == gm2tm);
 }
+
+#[rstest]
+fn test_manifest_xattrs() {
+    let mut tm = tracking::Manifest::<()>::default();
+    tm.mkfile("bin").unwrap();
+    let build = |tm: &tracking::Manifest, format| {
+        Manifest::builder()
+            .with_header(|h| h.with_encoding_format(format))
+            .build(tm.root())
+    };
+    let plain = build(&tm, EncodingFormat::FlatBuffers);
+    for tree in plain.iter_trees() {
+        let mut encoded = Vec::new();
+        tree.digest_encode(&mut encoded).unwrap();
+        let mut legacy = Vec::new();
+        tree.legacy_encode(&mut legacy).unwrap();
+        assert_eq!(
+            encoded, legacy,
+            "trees without xattrs should keep their original digest"
+        );
+    }
+
+    let mut tm = tracking::Manifest::<()>::default();
+    tm.mkfile("bin")
+        .unwrap()
+        .xattrs
+        .insert("security.capability".into(), vec![1, 0, 0, 2]);
+    let with_xattrs = build(&tm, EncodingFormat::FlatBuffers);
+    assert_ne!(
+        with_xattrs.digest().unwrap(),
+        plain.digest().unwrap(),
+        "xattrs should contribute to the manifest digest"
+    );
+    assert!(
+        with_xattrs.to_tracking_manifest() == tm,
+        "xattrs should survive a round trip through the graph manifest"
+    );
+
+    let legacy = build(&tm, EncodingFormat::Legacy);
+    assert_eq!(
+        legacy.digest().unwrap(),
+        plain.digest().unwrap(),
+        "xattrs cannot be stored using the legacy encoding format"
+    );
+}
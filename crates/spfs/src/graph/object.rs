@@ -767,6 +767,11 @@ impl HeaderBuilder {
         self
     }
 
+    /// The encoding format that will be used for the built header
+    pub fn encoding_format(&self) -> EncodingFormat {
+        self.encoding_format
+    }
+
     /// Copy valid and known components from another header
     pub fn copy_from(mut self, other: &Header) -> Self {
         if let Some(digest_strategy) = other.digest_strategy() {
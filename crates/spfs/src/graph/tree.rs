@@ -85,7 +85,7 @@ impl encoding::Digestible for Tree<'_> {
 
     fn digest(&self) -> std::result::Result<spfs_proto::Digest, Self::Error> {
         let mut hasher = encoding::Hasher::new_sync();
-        self.legacy_encode(&mut hasher)?;
+        self.digest_encode(&mut hasher)?;
         Ok(hasher.digest())
     }
 }
@@ -128,6 +128,8 @@ impl TreeBuf {
                             mode: entry.mode(),
                             size_: entry.size_for_legacy_encode(),
                             name: Some(name),
+                            xattrs: None,
+                            mtime_ns: 0,
                         },
                     )
                 })
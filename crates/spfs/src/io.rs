@@ -80,16 +80,25 @@ pub fn format_diffs<'a, U1: 'a, U2: 'a>(
     let mut outputs = Vec::new();
     for diff in diffs {
         let mut abouts = Vec::new();
-        if let tracking::DiffMode::Changed(a, b) = &diff.mode {
-            if a.mode != b.mode {
-                abouts.push(format!("mode {{{:06o}=>{:06o}}}", a.mode, b.mode));
+        match &diff.mode {
+            tracking::DiffMode::Changed(a, b) => {
+                if a.mode != b.mode {
+                    abouts.push(format!("mode {{{:06o}=>{:06o}}}", a.mode, b.mode));
+                }
+                if a.object != b.object {
+                    abouts.push("content".to_string());
+                }
+                if a.size() != b.size() {
+                    abouts.push(format!("size {{{}=>{}}}", a.size(), b.size()));
+                }
             }
-            if a.object != b.object {
-                abouts.push("content".to_string());
-            }
-            if a.size() != b.size() {
-                abouts.push(format!("size {{{}=>{}}}", a.size(), b.size()));
+            tracking::DiffMode::Renamed(from, a, b) => {
+                abouts.push(format!("from /spfs{from}"));
+                if a.mode != b.mode {
+                    abouts.push(format!("mode {{{:06o}=>{:06o}}}", a.mode, b.mode));
+                }
             }
+            _ => {}
         }
         let about = if !abouts.is_empty() {
             format!(" [{}]", abouts.join(", ")).dimmed().to_string()
@@ -103,6 +112,7 @@ pub fn format_diffs<'a, U1: 'a, U2: 'a>(
             tracking::DiffMode::Added(..) => out.green(),
             tracking::DiffMode::Removed(..) => out.red(),
             tracking::DiffMode::Changed(..) => out.bright_blue(),
+            tracking::DiffMode::Renamed(..) => out.yellow(),
             _ => out.dimmed(),
         };
         outputs.push(out.to_string())
@@ -48,7 +48,7 @@ pub use bootstrap::{
 pub use check::Checker;
 pub use clean::Cleaner;
 pub use commit::Committer;
-pub use diff::{diff, diff_runtime_changes, runtime_active_changes};
+pub use diff::{diff, diff_runtime_changes, diff_summary, runtime_active_changes};
 pub use encoding::Digest;
 pub use error::{Error, OsError, OsErrorExt, Result};
 pub use resolve::{
@@ -63,16 +63,20 @@ pub use resolve::{
 };
 pub use spfs_encoding as encoding;
 pub use status::{
+    RuntimeBackingRepo,
     active_runtime,
     change_to_durable_runtime,
     compute_runtime_manifest,
     exit_runtime,
     get_runtime_backing_repo,
+    get_runtime_backing_repo_with_fallback,
     initialize_runtime,
     make_active_runtime_editable,
     make_runtime_durable,
     reinitialize_runtime,
+    reinitialize_runtime_preserving_env,
     remount_runtime,
+    resolve_runtime_layers,
 };
 pub use sync::Syncer;
 
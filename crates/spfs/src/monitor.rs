@@ -363,6 +363,29 @@ pub async fn wait_for_empty_runtime(rt: &runtime::Runtime, config: &crate::Confi
     Ok(())
 }
 
+/// Check if the monitor process of the given runtime appears to still be running.
+///
+/// This only makes sense for runtimes that were started on the current machine.
+pub fn is_monitor_running(rt: &runtime::Runtime) -> bool {
+    if let Some(pid) = rt.status.monitor {
+        // we are blatantly ignoring the fact that this pid might
+        // have been reused and is not the monitor anymore. Given
+        // that there will always be a race condition to this effect
+        // even if we did try to check the command line args for this
+        // process. So we stick on the extra conservative side
+        is_process_running(pid)
+    } else {
+        false
+    }
+}
+
+fn is_process_running(pid: u32) -> bool {
+    // sending a null signal to the pid just allows us to check
+    // if the process actually exists without affecting it
+    let pid = nix::unistd::Pid::from_raw(pid as i32);
+    nix::sys::signal::kill(pid, None).is_ok()
+}
+
 /// Identify the mount namespace of the provided process id.
 ///
 /// Return None if the pid is not found.
@@ -28,6 +28,30 @@ pub async fn wait_for_empty_runtime(_rt: &runtime::Runtime, _config: &crate::Con
     todo!()
 }
 
+/// Check if the monitor process of the given runtime appears to still be running.
+///
+/// This only makes sense for runtimes that were started on the current machine.
+pub fn is_monitor_running(rt: &runtime::Runtime) -> bool {
+    if let Some(pid) = rt.status.monitor {
+        // as with unix, a reused pid will be seen as a running monitor
+        is_process_running(pid)
+    } else {
+        false
+    }
+}
+
+fn is_process_running(pid: u32) -> bool {
+    // PROCESS_SYNCHRONIZE seems like the most limited access we can request,
+    // which simply allows us to wait on the PID
+    let access = windows::Win32::System::Threading::PROCESS_SYNCHRONIZE;
+    let result = unsafe { windows::Win32::System::Threading::OpenProcess(access, false, pid) };
+    let Ok(handle) = result else {
+        return false;
+    };
+    let _ = unsafe { windows::Win32::Foundation::CloseHandle(handle) };
+    true
+}
+
 /// Identify the mount namespace of the provided process id.
 ///
 /// Return None if the pid is not found.
@@ -5,8 +5,13 @@
 use std::convert::{TryFrom, TryInto};
 use std::ops::Not;
 
+use super::Validate;
 use crate::{Error, Result, encoding, graph, storage, tracking};
 
+#[cfg(test)]
+#[path = "./conversions_test.rs"]
+mod conversions_test;
+
 pub(crate) fn convert_to_datetime(
     source: Option<super::DateTime>,
 ) -> Result<chrono::DateTime<chrono::Utc>> {
@@ -77,6 +82,7 @@ impl TryFrom<Option<super::Tag>> for tracking::Tag {
 impl TryFrom<super::Tag> for tracking::Tag {
     type Error = Error;
     fn try_from(source: super::Tag) -> Result<Self> {
+        source.validate()?;
         let org = source.org.is_empty().not().then_some(source.org);
         let mut tag = Self::new(org, source.name, convert_digest(source.target)?)?;
         tag.parent = convert_digest(source.parent)?;
@@ -218,6 +224,7 @@ impl TryFrom<super::Platform> for graph::Platform {
     type Error = Error;
 
     fn try_from(source: super::Platform) -> Result<Self> {
+        source.validate()?;
         Ok(Self::from(
             source
                 .stack
@@ -246,7 +253,11 @@ impl From<&graph::Layer> for super::Layer {
 impl TryFrom<super::Layer> for graph::Layer {
     type Error = Error;
     fn try_from(source: super::Layer) -> Result<Self> {
-        let digest = Some(convert_digest(source.manifest)?);
+        source.validate()?;
+        let digest = source
+            .manifest
+            .map(encoding::Digest::try_from)
+            .transpose()?;
 
         if let Some(manifest_digest) = digest {
             if !source.annotations.is_empty() {
@@ -293,6 +304,7 @@ impl From<&graph::Manifest> for super::Manifest {
 impl TryFrom<super::Manifest> for graph::Manifest {
     type Error = Error;
     fn try_from(source: super::Manifest) -> Result<Self> {
+        source.validate()?;
         let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
         let make_tree = |entry: super::Tree| {
             let entries = entry
@@ -314,6 +326,8 @@ impl TryFrom<super::Manifest> for graph::Manifest {
                             mode: entry.mode,
                             size_: entry.size,
                             name: Some(name),
+                            xattrs: None,
+                            mtime_ns: 0,
                         },
                     ))
                 })
@@ -422,6 +436,7 @@ impl From<&graph::Blob> for super::Blob {
 impl TryFrom<super::Blob> for graph::Blob {
     type Error = Error;
     fn try_from(source: super::Blob) -> Result<Self> {
+        source.validate()?;
         Ok(Self::new(convert_digest(source.payload)?, source.size))
     }
 }
@@ -0,0 +1,71 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+
+use crate::fixtures::*;
+use crate::prelude::*;
+use crate::{graph, proto, tracking};
+
+#[rstest]
+fn test_digest_round_trip() {
+    let digest = random_digest();
+    let message = proto::Digest::from(digest);
+    let actual = crate::encoding::Digest::try_from(message).unwrap();
+    assert_eq!(actual, digest);
+}
+
+#[rstest]
+fn test_tag_round_trip() {
+    let mut tag = tracking::Tag::new(Some("org".into()), "name", random_digest()).unwrap();
+    tag.parent = random_digest();
+    let message = proto::Tag::from(&tag);
+    let actual = tracking::Tag::try_from(message).unwrap();
+    assert_eq!(actual, tag);
+}
+
+#[rstest]
+fn test_platform_round_trip() {
+    let platform = graph::Platform::from(
+        [random_digest(), random_digest()]
+            .into_iter()
+            .collect::<graph::Stack>(),
+    );
+    let message = proto::Platform::from(&platform);
+    let actual = graph::Platform::try_from(message).unwrap();
+    assert_eq!(actual.digest().unwrap(), platform.digest().unwrap());
+}
+
+#[rstest]
+#[case::manifest_only(graph::Layer::new(random_digest()))]
+#[case::annotations_only(graph::Layer::new_with_annotations(vec![
+    ("key", graph::AnnotationValue::string("value")),
+]))]
+#[case::manifest_and_annotations(graph::Layer::new_with_manifest_and_annotations(
+    random_digest(),
+    vec![("key", graph::AnnotationValue::string("value"))],
+))]
+fn test_layer_round_trip(#[case] layer: graph::Layer) {
+    let message = proto::Layer::from(&layer);
+    let actual = graph::Layer::try_from(message).unwrap();
+    assert_eq!(actual.digest().unwrap(), layer.digest().unwrap());
+}
+
+#[rstest]
+fn test_blob_round_trip() {
+    let blob = graph::Blob::new(random_digest(), 42);
+    let message = proto::Blob::from(&blob);
+    let actual = graph::Blob::try_from(message).unwrap();
+    assert_eq!(actual.digest().unwrap(), blob.digest().unwrap());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_manifest_round_trip() {
+    let tmprepo = tmprepo("fs").await;
+    let manifest = generate_tree(&tmprepo).await.to_graph_manifest();
+    let message = proto::Manifest::from(&manifest);
+    let actual = graph::Manifest::try_from(message).unwrap();
+    assert_eq!(actual.digest().unwrap(), manifest.digest().unwrap());
+}
@@ -6,6 +6,7 @@
 
 mod conversions;
 mod result;
+mod validate;
 mod generated {
     #![allow(clippy::derive_partial_eq_without_eq)]
     tonic::include_proto!("spfs");
@@ -14,5 +15,6 @@ mod generated {
 pub(crate) use conversions::{convert_digest, convert_from_datetime};
 pub use generated::*;
 pub(crate) use result::RpcResult;
+pub use validate::Validate;
 #[cfg(feature = "server")]
 pub(crate) use {conversions::convert_to_datetime, result::handle_error};
@@ -0,0 +1,185 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use crate::{Error, Result, encoding};
+
+#[cfg(test)]
+#[path = "./validate_test.rs"]
+mod validate_test;
+
+/// Checks the invariants of a decoded rpc message.
+///
+/// Generated message types allow every field to be left empty, so
+/// messages that are received over the wire are validated before
+/// being converted, rather than failing somewhere further downstream.
+pub trait Validate {
+    /// Return an [`Error::InvalidRpcMessage`] describing the
+    /// first problem found with this message, if any
+    fn validate(&self) -> Result<()>;
+}
+
+fn invalid(message: &'static str, field: &'static str, reason: impl Into<String>) -> Error {
+    Error::InvalidRpcMessage {
+        message,
+        field,
+        reason: reason.into(),
+    }
+}
+
+fn validate_digest(
+    message: &'static str,
+    field: &'static str,
+    digest: Option<&super::Digest>,
+) -> Result<()> {
+    match digest {
+        None => Err(invalid(message, field, "missing required digest")),
+        Some(digest) => digest.validate().map_err(|err| match err {
+            Error::InvalidRpcMessage { reason, .. } => invalid(message, field, reason),
+            err => err,
+        }),
+    }
+}
+
+impl Validate for super::Digest {
+    fn validate(&self) -> Result<()> {
+        if self.bytes.len() != encoding::DIGEST_SIZE {
+            return Err(invalid(
+                "Digest",
+                "bytes",
+                format!(
+                    "expected {} bytes, got {}",
+                    encoding::DIGEST_SIZE,
+                    self.bytes.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Tag {
+    fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(invalid("Tag", "name", "must not be empty"));
+        }
+        validate_digest("Tag", "target", self.target.as_ref())?;
+        validate_digest("Tag", "parent", self.parent.as_ref())?;
+        if self.time.is_none() {
+            return Err(invalid("Tag", "time", "missing required timestamp"));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Object {
+    fn validate(&self) -> Result<()> {
+        use super::object::Kind;
+        match &self.kind {
+            Some(Kind::Platform(o)) => o.validate(),
+            Some(Kind::Layer(o)) => o.validate(),
+            Some(Kind::Manifest(o)) => o.validate(),
+            Some(Kind::Blob(o)) => o.validate(),
+            Some(Kind::Tree(_)) | Some(Kind::Mask(_)) => Err(invalid(
+                "Object",
+                "kind",
+                "deprecated object kinds are not supported",
+            )),
+            Some(Kind::Buffer(buf)) if buf.is_empty() => {
+                Err(invalid("Object", "buffer", "must not be empty"))
+            }
+            Some(Kind::Buffer(_)) => Ok(()),
+            None => Err(invalid("Object", "kind", "missing required object kind")),
+        }
+    }
+}
+
+impl Validate for super::Platform {
+    fn validate(&self) -> Result<()> {
+        for digest in self.stack.iter() {
+            validate_digest("Platform", "stack", Some(digest))?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Layer {
+    fn validate(&self) -> Result<()> {
+        if self.manifest.is_none() && self.annotations.is_empty() {
+            return Err(invalid(
+                "Layer",
+                "manifest",
+                "a layer requires at least one of: a manifest digest, or an annotation",
+            ));
+        }
+        if let Some(manifest) = self.manifest.as_ref() {
+            validate_digest("Layer", "manifest", Some(manifest))?;
+        }
+        for annotation in self.annotations.iter() {
+            annotation.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Annotation {
+    fn validate(&self) -> Result<()> {
+        use super::annotation::Value;
+        if self.key.is_empty() {
+            return Err(invalid("Annotation", "key", "must not be empty"));
+        }
+        if let Some(Value::Digest(digest)) = self.value.as_ref() {
+            validate_digest("Annotation", "value", Some(digest))?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Manifest {
+    fn validate(&self) -> Result<()> {
+        if let Some(root) = self.root.as_ref() {
+            root.validate()?;
+        } else if !self.trees.is_empty() {
+            return Err(invalid(
+                "Manifest",
+                "root",
+                "missing root tree for a manifest with other trees",
+            ));
+        }
+        for tree in self.trees.iter() {
+            tree.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Tree {
+    fn validate(&self) -> Result<()> {
+        for entry in self.entries.iter() {
+            entry.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for super::Entry {
+    fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(invalid("Entry", "name", "must not be empty"));
+        }
+        if super::EntryKind::try_from(self.kind).is_err() {
+            return Err(invalid(
+                "Entry",
+                "kind",
+                format!("unknown entry kind {}", self.kind),
+            ));
+        }
+        validate_digest("Entry", "object", self.object.as_ref())
+    }
+}
+
+impl Validate for super::Blob {
+    fn validate(&self) -> Result<()> {
+        validate_digest("Blob", "payload", self.payload.as_ref())
+    }
+}
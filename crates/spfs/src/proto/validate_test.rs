@@ -0,0 +1,86 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+
+use super::Validate;
+use crate::fixtures::*;
+use crate::{Error, proto};
+
+fn valid_tag() -> proto::Tag {
+    proto::Tag {
+        org: String::new(),
+        name: "name".into(),
+        target: Some(random_digest().into()),
+        parent: Some(random_digest().into()),
+        user: "user".into(),
+        time: Some(proto::convert_from_datetime(&chrono::Utc::now())),
+    }
+}
+
+#[rstest]
+fn test_validate_tag() {
+    valid_tag()
+        .validate()
+        .expect("a complete tag should be valid");
+}
+
+#[rstest]
+#[case::no_name(proto::Tag { name: String::new(), ..valid_tag() }, "name")]
+#[case::no_target(proto::Tag { target: None, ..valid_tag() }, "target")]
+#[case::short_parent(
+    proto::Tag { parent: Some(proto::Digest { bytes: vec![0; 4] }), ..valid_tag() },
+    "parent",
+)]
+#[case::no_time(proto::Tag { time: None, ..valid_tag() }, "time")]
+fn test_validate_tag_invalid(#[case] tag: proto::Tag, #[case] expected: &str) {
+    let err = tag.validate().expect_err("tag should be invalid");
+    assert!(
+        matches!(&err, Error::InvalidRpcMessage { message: "Tag", field, .. } if *field == expected),
+        "expected an error for Tag.{expected}, got: {err}"
+    );
+    let err = crate::tracking::Tag::try_from(tag).expect_err("conversion should fail");
+    assert!(matches!(err, Error::InvalidRpcMessage { .. }));
+}
+
+#[rstest]
+fn test_validate_layer_requires_content() {
+    let layer = proto::Layer {
+        manifest: None,
+        annotations: Vec::new(),
+    };
+    let err = layer.validate().expect_err("empty layer should be invalid");
+    assert!(matches!(
+        err,
+        Error::InvalidRpcMessage {
+            message: "Layer",
+            ..
+        }
+    ));
+}
+
+#[rstest]
+fn test_validate_entry_requires_object() {
+    let manifest = proto::Manifest {
+        root: Some(proto::Tree {
+            entries: vec![proto::Entry {
+                object: None,
+                kind: proto::EntryKind::Blob as i32,
+                mode: 0o644,
+                size: 0,
+                name: "file".into(),
+            }],
+        }),
+        trees: Vec::new(),
+    };
+    let err = manifest.validate().expect_err("entry should be invalid");
+    assert!(matches!(
+        err,
+        Error::InvalidRpcMessage {
+            message: "Entry",
+            field: "object",
+            ..
+        }
+    ));
+}
@@ -31,10 +31,12 @@ pub use storage::{
     KeyValuePairBuf,
     MountBackend,
     OwnedRuntime,
+    PruneProtection,
     Runtime,
     STARTUP_FILES_LOCATION,
     Status,
     Storage,
+    is_runtime_tag,
     makedirs_with_perms,
 };
 #[cfg(windows)]
@@ -431,6 +431,16 @@ impl OwnedRuntime {
 #[derive(Clone, Debug)]
 pub struct EnvKeyValue(pub String, pub String);
 
+/// The reason that a runtime is kept when pruning runtimes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PruneProtection {
+    /// The runtime is the one currently in use
+    Active,
+    /// The runtime is durable, and durable runtimes were not
+    /// selected for removal
+    Durable,
+}
+
 /// Represents an active spfs session.
 ///
 /// The runtime contains the working files for a spfs
@@ -495,6 +505,35 @@ impl Runtime {
         self.data.is_durable()
     }
 
+    /// True if this runtime was started on the current machine
+    /// and its monitor process is no longer running.
+    ///
+    /// Orphaned runtimes are left behind when the monitor is killed
+    /// or crashes, and will not be cleaned up on their own.
+    pub fn is_orphaned(&self) -> bool {
+        let is_same_host =
+            whoami::fallible::hostname().is_ok_and(|host| host == self.author.host_name);
+        is_same_host && self.status.monitor.is_some() && !crate::monitor::is_monitor_running(self)
+    }
+
+    /// Why this runtime must be kept when pruning runtimes, if at all.
+    ///
+    /// The active runtime is never pruned, and durable runtimes are
+    /// only pruned when `remove_durable` is set.
+    pub fn prune_protection(
+        &self,
+        active_runtime: Option<&str>,
+        remove_durable: bool,
+    ) -> Option<PruneProtection> {
+        if active_runtime == Some(self.name()) {
+            return Some(PruneProtection::Active);
+        }
+        if self.is_durable() && !remove_durable {
+            return Some(PruneProtection::Durable);
+        }
+        None
+    }
+
     /// Store a list of arbitrary key-value string pairs in the runtime
     pub async fn add_annotations(
         &mut self,
@@ -1308,6 +1347,11 @@ impl std::fmt::Display for RuntimeDataType {
     }
 }
 
+/// True if the given tag is one used to store the data of a runtime
+pub fn is_runtime_tag(spec: &tracking::TagSpec) -> bool {
+    spec.path().starts_with("spfs/runtimes")
+}
+
 fn runtime_tag<S: std::fmt::Display>(
     data_type: RuntimeDataType,
     name: S,
@@ -11,7 +11,7 @@ use spfs_encoding::Digestible;
 
 #[cfg(unix)]
 use super::makedirs_with_perms;
-use super::{Data, Storage};
+use super::{Data, PruneProtection, Storage};
 use crate::fixtures::*;
 use crate::graph::object::{DigestStrategy, EncodingFormat};
 use crate::graph::{AnnotationValue, Layer, Platform};
@@ -459,6 +459,39 @@ async fn test_storage_remove_runtime(tmpdir: tempfile::TempDir) {
         .expect("should remove runtime properly");
 }
 
+#[rstest]
+#[case::transient(false, None, false, None)]
+#[case::active(false, Some("test-runtime"), true, Some(PruneProtection::Active))]
+#[case::active_durable(true, Some("test-runtime"), true, Some(PruneProtection::Active))]
+#[case::other_active(false, Some("other-runtime"), false, None)]
+#[case::durable(true, None, false, Some(PruneProtection::Durable))]
+#[case::remove_durable(true, None, true, None)]
+#[tokio::test]
+async fn test_runtime_prune_protection(
+    tmpdir: tempfile::TempDir,
+    #[case] durable: bool,
+    #[case] active_runtime: Option<&str>,
+    #[case] remove_durable: bool,
+    #[case] expected: Option<PruneProtection>,
+) {
+    let root = tmpdir.path().to_string_lossy().to_string();
+    let repo = crate::storage::RepositoryHandle::from(
+        crate::storage::fs::MaybeOpenFsRepository::create(root)
+            .await
+            .unwrap(),
+    );
+    let storage = Storage::new(repo).unwrap();
+
+    let runtime = storage
+        .create_named_runtime("test-runtime", durable, Vec::new())
+        .await
+        .expect("failed to create runtime");
+    assert_eq!(
+        runtime.prune_protection(active_runtime, remove_durable),
+        expected
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_storage_iter_runtimes(tmpdir: tempfile::TempDir) {
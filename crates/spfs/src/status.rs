@@ -9,8 +9,13 @@ mod os;
 pub use os::*;
 
 use super::config::get_config;
+use crate::prelude::*;
 use crate::storage::FromConfig;
-use crate::{Error, Result, runtime, tracking};
+use crate::{Error, Result, graph, runtime, tracking};
+
+#[cfg(test)]
+#[path = "./status_test.rs"]
+mod status_test;
 
 static SPFS_RUNTIME: &str = "SPFS_RUNTIME";
 const RUNTIME_REPO_NAME: &str = "<runtime>";
@@ -66,12 +71,153 @@ pub async fn get_runtime_backing_repo(
     }
 }
 
+/// Identifies which repository was selected to back a runtime,
+/// see [`get_runtime_backing_repo_with_fallback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeBackingRepo {
+    /// The repository normally used for the runtime, as
+    /// returned by [`get_runtime_backing_repo`]
+    Primary,
+    /// A configured remote repository, by name
+    Remote(String),
+}
+
+impl std::fmt::Display for RuntimeBackingRepo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => RUNTIME_REPO_NAME.fmt(f),
+            Self::Remote(name) => name.fmt(f),
+        }
+    }
+}
+
+/// Get the repository that is being used as the backing storage for the given
+/// runtime, falling back to the configured remotes when it cannot be opened.
+///
+/// The repository from [`get_runtime_backing_repo`] is returned whenever it
+/// can be opened, without checking its contents. Otherwise, the configured
+/// remotes are checked in turn (starting with `origin`) and the first one that
+/// contains all of the layers in the runtime's stack is returned instead.
+///
+/// See [`resolve_runtime_layers`] to also fall back when the repository is
+/// missing some of the runtime's layers.
+pub async fn get_runtime_backing_repo_with_fallback(
+    rt: &runtime::Runtime,
+) -> Result<(crate::storage::RepositoryHandle, RuntimeBackingRepo)> {
+    match get_runtime_backing_repo(rt).await {
+        Ok(repo) => Ok((repo, RuntimeBackingRepo::Primary)),
+        Err(err) => {
+            tracing::debug!("runtime backing repo is not available: {err}");
+            let config = get_config()?;
+            find_remote_with_runtime_layers(&config, rt)
+                .await
+                .ok_or(err)
+        }
+    }
+}
+
+/// Resolve the layers in the runtime's stack, along with the repository
+/// that they were read from.
+///
+/// The layers are read from the repository from [`get_runtime_backing_repo`].
+/// Only if it cannot be opened or is missing some of the layers are the
+/// configured remotes checked for one that contains all of them.
+pub async fn resolve_runtime_layers(
+    rt: &runtime::Runtime,
+) -> Result<(
+    crate::storage::RepositoryHandle,
+    RuntimeBackingRepo,
+    Vec<graph::Layer>,
+)> {
+    let config = get_config()?;
+    resolve_runtime_layers_from(&config, rt, get_runtime_backing_repo(rt).await).await
+}
+
+async fn resolve_runtime_layers_from(
+    config: &crate::Config,
+    rt: &runtime::Runtime,
+    primary: Result<crate::storage::RepositoryHandle>,
+) -> Result<(
+    crate::storage::RepositoryHandle,
+    RuntimeBackingRepo,
+    Vec<graph::Layer>,
+)> {
+    let primary_err = match primary {
+        Ok(repo) => match super::resolve_stack_to_layers(&rt.status.stack, Some(&repo)).await {
+            Ok(layers) => return Ok((repo, RuntimeBackingRepo::Primary, layers)),
+            Err(err) if err.try_next_repo() => {
+                tracing::debug!("runtime backing repo is missing layers: {err}");
+                err
+            }
+            Err(err) => return Err(err),
+        },
+        Err(err) => {
+            tracing::debug!("runtime backing repo is not available: {err}");
+            err
+        }
+    };
+
+    let Some((repo, source)) = find_remote_with_runtime_layers(config, rt).await else {
+        return Err(primary_err);
+    };
+    let layers = super::resolve_stack_to_layers(&rt.status.stack, Some(&repo)).await?;
+    Ok((repo, source, layers))
+}
+
+/// Find the first configured remote, starting with `origin`, that
+/// contains all of the layers in the runtime's stack.
+async fn find_remote_with_runtime_layers(
+    config: &crate::Config,
+    rt: &runtime::Runtime,
+) -> Option<(crate::storage::RepositoryHandle, RuntimeBackingRepo)> {
+    let mut remote_names = config.list_remote_names();
+    remote_names.sort_by_key(|name| (name != "origin", name.clone()));
+    for name in remote_names {
+        let repo = match config.get_remote(&name).await {
+            Ok(repo) => repo,
+            Err(err) => {
+                tracing::debug!("skipping remote '{name}' as runtime backing repo: {err}");
+                continue;
+            }
+        };
+        if has_all_runtime_layers(&repo, rt).await {
+            tracing::warn!("using remote '{name}' as the backing repo for runtime");
+            return Some((repo, RuntimeBackingRepo::Remote(name)));
+        }
+    }
+    None
+}
+
+/// True if all of the layers in the runtime's stack exist in the given repo
+async fn has_all_runtime_layers(
+    repo: &crate::storage::RepositoryHandle,
+    rt: &runtime::Runtime,
+) -> bool {
+    for digest in rt.status.stack.iter_bottom_up() {
+        if !repo.has_object(digest).await {
+            return false;
+        }
+    }
+    true
+}
+
 /// Calculate the file manifest for the layers in the given runtime.
 ///
 /// The returned manifest DOES NOT include any active changes to the runtime.
 pub async fn compute_runtime_manifest(rt: &runtime::Runtime) -> Result<tracking::Manifest> {
-    let spec = rt.status.stack.iter_bottom_up().collect();
-    super::compute_environment_manifest(&spec, &get_runtime_backing_repo(rt).await?).await
+    let (repo, _, layers) = resolve_runtime_layers(rt).await?;
+    let mut manifest = tracking::Manifest::default();
+    for layer in layers {
+        if let Some(manifest_digest) = layer.manifest() {
+            manifest.update(
+                &repo
+                    .read_manifest(*manifest_digest)
+                    .await?
+                    .to_tracking_manifest(),
+            )
+        }
+    }
+    Ok(manifest)
 }
 
 /// Return the currently active runtime
@@ -0,0 +1,148 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::{fixture, rstest};
+
+use super::{RuntimeBackingRepo, resolve_runtime_layers_from};
+use crate::config::Config;
+use crate::fixtures::*;
+use crate::prelude::*;
+use crate::{encoding, runtime, storage};
+
+#[fixture]
+async fn config(tmpdir: tempfile::TempDir) -> (tempfile::TempDir, Config) {
+    let repo_path = tmpdir.path().join("repo");
+    crate::storage::fs::MaybeOpenFsRepository::create(&repo_path)
+        .await
+        .expect("failed to make repo for test");
+    let origin_path = tmpdir.path().join("origin");
+    crate::storage::fs::MaybeOpenFsRepository::create(&origin_path)
+        .await
+        .expect("failed to make repo for test");
+    let mut conf = Config::default();
+    conf.remote.insert(
+        "origin".to_string(),
+        crate::config::Remote::Address(crate::config::RemoteAddress {
+            address: url::Url::from_file_path(&origin_path).unwrap(),
+        }),
+    );
+    conf.storage.root = repo_path;
+    (tmpdir, conf)
+}
+
+/// Commit a small layer into the given repo
+async fn make_layer(
+    repo: &storage::RepositoryHandle,
+    tmpdir: &std::path::Path,
+) -> encoding::Digest {
+    let src_dir = tmpdir.join("source");
+    ensure(src_dir.join("dir/file.txt"), "hello");
+    let manifest = crate::Committer::new(repo)
+        .commit_dir(src_dir.as_path())
+        .await
+        .unwrap();
+    repo.create_layer(&manifest.to_graph_manifest())
+        .await
+        .unwrap()
+        .digest()
+        .unwrap()
+}
+
+/// Make a runtime with the given layer in its stack
+async fn make_runtime(tmpdir: &std::path::Path, layer: encoding::Digest) -> runtime::Runtime {
+    let root = tmpdir.join("runtimes");
+    let repo = storage::RepositoryHandle::from(
+        storage::fs::MaybeOpenFsRepository::create(root)
+            .await
+            .unwrap(),
+    );
+    let storage = runtime::Storage::new(repo).unwrap();
+    let mut rt = storage.create_owned_runtime().await.unwrap();
+    rt.push_digest(layer);
+    rt
+}
+
+#[cfg(unix)]
+#[rstest]
+fn test_restore_environment() {
+    const CHANGED: &str = "SPFS_TEST_RESTORE_ENVIRONMENT_CHANGED";
+    const REMOVED: &str = "SPFS_TEST_RESTORE_ENVIRONMENT_REMOVED";
+    const ADDED: &str = "SPFS_TEST_RESTORE_ENVIRONMENT_ADDED";
+    // Safety: these variables are only used by this test
+    unsafe {
+        std::env::set_var(CHANGED, "user");
+        std::env::set_var(REMOVED, "user");
+        std::env::remove_var(ADDED);
+    }
+    let original = std::env::vars_os().collect::<Vec<_>>();
+    // Safety: these variables are only used by this test
+    unsafe {
+        std::env::set_var(CHANGED, "runtime");
+        std::env::remove_var(REMOVED);
+        std::env::set_var(ADDED, "runtime");
+        super::restore_environment(original);
+    }
+    assert_eq!(std::env::var(CHANGED).as_deref(), Ok("user"));
+    assert_eq!(std::env::var(REMOVED).as_deref(), Ok("user"));
+    assert_eq!(
+        std::env::var(ADDED).as_deref(),
+        Ok("runtime"),
+        "variables added by the reinitialize should be kept"
+    );
+    // Safety: these variables are only used by this test
+    unsafe {
+        std::env::remove_var(CHANGED);
+        std::env::remove_var(REMOVED);
+        std::env::remove_var(ADDED);
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_resolve_runtime_layers_from_primary(#[future] config: (tempfile::TempDir, Config)) {
+    init_logging();
+    let (tmpdir, config) = config.await;
+    let local: storage::RepositoryHandle = config.get_local_repository().await.unwrap().into();
+    let layer = make_layer(&local, tmpdir.path()).await;
+    let rt = make_runtime(tmpdir.path(), layer).await;
+
+    let (_, source, layers) = resolve_runtime_layers_from(&config, &rt, Ok(local))
+        .await
+        .unwrap();
+    assert_eq!(source, RuntimeBackingRepo::Primary);
+    assert_eq!(
+        layers
+            .iter()
+            .map(|l| l.digest().unwrap())
+            .collect::<Vec<_>>(),
+        vec![layer]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_resolve_runtime_layers_falls_back_to_remote(
+    #[future] config: (tempfile::TempDir, Config),
+) {
+    init_logging();
+    let (tmpdir, config) = config.await;
+    let local: storage::RepositoryHandle = config.get_local_repository().await.unwrap().into();
+    let origin = config.get_remote("origin").await.unwrap();
+    let layer = make_layer(&origin, tmpdir.path()).await;
+    assert!(!local.has_object(layer).await);
+    let rt = make_runtime(tmpdir.path(), layer).await;
+
+    let (repo, source, layers) = resolve_runtime_layers_from(&config, &rt, Ok(local))
+        .await
+        .unwrap();
+    assert_eq!(source, RuntimeBackingRepo::Remote("origin".to_string()));
+    assert!(repo.has_object(layer).await);
+    assert_eq!(
+        layers
+            .iter()
+            .map(|l| l.digest().unwrap())
+            .collect::<Vec<_>>(),
+        vec![layer]
+    );
+}
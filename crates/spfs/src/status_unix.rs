@@ -136,6 +136,12 @@ pub async unsafe fn change_to_durable_runtime(
 /// This function will run blocking IO on the current thread. Although this is not ideal,
 /// the mount namespacing operated per-thread and so restricts our ability to move execution.
 ///
+/// Only the runtime's filesystem is rebuilt. This is usually run in a separate
+/// `spfs-enter --remount` process, and so the environment of any processes
+/// already running in the runtime (like an interactive shell) is left untouched.
+/// See [`reinitialize_runtime_preserving_env`] to also keep the environment of
+/// the current process.
+///
 /// # Safety
 ///
 /// This function sets environment variables, see [`std::env::set_var`] for
@@ -202,6 +208,48 @@ pub async unsafe fn reinitialize_runtime(
     Ok(render_result.render_summary)
 }
 
+/// Reinitialize the current spfs runtime as rt, keeping the current environment.
+///
+/// This behaves like [`reinitialize_runtime`], but any environment variables that
+/// were set when it was called are re-applied afterwards, so that edits made
+/// to the environment before the reinitialize are not clobbered by it.
+///
+/// # Safety
+///
+/// This function sets environment variables, see [`std::env::set_var`] for
+/// more details on safety.
+pub async unsafe fn reinitialize_runtime_preserving_env(
+    global_overlayfs_options: &OverlayFsOptions,
+    rt: &mut runtime::Runtime,
+) -> Result<RenderSummary> {
+    let original = std::env::vars_os().collect::<Vec<_>>();
+    // Safety: the responsibility of the caller.
+    let result = unsafe { reinitialize_runtime(global_overlayfs_options, rt).await };
+    // Safety: the responsibility of the caller.
+    unsafe { restore_environment(original) };
+    result
+}
+
+/// Re-apply any of the given variables that are missing from or
+/// differ in the current environment.
+///
+/// # Safety
+///
+/// This function sets environment variables, see [`std::env::set_var`] for
+/// more details on safety.
+pub(crate) unsafe fn restore_environment(
+    original: impl IntoIterator<Item = (std::ffi::OsString, std::ffi::OsString)>,
+) {
+    for (name, value) in original {
+        if std::env::var_os(&name).as_ref() == Some(&value) {
+            continue;
+        }
+        tracing::debug!(?name, "restoring environment variable");
+        // Safety: the responsibility of the caller.
+        unsafe { std::env::set_var(name, value) };
+    }
+}
+
 /// Initialize the current runtime as rt.
 ///
 /// This function will run blocking IO on the current thread. Although this is not ideal,
@@ -34,6 +34,13 @@ pub async fn reinitialize_runtime(_rt: &mut runtime::Runtime) -> Result<RenderSu
     todo!()
 }
 
+/// Reinitialize the current spfs runtime as rt, keeping the current environment.
+pub async fn reinitialize_runtime_preserving_env(
+    rt: &mut runtime::Runtime,
+) -> Result<RenderSummary> {
+    reinitialize_runtime(rt).await
+}
+
 /// Initialize the current runtime as rt.
 ///
 /// This function will run blocking IO on the current thread. Although this is not ideal,
@@ -34,3 +34,40 @@ async fn test_object_existence(
     let actual = tmprepo.has_object(digest).await;
     assert!(!actual, "object should not exist after being removed");
 }
+
+#[rstest]
+#[case::fs(tmprepo("fs"))]
+#[case::tar(tmprepo("tar"))]
+#[cfg_attr(feature = "server", case::rpc(tmprepo("rpc")))]
+#[tokio::test]
+async fn test_iter_objects_with_prefix(
+    #[case]
+    #[future]
+    tmprepo: TempRepo,
+) {
+    use futures::TryStreamExt;
+
+    let tmprepo = tmprepo.await;
+    let mut digests = Vec::new();
+    for size in 0..10 {
+        let obj = graph::Blob::new(encoding::EMPTY_DIGEST.into(), size);
+        tmprepo.write_object(&obj).await.unwrap();
+        digests.push(obj.digest().unwrap());
+    }
+
+    let target = digests[3];
+    let prefix = encoding::PartialDigest::from(&target.as_bytes()[..4]);
+    let found: Vec<_> = tmprepo
+        .iter_objects_with_prefix(prefix)
+        .map_ok(|(digest, _)| digest)
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(found.contains(&target), "should find the prefixed object");
+    assert!(
+        found
+            .iter()
+            .all(|digest| digest.as_bytes().starts_with(&target.as_bytes()[..4])),
+        "should only find objects that match the prefix: {found:?}"
+    );
+}
@@ -234,6 +234,13 @@ impl graph::DatabaseView for FallbackProxy {
         self.primary.iter_objects()
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        self.primary.iter_objects_with_prefix(prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         self.primary.walk_objects(root)
     }
@@ -41,6 +41,13 @@ impl DatabaseView for super::MaybeOpenFsRepository {
         graph::DatabaseIterator::new(self)
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        graph::DatabaseIterator::with_prefix(self, prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         graph::DatabaseWalker::new(self, *root)
     }
@@ -115,6 +122,13 @@ impl DatabaseView for super::OpenFsRepository {
         graph::DatabaseIterator::new(self)
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        graph::DatabaseIterator::with_prefix(self, prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         graph::DatabaseWalker::new(self, *root)
     }
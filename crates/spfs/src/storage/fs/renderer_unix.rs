@@ -11,6 +11,7 @@ use futures::future::ready;
 use futures::{FutureExt, StreamExt};
 use nix::fcntl::OFlag;
 use nix::sys::stat::Mode;
+use nix::sys::time::TimeSpec;
 use nix::unistd::geteuid;
 use rand::prelude::*;
 use tokio::io::AsyncReadExt;
@@ -131,7 +132,21 @@ where
                                 .await;
                             if res.is_ok() {
                                 let mode = Mode::from_bits_truncate(entry.mode());
+                                #[cfg(feature = "xattrs")]
+                                let xattrs: tracking::Xattrs = entry
+                                    .xattrs()
+                                    .map(|(name, value)| (name.to_owned(), value.to_vec()))
+                                    .collect();
+                                #[cfg(feature = "xattrs")]
+                                let child_dir = child_dir.into_std().await;
+                                let name = PathBuf::from(entry.name());
+                                let mtime = entry.mtime();
                                 res = tokio::task::spawn_blocking(move || {
+                                    #[cfg(feature = "xattrs")]
+                                    crate::tracking::xattrs::write_xattrs(&child_dir, &xattrs, &name);
+                                    // children have all been rendered, so nothing
+                                    // else will touch the time of this directory
+                                    restore_mtime(child_dir.as_raw_fd(), mtime, &name);
                                     nix::sys::stat::fchmod(
                                         child_dir.as_raw_fd(),
                                         mode,
@@ -533,7 +548,19 @@ where
                 )
             })?;
         let mode = entry.mode();
+        #[cfg(feature = "xattrs")]
+        let xattrs: tracking::Xattrs = entry
+            .xattrs()
+            .map(|(name, value)| (name.to_owned(), value.to_vec()))
+            .collect();
+        #[cfg(feature = "xattrs")]
+        let rendered_file = rendered_file.into_std().await;
+        let name = PathBuf::from(entry.name());
+        let mtime = entry.mtime();
         tokio::task::spawn_blocking(move || {
+            #[cfg(feature = "xattrs")]
+            crate::tracking::xattrs::write_xattrs(&rendered_file, &xattrs, &name);
+            restore_mtime(rendered_file.as_raw_fd(), mtime, &name);
             nix::sys::stat::fchmod(rendered_file.as_raw_fd(), Mode::from_bits_truncate(mode))
         })
         .await
@@ -594,12 +621,37 @@ where
                     )),
                 }
             } else {
+                if let Some(mtime) = entry.mtime()
+                    && let Err(err) = nix::sys::stat::utimensat(
+                        Some(target_dir_fd),
+                        entry.name(),
+                        &TimeSpec::UTIME_OMIT,
+                        &mtime_timespec(mtime),
+                        nix::sys::stat::UtimensatFlags::NoFollowSymlink,
+                    )
+                {
+                    tracing::warn!(path = ?entry.name(), "failed to restore modification time: {err}");
+                }
                 Ok(RenderBlobResult::SymlinkWritten)
             };
         }
         // Free up file resources as early as possible.
         drop(reader);
 
+        // hard links share their metadata with the payload and
+        // every other render of it, so blobs that carry their own
+        // modification time or extended attributes must be copied
+        let render_type = if entry.mtime().is_some() {
+            RenderType::Copy
+        } else {
+            render_type
+        };
+        #[cfg(feature = "xattrs")]
+        let render_type = match entry.xattrs().next() {
+            Some(_) => RenderType::Copy,
+            None => render_type,
+        };
+
         let committed_path = self.repo.payloads().build_digest_path(entry.object());
         Ok(match render_type {
             RenderType::HardLink(hard_link_type) => {
@@ -621,6 +673,24 @@ where
     }
 }
 
+fn mtime_timespec(mtime: std::time::SystemTime) -> TimeSpec {
+    match mtime.duration_since(std::time::UNIX_EPOCH) {
+        Ok(since) => TimeSpec::from(since),
+        Err(err) => -TimeSpec::from(err.duration()),
+    }
+}
+
+/// Set the modification time of an open file or directory, if one
+/// was preserved, logging rather than failing on any error.
+fn restore_mtime(fd: std::os::fd::RawFd, mtime: Option<std::time::SystemTime>, path: &Path) {
+    let Some(mtime) = mtime else {
+        return;
+    };
+    if let Err(err) = nix::sys::stat::futimens(fd, &TimeSpec::UTIME_OMIT, &mtime_timespec(mtime)) {
+        tracing::warn!(?path, "failed to restore modification time: {err}");
+    }
+}
+
 async fn create_and_open_dir_at<A>(dir_fd: A, name: String) -> std::io::Result<tokio::fs::File>
 where
     A: AsRawFd + Send + 'static,
@@ -349,6 +349,13 @@ impl DatabaseView for RepositoryHandle {
         each_variant!(self, repo, { repo.iter_objects() })
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        each_variant!(self, repo, { repo.iter_objects_with_prefix(prefix) })
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         each_variant!(self, repo, { repo.walk_objects(root) })
     }
@@ -524,6 +531,13 @@ impl DatabaseView for Arc<RepositoryHandle> {
         each_variant!(&**self, repo, { repo.iter_objects() })
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        each_variant!(&**self, repo, { repo.iter_objects_with_prefix(prefix) })
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         each_variant!(&**self, repo, { repo.walk_objects(root) })
     }
@@ -74,6 +74,13 @@ where
         self.inner.iter_objects()
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        self.inner.iter_objects_with_prefix(prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         self.inner.walk_objects(root)
     }
@@ -61,6 +61,13 @@ impl graph::DatabaseView for super::RpcRepository {
         graph::DatabaseIterator::new(self)
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        graph::DatabaseIterator::with_prefix(self, prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         graph::DatabaseWalker::new(self, *root)
     }
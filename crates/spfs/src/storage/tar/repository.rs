@@ -236,6 +236,13 @@ impl graph::DatabaseView for TarRepository {
         self.repo.iter_objects()
     }
 
+    fn iter_objects_with_prefix(
+        &self,
+        prefix: encoding::PartialDigest,
+    ) -> graph::DatabaseIterator<'_> {
+        self.repo.iter_objects_with_prefix(prefix)
+    }
+
     fn walk_objects<'db>(&'db self, root: &encoding::Digest) -> graph::DatabaseWalker<'db> {
         self.repo.walk_objects(root)
     }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use relative_path::RelativePathBuf;
 
@@ -19,6 +19,9 @@ pub enum DiffMode<U1 = (), U2 = ()> {
     Changed(Entry<U1>, Entry<U2>),
     Added(Entry<U2>),
     Removed(Entry<U1>),
+    /// A file that was moved from the given path without
+    /// changing its content, see [`detect_renames`]
+    Renamed(RelativePathBuf, Entry<U1>, Entry<U2>),
 }
 
 impl<U1, U2> std::fmt::Display for DiffMode<U1, U2> {
@@ -28,6 +31,7 @@ impl<U1, U2> std::fmt::Display for DiffMode<U1, U2> {
             Self::Changed(..) => f.write_str("~"),
             Self::Added(..) => f.write_str("+"),
             Self::Removed(..) => f.write_str("-"),
+            Self::Renamed(..) => f.write_str(">"),
         }
     }
 }
@@ -45,6 +49,9 @@ impl<U1, U2> DiffMode<U1, U2> {
     pub fn is_removed(&self) -> bool {
         matches!(self, Self::Removed(..))
     }
+    pub fn is_renamed(&self) -> bool {
+        matches!(self, Self::Renamed(..))
+    }
 
     /// True if the underlying entry is/was a directory.
     ///
@@ -56,6 +63,7 @@ impl<U1, U2> DiffMode<U1, U2> {
             DiffMode::Changed(a, b) => a.is_dir() && b.is_dir(),
             DiffMode::Added(entry) => entry.is_dir(),
             DiffMode::Removed(entry) => entry.is_dir(),
+            DiffMode::Renamed(_, a, b) => a.is_dir() && b.is_dir(),
         }
     }
 }
@@ -63,18 +71,31 @@ impl<U1, U2> DiffMode<U1, U2> {
 impl<U1> DiffMode<U1, U1> {
     /// The associated user data from the underlying entry.
     ///
-    /// In the case of a [`Self::Changed`] entry, the original entry
-    /// data is returned.
+    /// In the case of a [`Self::Changed`] or [`Self::Renamed`] entry,
+    /// the original entry data is returned.
     pub fn user_data(&self) -> &U1 {
         match self {
             DiffMode::Unchanged(entry) => &entry.user_data,
             DiffMode::Changed(a, _) => &a.user_data,
             DiffMode::Added(entry) => &entry.user_data,
             DiffMode::Removed(entry) => &entry.user_data,
+            DiffMode::Renamed(_, a, _) => &a.user_data,
         }
     }
 }
 
+/// How much two manifests differ, see [`compute_diff_summary`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum DiffSummary {
+    /// Every path is unchanged
+    NoChanges,
+    /// The same paths exist with the same content, but some of them
+    /// have different permissions or other metadata
+    OnlyMetadata,
+    /// Paths were added or removed, or have different content
+    ContentChanged,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Diff<U1 = (), U2 = ()> {
     pub mode: DiffMode<U1, U2>,
@@ -95,16 +116,25 @@ impl<U1, U2> std::fmt::Display for Diff<U1, U2> {
 impl<U1, U2> Diff<U1, U2> {
     fn details(&self) -> String {
         let mut details = String::new();
-        if let DiffMode::Changed(a, b) = &self.mode {
-            if a.mode != b.mode {
-                details = format!("{details} {{{:06o} => {:06o}}}", a.mode, b.mode);
-            }
-            if a.kind != b.kind {
-                details = format!("{details} {{{} => {}}}", a.kind, b.kind);
+        match &self.mode {
+            DiffMode::Changed(a, b) => {
+                if a.mode != b.mode {
+                    details = format!("{details} {{{:06o} => {:06o}}}", a.mode, b.mode);
+                }
+                if a.kind != b.kind {
+                    details = format!("{details} {{{} => {}}}", a.kind, b.kind);
+                }
+                if a.object != b.object {
+                    details = format!("{details} {{!content!}}");
+                }
             }
-            if a.object != b.object {
-                details = format!("{details} {{!content!}}");
+            DiffMode::Renamed(from, a, b) => {
+                details = format!("{details} {{from {from}}}");
+                if a.mode != b.mode {
+                    details = format!("{details} {{{:06o} => {:06o}}}", a.mode, b.mode);
+                }
             }
+            _ => {}
         }
         details
     }
@@ -134,6 +164,109 @@ pub fn compute_diff<U1: Clone>(a: &Manifest<U1>, b: &Manifest<U1>) -> Vec<Diff<U
     changes
 }
 
+/// Summarize the differences between two manifests.
+///
+/// This walks the manifests just like [`compute_diff`], but does not
+/// create a [`Diff`] for each path and stops as soon as any path is
+/// found to be added, removed or to have different content.
+pub fn compute_diff_summary<U1, U2>(a: &Manifest<U1>, b: &Manifest<U2>) -> DiffSummary {
+    let mut summary = DiffSummary::NoChanges;
+    for node in a.walk() {
+        let Some(b_entry) = b.get_path(&node.path) else {
+            return DiffSummary::ContentChanged;
+        };
+        if node.entry.kind != b_entry.kind
+            || (!node.entry.kind.is_tree() && node.entry.object != b_entry.object)
+        {
+            // directories are not compared by digest, since any change
+            // to their contents is found when walking those paths
+            return DiffSummary::ContentChanged;
+        }
+        if node.entry.mode != b_entry.mode
+            || node.entry.xattrs != b_entry.xattrs
+            || node.entry.mtime != b_entry.mtime
+        {
+            summary = DiffSummary::OnlyMetadata;
+        }
+    }
+    for node in b.walk() {
+        // a mask for a path that was never there is ignored, the same
+        // as it is by compute_diff
+        if a.get_path(&node.path).is_none() && !node.entry.kind.is_mask() {
+            return DiffSummary::ContentChanged;
+        }
+    }
+    summary
+}
+
+/// Pair up removed and added files that have the same content,
+/// replacing each pair with a single [`DiffMode::Renamed`] entry.
+///
+/// This is not done by [`compute_diff`] because it only compares the
+/// content digests of entries, and so two unrelated files that happen
+/// to have the same content would also be identified as a rename. Empty
+/// files are never considered as renamed for the same reason. Where more
+/// than one removed file matches, they are paired in path order.
+pub fn detect_renames<U1, U2>(diffs: Vec<Diff<U1, U2>>) -> Vec<Diff<U1, U2>> {
+    fn is_candidate<T>(entry: &Entry<T>) -> bool {
+        entry.kind.is_blob() && entry.size() > 0
+    }
+
+    let mut removed: HashMap<_, VecDeque<usize>> = HashMap::new();
+    for (index, diff) in diffs.iter().enumerate() {
+        if let DiffMode::Removed(entry) = &diff.mode
+            && is_candidate(entry)
+        {
+            removed
+                .entry((entry.object, entry.is_symlink()))
+                .or_default()
+                .push_back(index);
+        }
+    }
+
+    // maps the index of each added file to the removed file that it replaces
+    let mut renames = HashMap::new();
+    for (index, diff) in diffs.iter().enumerate() {
+        if let DiffMode::Added(entry) = &diff.mode
+            && is_candidate(entry)
+            && let Some(from) = removed
+                .get_mut(&(entry.object, entry.is_symlink()))
+                .and_then(VecDeque::pop_front)
+        {
+            renames.insert(index, from);
+        }
+    }
+    if renames.is_empty() {
+        return diffs;
+    }
+
+    let mut diffs: Vec<_> = diffs.into_iter().map(Some).collect();
+    for index in 0..diffs.len() {
+        let Some(from) = renames.get(&index) else {
+            continue;
+        };
+        let Some(Diff {
+            mode: DiffMode::Removed(original),
+            path: original_path,
+        }) = diffs[*from].take()
+        else {
+            unreachable!("renames are only paired with removed entries");
+        };
+        let Some(Diff {
+            mode: DiffMode::Added(entry),
+            path,
+        }) = diffs[index].take()
+        else {
+            unreachable!("renames are only detected for added entries");
+        };
+        diffs[index] = Some(Diff {
+            mode: DiffMode::Renamed(original_path, original, entry),
+            path,
+        });
+    }
+    diffs.into_iter().flatten().collect()
+}
+
 // Allow: most instances will be of the large variant; boxing is
 // counter-productive.
 #[allow(clippy::large_enum_variant)]
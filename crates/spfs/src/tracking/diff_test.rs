@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::os::unix::fs::PermissionsExt;
+
 use relative_path::RelativePath;
 use rstest::rstest;
 
-use super::{Diff, DiffMode, compute_diff};
+use super::{Diff, DiffMode, DiffSummary, compute_diff, compute_diff_summary, detect_renames};
 use crate::fixtures::*;
 use crate::tracking::{Entry, Manifest, compute_manifest};
 
@@ -99,3 +101,98 @@ async fn test_compute_diff_removed(tmpdir: tempfile::TempDir) {
     assert!(matches!(third.mode, DiffMode::Removed(..)));
     assert_eq!(&third.path, &RelativePath::new("/dir/dir/file"));
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_detect_renames(tmpdir: tempfile::TempDir) {
+    let dir = tmpdir.path();
+    let a_dir = dir.join("a");
+    let b_dir = dir.join("b");
+    std::fs::create_dir_all(&a_dir).unwrap();
+    std::fs::create_dir_all(&b_dir).unwrap();
+    std::fs::write(a_dir.join("original"), "data").unwrap();
+    std::fs::write(a_dir.join("removed"), "other").unwrap();
+    std::fs::write(a_dir.join("empty"), "").unwrap();
+    std::fs::write(b_dir.join("moved"), "data").unwrap();
+    std::fs::write(b_dir.join("empty_moved"), "").unwrap();
+
+    let a = compute_manifest(a_dir).await.unwrap();
+    let b = compute_manifest(b_dir).await.unwrap();
+    let diffs = compute_diff(&a, &b);
+    assert!(
+        !diffs.iter().any(|d| d.mode.is_renamed()),
+        "renames should not be detected by default"
+    );
+
+    let actual: Vec<_> = detect_renames(diffs)
+        .into_iter()
+        .filter(|d| !d.mode.is_unchanged())
+        .map(|d| d.to_string())
+        .collect();
+    assert_eq!(
+        actual,
+        vec![
+            "- /empty",
+            "+ /empty_moved",
+            "> /moved {from /original}",
+            "- /removed",
+        ],
+        "only files with the same non-empty content should be renamed"
+    );
+}
+
+#[rstest]
+// the same files with the same permissions
+#[case("data", 0o644, "data", 0o644, DiffSummary::NoChanges)]
+// only the permissions differ
+#[case("data", 0o644, "data", 0o600, DiffSummary::OnlyMetadata)]
+// the content differs, but not the size
+#[case("data", 0o644, "date", 0o644, DiffSummary::ContentChanged)]
+// both the content and the permissions differ
+#[case("data", 0o644, "more data", 0o600, DiffSummary::ContentChanged)]
+#[tokio::test]
+async fn test_compute_diff_summary(
+    tmpdir: tempfile::TempDir,
+    #[case] a_data: &str,
+    #[case] a_mode: u32,
+    #[case] b_data: &str,
+    #[case] b_mode: u32,
+    #[case] expected: DiffSummary,
+) {
+    let dir = tmpdir.path();
+    for (name, data, mode) in [("a", a_data, a_mode), ("b", b_data, b_mode)] {
+        let root = dir.join(name);
+        std::fs::create_dir_all(root.join("dir")).unwrap();
+        std::fs::write(root.join("dir/unchanged"), "same").unwrap();
+        std::fs::write(root.join("dir/file"), data).unwrap();
+        std::fs::set_permissions(root.join("dir/file"), std::fs::Permissions::from_mode(mode))
+            .unwrap();
+    }
+
+    let a = compute_manifest(dir.join("a")).await.unwrap();
+    let b = compute_manifest(dir.join("b")).await.unwrap();
+    assert_eq!(compute_diff_summary(&a, &b), expected);
+    assert_eq!(compute_diff_summary(&b, &a), expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_compute_diff_summary_added_and_removed(tmpdir: tempfile::TempDir) {
+    let dir = tmpdir.path();
+    let a_dir = dir.join("a");
+    let b_dir = dir.join("b");
+    std::fs::create_dir_all(&a_dir).unwrap();
+    std::fs::create_dir_all(&b_dir).unwrap();
+    std::fs::write(a_dir.join("file"), "data").unwrap();
+    std::fs::write(b_dir.join("file"), "data").unwrap();
+    std::fs::write(b_dir.join("added"), "").unwrap();
+
+    let a = compute_manifest(a_dir).await.unwrap();
+    let b = compute_manifest(b_dir).await.unwrap();
+    assert_eq!(compute_diff_summary(&a, &b), DiffSummary::ContentChanged);
+    assert_eq!(compute_diff_summary(&b, &a), DiffSummary::ContentChanged);
+    assert_eq!(
+        compute_diff_summary(&Manifest::<()>::default(), &Manifest::<()>::default()),
+        DiffSummary::NoChanges
+    );
+}
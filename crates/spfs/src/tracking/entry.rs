@@ -97,10 +97,15 @@ impl encoding::Encodable for EntryKind {
 
 impl encoding::Decodable for EntryKind {
     fn decode(reader: &mut impl BufRead) -> Result<Self> {
-        Self::from_str(encoding::read_string(reader)?.as_str())
+        Self::from_str(
+            encoding::read_string_with_limit(reader, encoding::MAX_DECODED_STRING_LENGTH)?.as_str(),
+        )
     }
 }
 
+/// The extended attributes of an entry, by name
+pub type Xattrs = std::collections::BTreeMap<String, Vec<u8>>;
+
 /// An entry in the manifest identifies a directory or file in the tree
 ///
 /// Any associated user data is not considered for comparison, sorting, etc.
@@ -113,6 +118,12 @@ pub struct Entry<T = ()> {
     pub user_data: T,
     /// The size associated with non-blob entries.
     pub legacy_size: u64,
+    /// Extended attributes of this entry, which are only
+    /// collected when the `xattrs` feature is enabled.
+    pub xattrs: Xattrs,
+    /// The modification time of this entry, which is only
+    /// collected when explicitly requested at commit time.
+    pub mtime: Option<std::time::SystemTime>,
 }
 
 impl<T> std::fmt::Debug for Entry<T>
@@ -129,14 +140,22 @@ where
             entries,
             user_data,
             legacy_size: _,
+            xattrs,
+            mtime,
         } = self;
-        f.debug_struct("Entry")
-            .field("kind", kind)
+        let mut f = f.debug_struct("Entry");
+        f.field("kind", kind)
             .field("mode", &format!("{mode:#06o}"))
             .field("object", object)
             .field("entries", entries)
-            .field("user_data", user_data)
-            .finish()
+            .field("user_data", user_data);
+        if !xattrs.is_empty() {
+            f.field("xattrs", &xattrs.keys().collect::<Vec<_>>());
+        }
+        if let Some(mtime) = mtime {
+            f.field("mtime", mtime);
+        }
+        f.finish()
     }
 }
 
@@ -151,11 +170,15 @@ impl<T, T2> PartialEq<Entry<T2>> for Entry<T> {
             entries,
             user_data: _,
             legacy_size: _,
+            xattrs,
+            mtime,
         } = other;
         if self.kind != *kind
             || self.mode != *mode
             || self.size() != other.size()
             || self.object != *object
+            || self.xattrs != *xattrs
+            || self.mtime != *mtime
         {
             return false;
         }
@@ -196,6 +219,8 @@ impl<T> Entry<T> {
             entries: Default::default(),
             user_data,
             legacy_size: 0,
+            xattrs: Default::default(),
+            mtime: None,
         }
     }
 
@@ -208,6 +233,8 @@ impl<T> Entry<T> {
             entries: Default::default(),
             user_data,
             legacy_size: 0,
+            xattrs: Default::default(),
+            mtime: None,
         }
     }
 
@@ -221,6 +248,8 @@ impl<T> Entry<T> {
             entries: Default::default(),
             user_data,
             legacy_size: 0,
+            xattrs: Default::default(),
+            mtime: None,
         }
     }
 
@@ -234,6 +263,8 @@ impl<T> Entry<T> {
             entries: Default::default(),
             user_data,
             legacy_size: 0,
+            xattrs: Default::default(),
+            mtime: None,
         }
     }
 
@@ -318,6 +349,8 @@ impl<T> Entry<T> {
                 .collect(),
             user_data: (),
             legacy_size: self.legacy_size,
+            xattrs: self.xattrs,
+            mtime: self.mtime,
         }
     }
 
@@ -334,6 +367,8 @@ impl<T> Entry<T> {
                 .collect(),
             user_data,
             legacy_size: self.legacy_size,
+            xattrs: self.xattrs,
+            mtime: self.mtime,
         }
     }
 
@@ -351,6 +386,8 @@ impl<T> Entry<T> {
                 .collect(),
             user_data,
             legacy_size: self.legacy_size,
+            xattrs: self.xattrs,
+            mtime: self.mtime,
         }
     }
 }
@@ -363,6 +400,8 @@ where
         self.kind = other.kind;
         self.object = other.object;
         self.mode = other.mode;
+        self.xattrs = other.xattrs.clone();
+        self.mtime = other.mtime;
         if !self.kind.is_tree() {
             return;
         }
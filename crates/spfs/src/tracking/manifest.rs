@@ -454,6 +454,7 @@ where
     reporter: R,
     blob_semaphore: Arc<Semaphore>,
     max_concurrent_branches: usize,
+    preserve_mtimes: bool,
 }
 
 impl ManifestBuilder<(), (), ()> {
@@ -470,6 +471,7 @@ impl Default for ManifestBuilder<(), (), ()> {
             reporter: (),
             blob_semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_BLOBS)),
             max_concurrent_branches: DEFAULT_MAX_CONCURRENT_BRANCHES,
+            preserve_mtimes: false,
         }
     }
 }
@@ -500,6 +502,16 @@ where
         self
     }
 
+    /// Record the modification time of each file and directory.
+    ///
+    /// By default, modification times are not recorded so that the
+    /// same content always produces the same manifest. See
+    /// [`crate::Committer::with_preserve_mtimes`].
+    pub fn with_preserve_mtimes(mut self, preserve_mtimes: bool) -> Self {
+        self.preserve_mtimes = preserve_mtimes;
+        self
+    }
+
     /// Use the provided hasher when building the manifest.
     ///
     /// The hasher turns blob contents into a digest to be included
@@ -516,6 +528,7 @@ where
             reporter: self.reporter,
             blob_semaphore: self.blob_semaphore,
             max_concurrent_branches: self.max_concurrent_branches,
+            preserve_mtimes: self.preserve_mtimes,
         }
     }
 
@@ -535,6 +548,7 @@ where
             reporter: self.reporter,
             blob_semaphore: self.blob_semaphore,
             max_concurrent_branches: self.max_concurrent_branches,
+            preserve_mtimes: self.preserve_mtimes,
         }
     }
 
@@ -549,6 +563,7 @@ where
             reporter,
             blob_semaphore: self.blob_semaphore,
             max_concurrent_branches: self.max_concurrent_branches,
+            preserve_mtimes: self.preserve_mtimes,
         }
     }
 
@@ -727,6 +742,19 @@ where
             entry.mode = 0o644;
         }
 
+        if self.preserve_mtimes && !entry.kind.is_mask() {
+            entry.mtime = stat_result.modified().ok();
+        }
+
+        // symlinks are never rendered with extended attributes,
+        // and masks are removals rather than rendered content
+        #[cfg(all(unix, feature = "xattrs"))]
+        if !file_type.is_symlink() && !entry.kind.is_mask() {
+            let path = path.as_ref().to_owned();
+            entry.xattrs =
+                tokio::task::spawn_blocking(move || super::xattrs::read_xattrs(&path)).await?;
+        }
+
         self.reporter.computed_entry(&entry);
         Ok(entry)
     }
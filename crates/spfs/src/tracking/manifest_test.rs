@@ -7,6 +7,7 @@ use rstest::rstest;
 use super::{EntryKind, Manifest, compute_manifest};
 use crate::encoding::prelude::*;
 use crate::fixtures::*;
+use crate::graph::object::EncodingFormat;
 
 #[rstest]
 #[tokio::test]
@@ -48,6 +49,46 @@ async fn test_manifest_relative_paths(tmpdir: tempfile::TempDir) {
     assert!(manifest.get_path("/dir1.0/dir2.0/file.txt").is_some());
     assert!(manifest.get_path("dir1.0/dir2.1/file.txt").is_some());
 }
+#[rstest]
+#[tokio::test]
+async fn test_manifest_preserve_mtimes(tmpdir: tempfile::TempDir) {
+    let dir = tmpdir.path();
+    ensure(dir.join("dir/file.txt"), "somedata");
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+    std::fs::File::options()
+        .write(true)
+        .open(dir.join("dir/file.txt"))
+        .unwrap()
+        .set_modified(mtime)
+        .unwrap();
+
+    let manifest = compute_manifest(dir).await.unwrap();
+    assert_eq!(
+        manifest.get_path("dir/file.txt").unwrap().mtime,
+        None,
+        "modification times should not be recorded by default"
+    );
+
+    let manifest = super::ManifestBuilder::new()
+        .with_preserve_mtimes(true)
+        .compute_manifest(dir)
+        .await
+        .unwrap();
+    assert_eq!(
+        manifest.get_path("dir/file.txt").unwrap().mtime,
+        Some(mtime)
+    );
+    assert!(manifest.get_path("dir").unwrap().mtime.is_some());
+
+    let graph = crate::graph::Manifest::builder()
+        .with_header(|h| h.with_encoding_format(EncodingFormat::FlatBuffers))
+        .build(manifest.root());
+    assert!(
+        graph.to_tracking_manifest() == manifest,
+        "modification times should survive a round trip through the graph manifest"
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_manifest_sorting(tmpdir: tempfile::TempDir) {
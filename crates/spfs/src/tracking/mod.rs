@@ -11,10 +11,12 @@ mod env;
 pub mod manifest;
 mod object;
 mod tag;
+#[cfg(all(unix, feature = "xattrs"))]
+pub(crate) mod xattrs;
 
 pub use blob_reader::{BlobRead, BlobReadExt};
-pub use diff::{Diff, DiffMode, compute_diff};
-pub use entry::{Entry, EntryKind};
+pub use diff::{Diff, DiffMode, DiffSummary, compute_diff, compute_diff_summary, detect_renames};
+pub use entry::{Entry, EntryKind, Xattrs};
 pub use env::{
     ENV_SPEC_EMPTY,
     ENV_SPEC_SEPARATOR,
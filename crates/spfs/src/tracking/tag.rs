@@ -127,17 +127,28 @@ impl Encodable for Tag {
 
 impl encoding::Decodable for Tag {
     fn decode(mut reader: &mut impl BufRead) -> Result<Self> {
-        let org = encoding::read_string(&mut reader)?;
+        let org =
+            encoding::read_string_with_limit(&mut reader, encoding::MAX_DECODED_STRING_LENGTH)?;
         let org = match org.as_str() {
             "" => None,
             _ => Some(org),
         };
         Ok(Tag {
             org,
-            name: encoding::read_string(&mut *reader)?,
+            name: encoding::read_string_with_limit(
+                &mut *reader,
+                encoding::MAX_DECODED_STRING_LENGTH,
+            )?,
             target: encoding::read_digest(&mut *reader)?,
-            user: encoding::read_string(&mut *reader)?,
-            time: DateTime::parse_from_rfc3339(&encoding::read_string(&mut *reader)?)?.into(),
+            user: encoding::read_string_with_limit(
+                &mut *reader,
+                encoding::MAX_DECODED_STRING_LENGTH,
+            )?,
+            time: DateTime::parse_from_rfc3339(&encoding::read_string_with_limit(
+                &mut *reader,
+                encoding::MAX_DECODED_STRING_LENGTH,
+            )?)?
+            .into(),
             parent: encoding::read_digest(reader)?,
         })
     }
@@ -75,3 +75,25 @@ async fn test_tag_name_violation(#[case] tag: &str) {
     // It should not be possible to push a tag with an illegal name.
     TagSpec::parse(tag).expect_err(&format!("tag name '{tag}' expected to not parse"));
 }
+
+#[rstest]
+fn test_tag_decode_oversized_string() {
+    let mut writer = Vec::new();
+    encoding::write_string(&mut writer, "").unwrap();
+    encoding::write_string(
+        &mut writer,
+        &"a".repeat(encoding::MAX_DECODED_STRING_LENGTH + 1),
+    )
+    .unwrap();
+    let mut reader = std::io::BufReader::new(writer.as_slice());
+    let res = Tag::decode(&mut reader);
+    assert!(
+        matches!(
+            res,
+            Err(crate::Error::Encoding(
+                encoding::Error::StringTooLong { .. }
+            ))
+        ),
+        "expected an oversized tag name to be rejected, got {res:?}"
+    );
+}
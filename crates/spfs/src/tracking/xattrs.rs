@@ -0,0 +1,102 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+//! Capture and restore of extended file attributes
+
+use super::Xattrs;
+
+#[cfg(test)]
+#[path = "./xattrs_test.rs"]
+mod xattrs_test;
+
+/// Only attributes in the user namespace describe the file content
+/// itself. The security and trusted namespaces carry host-specific
+/// policy (selinux labels, capabilities, overlayfs state) that must
+/// not be captured from one machine and applied on another.
+const CAPTURED_PREFIX: &str = "user.";
+
+/// Attribute name prefixes within the user namespace that are used
+/// by overlayfs to track the state of its upper directory rather
+/// than describing the file itself.
+const IGNORED_PREFIXES: &[&str] = &["user.overlay."];
+
+/// True if the named attribute should be captured and restored.
+fn is_captured(name: &str) -> bool {
+    name.starts_with(CAPTURED_PREFIX)
+        && !IGNORED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+fn is_unsupported(err: &std::io::Error) -> bool {
+    err.raw_os_error() == Some(libc::ENOTSUP)
+}
+
+/// Read the extended attributes of the file or directory at `path`.
+///
+/// Symlinks are not followed. Any failure to read the attributes
+/// is logged and results in whatever attributes could be read,
+/// rather than failing the whole operation.
+pub(crate) fn read_xattrs(path: &std::path::Path) -> Xattrs {
+    let mut xattrs = Xattrs::new();
+    let names = match xattr::list(path) {
+        Ok(names) => names,
+        Err(err) if is_unsupported(&err) => {
+            tracing::debug!(?path, "extended attributes are not supported");
+            return xattrs;
+        }
+        Err(err) => {
+            tracing::warn!(?path, "failed to list extended attributes: {err}");
+            return xattrs;
+        }
+    };
+    for name in names {
+        let Some(name) = name.to_str() else {
+            tracing::warn!(
+                ?path,
+                ?name,
+                "skipping extended attribute with non-utf8 name"
+            );
+            continue;
+        };
+        if !is_captured(name) {
+            continue;
+        }
+        match xattr::get(path, name) {
+            Ok(Some(value)) => {
+                xattrs.insert(name.to_owned(), value);
+            }
+            // removed since being listed
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!(?path, "failed to read extended attribute {name}: {err}");
+            }
+        }
+    }
+    xattrs
+}
+
+/// Set the given extended attributes on an open file or directory.
+///
+/// This should happen before the final permissions are applied,
+/// since some attributes cannot be set on a read-only file.
+/// Failures are logged rather than returned, since the rendered
+/// content is still usable without its attributes.
+pub(crate) fn write_xattrs(file: &std::fs::File, xattrs: &Xattrs, path: &std::path::Path) {
+    use xattr::FileExt;
+
+    for (name, value) in xattrs.iter() {
+        if !is_captured(name) {
+            tracing::debug!(?path, "skipping extended attribute {name}");
+            continue;
+        }
+        if let Err(err) = file.set_xattr(name, value) {
+            if is_unsupported(&err) {
+                tracing::warn!(?path, "extended attributes are not supported, skipping");
+                return;
+            }
+            tracing::warn!(?path, "failed to set extended attribute {name}: {err}");
+        }
+    }
+}
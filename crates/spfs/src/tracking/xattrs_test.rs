@@ -0,0 +1,44 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+
+use super::{is_captured, read_xattrs};
+use crate::fixtures::*;
+
+#[rstest]
+#[case("user.mime_type", true)]
+#[case("user.overlay.origin", false)]
+#[case("security.selinux", false)]
+#[case("security.capability", false)]
+#[case("trusted.overlay.opaque", false)]
+#[case("trusted.md5sum", false)]
+#[case("system.posix_acl_access", false)]
+fn test_xattr_namespace_filter(#[case] name: &str, #[case] expected: bool) {
+    assert_eq!(is_captured(name), expected);
+}
+
+#[rstest]
+fn test_read_xattrs_user_only(tmpdir: tempfile::TempDir) {
+    let path = tmpdir.path().join("file");
+    std::fs::write(&path, "data").unwrap();
+    if let Err(err) = xattr::set(&path, "user.spfs.test", b"value") {
+        // not all filesystems used for testing support user attributes
+        tracing::warn!("skipping test, cannot set xattr: {err}");
+        return;
+    }
+    // non-user namespaces are generally only writable with elevated
+    // privileges, but when possible they should still be excluded
+    let _ = xattr::set(&path, "trusted.spfs.test", b"value");
+
+    let xattrs = read_xattrs(&path);
+    assert_eq!(
+        xattrs.get("user.spfs.test").map(|v| v.as_slice()),
+        Some(b"value".as_slice())
+    );
+    assert!(
+        xattrs.keys().all(|name| name.starts_with("user.")),
+        "only user attributes should be captured, got {xattrs:?}"
+    );
+}
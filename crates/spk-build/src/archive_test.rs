@@ -4,13 +4,21 @@
 
 use rstest::rstest;
 use spfstest::spfstest;
+use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::option_map;
+use spk_schema::ident::AsVersionIdent;
 use spk_schema::{Package, recipe};
 use spk_solve::SolverImpl;
-use spk_storage::export_package;
 use spk_storage::fixtures::*;
+use spk_storage::{
+    Repository,
+    SpfsRepository,
+    export_package,
+    export_package_excluding_components,
+    import_package,
+};
 
-use crate::{BinaryPackageBuilder, BuildSource};
+use crate::{BinaryPackageBuilder, BuildSource, SourcePackageBuilder};
 
 fn step_solver() -> SolverImpl {
     SolverImpl::Step(spk_solve::StepSolver::default())
@@ -20,6 +28,17 @@ fn resolvo_solver() -> SolverImpl {
     SolverImpl::Resolvo(spk_solve::ResolvoSolver::default())
 }
 
+fn as_spfs(repo: &spk_solve::RepositoryHandle) -> &SpfsRepository {
+    match repo {
+        spk_solve::RepositoryHandle::SPFS(repo) => repo,
+        spk_solve::RepositoryHandle::Mem(_)
+        | spk_solve::RepositoryHandle::Runtime(_)
+        | spk_solve::RepositoryHandle::Indexed(_) => {
+            panic!("only spfs repositories are supported")
+        }
+    }
+}
+
 #[spfstest]
 #[rstest]
 #[case::step(step_solver())]
@@ -52,3 +71,61 @@ async fn test_archive_create_parents(#[case] solver: SolverImpl) {
         .await
         .expect("export should create dirs as needed");
 }
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_archive_excluding_source() {
+    let rt = spfs_runtime().await;
+    let spec = recipe!(
+        {
+            "pkg": "spk-archive-test/0.0.1",
+            "build": {"script": "touch /spfs/file.txt"},
+        }
+    );
+    rt.tmprepo.publish_recipe(&spec).await.unwrap();
+    SourcePackageBuilder::from_recipe(spec.clone())
+        .build_and_publish(".", &*rt.tmprepo)
+        .await
+        .unwrap();
+    let (spec, _) = BinaryPackageBuilder::from_recipe_with_solver(spec, step_solver())
+        .with_repository(rt.tmprepo.clone())
+        .build_and_publish(option_map! {}, &*rt.tmprepo)
+        .await
+        .unwrap();
+
+    let filename = rt.tmpdir.path().join("archive.spk");
+    let repo = as_spfs(&rt.tmprepo);
+    export_package_excluding_components(
+        &[repo],
+        spec.ident().as_version_ident().to_any_ident(None),
+        &[Component::Source],
+        &filename,
+    )
+    .await
+    .expect("failed to export");
+
+    let dest = make_repo(RepoKind::Spfs).await;
+    let dest = as_spfs(&dest);
+    import_package(&filename, &spfs::Syncer::new(dest, dest))
+        .await
+        .expect("failed to import");
+
+    let builds = dest
+        .list_package_builds(spec.ident().as_version_ident())
+        .await
+        .unwrap();
+    assert_eq!(
+        builds,
+        vec![spec.ident().clone()],
+        "only the binary build should be imported"
+    );
+    let mut components = dest
+        .read_components(spec.ident())
+        .await
+        .unwrap()
+        .into_keys()
+        .collect::<Vec<_>>();
+    components.sort();
+    assert_eq!(components, vec![Component::Build, Component::Run]);
+}
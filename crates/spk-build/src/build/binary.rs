@@ -5,10 +5,12 @@
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
 use futures::StreamExt;
+use itertools::Itertools;
 use relative_path::RelativePathBuf;
 use spfs::prelude::*;
 use spfs::tracking::DiffMode;
@@ -49,8 +51,25 @@ use spk_solve::solution::Solution;
 use spk_solve::{DecisionFormatter, Named, SolverExt, SolverMut};
 use spk_storage as storage;
 
-use crate::report::{BuildOutputReport, BuildReport, BuildSetupReport};
-use crate::validation::{Report, Validator};
+use super::{BuildEnvSnapshot, CompilerCache, CompilerCacheStats};
+use crate::report::{
+    BuildOutputReport,
+    BuildPhase,
+    BuildPhaseResult,
+    BuildReport,
+    BuildReporter,
+    BuildReporters,
+    BuildResult,
+    BuildSetupReport,
+};
+use crate::validation::{
+    CustomValidator,
+    Report,
+    SeverityOverrides,
+    ValidationContext,
+    Validator,
+    ValidatorRegistry,
+};
 use crate::{Error, Result};
 
 #[cfg(test)]
@@ -141,6 +160,7 @@ where
 /// ```
 pub struct BinaryPackageBuilder<Recipe, Solver> {
     prefix: PathBuf,
+    metadata_root: Option<PathBuf>,
     recipe: Recipe,
     source: BuildSource,
     solver: Solver,
@@ -152,6 +172,9 @@ pub struct BinaryPackageBuilder<Recipe, Solver> {
     interactive: bool,
     conflicting_packages: HashMap<ConflictingPackagePair, HashSet<RelativePathBuf>>,
     allow_circular_dependencies: bool,
+    reporter: BuildReporters,
+    validation_severity: Option<SeverityOverrides>,
+    validators: ValidatorRegistry,
 }
 
 impl<Recipe, Solver> BinaryPackageBuilder<Recipe, Solver>
@@ -168,6 +191,7 @@ where
             recipe,
             source,
             prefix: PathBuf::from("/spfs"),
+            metadata_root: None,
             solver,
             environment: Default::default(),
             #[cfg(test)]
@@ -183,6 +207,9 @@ where
             interactive: false,
             conflicting_packages: Default::default(),
             allow_circular_dependencies: false,
+            reporter: Default::default(),
+            validation_severity: None,
+            validators: Default::default(),
         }
     }
 }
@@ -226,6 +253,25 @@ where
         self
     }
 
+    /// Use an alternate root directory for the build metadata.
+    ///
+    /// By default, the package spec, options, build script and
+    /// environment snapshot are written within the build prefix. This
+    /// can be used to stage these files elsewhere while the build runs,
+    /// in which case they are copied into the prefix once the build
+    /// script completes so that they are still captured in the
+    /// resulting package. Component markers and source packages
+    /// always remain within the prefix.
+    pub fn with_metadata_root(&mut self, root: PathBuf) -> &mut Self {
+        self.metadata_root = Some(root);
+        self
+    }
+
+    /// The root directory that build metadata paths are relative to.
+    fn metadata_root(&self) -> &Path {
+        self.metadata_root.as_deref().unwrap_or(&self.prefix)
+    }
+
     /// Define the source files that this build should run against
     pub fn with_source(&mut self, source: BuildSource) -> &mut Self {
         self.source = source;
@@ -259,6 +305,35 @@ where
         self
     }
 
+    /// Report the progress of the build to the given reporter.
+    pub fn with_reporter(&mut self, reporter: BuildReporters) -> &mut Self {
+        self.reporter = reporter;
+        self
+    }
+
+    /// Use the given severities when handling failed validations.
+    ///
+    /// By default, the severities are loaded from the spk config.
+    pub fn with_validation_severity(&mut self, overrides: SeverityOverrides) -> &mut Self {
+        self.validation_severity = Some(overrides);
+        self
+    }
+
+    /// Run the given validators in addition to the package's validation rules.
+    pub fn with_validators(&mut self, validators: ValidatorRegistry) -> &mut Self {
+        self.validators = validators;
+        self
+    }
+
+    /// Add one validator to be run in addition to the package's validation rules.
+    pub fn with_validator<V>(&mut self, validator: V) -> &mut Self
+    where
+        V: CustomValidator + 'static,
+    {
+        self.validators.register(validator);
+        self
+    }
+
     /// Interactive builds stop just before running the build
     /// script and attempt to spawn an interactive shell process
     /// for the user to inspect and debug the build
@@ -312,6 +387,30 @@ where
         &mut self,
         variant: V,
     ) -> Result<BuildReport<Recipe::Output, Override<Override<V>>>>
+    where
+        V: Variant + Clone + Send + Sync,
+    {
+        let reporter = self.reporter.clone();
+        let package = self.recipe.ident().clone();
+        reporter.build_started(&package);
+        let start = Instant::now();
+        let result = self.build_variant(variant, &reporter).await;
+        reporter.build_finished(&BuildResult {
+            package,
+            build: result
+                .as_ref()
+                .ok()
+                .map(|report| report.setup.package.ident().clone()),
+            duration: start.elapsed(),
+        });
+        result
+    }
+
+    async fn build_variant<V>(
+        &mut self,
+        variant: V,
+        reporter: &BuildReporters,
+    ) -> Result<BuildReport<Recipe::Output, Override<Override<V>>>>
     where
         V: Variant + Clone + Send + Sync,
     {
@@ -330,17 +429,21 @@ where
 
         if let BuildSource::SourcePackage(ident) = self.source.clone() {
             tracing::debug!("Resolving source package for build");
-            let solution = self.resolve_source_package(&all_options, ident).await?;
-            runtime
-                .status
-                .stack
-                .extend(resolve_runtime_layers(requires_localization, &solution).await?);
+            let layers = report_phase(reporter, BuildPhase::ResolveSource, async {
+                let solution = self.resolve_source_package(&all_options, ident).await?;
+                Ok::<_, Error>(resolve_runtime_layers(requires_localization, &solution).await?)
+            })
+            .await?;
+            runtime.status.stack.extend(layers);
         };
 
         tracing::debug!("Resolving build environment");
-        let solution = self
-            .resolve_build_environment(&all_options, &variant)
-            .await?;
+        let solution = report_phase(
+            reporter,
+            BuildPhase::ResolveEnvironment,
+            self.resolve_build_environment(&all_options, &variant),
+        )
+        .await?;
         self.environment
             .extend(solution.to_environment(Some(std::env::vars())));
 
@@ -400,9 +503,21 @@ where
             // by the setup validators, and then replaced during the build
             output: Default::default(),
         };
-        self.validate_build_setup(&report).await?;
-        report.output = self.build_and_commit_artifacts(&report.setup).await?;
-        self.validate_build_output(&report).await?;
+        report_phase(
+            reporter,
+            BuildPhase::ValidateSetup,
+            self.validate_build_setup(&report),
+        )
+        .await?;
+        report.output = self
+            .build_and_commit_artifacts(&report.setup, reporter)
+            .await?;
+        report_phase(
+            reporter,
+            BuildPhase::ValidateOutput,
+            self.validate_build_output(&report),
+        )
+        .await?;
         Ok(report)
     }
 
@@ -493,6 +608,16 @@ where
         Ok(solution)
     }
 
+    fn validation_severity(&self) -> Result<SeverityOverrides> {
+        match &self.validation_severity {
+            Some(overrides) => Ok(overrides.clone()),
+            None => {
+                let config = spk_config::get_config()?;
+                SeverityOverrides::from_config(&config.validation)
+            }
+        }
+    }
+
     async fn validate_build_setup<V>(&self, report: &BuildReport<Recipe::Output, V>) -> Result<()>
     where
         V: Variant + Send + Sync,
@@ -509,7 +634,10 @@ where
             tracing::trace!(" > {validator:?}");
             validations.push_back(async move { validator.validate_setup(&report.setup).await });
         }
-        Report::from_iter(validations.collect::<Vec<_>>().await).into_result()
+        let mut errors = Report::from_iter(validations.collect::<Vec<_>>().await).into_errors();
+        let context = ValidationContext::from_setup(&report.setup);
+        errors.extend(self.validators.validate_setup(&context).await);
+        self.validation_severity()?.into_result(errors)
     }
 
     async fn validate_build_output<V>(&self, report: &BuildReport<Recipe::Output, V>) -> Result<()>
@@ -523,16 +651,36 @@ where
         for validator in validators {
             validations.push_back(async move { validator.validate_build(report).await });
         }
-        Report::from_iter(validations.collect::<Vec<_>>().await).into_result()
+        let mut errors = Report::from_iter(validations.collect::<Vec<_>>().await).into_errors();
+        let context = ValidationContext::from_build(report);
+        errors.extend(self.validators.validate_build(&context).await);
+        self.validation_severity()?.into_result(errors)
     }
 
     async fn build_and_commit_artifacts<V: Variant>(
         &mut self,
         input: &BuildSetupReport<Recipe::Output, V>,
+        reporter: &BuildReporters,
     ) -> Result<BuildOutputReport> {
         let options = input.variant.options();
-        self.build_artifacts(&input.package, &options).await?;
+        let snapshot = BuildEnvSnapshot::new(&input.environment, &options);
+        let compiler_cache = report_phase(
+            reporter,
+            BuildPhase::BuildScript,
+            self.build_artifacts(&input.package, &options, &snapshot),
+        )
+        .await?;
+
+        let mut output =
+            report_phase(reporter, BuildPhase::Commit, self.commit_artifacts(input)).await?;
+        output.compiler_cache = compiler_cache;
+        Ok(output)
+    }
 
+    async fn commit_artifacts<V: Variant>(
+        &self,
+        input: &BuildSetupReport<Recipe::Output, V>,
+    ) -> Result<BuildOutputReport> {
         let source_ident =
             VersionIdent::new(self.recipe.name().to_owned(), self.recipe.version().clone())
                 .into_any_ident(Some(Build::Source));
@@ -578,29 +726,44 @@ where
             })
             .collect();
 
-        tracing::info!("Committing package contents...");
         commit_component_layers(input, collected_changes).await
     }
 
-    async fn build_artifacts<O>(&mut self, package: &Recipe::Output, options: O) -> Result<()>
+    async fn build_artifacts<O>(
+        &mut self,
+        package: &Recipe::Output,
+        options: O,
+        snapshot: &BuildEnvSnapshot,
+    ) -> Result<Option<CompilerCacheStats>>
     where
         O: AsRef<OptionMap>,
     {
         let pkg = package.ident();
-        let metadata_dir = data_path(pkg).to_path(&self.prefix);
-        let build_spec = build_spec_path(pkg).to_path(&self.prefix);
-        let build_options = build_options_path(pkg).to_path(&self.prefix);
-        let build_script = build_script_path(pkg).to_path(&self.prefix);
+        let metadata_root = self.metadata_root().to_owned();
+        let metadata_dir = data_path(pkg).to_path(&metadata_root);
+        let build_spec = build_spec_path(pkg).to_path(&metadata_root);
+        let build_options = build_options_path(pkg).to_path(&metadata_root);
+        let build_script = build_script_path(pkg).to_path(&metadata_root);
+        let build_env = build_env_path(pkg).to_path(&metadata_root);
+
+        let prefix_metadata_dir = data_path(pkg).to_path(&self.prefix);
 
         std::fs::create_dir_all(&metadata_dir)
             .map_err(|err| Error::DirectoryCreateError(metadata_dir.to_owned(), err))?;
+        std::fs::create_dir_all(&prefix_metadata_dir)
+            .map_err(|err| Error::DirectoryCreateError(prefix_metadata_dir.to_owned(), err))?;
         {
-            let mut writer = std::fs::File::create(&build_spec)
-                .map_err(|err| Error::FileOpenError(build_spec.to_owned(), err))?;
+            // the spec is written aside and then renamed into place so
+            // that an interrupted build never leaves a truncated spec
+            let temp_spec = build_spec.with_extension("yaml.tmp");
+            let mut writer = std::fs::File::create(&temp_spec)
+                .map_err(|err| Error::FileOpenError(temp_spec.to_owned(), err))?;
             serde_yaml::to_writer(&mut writer, package)
                 .map_err(|err| Error::String(format!("Failed to save build spec: {err}")))?;
             writer
                 .sync_data()
+                .map_err(|err| Error::FileWriteError(temp_spec.to_owned(), err))?;
+            std::fs::rename(&temp_spec, &build_spec)
                 .map_err(|err| Error::FileWriteError(build_spec.to_owned(), err))?;
         }
         {
@@ -622,6 +785,16 @@ where
                 .sync_data()
                 .map_err(|err| Error::FileWriteError(build_options.to_owned(), err))?;
         }
+        {
+            let mut writer = std::fs::File::create(&build_env)
+                .map_err(|err| Error::FileOpenError(build_env.to_owned(), err))?;
+            serde_json::to_writer_pretty(&mut writer, snapshot).map_err(|err| {
+                Error::String(format!("Failed to save build environment snapshot: {err}"))
+            })?;
+            writer
+                .sync_data()
+                .map_err(|err| Error::FileWriteError(build_env.to_owned(), err))?;
+        }
         for cmpt in package.components().iter() {
             let marker_path = component_marker_path(pkg, &cmpt.name).to_path(&self.prefix);
             std::fs::File::create(&marker_path)
@@ -635,6 +808,11 @@ where
             BuildSource::LocalPath(path) => path.clone(),
         };
 
+        let compiler_cache = CompilerCache::from_config(&spk_config::get_config()?.build)?;
+        if let Some(cache) = &compiler_cache {
+            cache.create_dir()?;
+        }
+
         let runtime = spfs::active_runtime().await?;
         let cmd = if self.interactive {
             println!("\nNow entering an interactive build shell");
@@ -662,6 +840,9 @@ where
         cmd.envs(options.as_ref().to_environment());
         cmd.envs(package.get_build_env());
         cmd.env("PREFIX", &self.prefix);
+        if let Some(cache) = &compiler_cache {
+            cmd.envs(cache.environment());
+        }
         // force the base environment to be setup using bash, so that the
         // spfs startup and build environment are predictable and consistent
         // (eg in case the user's shell does not have startup scripts in
@@ -669,6 +850,7 @@ where
         cmd.env("SHELL", "bash");
         cmd.current_dir(&source_dir);
 
+        let stats_before = compiler_cache.as_ref().and_then(CompilerCache::stats);
         match cmd
             .status()
             .map_err(|err| {
@@ -693,7 +875,17 @@ where
             }
         }
 
-        self.generate_startup_scripts(package)
+        if metadata_dir != prefix_metadata_dir {
+            collect_metadata(&metadata_dir, &prefix_metadata_dir)?;
+        }
+        self.generate_startup_scripts(package)?;
+
+        let stats = compiler_cache.as_ref().and_then(|cache| {
+            let stats = cache.stats()?.since(&stats_before.unwrap_or_default());
+            tracing::info!("{} usage: {stats}", cache.kind());
+            Some(stats)
+        });
+        Ok(stats)
     }
 
     fn generate_startup_scripts(&self, package: &impl Package) -> Result<()> {
@@ -743,6 +935,36 @@ where
     }
 }
 
+/// Copy the build metadata files staged in `from` into `to`, so that
+/// they are captured as part of the package.
+fn collect_metadata(from: &Path, to: &Path) -> Result<()> {
+    let entries =
+        std::fs::read_dir(from).map_err(|err| Error::FileOpenError(from.to_owned(), err))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::FileOpenError(from.to_owned(), err))?;
+        let target = to.join(entry.file_name());
+        std::fs::copy(entry.path(), &target)
+            .map_err(|err| Error::FileWriteError(target.to_owned(), err))?;
+    }
+    Ok(())
+}
+
+/// Run one phase of a build, notifying the reporter of its progress.
+async fn report_phase<F, T>(reporter: &BuildReporters, phase: BuildPhase, fut: F) -> Result<T>
+where
+    F: std::future::Future<Output = Result<T>>,
+{
+    reporter.phase_started(phase);
+    let start = Instant::now();
+    let result = fut.await;
+    reporter.phase_finished(&BuildPhaseResult {
+        phase,
+        duration: start.elapsed(),
+        success: result.is_ok(),
+    });
+    result
+}
+
 /// Commit changes discovered in the runtime as a package.
 ///
 /// Only the changes also present in `filter` will be committed. It is
@@ -774,6 +996,11 @@ where
         .read_manifest(*manifest_digest)
         .await?
         .to_tracking_manifest();
+    validate_component_markers(
+        input.package.ident(),
+        &collected_layer,
+        &input.package.components(),
+    )?;
     let manifests = split_manifest_by_component(
         input.package.ident(),
         &collected_layer,
@@ -801,9 +1028,48 @@ where
         collected_layer,
         collected_changes,
         components,
+        compiler_cache: None,
     })
 }
 
+/// Ensure that the collected package files contain a valid marker
+/// for each declared component, and none for any other component.
+fn validate_component_markers(
+    pkg: &BuildIdent,
+    manifest: &spfs::tracking::Manifest,
+    components: &ComponentSpecList<ComponentSpec>,
+) -> Result<()> {
+    for component in components.iter() {
+        let path = component_marker_path(pkg, &component.name);
+        match manifest.get_path(path.as_str()) {
+            Some(entry) if entry.kind.is_blob() => {}
+            _ => {
+                return Err(Error::InvalidComponentMarker {
+                    component: component.name.clone(),
+                    path,
+                });
+            }
+        }
+    }
+
+    let metadata_dir = data_path(pkg);
+    let Some(metadata) = manifest.get_path(metadata_dir.as_str()) else {
+        return Ok(());
+    };
+    for name in metadata.entries.keys().sorted() {
+        let Some(component_name) = name.strip_suffix(".cmpt") else {
+            continue;
+        };
+        if !components.iter().any(|c| c.name.as_str() == component_name) {
+            return Err(Error::StrayComponentMarker {
+                name: component_name.to_string(),
+                path: metadata_dir.join(name),
+            });
+        }
+    }
+    Ok(())
+}
+
 fn split_manifest_by_component(
     pkg: &BuildIdent,
     manifest: &spfs::tracking::Manifest,
@@ -826,6 +1092,7 @@ fn split_manifest_by_component(
         relevant_paths.insert(build_spec_path(pkg));
         relevant_paths.insert(build_options_path(pkg));
         relevant_paths.insert(build_script_path(pkg));
+        relevant_paths.insert(build_env_path(pkg));
         relevant_paths.insert(component_marker_path(pkg, &component.name));
         relevant_paths.extend(path_and_parents(data_path(pkg)));
         for node in manifest.walk() {
@@ -897,6 +1164,14 @@ pub fn build_script_path(pkg: &BuildIdent) -> RelativePathBuf {
     data_path(pkg).join("build.sh")
 }
 
+/// Return the file path for the given build's build.env.json file.
+///
+/// This file is created during a build and stores a
+/// [`BuildEnvSnapshot`] of the resolved build environment
+pub fn build_env_path(pkg: &BuildIdent) -> RelativePathBuf {
+    data_path(pkg).join("build.env.json")
+}
+
 /// Return the file path for the given build's build.sh file.
 ///
 /// This file is created during a build and stores the bash
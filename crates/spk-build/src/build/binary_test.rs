@@ -3,6 +3,7 @@
 // https://github.com/spkenv/spk
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use rstest::rstest;
 use spfs::encoding::EMPTY_DIGEST;
@@ -48,6 +49,8 @@ fn test_split_manifest_permissions() {
                 entries: Default::default(),
                 user_data: (),
                 legacy_size: 0,
+                xattrs: Default::default(),
+                mtime: None,
             },
         )
         .unwrap();
@@ -120,6 +123,184 @@ fn resolvo_solver() -> SolverImpl {
     SolverImpl::Resolvo(spk_solve::ResolvoSolver::default())
 }
 
+fn blob_entry() -> spfs::tracking::Entry {
+    spfs::tracking::Entry {
+        kind: spfs::tracking::EntryKind::Blob(0),
+        object: EMPTY_DIGEST.into(),
+        mode: 0o644,
+        entries: Default::default(),
+        user_data: (),
+        legacy_size: 0,
+        xattrs: Default::default(),
+        mtime: None,
+    }
+}
+
+#[rstest]
+fn test_validate_component_markers() {
+    use spfs::tracking::Manifest;
+    let pkg: spk_schema::BuildIdent = "mypkg/1.0.0/3I42H3S6".parse().unwrap();
+    let spec = ComponentSpecList::default();
+    let mut manifest = Manifest::default();
+    manifest
+        .mkdirs(data_path(&pkg).as_str())
+        .expect("metadata dir should be created");
+    for marker in ["build.cmpt", "run.cmpt"] {
+        manifest
+            .mknod(data_path(&pkg).join(marker).as_str(), blob_entry())
+            .unwrap();
+    }
+    super::validate_component_markers(&pkg, &manifest, &spec)
+        .expect("all declared components have markers");
+
+    let mut stray = manifest.clone();
+    stray
+        .mknod(data_path(&pkg).join("extra.cmpt").as_str(), blob_entry())
+        .unwrap();
+    let res = super::validate_component_markers(&pkg, &stray, &spec);
+    assert!(
+        matches!(res, Err(crate::Error::StrayComponentMarker { ref name, .. }) if name == "extra"),
+        "expected stray marker error, got {res:?}"
+    );
+
+    let mut missing = Manifest::default();
+    missing.mkdirs(data_path(&pkg).as_str()).unwrap();
+    missing
+        .mknod(data_path(&pkg).join("build.cmpt").as_str(), blob_entry())
+        .unwrap();
+    let res = super::validate_component_markers(&pkg, &missing, &spec);
+    assert!(
+        matches!(
+            res,
+            Err(crate::Error::InvalidComponentMarker { ref component, .. }) if *component == Component::Run
+        ),
+        "expected missing marker error, got {res:?}"
+    );
+}
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_build_with_metadata_root(tmpdir: tempfile::TempDir) {
+    let rt = spfs_runtime().await;
+    let spec = recipe!(
+        {
+            "pkg": "metadata-root/1.0.0",
+            "build": {"script": "touch /spfs/file.txt"},
+        }
+    );
+    rt.tmprepo.publish_recipe(&spec).await.unwrap();
+
+    let (pkg, _) = BinaryPackageBuilder::from_recipe_with_solver(spec, step_solver())
+        .with_source(BuildSource::LocalPath(".".into()))
+        .with_repository(rt.tmprepo.clone())
+        .with_metadata_root(tmpdir.path().to_owned())
+        .build_and_publish(option_map! {}, &*rt.tmprepo)
+        .await
+        .unwrap();
+
+    assert!(
+        super::build_spec_path(pkg.ident())
+            .to_path(tmpdir.path())
+            .exists(),
+        "metadata should be staged in the alternate root"
+    );
+    let leftovers = std::fs::read_dir(data_path(pkg.ident()).to_path(tmpdir.path()))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".yaml.tmp"))
+        .collect::<Vec<_>>();
+    assert!(
+        leftovers.is_empty(),
+        "the spec should be renamed into place, found: {leftovers:?}"
+    );
+
+    let digest = *storage::local_repository()
+        .await
+        .unwrap()
+        .read_components(pkg.ident())
+        .await
+        .unwrap()
+        .get(&Component::Run)
+        .unwrap();
+    let config = spfs::get_config().unwrap();
+    let repo = config.get_local_repository().await.unwrap();
+    let layer = repo.read_layer(digest).await.unwrap();
+    let manifest_digest = match layer.manifest() {
+        None => panic!("This layer should have a manifest digest!"),
+        Some(d) => d,
+    };
+    let manifest = repo
+        .read_manifest(*manifest_digest)
+        .await
+        .unwrap()
+        .to_tracking_manifest();
+
+    for path in [
+        super::build_spec_path(pkg.ident()),
+        super::build_options_path(pkg.ident()),
+        super::build_script_path(pkg.ident()),
+        super::component_marker_path(pkg.ident(), &Component::Run),
+    ] {
+        assert!(
+            manifest.get_path(&path).is_some(),
+            "{path} should be captured in the published package"
+        );
+    }
+}
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_build_reports_phases(tmpdir: tempfile::TempDir) {
+    use std::sync::Mutex;
+
+    use crate::report::{BuildPhase, BuildPhaseResult, BuildReporter, BuildReporters};
+
+    #[derive(Default)]
+    struct RecordingReporter(Arc<Mutex<Vec<(BuildPhase, bool)>>>);
+
+    impl BuildReporter for RecordingReporter {
+        fn phase_finished(&self, result: &BuildPhaseResult) {
+            self.0.lock().unwrap().push((result.phase, result.success));
+        }
+    }
+
+    let rt = spfs_runtime().await;
+    let recipe = recipe!({
+        "pkg": "test/1.0.0",
+        "build": {
+            "script": "true",
+            "validation": {
+                "rules": [{"allow": "EmptyPackage"}]
+            }
+        }
+    });
+    rt.tmprepo.publish_recipe(&recipe).await.unwrap();
+
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    BinaryPackageBuilder::from_recipe_with_solver(recipe, step_solver())
+        .with_source(BuildSource::LocalPath(tmpdir.path().to_owned()))
+        .with_reporter(BuildReporters::custom(Box::new(RecordingReporter(
+            Arc::clone(&phases),
+        ))))
+        .build_and_publish(&option_map! {}, &*rt.tmprepo)
+        .await
+        .unwrap();
+
+    // a local source does not need to be resolved
+    assert_eq!(
+        *phases.lock().unwrap(),
+        vec![
+            (BuildPhase::ResolveEnvironment, true),
+            (BuildPhase::ValidateSetup, true),
+            (BuildPhase::BuildScript, true),
+            (BuildPhase::Commit, true),
+            (BuildPhase::ValidateOutput, true),
+        ]
+    );
+}
+
 #[spfstest]
 #[rstest]
 #[case::step(step_solver())]
@@ -0,0 +1,184 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+#[cfg(test)]
+#[path = "./compiler_cache_test.rs"]
+mod compiler_cache_test;
+
+/// A compiler caching tool that can be used by build scripts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CompilerCacheKind {
+    Ccache,
+    Sccache,
+}
+
+/// A compiler cache that is shared between builds.
+///
+/// The cache directory lives outside of /spfs, so nothing that is
+/// written into it is ever collected as part of a package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompilerCache {
+    kind: CompilerCacheKind,
+    dir: PathBuf,
+}
+
+/// The number of compilations that were served from a compiler
+/// cache and the number that were not.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompilerCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CompilerCacheStats {
+    /// The counts accumulated since the given earlier stats were taken.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            hits: self.hits.saturating_sub(earlier.hits),
+            misses: self.misses.saturating_sub(earlier.misses),
+        }
+    }
+}
+
+impl std::fmt::Display for CompilerCacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)
+    }
+}
+
+impl CompilerCache {
+    pub fn new<P: Into<PathBuf>>(kind: CompilerCacheKind, dir: P) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_absolute() {
+            return Err(Error::String(format!(
+                "Compiler cache directory must be an absolute path, got: {}",
+                dir.display()
+            )));
+        }
+        if dir.starts_with("/spfs") {
+            return Err(Error::String(format!(
+                "Compiler cache directory cannot be inside of /spfs, got: {}",
+                dir.display()
+            )));
+        }
+        Ok(Self { kind, dir })
+    }
+
+    /// Load the compiler cache settings from the spk config, if
+    /// compiler caching is enabled.
+    pub fn from_config(config: &spk_config::Build) -> Result<Option<Self>> {
+        if config.compiler_cache.is_empty() {
+            return Ok(None);
+        }
+        let kind = CompilerCacheKind::from_str(&config.compiler_cache).map_err(|_| {
+            Error::String(format!(
+                "Invalid compiler cache '{}', expected one of: ccache, sccache",
+                config.compiler_cache
+            ))
+        })?;
+        if config.compiler_cache_dir.is_empty() {
+            return Err(Error::String(
+                "A compiler_cache_dir must be configured to use a compiler cache".into(),
+            ));
+        }
+        Self::new(kind, &config.compiler_cache_dir).map(Some)
+    }
+
+    pub fn kind(&self) -> CompilerCacheKind {
+        self.kind
+    }
+
+    /// Ensure that the cache directory exists.
+    pub fn create_dir(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|err| Error::DirectoryCreateError(self.dir.clone(), err))
+    }
+
+    /// The environment variables that enable this cache in a build.
+    pub fn environment(&self) -> Vec<(&'static str, String)> {
+        let launcher = self.kind.to_string();
+        let dir = self.dir.display().to_string();
+        let mut env = match self.kind {
+            CompilerCacheKind::Ccache => vec![("CCACHE_DIR", dir)],
+            CompilerCacheKind::Sccache => {
+                vec![("SCCACHE_DIR", dir), ("RUSTC_WRAPPER", launcher.clone())]
+            }
+        };
+        env.push(("CMAKE_C_COMPILER_LAUNCHER", launcher.clone()));
+        env.push(("CMAKE_CXX_COMPILER_LAUNCHER", launcher));
+        env
+    }
+
+    /// Read the current statistics of this cache, returning None if
+    /// they could not be read, eg because the tool is not installed.
+    pub fn stats(&self) -> Option<CompilerCacheStats> {
+        let mut cmd = std::process::Command::new(self.kind.to_string());
+        cmd.envs(self.environment());
+        match self.kind {
+            CompilerCacheKind::Ccache => cmd.arg("--print-stats"),
+            CompilerCacheKind::Sccache => cmd.args(["--show-stats", "--stats-format=json"]),
+        };
+        let output = match cmd.output() {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                tracing::debug!(
+                    status = ?output.status,
+                    "failed to read {} stats: {}",
+                    self.kind,
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                return None;
+            }
+            Err(err) => {
+                tracing::debug!(?err, "failed to run {}", self.kind);
+                return None;
+            }
+        };
+        let output = String::from_utf8_lossy(&output.stdout);
+        match self.kind {
+            CompilerCacheKind::Ccache => Some(parse_ccache_stats(&output)),
+            CompilerCacheKind::Sccache => parse_sccache_stats(&output),
+        }
+    }
+}
+
+/// Parse the tab-separated output of `ccache --print-stats`.
+fn parse_ccache_stats(output: &str) -> CompilerCacheStats {
+    let mut stats = CompilerCacheStats::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once('\t') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<u64>() else {
+            continue;
+        };
+        match key {
+            "direct_cache_hit" | "preprocessed_cache_hit" => stats.hits += value,
+            "cache_miss" => stats.misses += value,
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// Parse the output of `sccache --show-stats --stats-format=json`.
+fn parse_sccache_stats(output: &str) -> Option<CompilerCacheStats> {
+    let value: serde_json::Value = serde_json::from_str(output).ok()?;
+    let total = |name: &str| -> u64 {
+        value["stats"][name]["counts"]
+            .as_object()
+            .map(|counts| counts.values().filter_map(|v| v.as_u64()).sum())
+            .unwrap_or_default()
+    };
+    Some(CompilerCacheStats {
+        hits: total("cache_hits"),
+        misses: total("cache_misses"),
+    })
+}
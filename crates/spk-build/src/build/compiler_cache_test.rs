@@ -0,0 +1,67 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+
+use super::{
+    CompilerCache,
+    CompilerCacheKind,
+    CompilerCacheStats,
+    parse_ccache_stats,
+    parse_sccache_stats,
+};
+
+#[rstest]
+fn test_compiler_cache_disabled_by_default() {
+    let config = spk_config::Build::default();
+    assert_eq!(CompilerCache::from_config(&config).unwrap(), None);
+}
+
+#[rstest]
+#[case::relative("ccache", "relative/cache")]
+#[case::inside_spfs("ccache", "/spfs/cache")]
+#[case::missing_dir("sccache", "")]
+#[case::unknown_tool("distcc", "/tmp/cache")]
+fn test_compiler_cache_invalid_config(#[case] tool: &str, #[case] dir: &str) {
+    let config = spk_config::Build {
+        compiler_cache: tool.into(),
+        compiler_cache_dir: dir.into(),
+    };
+    CompilerCache::from_config(&config).expect_err("config should be rejected");
+}
+
+#[rstest]
+fn test_compiler_cache_environment() {
+    let cache = CompilerCache::new(CompilerCacheKind::Ccache, "/tmp/cache").unwrap();
+    let env = cache.environment();
+    assert!(env.contains(&("CCACHE_DIR", "/tmp/cache".into())));
+    assert!(env.contains(&("CMAKE_CXX_COMPILER_LAUNCHER", "ccache".into())));
+}
+
+#[rstest]
+fn test_parse_ccache_stats() {
+    let output = "stats_updated_timestamp\t1700000000\n\
+                  direct_cache_hit\t3\n\
+                  preprocessed_cache_hit\t2\n\
+                  cache_miss\t4\n";
+    assert_eq!(
+        parse_ccache_stats(output),
+        CompilerCacheStats { hits: 5, misses: 4 }
+    );
+}
+
+#[rstest]
+fn test_parse_sccache_stats() {
+    let output = r#"{"stats": {
+        "cache_hits": {"counts": {"C/C++": 7, "Rust": 1}},
+        "cache_misses": {"counts": {"C/C++": 2}}
+    }}"#;
+    let stats = parse_sccache_stats(output).unwrap();
+    assert_eq!(stats, CompilerCacheStats { hits: 8, misses: 2 });
+    let earlier = CompilerCacheStats { hits: 6, misses: 2 };
+    assert_eq!(
+        stats.since(&earlier),
+        CompilerCacheStats { hits: 2, misses: 0 }
+    );
+}
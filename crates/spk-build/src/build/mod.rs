@@ -3,12 +3,15 @@
 // https://github.com/spkenv/spk
 
 mod binary;
+mod compiler_cache;
+mod snapshot;
 mod sources;
 
 pub use binary::{
     BinaryPackageBuilder,
     BuildError,
     BuildSource,
+    build_env_path,
     build_options_path,
     build_script_path,
     build_spec_path,
@@ -16,4 +19,6 @@ pub use binary::{
     component_marker_path,
     source_package_path,
 };
+pub use compiler_cache::{CompilerCache, CompilerCacheKind, CompilerCacheStats};
+pub use snapshot::{BuildEnvDiff, BuildEnvSnapshot, ValueChange};
 pub use sources::{CollectionError, SourcePackageBuilder, validate_source_changeset};
@@ -0,0 +1,113 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use spk_schema::BuildIdent;
+use spk_schema::foundation::name::{OptNameBuf, PkgNameBuf};
+use spk_schema::foundation::option_map::OptionMap;
+use spk_schema::prelude::*;
+use spk_solve::Solution;
+
+#[cfg(test)]
+#[path = "./snapshot_test.rs"]
+mod snapshot_test;
+
+/// A record of the environment that a binary package was built in.
+///
+/// This is saved alongside each build so that two builds of the same
+/// package can later be compared to find what changed between them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildEnvSnapshot {
+    /// The packages that were resolved into the build environment
+    #[serde(default)]
+    pub packages: BTreeMap<PkgNameBuf, BuildIdent>,
+    /// The build options used for the build
+    #[serde(default)]
+    pub options: OptionMap,
+    /// The environment variables defined by the resolved packages
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+}
+
+impl BuildEnvSnapshot {
+    /// Capture the relevant details of a resolved build environment.
+    pub fn new(solution: &Solution, options: &OptionMap) -> Self {
+        Self {
+            packages: solution
+                .items()
+                .map(|item| (item.spec.name().to_owned(), item.spec.ident().clone()))
+                .collect(),
+            options: options.clone(),
+            environment: solution
+                .to_environment(None::<Vec<(String, String)>>)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Compute the differences from this snapshot to another.
+    pub fn diff(&self, other: &Self) -> BuildEnvDiff {
+        BuildEnvDiff {
+            packages: diff_maps(&self.packages, &other.packages),
+            options: diff_maps(&*self.options, &*other.options),
+            environment: diff_maps(&self.environment, &other.environment),
+        }
+    }
+}
+
+/// Describes how a single named value differs between two snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueChange<T> {
+    /// The value only exists in the second snapshot
+    Added(T),
+    /// The value only exists in the first snapshot
+    Removed(T),
+    /// The value exists in both snapshots, first and second respectively
+    Changed(T, T),
+}
+
+/// The differences between two [`BuildEnvSnapshot`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildEnvDiff {
+    pub packages: BTreeMap<PkgNameBuf, ValueChange<BuildIdent>>,
+    pub options: BTreeMap<OptNameBuf, ValueChange<String>>,
+    pub environment: BTreeMap<String, ValueChange<String>>,
+}
+
+impl BuildEnvDiff {
+    /// True if the two snapshots were the same.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.options.is_empty() && self.environment.is_empty()
+    }
+}
+
+fn diff_maps<K, V>(a: &BTreeMap<K, V>, b: &BTreeMap<K, V>) -> BTreeMap<K, ValueChange<V>>
+where
+    K: Ord + Clone,
+    V: PartialEq + Clone,
+{
+    let mut changes = BTreeMap::new();
+    for (name, value) in a.iter() {
+        match b.get(name) {
+            None => {
+                changes.insert(name.clone(), ValueChange::Removed(value.clone()));
+            }
+            Some(other) if other != value => {
+                changes.insert(
+                    name.clone(),
+                    ValueChange::Changed(value.clone(), other.clone()),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, value) in b.iter() {
+        if !a.contains_key(name) {
+            changes.insert(name.clone(), ValueChange::Added(value.clone()));
+        }
+    }
+    changes
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::BTreeMap;
+
+use rstest::rstest;
+use spk_schema::foundation::{opt_name, option_map, pkg_name};
+use spk_schema::ident::parse_build_ident;
+use spk_schema::prelude::*;
+
+use super::{BuildEnvSnapshot, ValueChange};
+
+fn snapshot(packages: &[&str], options: spk_schema::OptionMap) -> BuildEnvSnapshot {
+    let packages = packages
+        .iter()
+        .map(|p| {
+            let ident = parse_build_ident(p).unwrap();
+            (ident.name().to_owned(), ident)
+        })
+        .collect();
+    BuildEnvSnapshot {
+        packages,
+        options,
+        environment: BTreeMap::new(),
+    }
+}
+
+#[rstest]
+fn test_diff_identical_is_empty() {
+    let a = snapshot(&["python/3.9.0/3I42H3S6"], option_map! {"debug" => "off"});
+    assert!(a.diff(&a.clone()).is_empty());
+}
+
+#[rstest]
+fn test_diff_packages() {
+    let a = snapshot(
+        &["python/3.9.0/3I42H3S6", "gcc/9.3.1/3I42H3S6"],
+        option_map! {},
+    );
+    let b = snapshot(
+        &["python/3.10.0/3I42H3S6", "cmake/3.20.0/3I42H3S6"],
+        option_map! {},
+    );
+    let diff = a.diff(&b);
+    assert_eq!(diff.packages.len(), 3);
+    assert!(matches!(
+        diff.packages.get(pkg_name!("python")),
+        Some(ValueChange::Changed(_, _))
+    ));
+    assert!(matches!(
+        diff.packages.get(pkg_name!("gcc")),
+        Some(ValueChange::Removed(_))
+    ));
+    assert!(matches!(
+        diff.packages.get(pkg_name!("cmake")),
+        Some(ValueChange::Added(_))
+    ));
+}
+
+#[rstest]
+fn test_diff_options_and_environment() {
+    let mut a = snapshot(&[], option_map! {"debug" => "off", "arch" => "x86_64"});
+    let mut b = snapshot(&[], option_map! {"debug" => "on", "arch" => "x86_64"});
+    a.environment
+        .insert("SPK_PKG_python".into(), "3.9.0".into());
+    b.environment
+        .insert("SPK_PKG_cmake".into(), "3.20.0".into());
+
+    let diff = a.diff(&b);
+    assert_eq!(
+        diff.options.get(opt_name!("debug")),
+        Some(&ValueChange::Changed("off".into(), "on".into()))
+    );
+    assert!(diff.options.get(opt_name!("arch")).is_none());
+    assert_eq!(
+        diff.environment.get("SPK_PKG_python"),
+        Some(&ValueChange::Removed("3.9.0".into()))
+    );
+    assert_eq!(
+        diff.environment.get("SPK_PKG_cmake"),
+        Some(&ValueChange::Added("3.20.0".into()))
+    );
+}
+
+#[rstest]
+fn test_snapshot_round_trip() {
+    let a = snapshot(&["python/3.9.0/3I42H3S6"], option_map! {"debug" => "off"});
+    let json = serde_json::to_string(&a).unwrap();
+    let b: BuildEnvSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(a, b);
+}
@@ -34,6 +34,22 @@ pub enum Error {
     #[error(transparent)]
     #[diagnostic(forward(0))]
     ProcessSpawnError(spfs::Error),
+    #[error("Component '{component}' has no valid marker file at {path}")]
+    #[diagnostic(help(
+        "Component marker files are created by spk and must not be removed or replaced during the build"
+    ))]
+    InvalidComponentMarker {
+        component: spk_schema::foundation::ident_component::Component,
+        path: relative_path::RelativePathBuf,
+    },
+    #[error("Found a marker file for undeclared component '{name}' at {path}")]
+    #[diagnostic(help(
+        "Files ending in '.cmpt' in the package metadata directory are reserved for component markers"
+    ))]
+    StrayComponentMarker {
+        name: String,
+        path: relative_path::RelativePathBuf,
+    },
     #[error("Package validation failed")]
     ValidationFailed {
         #[related]
@@ -47,6 +63,9 @@ pub enum Error {
     BuildManifest(#[from] spfs::tracking::manifest::MkError),
     #[error(transparent)]
     #[diagnostic(forward(0))]
+    SpkConfigError(#[from] spk_config::Error),
+    #[error(transparent)]
+    #[diagnostic(forward(0))]
     SpkExecError(Box<spk_exec::Error>),
     #[error(transparent)]
     #[diagnostic(forward(0))]
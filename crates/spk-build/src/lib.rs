@@ -13,8 +13,15 @@ mod archive_test;
 
 pub use build::{
     BinaryPackageBuilder,
+    BuildEnvDiff,
+    BuildEnvSnapshot,
     BuildSource,
+    CompilerCache,
+    CompilerCacheKind,
+    CompilerCacheStats,
     SourcePackageBuilder,
+    ValueChange,
+    build_env_path,
     build_options_path,
     build_script_path,
     build_spec_path,
@@ -3,10 +3,13 @@
 // https://github.com/spkenv/spk
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
 
+use spk_schema::foundation::format::FormatIdent;
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::name::OptNameBuf;
-use spk_schema::{BuildIdent, Package, Variant};
+use spk_schema::{BuildIdent, Package, Variant, VersionIdent};
 use spk_solve::Solution;
 
 /// The build report is constructed by the [`crate::BinaryPackageBuilder`]
@@ -57,6 +60,9 @@ pub struct BuildOutputReport {
     pub collected_changes: Vec<spfs::tracking::Diff<BuildIdent, BuildIdent>>,
     /// A report for each component generated by this build
     pub components: HashMap<Component, BuiltComponentReport>,
+    /// How well the compiler cache was used by the build script,
+    /// if one was configured and its statistics could be read
+    pub compiler_cache: Option<crate::CompilerCacheStats>,
 }
 
 /// Details for one component generated by a binary build
@@ -67,3 +73,148 @@ pub struct BuiltComponentReport {
     /// The set of files contained in this component
     pub manifest: spfs::tracking::Manifest,
 }
+
+/// A distinct step in the process of building a binary package.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BuildPhase {
+    /// Resolving and loading the source package into the build environment
+    ResolveSource,
+    /// Resolving and loading the build dependencies
+    ResolveEnvironment,
+    /// Running validators against the build environment
+    ValidateSetup,
+    /// Running the package build script
+    BuildScript,
+    /// Collecting the build output and committing it to component layers
+    Commit,
+    /// Running validators against the collected build output
+    ValidateOutput,
+}
+
+/// The outcome of a single phase of a build.
+#[derive(Clone, Debug)]
+pub struct BuildPhaseResult {
+    /// The phase that was completed
+    pub phase: BuildPhase,
+    /// The amount of time spent in this phase
+    pub duration: Duration,
+    /// False if this phase failed and stopped the build
+    pub success: bool,
+}
+
+/// The outcome of a whole build.
+#[derive(Clone, Debug)]
+pub struct BuildResult {
+    /// The package version that was being built
+    pub package: VersionIdent,
+    /// The identifier of the created build, if it was successful
+    pub build: Option<BuildIdent>,
+    /// The total amount of time spent on the build
+    pub duration: Duration,
+}
+
+#[derive(Clone)]
+pub enum BuildReporters {
+    /// No build progress is displayed.
+    Silent(Arc<SilentBuildReporter>),
+    /// Log the progress of each build phase.
+    Console(Arc<ConsoleBuildReporter>),
+    /// Provide a custom implementation for a BuildReporter.
+    Custom(Arc<Box<dyn BuildReporter>>),
+}
+
+impl BuildReporters {
+    /// Create a new silent reporter that does not output any progress
+    pub fn silent() -> Self {
+        Self::Silent(Arc::new(SilentBuildReporter))
+    }
+
+    /// Create a new console reporter that logs build progress
+    pub fn console() -> Self {
+        Self::Console(Arc::new(ConsoleBuildReporter))
+    }
+
+    /// Create a reporter with custom behavior
+    pub fn custom(reporter: Box<dyn BuildReporter>) -> Self {
+        Self::Custom(Arc::new(reporter))
+    }
+
+    fn as_reporter(&self) -> &dyn BuildReporter {
+        match self {
+            Self::Silent(r) => &**r,
+            Self::Console(r) => &**r,
+            Self::Custom(r) => &***r,
+        }
+    }
+}
+
+impl Default for BuildReporters {
+    fn default() -> Self {
+        Self::console()
+    }
+}
+
+impl BuildReporter for BuildReporters {
+    fn build_started(&self, package: &VersionIdent) {
+        self.as_reporter().build_started(package)
+    }
+
+    fn build_finished(&self, result: &BuildResult) {
+        self.as_reporter().build_finished(result)
+    }
+
+    fn phase_started(&self, phase: BuildPhase) {
+        self.as_reporter().phase_started(phase)
+    }
+
+    fn phase_finished(&self, result: &BuildPhaseResult) {
+        self.as_reporter().phase_finished(result)
+    }
+}
+
+/// Receives updates from a binary build process to be reported.
+///
+/// Every call to phase_started is followed up by a call to
+/// phase_finished, even if the phase fails. Phases that are not
+/// relevant to a build (eg: resolving the source package of a
+/// build from a local path) are not reported at all.
+pub trait BuildReporter: Send + Sync {
+    /// Called when a new build is started
+    fn build_started(&self, _package: &VersionIdent) {}
+
+    /// Called when a build has completed, successfully or not
+    fn build_finished(&self, _result: &BuildResult) {}
+
+    /// Called when a build enters a new phase
+    fn phase_started(&self, _phase: BuildPhase) {}
+
+    /// Called when a build phase has completed, successfully or not
+    fn phase_finished(&self, _result: &BuildPhaseResult) {}
+}
+
+#[derive(Default)]
+pub struct SilentBuildReporter;
+impl BuildReporter for SilentBuildReporter {}
+
+/// Logs the progress of a build through the standard tracing output.
+#[derive(Default)]
+pub struct ConsoleBuildReporter;
+
+impl BuildReporter for ConsoleBuildReporter {
+    fn build_finished(&self, result: &BuildResult) {
+        if let Some(build) = &result.build {
+            tracing::debug!("built {} in {:.2?}", build.format_ident(), result.duration);
+        }
+    }
+
+    fn phase_started(&self, phase: BuildPhase) {
+        if let BuildPhase::Commit = phase {
+            tracing::info!("Committing package contents...");
+        }
+    }
+
+    fn phase_finished(&self, result: &BuildPhaseResult) {
+        tracing::debug!("{} finished in {:.2?}", result.phase, result.duration);
+    }
+}
@@ -75,20 +75,22 @@ impl super::Validator for CollectAllFilesValidator {
                     )
                 }
             }
-            RuleKind::Deny => uncollected
-                .map(|diff| {
-                    let status = Status::Required(Error::CollectAllFilesRequired {
-                        path: diff.path.clone(),
-                    });
-                    let subject = Subject::Path(diff.mode.user_data().clone(), diff.path.clone());
-                    Outcome {
-                        subject,
-                        condition: ValidationMatcherDiscriminants::CollectAllFiles,
-                        locality: String::new(),
-                        status,
-                    }
-                })
-                .collect(),
+            RuleKind::Deny => {
+                let mut paths: Vec<_> = uncollected.map(|diff| diff.path.clone()).collect();
+                if paths.is_empty() {
+                    return std::iter::empty::<Outcome>().collect();
+                }
+                paths.sort();
+                // all uncollected files are reported together so that
+                // the author can see the complete set at once
+                Outcome {
+                    subject: Subject::Package(report.setup.package.ident().clone()),
+                    condition: ValidationMatcherDiscriminants::CollectAllFiles,
+                    locality: String::new(),
+                    status: Status::Required(Error::CollectAllFilesRequired { paths }),
+                }
+                .into()
+            }
         }
     }
 }
@@ -0,0 +1,102 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use spk_schema::foundation::env::data_path;
+use spk_schema::foundation::ident_component::Component;
+use spk_schema::validation::{
+    ValidationMatcherDiscriminants,
+    ValidationRuleDiscriminants as RuleKind,
+};
+use spk_schema::{Package, Variant};
+
+use super::{Error, Outcome, Report, Status, Subject};
+use crate::report::{BuildReport, BuildSetupReport};
+
+#[cfg(test)]
+#[path = "./empty_component_test.rs"]
+mod empty_component_test;
+
+pub struct EmptyComponentValidator<'a> {
+    pub kind: RuleKind,
+    pub components: &'a Vec<Component>,
+    pub min_files: Option<usize>,
+}
+
+impl super::validator::sealed::Sealed for EmptyComponentValidator<'_> {}
+
+#[async_trait::async_trait]
+impl super::Validator for EmptyComponentValidator<'_> {
+    async fn validate_setup<P, V>(&self, _setup: &BuildSetupReport<P, V>) -> Report
+    where
+        P: Package,
+        V: Variant + Send + Sync,
+    {
+        Report::entire_build_not_matched(ValidationMatcherDiscriminants::EmptyComponent)
+    }
+
+    async fn validate_build<P, V>(&self, report: &BuildReport<P, V>) -> Report
+    where
+        P: Package,
+        V: Variant + Send + Sync,
+    {
+        let min_files = self.min_files.unwrap_or(1);
+        let components: Vec<_> = if self.components.is_empty() {
+            report
+                .output
+                .components
+                .keys()
+                .filter(|c| !c.is_source())
+                .cloned()
+                .collect()
+        } else {
+            self.components.clone()
+        };
+        // every component includes the package metadata, which
+        // should not count towards the files that it provides
+        let metadata = data_path(report.setup.package.ident());
+        let outcomes = components.into_iter().map(|component| {
+            let files = report
+                .output
+                .components
+                .get(&component)
+                .map(|c| {
+                    c.manifest
+                        .walk()
+                        .filter(|node| node.entry.kind.is_blob())
+                        .filter(|node| node.path.strip_prefix(&metadata).is_err())
+                        .count()
+                })
+                .unwrap_or_default();
+            let is_empty = files < min_files;
+            let status = match self.kind {
+                RuleKind::Deny if is_empty => Status::Denied(Error::EmptyComponentDenied {
+                    component: component.clone(),
+                    files,
+                    min_files,
+                }),
+                RuleKind::Require if !is_empty => Status::Required(Error::EmptyComponentRequired {
+                    component: component.clone(),
+                    files,
+                    min_files,
+                }),
+                _ if is_empty => Status::Allowed,
+                _ => Status::NoMatch,
+            };
+            Outcome {
+                condition: ValidationMatcherDiscriminants::EmptyComponent,
+                // each component is validated independently, so that
+                // rules naming specific components can override others
+                locality: component.to_string(),
+                subject: Subject::Everything,
+                status,
+            }
+        });
+        let mut report: Report = outcomes.collect();
+        // a report must never be empty, even if there were no components
+        report.extend(Report::entire_build_not_matched(
+            ValidationMatcherDiscriminants::EmptyComponent,
+        ));
+        report
+    }
+}
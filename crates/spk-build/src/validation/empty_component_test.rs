@@ -0,0 +1,119 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use spfs::tracking::Manifest;
+use spk_schema::foundation::ident_component::Component;
+use spk_schema::validation::ValidationMatcher;
+use spk_schema::{OptionMap, OptionValues, Package, ValidationRule, v0};
+use spk_solve::Solution;
+
+use crate::report::{BuildOutputReport, BuildReport, BuildSetupReport, BuiltComponentReport};
+use crate::validation::Validator;
+
+fn report_with_files(files: &[(Component, &[&str])]) -> BuildReport<v0::PackageSpec, OptionMap> {
+    let package = v0::PackageSpec::new("test-pkg/1.0.0/3I42H3S6".parse().unwrap());
+    let mut output = BuildOutputReport::default();
+    for (component, paths) in files {
+        let mut manifest = Manifest::default();
+        for path in paths.iter() {
+            if let Some((parent, _)) = path.rsplit_once('/') {
+                manifest.mkdirs(parent).unwrap();
+            }
+            manifest
+                .mknod(path, spfs::tracking::Entry::empty_file_with_open_perms())
+                .unwrap();
+        }
+        output.components.insert(
+            component.clone(),
+            BuiltComponentReport {
+                layer: spfs::encoding::EMPTY_DIGEST.into(),
+                manifest,
+            },
+        );
+    }
+    BuildReport {
+        output,
+        setup: BuildSetupReport {
+            environment: Solution::default(),
+            variant: package.option_values(),
+            environment_filesystem: Manifest::new(
+                spfs::tracking::Entry::empty_dir_with_open_perms_with_data(package.ident().clone()),
+            ),
+            suppressed_requirements: Default::default(),
+            package,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_validate_empty_component() {
+    let report = report_with_files(&[
+        (Component::Build, &["include.h"]),
+        (
+            Component::Run,
+            // metadata files do not count towards the total
+            &["spk/pkg/test-pkg/1.0.0/3I42H3S6/spec.yaml"],
+        ),
+    ]);
+
+    ValidationRule::Deny {
+        condition: ValidationMatcher::EmptyComponent {
+            components: vec![Component::Build],
+            min_files: None,
+        },
+    }
+    .validate_build(&report)
+    .await
+    .into_result()
+    .expect("build component has a file");
+
+    let err = ValidationRule::Deny {
+        condition: ValidationMatcher::EmptyComponent {
+            components: Vec::new(),
+            min_files: None,
+        },
+    }
+    .validate_build(&report)
+    .await
+    .into_result()
+    .expect_err("run component is empty");
+    let crate::Error::ValidationFailed { errors } = err else {
+        panic!("expected validation failure, got {err:?}");
+    };
+    assert_eq!(
+        errors,
+        vec![crate::validation::Error::EmptyComponentDenied {
+            component: Component::Run,
+            files: 0,
+            min_files: 1,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_validate_empty_component_min_files() {
+    let report = report_with_files(&[(Component::Run, &["bin/a", "bin/b"])]);
+
+    ValidationRule::Deny {
+        condition: ValidationMatcher::EmptyComponent {
+            components: vec![Component::Run],
+            min_files: Some(2),
+        },
+    }
+    .validate_build(&report)
+    .await
+    .into_result()
+    .expect("run component has enough files");
+
+    ValidationRule::Deny {
+        condition: ValidationMatcher::EmptyComponent {
+            components: vec![Component::Run],
+            min_files: Some(3),
+        },
+    }
+    .validate_build(&report)
+    .await
+    .into_result()
+    .expect_err("run component has too few files");
+}
@@ -10,9 +10,14 @@ use miette::Diagnostic;
 use relative_path::RelativePathBuf;
 use spfs::env::SPFS_DIR;
 use spk_schema::BuildIdent;
+use spk_schema::foundation::ident_component::Component;
 use spk_schema::ident::RequestWithOptions;
 use thiserror::Error;
 
+#[cfg(test)]
+#[path = "./error_test.rs"]
+mod error_test;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Errors returned by failed package validation rules.
@@ -30,6 +35,26 @@ pub enum Error {
     #[error("This build was expected to install no files, but did")]
     #[diagnostic(severity(warning), code(spk::build::validation::empty_package))]
     EmptyPackageRequired,
+    #[error("Component '{component}' must contain at least {min_files} file(s), but has {files}")]
+    #[diagnostic(
+        severity(warning),
+        code(spk::build::validation::empty_component),
+        help("Check that the component's file match rules select the intended files")
+    )]
+    EmptyComponentDenied {
+        component: Component,
+        files: usize,
+        min_files: usize,
+    },
+    #[error(
+        "Component '{component}' was expected to contain fewer than {min_files} file(s), but has {files}"
+    )]
+    #[diagnostic(severity(warning), code(spk::build::validation::empty_component))]
+    EmptyComponentRequired {
+        component: Component,
+        files: usize,
+        min_files: usize,
+    },
 
     #[error(
         r#"Package must include a build requirement for {request}
@@ -92,11 +117,14 @@ pub enum Error {
     #[error(
         r#"Builds are expected to collect all installed files
 
-    this one was not collected: {SPFS_DIR}{path}
-"#
+{}"#,
+        format_uncollected_paths(.paths)
     )]
     #[diagnostic(severity(warning), code(spk::build::validation::collect_all_files))]
-    CollectAllFilesRequired { path: RelativePathBuf },
+    CollectAllFilesRequired {
+        /// Every installed file that was not collected, sorted
+        paths: Vec<RelativePathBuf>,
+    },
     #[error("This build was expected to ignore some files, but did not")]
     #[diagnostic(
         severity(warning),
@@ -181,6 +209,27 @@ pub enum Error {
     )]
     StrongInheritanceVarDescriptionRequired,
 
+    #[error("Validation command '{command}' {reason}\n{output}")]
+    #[diagnostic(severity(warning), code(spk::build::validation::external_command))]
+    ExternalCommandDenied {
+        command: String,
+        /// eg: "exited with status 1"
+        reason: String,
+        /// The captured stdout and stderr of the command
+        output: String,
+    },
+    #[error("Validation command '{command}' was expected to fail, but succeeded")]
+    #[diagnostic(severity(warning), code(spk::build::validation::external_command))]
+    ExternalCommandRequired { command: String },
+
+    #[error("Validator '{validator}' failed: {message}")]
+    #[diagnostic(severity(warning), code(spk::build::validation::custom))]
+    CustomValidatorDenied {
+        /// The name of the custom validator that found the issue
+        validator: String,
+        message: String,
+    },
+
     #[error("A valid SPDX license required, nothing specified")]
     #[diagnostic(severity(warning), code(spk::build::validation::spdx_license))]
     SpdxLicenseMissing,
@@ -191,3 +240,52 @@ pub enum Error {
     #[diagnostic(severity(warning), code(spk::build::validation::spdx_license))]
     SpdxLicenseDenied,
 }
+
+/// The maximum number of uncollected paths that are listed individually
+const MAX_LISTED_PATHS: usize = 25;
+
+/// The maximum number of directories used to summarize unlisted paths
+const MAX_SUMMARIZED_DIRS: usize = 10;
+
+/// List the given paths for display, summarizing any beyond
+/// the first few by their parent directory.
+fn format_uncollected_paths(paths: &[RelativePathBuf]) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    match paths.len() {
+        1 => out.push_str("    this one was not collected:\n"),
+        n => {
+            let _ = writeln!(out, "    these {n} were not collected:");
+        }
+    }
+    for path in paths.iter().take(MAX_LISTED_PATHS) {
+        let _ = writeln!(out, "      {SPFS_DIR}{path}");
+    }
+    let remaining = paths.get(MAX_LISTED_PATHS..).unwrap_or_default();
+    if remaining.is_empty() {
+        return out;
+    }
+
+    let mut by_dir = std::collections::BTreeMap::<_, usize>::new();
+    for path in remaining {
+        *by_dir
+            .entry(path.parent().map(|p| p.as_str()).unwrap_or_default())
+            .or_default() += 1;
+    }
+    let _ = writeln!(out, "    ...and {} more, in:", remaining.len());
+    let mut by_count: Vec<_> = by_dir.into_iter().collect();
+    by_count.sort_by(|(_, a), (_, b)| b.cmp(a));
+    for (dir, count) in by_count.iter().take(MAX_SUMMARIZED_DIRS) {
+        let _ = writeln!(out, "      {SPFS_DIR}{dir}/ ({count})");
+    }
+    if by_count.len() > MAX_SUMMARIZED_DIRS {
+        let others: usize = by_count[MAX_SUMMARIZED_DIRS..].iter().map(|(_, c)| c).sum();
+        let _ = writeln!(
+            out,
+            "      ({others} in {} other directories)",
+            by_count.len() - MAX_SUMMARIZED_DIRS
+        );
+    }
+    out
+}
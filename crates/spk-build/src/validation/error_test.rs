@@ -0,0 +1,34 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use relative_path::RelativePathBuf;
+
+use super::{Error, MAX_LISTED_PATHS};
+
+#[test]
+fn test_collect_all_files_lists_every_path() {
+    let err = Error::CollectAllFilesRequired {
+        paths: vec!["/bin/one".into(), "/bin/two".into()],
+    };
+    let message = err.to_string();
+    assert!(message.contains("these 2 were not collected"), "{message}");
+    assert!(message.contains("/spfs/bin/one"), "{message}");
+    assert!(message.contains("/spfs/bin/two"), "{message}");
+}
+
+#[test]
+fn test_collect_all_files_summarizes_many_paths() {
+    let mut paths: Vec<RelativePathBuf> = (0..MAX_LISTED_PATHS)
+        .map(|i| format!("/bin/{i:03}").into())
+        .collect();
+    paths.extend((0..100).map(|i| RelativePathBuf::from(format!("/share/doc/{i:03}"))));
+    let message = Error::CollectAllFilesRequired { paths }.to_string();
+    assert!(
+        message.contains("these 125 were not collected"),
+        "{message}"
+    );
+    assert!(message.contains("...and 100 more, in:"), "{message}");
+    assert!(message.contains("/spfs/share/doc/ (100)"), "{message}");
+    assert!(!message.contains("/spfs/share/doc/050"), "{message}");
+}
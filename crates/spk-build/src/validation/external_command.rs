@@ -0,0 +1,173 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use spfs::env::SPFS_DIR;
+use spk_schema::foundation::format::FormatIdent;
+use spk_schema::validation::{
+    ValidationMatcherDiscriminants,
+    ValidationRuleDiscriminants as RuleKind,
+};
+use spk_schema::{Package, Variant};
+use tokio::io::AsyncWriteExt;
+
+use super::{Error, Outcome, Report, Status, Subject};
+use crate::report::{BuildReport, BuildSetupReport};
+
+#[cfg(test)]
+#[path = "./external_command_test.rs"]
+mod external_command_test;
+
+/// The default number of seconds that an external command may run
+pub const DEFAULT_EXTERNAL_COMMAND_TIMEOUT: u64 = 300;
+
+/// Environment variables that are passed through to the external command,
+/// all others are removed.
+const PASSTHROUGH_VARS: &[&str] = &["PATH", "HOME", "USER", "LANG", "TMPDIR"];
+
+/// The maximum number of bytes of command output kept in the report
+const MAX_OUTPUT_LEN: usize = 16 * 1024;
+
+pub struct ExternalCommandValidator<'a> {
+    pub kind: RuleKind,
+    pub command: &'a Vec<String>,
+    pub timeout: Option<u64>,
+    pub allow_network: bool,
+}
+
+impl super::validator::sealed::Sealed for ExternalCommandValidator<'_> {}
+
+#[async_trait::async_trait]
+impl super::Validator for ExternalCommandValidator<'_> {
+    async fn validate_setup<P, V>(&self, _setup: &BuildSetupReport<P, V>) -> Report
+    where
+        P: Package,
+        V: Variant + Send + Sync,
+    {
+        Report::entire_build_not_matched(ValidationMatcherDiscriminants::ExternalCommand)
+    }
+
+    async fn validate_build<P, V>(&self, report: &BuildReport<P, V>) -> Report
+    where
+        P: Package,
+        V: Variant + Send + Sync,
+    {
+        let command = self.command.join(" ");
+        let failure = self.run(report).await.err();
+        let status = match (self.kind, failure) {
+            (RuleKind::Deny, Some((reason, output))) => {
+                Status::Denied(Error::ExternalCommandDenied {
+                    command: command.clone(),
+                    reason,
+                    output,
+                })
+            }
+            (RuleKind::Require, None) => Status::Required(Error::ExternalCommandRequired {
+                command: command.clone(),
+            }),
+            (_, Some(_)) => Status::Allowed,
+            (_, None) => Status::NoMatch,
+        };
+        Outcome {
+            condition: ValidationMatcherDiscriminants::ExternalCommand,
+            // each command is considered separately so that
+            // multiple commands do not override one another
+            locality: format!("{command}/"),
+            subject: Subject::Everything,
+            status,
+        }
+        .into()
+    }
+}
+
+impl ExternalCommandValidator<'_> {
+    /// Run the command, returning the reason and output of any failure.
+    ///
+    /// The command receives the list of files collected by the
+    /// build on stdin, one absolute path per line.
+    async fn run<P, V>(&self, report: &BuildReport<P, V>) -> Result<(), (String, String)>
+    where
+        P: Package,
+        V: Variant + Send + Sync,
+    {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(("is empty".to_string(), String::new()));
+        };
+        let ident = report.setup.package.ident();
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(args)
+            .env_clear()
+            .envs(
+                PASSTHROUGH_VARS
+                    .iter()
+                    .filter_map(|name| std::env::var_os(name).map(|v| (*name, v))),
+            )
+            .env("SPK_PKG", ident.format_ident().to_string())
+            .env("SPK_PKG_NAME", report.setup.package.name().as_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if !self.allow_network {
+            // SAFETY: unshare is async-signal-safe and only affects the child
+            unsafe {
+                cmd.pre_exec(|| {
+                    // Removing network access requires privileges that
+                    // are not always available, in which case the command
+                    // is run normally rather than failing the build.
+                    let _ = nix::sched::unshare(nix::sched::CloneFlags::CLONE_NEWNET);
+                    Ok(())
+                });
+            }
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|err| (format!("could not be started: {err}"), String::new()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            let files = report
+                .output
+                .collected_layer
+                .walk()
+                .filter(|node| node.entry.kind.is_blob())
+                .map(|node| format!("{}\n", node.path.to_path(SPFS_DIR).display()))
+                .collect::<String>();
+            tokio::spawn(async move {
+                // the command is not required to read its input
+                let _ = stdin.write_all(files.as_bytes()).await;
+            });
+        }
+
+        let timeout = Duration::from_secs(self.timeout.unwrap_or(DEFAULT_EXTERNAL_COMMAND_TIMEOUT));
+        let output = match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Err(_) => {
+                return Err((
+                    format!("timed out after {}s", timeout.as_secs()),
+                    String::new(),
+                ));
+            }
+            Ok(Err(err)) => return Err((format!("failed: {err}"), String::new())),
+            Ok(Ok(output)) => output,
+        };
+        if output.status.success() {
+            return Ok(());
+        }
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        if text.len() > MAX_OUTPUT_LEN {
+            let mut start = text.len() - MAX_OUTPUT_LEN;
+            while !text.is_char_boundary(start) {
+                start += 1;
+            }
+            text = format!("...{}", &text[start..]);
+        }
+        let reason = match output.status.code() {
+            Some(code) => format!("exited with status {code}"),
+            None => "was terminated by a signal".to_string(),
+        };
+        Err((reason, text.trim_end().to_string()))
+    }
+}
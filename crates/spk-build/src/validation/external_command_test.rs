@@ -0,0 +1,81 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use spfs::tracking::Manifest;
+use spk_schema::validation::ValidationMatcher;
+use spk_schema::{OptionMap, OptionValues, Package, ValidationRule, v0};
+use spk_solve::Solution;
+
+use crate::report::{BuildReport, BuildSetupReport};
+use crate::validation::Validator;
+
+fn empty_report() -> BuildReport<v0::PackageSpec, OptionMap> {
+    let package = v0::PackageSpec::new("test-pkg/1.0.0/3I42H3S6".parse().unwrap());
+    BuildReport {
+        setup: BuildSetupReport {
+            environment: Solution::default(),
+            variant: package.option_values(),
+            environment_filesystem: Manifest::new(
+                spfs::tracking::Entry::empty_dir_with_open_perms_with_data(package.ident().clone()),
+            ),
+            suppressed_requirements: Default::default(),
+            package,
+        },
+        output: Default::default(),
+    }
+}
+
+fn deny(command: &[&str], timeout: Option<u64>) -> ValidationRule {
+    ValidationRule::Deny {
+        condition: ValidationMatcher::ExternalCommand {
+            command: command.iter().map(|s| s.to_string()).collect(),
+            timeout,
+            allow_network: false,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_external_command_success() {
+    deny(&["true"], None)
+        .validate_build(&empty_report())
+        .await
+        .into_result()
+        .expect("a successful command should pass validation");
+}
+
+#[tokio::test]
+async fn test_external_command_failure_captures_output() {
+    let err = deny(
+        &["sh", "-c", "echo not allowed: $SPK_PKG_NAME; exit 3"],
+        None,
+    )
+    .validate_build(&empty_report())
+    .await
+    .into_result()
+    .expect_err("a failed command should fail validation");
+    let crate::Error::ValidationFailed { errors } = err else {
+        panic!("expected validation failure, got {err:?}");
+    };
+    let [crate::validation::Error::ExternalCommandDenied { reason, output, .. }] =
+        errors.as_slice()
+    else {
+        panic!("expected a single external command error, got {errors:?}");
+    };
+    assert_eq!(reason, "exited with status 3");
+    assert_eq!(output, "not allowed: test-pkg");
+}
+
+#[tokio::test]
+async fn test_external_command_timeout() {
+    let err = deny(&["sleep", "10"], Some(1))
+        .validate_build(&empty_report())
+        .await
+        .into_result()
+        .expect_err("a command that runs too long should fail validation");
+    assert!(
+        format!("{err:?}").contains("timed out"),
+        "expected a timeout, got {err:?}"
+    );
+}
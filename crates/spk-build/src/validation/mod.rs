@@ -5,11 +5,15 @@
 mod alter_existing_files;
 mod collect_all_files;
 mod collect_existing_files;
+mod empty_component;
 mod empty_package;
 mod error;
+mod external_command;
 mod inherit_requirements;
 mod long_var_description;
 mod recursive_build;
+mod registry;
+mod severity;
 mod spdx_license;
 mod strong_inheritance_var_desc;
 mod validator;
@@ -17,11 +21,15 @@ mod validator;
 pub use alter_existing_files::AlterExistingFilesValidator;
 pub use collect_all_files::CollectAllFilesValidator;
 pub use collect_existing_files::CollectExistingFilesValidator;
+pub use empty_component::EmptyComponentValidator;
 pub use empty_package::EmptyPackageValidator;
 pub use error::{Error, Result};
+pub use external_command::{DEFAULT_EXTERNAL_COMMAND_TIMEOUT, ExternalCommandValidator};
 pub use inherit_requirements::InheritRequirementsValidator;
 pub use long_var_description::LongVarDescriptionValidator;
 pub use recursive_build::RecursiveBuildValidator;
+pub use registry::{CustomValidator, ValidationContext, ValidatorRegistry};
+pub use severity::{Severity, SeverityOverrides};
 pub use spdx_license::SpdxLicenseValidator;
 pub use strong_inheritance_var_desc::StrongInheritanceVarDescriptionValidator;
 pub use validator::{Outcome, Report, Status, Subject, Validator};
@@ -0,0 +1,143 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::sync::Arc;
+
+use spk_schema::foundation::option_map::OptionMap;
+use spk_schema::{BuildIdent, Package, Variant};
+use spk_solve::solution::Solution;
+
+use super::Error;
+use crate::report::{BuildOutputReport, BuildReport, BuildSetupReport};
+
+#[cfg(test)]
+#[path = "./registry_test.rs"]
+mod registry_test;
+
+/// A validator that can be provided at runtime to check some
+/// aspect of a package build.
+///
+/// Unlike the validation rules in a package spec, these are run for
+/// every build made by a [`crate::BinaryPackageBuilder`] that they are
+/// registered with, see [`ValidatorRegistry`].
+#[async_trait::async_trait]
+pub trait CustomValidator: Send + Sync {
+    /// A short, unique name for this validator used when
+    /// reporting any issues that it finds
+    fn name(&self) -> &str;
+
+    /// Check the initial build setup before actually
+    /// running the build script
+    async fn validate_setup(&self, _context: &ValidationContext<'_>) -> Vec<Error> {
+        Vec::new()
+    }
+
+    /// Check the output and final status of the build
+    async fn validate_build(&self, context: &ValidationContext<'_>) -> Vec<Error>;
+}
+
+/// The details of a build that are provided to a [`CustomValidator`]
+pub struct ValidationContext<'a> {
+    /// The binary package being built
+    pub package: &'a BuildIdent,
+    /// The option values of the variant being built
+    pub options: OptionMap,
+    /// The resolved build environment
+    pub environment: &'a Solution,
+    /// The state of the filesystem before the build started, where
+    /// each entry is tagged with the package that owns it
+    pub environment_filesystem: &'a spfs::tracking::Manifest<BuildIdent>,
+    /// The output of the build, which is only available
+    /// once the build script has completed
+    pub output: Option<&'a BuildOutputReport>,
+}
+
+impl<'a> ValidationContext<'a> {
+    /// The context for validating a build before it is run
+    pub fn from_setup<P, V>(report: &'a BuildSetupReport<P, V>) -> Self
+    where
+        P: Package,
+        V: Variant,
+    {
+        Self {
+            package: report.package.ident(),
+            options: report.variant.options().into_owned(),
+            environment: &report.environment,
+            environment_filesystem: &report.environment_filesystem,
+            output: None,
+        }
+    }
+
+    /// The context for validating the output of a completed build
+    pub fn from_build<P, V>(report: &'a BuildReport<P, V>) -> Self
+    where
+        P: Package,
+        V: Variant,
+    {
+        Self {
+            output: Some(&report.output),
+            ..Self::from_setup(&report.setup)
+        }
+    }
+}
+
+/// A set of [`CustomValidator`]s that are run in addition to the
+/// validation rules of the package being built.
+///
+/// The registry is empty by default, so builds are only checked
+/// against their validation rules unless more validators are added.
+#[derive(Clone, Default)]
+pub struct ValidatorRegistry {
+    validators: Vec<Arc<dyn CustomValidator>>,
+}
+
+impl ValidatorRegistry {
+    /// Add a validator to this registry, to be run after any
+    /// that were previously registered.
+    pub fn register<V>(&mut self, validator: V) -> &mut Self
+    where
+        V: CustomValidator + 'static,
+    {
+        self.validators.push(Arc::new(validator));
+        self
+    }
+
+    /// True if no validators have been registered
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// The names of all registered validators, in the order that they run
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.validators.iter().map(|v| v.name())
+    }
+
+    /// Run all registered validators against the build setup,
+    /// collecting any issues that they find
+    pub async fn validate_setup(&self, context: &ValidationContext<'_>) -> Vec<Error> {
+        let mut errors = Vec::new();
+        for validator in self.validators.iter() {
+            tracing::trace!(" > custom validator {}", validator.name());
+            errors.extend(validator.validate_setup(context).await);
+        }
+        errors
+    }
+
+    /// Run all registered validators against the build output,
+    /// collecting any issues that they find
+    pub async fn validate_build(&self, context: &ValidationContext<'_>) -> Vec<Error> {
+        let mut errors = Vec::new();
+        for validator in self.validators.iter() {
+            tracing::trace!(" > custom validator {}", validator.name());
+            errors.extend(validator.validate_build(context).await);
+        }
+        errors
+    }
+}
+
+impl std::fmt::Debug for ValidatorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
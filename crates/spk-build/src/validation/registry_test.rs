@@ -0,0 +1,79 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use spfs::tracking::Manifest;
+use spk_schema::{OptionValues, Package, v0};
+use spk_solve::Solution;
+
+use super::{CustomValidator, ValidationContext, ValidatorRegistry};
+use crate::report::{BuildReport, BuildSetupReport};
+use crate::validation::Error;
+
+/// Denies any build that produces no files
+struct RequireOutputFiles;
+
+#[async_trait::async_trait]
+impl CustomValidator for RequireOutputFiles {
+    fn name(&self) -> &str {
+        "require-output-files"
+    }
+
+    async fn validate_build(&self, context: &ValidationContext<'_>) -> Vec<Error> {
+        let has_files = context
+            .output
+            .map(|o| !o.collected_layer.is_empty())
+            .unwrap_or_default();
+        if has_files {
+            return Vec::new();
+        }
+        vec![Error::CustomValidatorDenied {
+            validator: self.name().to_string(),
+            message: format!("{} has no files", context.package),
+        }]
+    }
+}
+
+#[tokio::test]
+async fn test_registry_runs_custom_validators() {
+    let package = v0::PackageSpec::new("test-pkg/1.0.0/3I42H3S6".parse().unwrap());
+    let report = BuildReport {
+        setup: BuildSetupReport {
+            environment: Solution::default(),
+            variant: package.option_values(),
+            environment_filesystem: Manifest::new(
+                spfs::tracking::Entry::empty_dir_with_open_perms_with_data(package.ident().clone()),
+            ),
+            suppressed_requirements: Default::default(),
+            package,
+        },
+        output: Default::default(),
+    };
+
+    let mut registry = ValidatorRegistry::default();
+    assert!(registry.is_empty());
+    registry.register(RequireOutputFiles);
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        vec!["require-output-files"]
+    );
+
+    let errors = registry
+        .validate_setup(&ValidationContext::from_setup(&report.setup))
+        .await;
+    assert!(
+        errors.is_empty(),
+        "validators without setup checks should pass"
+    );
+
+    let errors = registry
+        .validate_build(&ValidationContext::from_build(&report))
+        .await;
+    assert_eq!(
+        errors,
+        vec![Error::CustomValidatorDenied {
+            validator: "require-output-files".into(),
+            message: "test-pkg/1.0.0/3I42H3S6 has no files".into(),
+        }]
+    );
+}
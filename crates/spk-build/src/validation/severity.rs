@@ -0,0 +1,88 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use miette::Diagnostic;
+
+use super::Error;
+
+#[cfg(test)]
+#[path = "./severity_test.rs"]
+mod severity_test;
+
+/// How a failed validation is handled once it has been reported
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Severity {
+    /// The issue fails the build
+    #[default]
+    Error,
+    /// The issue is logged but does not fail the build
+    Warning,
+}
+
+/// Assigns a [`Severity`] to validation errors based on their
+/// error code (eg: `spk::build::validation::alter_existing_files`).
+///
+/// Any error without an override is given the default severity,
+/// which fails the build as all validation errors always have.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeverityOverrides {
+    by_code: HashMap<String, Severity>,
+}
+
+impl SeverityOverrides {
+    /// Load the severity overrides from the spk config.
+    pub fn from_config(config: &spk_config::Validation) -> crate::Result<Self> {
+        let mut overrides = Self::default();
+        for (code, value) in config.severity.iter() {
+            let severity = Severity::from_str(value).map_err(|_| {
+                crate::Error::String(format!(
+                    "Invalid severity '{value}' for validation code {code}, expected one of: error, warning"
+                ))
+            })?;
+            overrides.insert(code.clone(), severity);
+        }
+        Ok(overrides)
+    }
+
+    /// Set the severity for all errors with the given code, returning
+    /// any previous override.
+    pub fn insert<S: Into<String>>(&mut self, code: S, severity: Severity) -> Option<Severity> {
+        self.by_code.insert(code.into(), severity)
+    }
+
+    /// The severity to apply to the given validation error.
+    pub fn severity_of(&self, error: &Error) -> Severity {
+        error
+            .code()
+            .and_then(|code| self.by_code.get(&code.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Split the given errors into those that should fail the build
+    /// and those that should only be reported as warnings.
+    pub fn partition(&self, errors: Vec<Error>) -> (Vec<Error>, Vec<Error>) {
+        errors
+            .into_iter()
+            .partition(|e| self.severity_of(e) == Severity::Error)
+    }
+
+    /// Convert the given errors into a single result, logging
+    /// any that are only warnings.
+    pub fn into_result(&self, errors: Vec<Error>) -> crate::Result<()> {
+        let (errors, warnings) = self.partition(errors);
+        for warning in warnings {
+            tracing::warn!("Validation warning: {warning}");
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::Error::ValidationFailed { errors })
+        }
+    }
+}
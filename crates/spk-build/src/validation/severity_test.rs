@@ -0,0 +1,50 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::HashMap;
+
+use rstest::rstest;
+
+use super::{Severity, SeverityOverrides};
+use crate::validation::Error;
+
+#[rstest]
+fn test_default_severity_is_error() {
+    let overrides = SeverityOverrides::default();
+    assert_eq!(
+        overrides.severity_of(&Error::EmptyPackageDenied),
+        Severity::Error
+    );
+}
+
+#[rstest]
+fn test_severity_from_config() {
+    let config = spk_config::Validation {
+        severity: HashMap::from([(
+            "spk::build::validation::empty_package".to_string(),
+            "warning".to_string(),
+        )]),
+    };
+    let overrides = SeverityOverrides::from_config(&config).unwrap();
+    let (errors, warnings) = overrides.partition(vec![
+        Error::EmptyPackageDenied,
+        Error::CollectAllFilesRequired { paths: Vec::new() },
+    ]);
+    assert_eq!(
+        errors,
+        vec![Error::CollectAllFilesRequired { paths: Vec::new() }]
+    );
+    assert_eq!(warnings, vec![Error::EmptyPackageDenied]);
+}
+
+#[rstest]
+fn test_severity_from_config_invalid() {
+    let config = spk_config::Validation {
+        severity: HashMap::from([(
+            "spk::build::validation::empty_package".to_string(),
+            "fatal".to_string(),
+        )]),
+    };
+    SeverityOverrides::from_config(&config).expect_err("unknown severities should be rejected");
+}
@@ -8,7 +8,7 @@ use relative_path::RelativePathBuf;
 use spk_schema::validation::{ValidationMatcher, ValidationMatcherDiscriminants};
 use spk_schema::{BuildIdent, Package, Variant};
 
-use super::Error;
+use super::{Error, SeverityOverrides};
 use crate::report::{BuildReport, BuildSetupReport};
 
 #[cfg(test)]
@@ -50,6 +50,17 @@ macro_rules! rule_to_validator {
                 let $bind = super::EmptyPackageValidator { kind };
                 $op
             }
+            ValidationMatcher::EmptyComponent {
+                components,
+                min_files,
+            } => {
+                let $bind = super::EmptyComponentValidator {
+                    kind,
+                    components,
+                    min_files: *min_files,
+                };
+                $op
+            }
             ValidationMatcher::CollectAllFiles => {
                 let $bind = super::CollectAllFilesValidator { kind };
                 $op
@@ -82,6 +93,19 @@ macro_rules! rule_to_validator {
                 let $bind = super::InheritRequirementsValidator { kind, packages };
                 $op
             }
+            ValidationMatcher::ExternalCommand {
+                command,
+                timeout,
+                allow_network,
+            } => {
+                let $bind = super::ExternalCommandValidator {
+                    kind,
+                    command,
+                    timeout: *timeout,
+                    allow_network: *allow_network,
+                };
+                $op
+            }
         }
     }};
 }
@@ -232,14 +256,14 @@ impl Report {
 
     /// Convert this report to a single result based on the current state
     pub fn into_result(self) -> crate::Result<()> {
-        let validation_errors = self.into_errors();
-        if validation_errors.is_empty() {
-            Ok(())
-        } else {
-            Err(crate::Error::ValidationFailed {
-                errors: validation_errors,
-            })
-        }
+        self.into_result_with_severity(&SeverityOverrides::default())
+    }
+
+    /// Same as [`Self::into_result`], except that any errors given a
+    /// [`super::Severity::Warning`] by the provided overrides are logged
+    /// rather than failing the validation.
+    pub fn into_result_with_severity(self, overrides: &SeverityOverrides) -> crate::Result<()> {
+        overrides.into_result(self.into_errors())
     }
 }
 
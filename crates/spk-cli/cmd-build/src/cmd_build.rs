@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::num::NonZeroUsize;
+
 use clap::Args;
 use miette::Result;
 use spk_cli_common::flags::{self, PackageSpecifier};
@@ -51,6 +53,19 @@ pub struct Build {
     /// this package.
     #[clap(long)]
     pub allow_circular_dependencies: bool,
+
+    /// Build up to this many variants at once, each in its own runtime
+    ///
+    /// The output of each variant build is captured and only shown if
+    /// that variant fails to build. Cannot be combined with --interactive
+    /// or --env.
+    #[clap(long, short = 'j', default_value = "1")]
+    pub jobs: NonZeroUsize,
+
+    /// When building with --jobs, continue building the remaining
+    /// variants after one of them fails
+    #[clap(long)]
+    pub keep_going: bool,
 }
 
 #[derive(Debug)]
@@ -115,6 +130,9 @@ impl Run for Build {
                 packages,
                 variant: self.variant.clone(),
                 allow_circular_dependencies: self.allow_circular_dependencies,
+                jobs: self.jobs,
+                keep_going: self.keep_going,
+                created_builds_file: None,
                 created_builds: spk_cli_common::BuildResult::default(),
             };
             let exit_status = make_binary.run().await?;
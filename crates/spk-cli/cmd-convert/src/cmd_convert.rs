@@ -61,6 +61,7 @@ impl Run for Convert {
             progress: self.progress,
             requested: vec![converter_package],
             command,
+            overrides: Vec::new(),
         };
         env.run().await
     }
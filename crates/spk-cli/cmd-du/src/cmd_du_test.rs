@@ -105,10 +105,12 @@ async fn test_du_works_on_whole_repo(#[case] solver: SolverImpl) {
         "121local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/spec.yaml",
         "0local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.cmpt",
         "17local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.sh",
+        "130local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.env.json",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/options.json",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/run.cmpt",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/spec.yaml",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.sh",
+        "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.env.json",
     ];
 
     for output in opt.du.output.vec.lock().unwrap().iter() {
@@ -382,7 +384,7 @@ async fn test_du_summarize_output_enabled(#[case] solver: SolverImpl) {
     let mut opt = Opt::try_parse_from(["du", "local/my-pkg", "-s"]).unwrap();
     opt.du.run().await.unwrap();
 
-    let expected_output = format!("140local/my-pkg/{}", "".red());
+    let expected_output = format!("270local/my-pkg/{}", "".red());
     let mut generated_output = opt.du.output.vec.lock().unwrap()[0].clone();
     generated_output.retain(|c| !c.is_whitespace());
 
@@ -433,11 +435,13 @@ async fn test_du_summarize_output_is_not_enabled(#[case] solver: SolverImpl) {
         "2local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/options.json",
         "0local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.cmpt",
         "17local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.sh",
+        "130local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.env.json",
         "121local/my-pkg/1.0.0/3I42H3S6/:build/spk/pkg/my-pkg/1.0.0/3I42H3S6/spec.yaml",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/spec.yaml",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/options.json",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/run.cmpt",
         "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.sh",
+        "0local/my-pkg/1.0.0/3I42H3S6/:run/spk/pkg/my-pkg/1.0.0/3I42H3S6/build.env.json",
     ];
 
     for output in opt.du.output.vec.lock().unwrap().iter() {
@@ -503,7 +507,7 @@ async fn test_deprecate_flag(#[case] solver: SolverImpl) {
 
     let mut opt_with_deprecate_flag = Opt::try_parse_from(["du", "local/my-pkg", "-ds"]).unwrap();
     opt_with_deprecate_flag.du.run().await.unwrap();
-    let expected_output = format!("157local/my-pkg/{}", "DEPRECATED".red());
+    let expected_output = format!("287local/my-pkg/{}", "DEPRECATED".red());
     let mut generated_output = opt_with_deprecate_flag.du.output.vec.lock().unwrap()[0].clone();
     generated_output.retain(|c| !c.is_whitespace());
     assert_eq!(expected_output, generated_output);
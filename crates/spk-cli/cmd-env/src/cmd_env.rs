@@ -10,7 +10,7 @@ use miette::{Context, Result};
 use spfs::tracking::SpecFile;
 use spfs_cli_common::Progress;
 use spk_cli_common::{CommandArgs, Run, build_required_packages, flags};
-use spk_exec::setup_runtime_with_reporter;
+use spk_exec::{PackageOverride, setup_runtime_with_overrides};
 #[cfg(feature = "statsd")]
 use spk_solve::{SPK_RUN_TIME_METRIC, get_metrics_client};
 use spk_solve::{Solver, SolverMut};
@@ -48,6 +48,15 @@ pub struct Env {
     /// Options for showing progress
     #[clap(long, value_enum)]
     pub progress: Option<Progress>,
+
+    /// Use a local directory in place of a resolved package, as NAME=PATH
+    ///
+    /// The named package must be part of the solution. Its layers are
+    /// replaced by a layer committed from the directory, which should
+    /// be laid out as it would be under /spfs, eg: a local build output.
+    /// The rest of the solution is left as it was resolved.
+    #[clap(long = "override", value_name = "NAME=PATH")]
+    pub overrides: Vec<PackageOverride>,
 }
 
 #[async_trait::async_trait]
@@ -94,7 +103,7 @@ impl Run for Env {
 
         rt.status.editable =
             self.runtime.editable() || self.requests.any_build_stage_requests(&self.requested)?;
-        setup_runtime_with_reporter(&mut rt, &solution, {
+        setup_runtime_with_overrides(&mut rt, &solution, &self.overrides, {
             match self.progress.unwrap_or_default() {
                 Progress::Bars => spfs::sync::reporter::SyncReporters::console,
                 Progress::None => spfs::sync::reporter::SyncReporters::silent,
@@ -20,6 +20,14 @@ pub struct Explain {
     #[clap(short, long, global = true, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
+    /// Show what requested each package alongside its request
+    #[clap(long)]
+    pub requested_by: bool,
+
+    /// Show the repository that each resolved build came from
+    #[clap(long)]
+    pub show_repository: bool,
+
     /// The requests to resolve
     #[clap(name = "REQUESTS", required = true)]
     pub requested: Vec<String>,
@@ -83,6 +91,8 @@ impl Run for Explain {
             .decision_formatter_settings
             .get_formatter_builder(self.verbose + 1)?
             .with_solution(true)
+            .with_show_requested_by(self.requested_by)
+            .with_show_build_repository(self.show_repository)
             .build();
         solver.run_and_print_resolve(&formatter).await?;
 
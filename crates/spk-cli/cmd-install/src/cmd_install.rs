@@ -3,6 +3,7 @@
 // https://github.com/spkenv/spk
 
 use std::collections::HashSet;
+use std::ffi::OsString;
 use std::io::Write;
 
 use clap::Args;
@@ -10,7 +11,7 @@ use colored::Colorize;
 use futures::TryFutureExt;
 use miette::{Context, IntoDiagnostic, Result};
 use spk_cli_common::{CommandArgs, Run, build_required_packages, current_env, flags};
-use spk_exec::setup_current_runtime;
+use spk_exec::{setup_current_runtime, setup_fresh_runtime};
 use spk_schema::Package;
 use spk_schema::foundation::format::FormatIdent;
 use spk_schema::foundation::spec_ops::Named;
@@ -33,6 +34,14 @@ pub struct Install {
     #[clap(long, short)]
     yes: bool,
 
+    /// Start a new runtime with the installed packages if the current
+    /// runtime cannot be updated in place, instead of failing
+    ///
+    /// This happens when the current runtime has layers that are not
+    /// part of any package or has uncommitted changes.
+    #[clap(long)]
+    fresh_runtime: bool,
+
     /// The packages to install
     #[clap(name = "PKG", required = true)]
     pub packages: Vec<String>,
@@ -122,8 +131,29 @@ impl Run for Install {
         let compiled_solution = build_required_packages(&solution, solver)
             .await
             .wrap_err("Failed to build one or more packages from source")?;
-        setup_current_runtime(&compiled_solution).await?;
-        Ok(0)
+        match setup_current_runtime(&compiled_solution).await {
+            Err(spk_exec::Error::IncompatibleRuntime(discrepancies)) if self.fresh_runtime => {
+                for discrepancy in discrepancies {
+                    tracing::warn!("{discrepancy}");
+                }
+                tracing::warn!("The current runtime cannot be updated, starting a new one");
+                let rt = setup_fresh_runtime(&compiled_solution).await?;
+                let env = compiled_solution.to_environment(Some(std::env::vars()));
+                let mut command =
+                    spfs::build_command_for_runtime(&rt, OsString::new(), Vec::<OsString>::new())?;
+                command
+                    .vars
+                    .extend(env.into_iter().map(|(k, v)| (k.into(), v.into())));
+                command
+                    .exec()
+                    .map(|_| 0)
+                    .wrap_err("Failed to execute runtime command")
+            }
+            res => {
+                res?;
+                Ok(0)
+            }
+        }
     }
 }
 
@@ -2,19 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use clap::Args;
-use futures::TryFutureExt;
+use futures::{StreamExt, TryFutureExt};
 use itertools::Itertools;
 use miette::{Context, IntoDiagnostic, Report, Result, bail, miette};
 use spk_build::{BinaryPackageBuilder, BuildSource};
 use spk_cli_common::{BuildArtifact, BuildResult, CommandArgs, Run, flags, spk_exe};
-use spk_schema::OptionMap;
 use spk_schema::foundation::format::FormatIdent;
-use spk_schema::ident::{InitialRawRequest, PkgRequest, RequestedBy};
+use spk_schema::ident::{InitialRawRequest, PkgRequest, RequestedBy, parse_build_ident};
 use spk_schema::option_map::HOST_OPTIONS;
 use spk_schema::prelude::*;
+use spk_schema::{BuildIdent, OptionMap};
 use spk_storage as storage;
 
 #[cfg(test)]
@@ -59,6 +65,25 @@ pub struct MakeBinary {
     #[clap(long)]
     pub allow_circular_dependencies: bool,
 
+    /// Build up to this many variants at once, each in its own runtime
+    ///
+    /// The output of each variant build is captured and only shown if
+    /// that variant fails to build. Cannot be combined with --interactive
+    /// or --env.
+    #[clap(long, short = 'j', default_value = "1")]
+    pub jobs: NonZeroUsize,
+
+    /// When building with --jobs, continue building the remaining
+    /// variants after one of them fails
+    #[clap(long)]
+    pub keep_going: bool,
+
+    /// Append the identifier of each created build to this file
+    ///
+    /// Used to collect the builds made by the child processes of --jobs.
+    #[clap(long, hide = true)]
+    pub created_builds_file: Option<PathBuf>,
+
     /// Populated with created specs to generate a summary from the caller.
     #[clap(skip)]
     pub created_builds: BuildResult,
@@ -84,6 +109,9 @@ impl Run for MakeBinary {
                 "Building packages disabled when 'allow_payload_sharing_between_users' is enabled"
             );
         }
+        if self.jobs.get() > 1 && (self.interactive || self.env) {
+            bail!("--jobs cannot be used together with --interactive or --env");
+        }
 
         let options = self.options.get_options()?;
         #[rustfmt::skip]
@@ -127,124 +155,132 @@ impl Run for MakeBinary {
                 )
                 .collect::<Result<Vec<_>>>()?;
 
-            for variant_info in &variants_to_build {
-                let variant = match &variant_info.build_status {
-                    flags::VariantBuildStatus::Enabled(variant) => variant,
-                    flags::VariantBuildStatus::FilteredOut(mismatches) => {
-                        tracing::debug!(
-                            "Skipping variant that was filtered out:\n{this_location} didn't match on {mismatches}",
-                            this_location = variant_info.location,
-                            mismatches = mismatches.keys().join(", ")
-                        );
-                        continue;
-                    }
-                    flags::VariantBuildStatus::Duplicate(location) => {
-                        tracing::debug!(
-                            "Skipping variant that was already built:\n{this_location} is a duplicate of {location}",
-                            this_location = variant_info.location
-                        );
-                        continue;
+            if self.jobs.get() > 1 {
+                self.build_variants_concurrently(
+                    package.as_ref(),
+                    &filename,
+                    &options,
+                    &variants_to_build,
+                )
+                .await?;
+            } else {
+                for variant_info in &variants_to_build {
+                    let variant = match &variant_info.build_status {
+                        flags::VariantBuildStatus::Enabled(variant) => variant,
+                        flags::VariantBuildStatus::FilteredOut(mismatches) => {
+                            tracing::debug!(
+                                "Skipping variant that was filtered out:\n{this_location} didn't match on {mismatches}",
+                                this_location = variant_info.location,
+                                mismatches = mismatches.keys().join(", ")
+                            );
+                            continue;
+                        }
+                        flags::VariantBuildStatus::Duplicate(location) => {
+                            tracing::debug!(
+                                "Skipping variant that was already built:\n{this_location} is a duplicate of {location}",
+                                this_location = variant_info.location
+                            );
+                            continue;
+                        }
+                    };
+
+                    let mut overrides = OptionMap::default();
+                    if !self.options.no_host {
+                        overrides.extend(HOST_OPTIONS.get()?);
                     }
-                };
+                    overrides.extend(options.clone());
+                    let variant = (**variant).clone().with_overrides(overrides);
 
-                let mut overrides = OptionMap::default();
-                if !self.options.no_host {
-                    overrides.extend(HOST_OPTIONS.get()?);
-                }
-                overrides.extend(options.clone());
-                let variant = (**variant).clone().with_overrides(overrides);
-
-                tracing::info!(
-                    "building {location}:\n{variant}",
-                    location = variant_info.location
-                );
-
-                // Always show the solution packages for the solves
-                let mut fmt_builder = self
-                    .solver
-                    .decision_formatter_settings
-                    .get_formatter_builder(self.verbose)?;
-                let src_formatter = fmt_builder
-                    .with_solution(true)
-                    .with_header("Src Resolver ")
-                    .build();
-                let build_formatter = fmt_builder
-                    .with_solution(true)
-                    .with_header("Build Resolver ")
-                    .build();
-
-                let solver = self.solver.get_solver(&self.options).await?;
-                let mut builder =
-                    BinaryPackageBuilder::from_recipe_with_solver((*recipe).clone(), solver);
-                builder
-                    .with_repositories(repos.iter().cloned())
-                    .set_interactive(self.interactive)
-                    .with_source_formatter(src_formatter)
-                    .with_build_formatter(build_formatter)
-                    .with_allow_circular_dependencies(self.allow_circular_dependencies);
-
-                if self.here {
-                    let here = std::env::current_dir()
-                        .into_diagnostic()
-                        .wrap_err("Failed to get current directory")?;
-                    builder.with_source(BuildSource::LocalPath(here));
-                } else if let Some(flags::PackageSpecifier::WithSourceIdent((_, ref ident))) =
-                    package
-                {
-                    // Use the source package `AnyIdent` if the caller supplied one.
-                    builder.with_source(BuildSource::SourcePackage(ident.clone()));
-                }
-                let out = match builder.build_and_publish(&variant, &local).await {
-                    Err(err @ spk_build::Error::SpkSolverError(_))
-                    | Err(
-                        err @ spk_build::Error::SpkStorageError(spk_storage::Error::VersionExists(
-                            _,
-                        )),
-                    )
-                    | Err(
-                        err @ spk_build::Error::SpkStorageError(
-                            spk_storage::Error::PackageNotFound(_),
-                        ),
-                    ) => {
-                        if !self.created_builds.is_empty() {
-                            tracing::warn!("Completed builds:");
-                            for (_, artifact) in self.created_builds.iter() {
-                                tracing::warn!("   {artifact}");
-                            }
-                        }
+                    tracing::info!(
+                        "building {location}:\n{variant}",
+                        location = variant_info.location
+                    );
 
-                        tracing::error!(
-                            "{location} failed:\n{variant}",
-                            location = variant_info.location
-                        );
-                        return Err(err.into());
+                    // Always show the solution packages for the solves
+                    let mut fmt_builder = self
+                        .solver
+                        .decision_formatter_settings
+                        .get_formatter_builder(self.verbose)?;
+                    let src_formatter = fmt_builder
+                        .with_solution(true)
+                        .with_header("Src Resolver ")
+                        .build();
+                    let build_formatter = fmt_builder
+                        .with_solution(true)
+                        .with_header("Build Resolver ")
+                        .build();
+
+                    let solver = self.solver.get_solver(&self.options).await?;
+                    let mut builder =
+                        BinaryPackageBuilder::from_recipe_with_solver((*recipe).clone(), solver);
+                    builder
+                        .with_repositories(repos.iter().cloned())
+                        .set_interactive(self.interactive)
+                        .with_source_formatter(src_formatter)
+                        .with_build_formatter(build_formatter)
+                        .with_allow_circular_dependencies(self.allow_circular_dependencies);
+
+                    if self.here {
+                        let here = std::env::current_dir()
+                            .into_diagnostic()
+                            .wrap_err("Failed to get current directory")?;
+                        builder.with_source(BuildSource::LocalPath(here));
+                    } else if let Some(flags::PackageSpecifier::WithSourceIdent((_, ref ident))) =
+                        package
+                    {
+                        // Use the source package `AnyIdent` if the caller supplied one.
+                        builder.with_source(BuildSource::SourcePackage(ident.clone()));
                     }
-                    Ok((spec, _cmpts)) => spec,
-                    Err(err) => return Err(err.into()),
-                };
-                tracing::info!("created {}", out.ident().format_ident());
-                self.created_builds.push(
-                    filename.to_string_lossy().to_string(),
-                    BuildArtifact::Binary(
+                    let out = match builder.build_and_publish(&variant, &local).await {
+                        Err(err @ spk_build::Error::SpkSolverError(_))
+                        | Err(
+                            err @ spk_build::Error::SpkStorageError(
+                                spk_storage::Error::VersionExists(_),
+                            ),
+                        )
+                        | Err(
+                            err @ spk_build::Error::SpkStorageError(
+                                spk_storage::Error::PackageNotFound(_),
+                            ),
+                        ) => {
+                            if !self.created_builds.is_empty() {
+                                tracing::warn!("Completed builds:");
+                                for (_, artifact) in self.created_builds.iter() {
+                                    tracing::warn!("   {artifact}");
+                                }
+                            }
+
+                            tracing::error!(
+                                "{location} failed:\n{variant}",
+                                location = variant_info.location
+                            );
+                            return Err(err.into());
+                        }
+                        Ok((spec, _cmpts)) => spec,
+                        Err(err) => return Err(err.into()),
+                    };
+                    tracing::info!("created {}", out.ident().format_ident());
+                    self.record_created_build(
+                        &filename,
                         out.ident().clone(),
                         variant_info.location,
                         variant.options().into_owned(),
-                    ),
-                );
-
-                if self.env {
-                    let ident = out.ident().to_any_ident();
-                    let request = PkgRequest::from_ident(
-                        ident.clone(),
-                        RequestedBy::CommandLineRequest(InitialRawRequest(ident.to_string())),
-                    );
-                    let mut cmd = std::process::Command::new(spk_exe());
-                    cmd.args(["env", "--enable-repo", "local"])
-                        .arg(request.pkg.to_string());
-                    tracing::info!("entering environment with new package...");
-                    tracing::debug!("{:?}", cmd);
-                    let status = cmd.status().into_diagnostic()?;
-                    return Ok(status.code().unwrap_or(1));
+                    )?;
+
+                    if self.env {
+                        let ident = out.ident().to_any_ident();
+                        let request = PkgRequest::from_ident(
+                            ident.clone(),
+                            RequestedBy::CommandLineRequest(InitialRawRequest(ident.to_string())),
+                        );
+                        let mut cmd = std::process::Command::new(spk_exe());
+                        cmd.args(["env", "--enable-repo", "local"])
+                            .arg(request.pkg.to_string());
+                        tracing::info!("entering environment with new package...");
+                        tracing::debug!("{:?}", cmd);
+                        let status = cmd.status().into_diagnostic()?;
+                        return Ok(status.code().unwrap_or(1));
+                    }
                 }
             }
 
@@ -295,3 +331,256 @@ impl Run for MakeBinary {
         Ok(0)
     }
 }
+
+/// The result of building a single variant in a child process.
+enum VariantOutcome {
+    /// The variant was built, creating these builds
+    Built(Vec<BuildIdent>),
+    /// The variant failed to build, with the captured output
+    Failed(String),
+    /// The variant was not attempted because another variant failed
+    Skipped,
+}
+
+impl MakeBinary {
+    /// Record a newly created build so that it can be summarized
+    /// by the caller.
+    fn record_created_build(
+        &mut self,
+        filename: &Path,
+        ident: BuildIdent,
+        location: flags::VariantLocation,
+        options: OptionMap,
+    ) -> Result<()> {
+        if let Some(path) = &self.created_builds_file {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+            writeln!(file, "{ident}")
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to write to {}", path.display()))?;
+        }
+        self.created_builds.push(
+            filename.to_string_lossy().to_string(),
+            BuildArtifact::Binary(ident, location, options),
+        );
+        Ok(())
+    }
+
+    /// Build all the enabled variants, each one in a separate spk
+    /// process (and so a separate runtime), running up to `jobs` of
+    /// them at once.
+    ///
+    /// The results are reported in variant order, regardless of the
+    /// order in which the builds finish.
+    async fn build_variants_concurrently(
+        &mut self,
+        package: Option<&flags::PackageSpecifier>,
+        filename: &Path,
+        options: &OptionMap,
+        variants_to_build: &[flags::VariantInfo<'_>],
+    ) -> Result<()> {
+        let mut overrides = OptionMap::default();
+        if !self.options.no_host {
+            overrides.extend(HOST_OPTIONS.get()?);
+        }
+        overrides.extend(options.clone());
+
+        let mut enabled = Vec::new();
+        for variant_info in variants_to_build {
+            match &variant_info.build_status {
+                flags::VariantBuildStatus::Enabled(variant) => {
+                    let variant = (**variant).clone().with_overrides(overrides.clone());
+                    enabled.push((variant_info.location, variant));
+                }
+                flags::VariantBuildStatus::FilteredOut(mismatches) => {
+                    tracing::debug!(
+                        "Skipping variant that was filtered out:\n{this_location} didn't match on {mismatches}",
+                        this_location = variant_info.location,
+                        mismatches = mismatches.keys().join(", ")
+                    );
+                }
+                flags::VariantBuildStatus::Duplicate(location) => {
+                    tracing::debug!(
+                        "Skipping variant that was already built:\n{this_location} is a duplicate of {location}",
+                        this_location = variant_info.location
+                    );
+                }
+            }
+        }
+        if enabled.is_empty() {
+            return Ok(());
+        }
+
+        let total = enabled.len();
+        let progress = indicatif::ProgressBar::new(total as u64)
+            .with_style(
+                indicatif::ProgressStyle::default_bar()
+                    .template(
+                        "      {spinner} {msg:<16.green} [{bar:40.cyan/dim}] {pos:>8}/{len:6}",
+                    )
+                    .expect("Invalid progress bar template"),
+            )
+            .with_message("building variants");
+        progress.enable_steady_tick(Duration::from_millis(100));
+
+        let failed = AtomicBool::new(false);
+        let this = &*self;
+        let mut results = futures::stream::iter(enabled.into_iter().map(|(location, variant)| {
+            let failed = &failed;
+            let progress = &progress;
+            async move {
+                let outcome = if failed.load(Ordering::Relaxed) && !this.keep_going {
+                    VariantOutcome::Skipped
+                } else {
+                    this.build_variant_in_child(package, location)
+                        .await
+                        .unwrap_or_else(|err| VariantOutcome::Failed(format!("{err:?}")))
+                };
+                if let VariantOutcome::Failed(_) = outcome {
+                    failed.store(true, Ordering::Relaxed);
+                }
+                progress.inc(1);
+                (location, variant, outcome)
+            }
+        }))
+        .buffer_unordered(self.jobs.get())
+        .collect::<Vec<_>>()
+        .await;
+        progress.finish_and_clear();
+        results.sort_by_key(|(location, _, _)| *location);
+
+        let mut failures = 0;
+        for (location, variant, outcome) in results {
+            match outcome {
+                VariantOutcome::Built(idents) => {
+                    for ident in idents {
+                        tracing::info!("created {}", ident.format_ident());
+                        self.record_created_build(
+                            filename,
+                            ident,
+                            location,
+                            variant.options().into_owned(),
+                        )?;
+                    }
+                }
+                VariantOutcome::Failed(output) => {
+                    failures += 1;
+                    tracing::error!("{location} failed:\n{variant}\n{output}");
+                }
+                VariantOutcome::Skipped => {
+                    tracing::warn!("{location} was not built because another variant failed");
+                }
+            }
+        }
+
+        if failures > 0 {
+            if !self.created_builds.is_empty() {
+                tracing::warn!("Completed builds:");
+                for (_, artifact) in self.created_builds.iter() {
+                    tracing::warn!("   {artifact}");
+                }
+            }
+            bail!("{failures} of {total} variant builds failed");
+        }
+        Ok(())
+    }
+
+    /// Build a single variant by running `spk make-binary` for it.
+    async fn build_variant_in_child(
+        &self,
+        package: Option<&flags::PackageSpecifier>,
+        location: flags::VariantLocation,
+    ) -> Result<VariantOutcome> {
+        let created_builds_file = tempfile::NamedTempFile::new()
+            .into_diagnostic()
+            .wrap_err("Failed to create a file for collecting variant builds")?;
+        let mut cmd = self.variant_command(package, location, created_builds_file.path())?;
+        tracing::debug!("{:?}", cmd);
+        let output = cmd
+            .output()
+            .await
+            .into_diagnostic()
+            .wrap_err("Failed to run variant build")?;
+        if !output.status.success() {
+            let mut captured = String::from_utf8_lossy(&output.stdout).into_owned();
+            captured.push_str(&String::from_utf8_lossy(&output.stderr));
+            return Ok(VariantOutcome::Failed(captured));
+        }
+        let created = std::fs::read_to_string(created_builds_file.path())
+            .into_diagnostic()
+            .wrap_err("Failed to read the builds created by variant build")?;
+        created
+            .lines()
+            .map(|line| parse_build_ident(line).into_diagnostic())
+            .collect::<Result<Vec<_>>>()
+            .map(VariantOutcome::Built)
+    }
+
+    /// Create the command that builds a single variant with the same
+    /// settings as this one, in a new runtime.
+    fn variant_command(
+        &self,
+        package: Option<&flags::PackageSpecifier>,
+        location: flags::VariantLocation,
+        created_builds_file: &Path,
+    ) -> Result<tokio::process::Command> {
+        let mut cmd = tokio::process::Command::new(spk_exe());
+        cmd.arg("make-binary");
+        if self.verbose > 0 {
+            cmd.arg(format!("-{}", "v".repeat(self.verbose as usize)));
+        }
+        cmd.args(self.solver.to_args());
+        cmd.args(self.options.to_args());
+        let mut runtime = self.runtime.clone();
+        if let Some(name) = runtime.runtime_name.as_mut() {
+            // Variants are built at the same time, so each one needs a
+            // distinct name for its runtime.
+            match location {
+                flags::VariantLocation::Index(index) => name.push_str(&format!("-variant{index}")),
+                flags::VariantLocation::Bespoke(index) => {
+                    name.push_str(&format!("-bespoke{index}"))
+                }
+            }
+        }
+        cmd.args(runtime.to_args());
+        for (enabled, flag) in [
+            (self.here, "--here"),
+            (
+                self.allow_circular_dependencies,
+                "--allow-circular-dependencies",
+            ),
+        ] {
+            if enabled {
+                cmd.arg(flag);
+            }
+        }
+        cmd.arg("--workspace")
+            .arg(&self.packages.workspace.workspace);
+        match location {
+            flags::VariantLocation::Index(index) => {
+                cmd.arg("--variant").arg(index.to_string());
+            }
+            flags::VariantLocation::Bespoke(index) => {
+                let new_variant = self.variant.new_variant.get(index).ok_or_else(|| {
+                    miette!("No --new-variant was given for bespoke variant {index}")
+                })?;
+                cmd.arg("--new-variant").arg(new_variant);
+            }
+        }
+        cmd.arg("--created-builds-file").arg(created_builds_file);
+        if let Some(package) = package {
+            cmd.arg(package.get_specifier());
+        }
+        // Each variant needs its own runtime, even if this process
+        // was told not to create one.
+        cmd.env_remove("SPK_NO_RUNTIME");
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(cmd)
+    }
+}
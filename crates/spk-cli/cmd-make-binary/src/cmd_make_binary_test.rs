@@ -83,3 +83,102 @@ build:
         .await
         .expect("With override, build script should succeed.");
 }
+
+#[rstest]
+fn test_variant_command_forwards_flags() {
+    let opt = Opt::try_parse_from([
+        "make-binary",
+        "-vv",
+        "--no-runtime",
+        "--edit",
+        "--runtime-name=my-build",
+        "--live-layer=layer.yaml",
+        "--disable-repo=origin",
+        "--allow-builds",
+        "--check-impossible-all",
+        "--prerelease-policy=my-pkg=IncludeAll",
+        "--timeout=5",
+        "--show-solution",
+        "--solver-to-run=cli",
+        "--decision-format=json",
+        "--compare-solvers",
+        "--opt=variable=override",
+        "--no-host",
+        "--here",
+        "--allow-circular-dependencies",
+        "--jobs=4",
+        "--keep-going",
+        "--variant=1",
+        "simple.spk.yaml",
+    ])
+    .unwrap();
+    let package = opt.mkb.packages.packages.first();
+
+    let cmd = opt
+        .mkb
+        .variant_command(
+            package,
+            spk_cli_common::flags::VariantLocation::Index(1),
+            std::path::Path::new("created-builds"),
+        )
+        .unwrap();
+    let args = cmd
+        .as_std()
+        .get_args()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(
+        !args.iter().any(|arg| arg == "--no-runtime"),
+        "each variant should get its own runtime: {args:?}"
+    );
+    assert!(
+        args.iter().any(|arg| arg == "--compare-solvers"),
+        "solver settings should be forwarded: {args:?}"
+    );
+
+    let child = Opt::try_parse_from(&args).expect("forwarded args should parse");
+    let child = child.mkb;
+    assert_eq!(child.verbose, 2);
+    assert!(child.runtime.edit);
+    assert_eq!(
+        child.runtime.runtime_name.as_deref(),
+        Some("my-build-variant1")
+    );
+    assert_eq!(
+        child.runtime.live_layer,
+        Some(vec!["layer.yaml".to_string()])
+    );
+    assert_eq!(child.solver.repos.disable_repo, vec!["origin".to_string()]);
+    assert!(child.solver.allow_builds);
+    assert!(child.solver.check_impossible_all);
+    assert_eq!(
+        child.solver.prerelease_policies,
+        vec!["my-pkg=IncludeAll".to_string()]
+    );
+    let settings = &child.solver.decision_formatter_settings;
+    assert_eq!(settings.timeout, 5);
+    assert!(settings.show_solution);
+    assert!(matches!(
+        settings.solver_to_run,
+        spk_cli_common::flags::SolverToRun::Cli
+    ));
+    assert!(matches!(
+        settings.decision_format,
+        spk_cli_common::flags::DecisionFormat::Json
+    ));
+    assert_eq!(child.options.options, vec!["variable=override".to_string()]);
+    assert!(child.options.no_host);
+    assert!(child.here);
+    assert!(child.allow_circular_dependencies);
+    assert_eq!(child.jobs.get(), 1, "variants should not build in parallel");
+    assert!(!child.keep_going);
+    assert_eq!(
+        child.created_builds_file,
+        Some(std::path::PathBuf::from("created-builds"))
+    );
+    assert_eq!(
+        child.packages.packages.len(),
+        1,
+        "the package should be forwarded"
+    );
+}
@@ -46,6 +46,13 @@ pub struct CmdTest {
     #[clap(long)]
     here: bool,
 
+    /// Run install tests with only the tested package's run component
+    ///
+    /// By default, all of the package's components are installed for
+    /// its install tests, which can hide files that are missing at runtime
+    #[clap(long)]
+    isolated_install: bool,
+
     /// The package(s) to test
     ///
     /// This can be a file name or `<name>/<version>` of an existing package
@@ -241,6 +248,7 @@ impl Run for CmdTest {
                                     )
                                     .with_requirements(options_reqs.clone())
                                     .with_source(source.clone())
+                                    .with_isolation(self.isolated_install)
                                     .watch_environment_formatter(install_formatter);
 
                                 Box::new(tester)
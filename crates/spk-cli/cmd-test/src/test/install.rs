@@ -37,6 +37,7 @@ where
     source: Option<PathBuf>,
     env_formatter: DecisionFormatter,
     variant: V,
+    isolated: bool,
 }
 
 impl<V, Solver> PackageInstallTester<V, Solver>
@@ -56,6 +57,7 @@ where
             source: None,
             env_formatter: DecisionFormatter::default(),
             variant,
+            isolated: false,
         }
     }
 
@@ -87,6 +89,16 @@ where
         self
     }
 
+    /// Only install the components of the package that a consumer
+    /// would get by default, rather than all of them.
+    ///
+    /// This catches tests that pass only because of files in the
+    /// package's build component, which are not installed at runtime.
+    pub fn with_isolation(&mut self, isolated: bool) -> &mut Self {
+        self.isolated = isolated;
+        self
+    }
+
     /// Provide a formatter to use when resolving the test environment.
     pub fn watch_environment_formatter(&mut self, formatter: DecisionFormatter) -> &mut Self {
         self.env_formatter = formatter;
@@ -118,7 +130,12 @@ where
             .to_any_ident(None)
             .with_build(Some(Build::BuildId(build_digest_for_variant)));
 
-        let pkg = RangeIdent::double_equals(&build_to_test, [Component::All]);
+        let component = if self.isolated {
+            Component::default_for_run()
+        } else {
+            Component::All
+        };
+        let pkg = RangeIdent::double_equals(&build_to_test, [component]);
         let request = PkgRequest::new(pkg, RequestedBy::InstallTest(self.recipe.ident().clone()))
             .with_prerelease(Some(PreReleasePolicy::IncludeAll))
             .with_pin(None)
@@ -20,10 +20,10 @@ use solve::{
     SolverImpl,
     SolverMut,
 };
-use spk_schema::foundation::format::FormatIdent;
+use spk_schema::foundation::format::{ChangeMarkers, FormatIdent};
 use spk_schema::foundation::ident_build::Build;
 use spk_schema::foundation::ident_component::Component;
-use spk_schema::foundation::name::OptName;
+use spk_schema::foundation::name::{OptName, PkgNameBuf};
 use spk_schema::foundation::option_map::OptionMap;
 use spk_schema::foundation::version::CompatRule;
 use spk_schema::ident::{
@@ -36,6 +36,7 @@ use spk_schema::ident::{
     PkgRequestOptionValue,
     PkgRequestOptions,
     PkgRequestWithOptions,
+    PreReleasePolicy,
     RangeIdent,
     RequestedBy,
     VarRequest,
@@ -58,7 +59,7 @@ use spk_solve::{SPK_RUN_TIME_METRIC, get_metrics_client};
 use spk_storage as storage;
 use spk_storage::IndexedRepository;
 use spk_workspace::{FindOrLoadPackageTemplateError, FindPackageTemplateError};
-pub use variant::{Variant, VariantBuildStatus, VariantLocation};
+pub use variant::{Variant, VariantBuildStatus, VariantInfo, VariantLocation};
 
 use crate::parsing::{VariantIndex, stage_specifier};
 use crate::{CommandArgs, Error};
@@ -113,6 +114,29 @@ pub struct Runtime {
 }
 
 impl Runtime {
+    /// Reconstruct the command line arguments for these settings.
+    ///
+    /// `--no-runtime` is never included, so that a child spk process
+    /// given these arguments will create its own runtime.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.edit {
+            args.push("--edit".to_string());
+        }
+        if let Some(name) = &self.runtime_name {
+            args.push("--runtime-name".to_string());
+            args.push(name.clone());
+        }
+        if self.keep_runtime {
+            args.push("--keep-runtime".to_string());
+        }
+        for live_layer in self.live_layer.iter().flatten() {
+            args.push("--live-layer".to_string());
+            args.push(live_layer.clone());
+        }
+        args
+    }
+
     /// True if the flags are requesting an editable runtime
     pub fn editable(&self) -> bool {
         // clap will ensure that edit is only true if provided
@@ -287,9 +311,45 @@ pub struct Solver {
     /// requests, build validation before a resolve, and for build keys
     #[clap(long, env = "SPK_SOLVER_CHECK_IMPOSSIBLE_ALL")]
     pub check_impossible_all: bool,
+
+    /// Set the prerelease policy for requests that don't set their own
+    ///
+    /// Given as POLICY, this is the default for all packages. Given as
+    /// NAME=POLICY, it is used for that package only, taking precedence
+    /// over the default (eg: --prerelease-policy my-pkg=IncludeAll).
+    #[clap(long = "prerelease-policy", value_name = "[NAME=]POLICY")]
+    pub prerelease_policies: Vec<String>,
 }
 
 impl Solver {
+    /// Reconstruct the command line arguments for these settings.
+    ///
+    /// This is useful when the same solver configuration needs to be
+    /// used by a child spk process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = self.repos.to_args();
+        args.extend(self.decision_formatter_settings.to_args());
+        for (enabled, flag) in [
+            (self.allow_builds, "--allow-builds"),
+            (self.check_impossible_initial, "--check-impossible-initial"),
+            (
+                self.check_impossible_validation,
+                "--check-impossible-validation",
+            ),
+            (self.check_impossible_builds, "--check-impossible-builds"),
+            (self.check_impossible_all, "--check-impossible-all"),
+        ] {
+            if enabled {
+                args.push(flag.to_string());
+            }
+        }
+        for policy in self.prerelease_policies.iter() {
+            args.push("--prerelease-policy".to_string());
+            args.push(policy.clone());
+        }
+        args
+    }
+
     pub async fn get_solver(
         &self,
         options: &Options,
@@ -321,6 +381,7 @@ impl Solver {
             solver.add_repository(repo);
         }
         solver.set_binary_only(!self.allow_builds);
+        solver.set_prerelease_policies(self.get_prerelease_policies()?);
 
         for r in options.get_var_requests()? {
             solver.add_request(r.into());
@@ -328,6 +389,37 @@ impl Solver {
 
         Ok(solver)
     }
+
+    /// Parse the prerelease policies given on the command line.
+    pub fn get_prerelease_policies(&self) -> Result<solve::PreReleasePolicies> {
+        let mut default = None;
+        let mut packages = Vec::new();
+        for value in self.prerelease_policies.iter() {
+            match value.split_once('=') {
+                Some((name, policy)) => {
+                    let name = PkgNameBuf::try_from(name)
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("Invalid --prerelease-policy: {value}"))?;
+                    let policy = policy
+                        .parse::<PreReleasePolicy>()
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("Invalid --prerelease-policy: {value}"))?;
+                    packages.push((name, policy));
+                }
+                None => {
+                    let policy = value
+                        .parse::<PreReleasePolicy>()
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("Invalid --prerelease-policy: {value}"))?;
+                    default = Some(policy);
+                }
+            }
+        }
+        Ok(packages.into_iter().fold(
+            solve::PreReleasePolicies::new(default),
+            |policies, (name, policy)| policies.with_package(name, policy),
+        ))
+    }
 }
 
 #[derive(Args, Clone)]
@@ -355,6 +447,19 @@ pub struct Options {
 }
 
 impl Options {
+    /// Reconstruct the command line arguments for these settings.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for option in self.options.iter() {
+            args.push("--opt".to_string());
+            args.push(option.clone());
+        }
+        if self.no_host {
+            args.push("--no-host".to_string());
+        }
+        args
+    }
+
     pub fn get_options(&self) -> Result<OptionMap> {
         let mut opts = match self.no_host {
             true => OptionMap::default(),
@@ -803,7 +908,22 @@ impl Workspace {
             )) => {
                 let mut builder = spk_workspace::Workspace::builder();
 
-                if self.workspace.is_dir() {
+                // only search parent directories when no location was given,
+                // like cargo does when looking for a Cargo.toml file
+                let discovered = if self.workspace == std::path::Path::new(".") {
+                    spk_workspace::WorkspaceFile::discover_recipes_dir(&self.workspace)
+                } else {
+                    None
+                };
+
+                if let Some(dir) = discovered {
+                    let is_cwd = std::env::current_dir()
+                        .is_ok_and(|cwd| dir.canonicalize().is_ok_and(|dir| dir == cwd));
+                    if !is_cwd {
+                        tracing::info!("Using spec files found in {}", dir.display());
+                    }
+                    builder = builder.with_root(dir);
+                } else if self.workspace.is_dir() {
                     tracing::debug!(
                         "Using virtual workspace in {d}",
                         d = self.workspace.to_string_lossy()
@@ -1117,6 +1237,41 @@ pub struct Repositories {
 }
 
 impl Repositories {
+    /// Reconstruct the command line arguments for these settings.
+    ///
+    /// This is useful when the same repositories need to be used by a
+    /// child spk process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.local_repo_only {
+            args.push("--local-repo-only".to_string());
+        }
+        if self.no_local_repo {
+            args.push("--no-local-repo".to_string());
+        }
+        for repo in self.enable_repo.iter() {
+            args.push("--enable-repo".to_string());
+            args.push(repo.clone());
+        }
+        for repo in self.disable_repo.iter() {
+            args.push("--disable-repo".to_string());
+            args.push(repo.clone());
+        }
+        if let Some(when) = &self.when {
+            args.push("--when".to_string());
+            args.push(when.to_string());
+        }
+        if let Some(wrap_origin) = &self.wrap_origin {
+            args.push("--wrap-origin".to_string());
+            args.push(wrap_origin.to_string_lossy().into_owned());
+        }
+        if let Some(value) = self.index_use.and_then(|i| i.to_possible_value()) {
+            args.push("--index-use".to_string());
+            args.push(value.get_name().to_string());
+        }
+        args
+    }
+
     /// Get the repositories to use based on command-line options.
     ///
     /// This method enables the local repository by default, except if any
@@ -1403,6 +1558,23 @@ impl From<SolverToRun> for MultiSolverKind {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum DecisionFormat {
+    /// Human-readable lines
+    Text,
+    /// One json object per line
+    Json,
+}
+
+impl From<DecisionFormat> for solve::OutputFormat {
+    fn from(item: DecisionFormat) -> solve::OutputFormat {
+        match item {
+            DecisionFormat::Text => solve::OutputFormat::Text,
+            DecisionFormat::Json => solve::OutputFormat::Json,
+        }
+    }
+}
+
 impl From<SolverToShow> for MultiSolverKind {
     fn from(item: SolverToShow) -> MultiSolverKind {
         match item {
@@ -1449,6 +1621,27 @@ pub struct DecisionFormatterSettings {
     #[clap(long)]
     pub show_solution: bool,
 
+    /// Show each package of the solution table as soon as the solver
+    /// resolves it, instead of waiting until the solve is complete.
+    /// This avoids a long pause before anything is shown for large
+    /// solutions, but the columns are only as wide as the packages
+    /// shown so far.
+    #[clap(long)]
+    pub stream_solution: bool,
+
+    /// Show the solution as a tree of which packages required which,
+    /// instead of as a list. Packages that are required by more than
+    /// one other package are only expanded the first time they are
+    /// shown, and are marked with (*) after that.
+    #[clap(long)]
+    pub solution_tree: bool,
+
+    /// Show the solution as a markdown table without any colors,
+    /// instead of as a list. This is useful for including the
+    /// solution in documentation or a review comment.
+    #[clap(long, conflicts_with = "solution_tree")]
+    pub solution_markdown: bool,
+
     /// Set the threshold of a longer than acceptable solves, in seconds.
     ///
     #[clap(long, env = "SPK_SOLVER_LONG_SOLVE_THRESHOLD", default_value_t = 15)]
@@ -1489,6 +1682,12 @@ pub struct DecisionFormatterSettings {
     #[clap(long)]
     pub show_search_size: bool,
 
+    /// Control how the solver's decisions are written out. With 'json',
+    /// each decision is printed as a json object on its own line and
+    /// the solution and stats are logged instead of printed.
+    #[clap(long, value_enum, default_value_t = DecisionFormat::Text)]
+    pub decision_format: DecisionFormat,
+
     /// Run all the solvers to completion and produce a report
     /// comparing them.
     #[clap(long)]
@@ -1529,6 +1728,63 @@ pub struct DecisionFormatterSettings {
 }
 
 impl DecisionFormatterSettings {
+    /// Reconstruct the command line arguments for these settings.
+    ///
+    /// Settings that have a value are always included, so that a child
+    /// spk process uses the same values even if its environment differs.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--increase-verbosity".to_string(),
+            self.increase_verbosity.to_string(),
+            "--max-verbosity-increase-level".to_string(),
+            self.max_verbosity_increase_level.to_string(),
+            "--timeout".to_string(),
+            self.timeout.to_string(),
+            "--long-solves".to_string(),
+            self.long_solves.to_string(),
+            "--max-frequent-errors".to_string(),
+            self.max_frequent_errors.to_string(),
+            "--output-to-dir-min-verbosity".to_string(),
+            self.output_to_dir_min_verbosity.to_string(),
+            "--output-file-prefix".to_string(),
+            self.output_file_prefix.clone(),
+        ];
+        for (enabled, flag) in [
+            (self.time, "--time"),
+            (self.show_solution, "--show-solution"),
+            (self.stream_solution, "--stream-solution"),
+            (self.solution_tree, "--solution-tree"),
+            (self.solution_markdown, "--solution-markdown"),
+            (self.status_bar, "--status-bar"),
+            (self.show_search_size, "--show-search-size"),
+            (self.compare_solvers, "--compare-solvers"),
+            (self.stop_on_block, "--stop-on-block"),
+            (self.step_on_block, "--step-on-block"),
+            (self.step_on_decision, "--step-on-decision"),
+        ] {
+            if enabled {
+                args.push(flag.to_string());
+            }
+        }
+        if let Some(value) = self.solver_to_run.to_possible_value() {
+            args.push("--solver-to-run".to_string());
+            args.push(value.get_name().to_string());
+        }
+        if let Some(value) = self.solver_to_show.to_possible_value() {
+            args.push("--solver-to-show".to_string());
+            args.push(value.get_name().to_string());
+        }
+        if let Some(value) = self.decision_format.to_possible_value() {
+            args.push("--decision-format".to_string());
+            args.push(value.get_name().to_string());
+        }
+        if let Some(output_to_dir) = &self.output_to_dir {
+            args.push("--output-to-dir".to_string());
+            args.push(output_to_dir.to_string_lossy().into_owned());
+        }
+        args
+    }
+
     /// Get a decision formatter configured from the command line
     /// options and their defaults.
     pub fn get_formatter(&self, verbosity: u8) -> Result<DecisionFormatter> {
@@ -1558,12 +1814,17 @@ impl DecisionFormatterSettings {
             .with_max_verbosity_increase_level(self.max_verbosity_increase_level)
             .with_timeout(self.timeout)
             .with_solution(self.show_solution)
+            .with_stream_solution(self.stream_solution)
+            .with_solution_tree(self.solution_tree)
+            .with_solution_markdown(self.solution_markdown)
             .with_long_solves_threshold(self.long_solves)
             .with_max_frequent_errors(self.max_frequent_errors)
             .with_status_bar(self.status_bar)
             .with_solver_to_run(self.solver_to_run.into())
             .with_solver_to_show(self.solver_to_show.into())
             .with_search_space_size(self.show_search_size)
+            .with_output_format(self.decision_format.into())
+            .with_change_markers(ChangeMarkers::detect())
             .with_stop_on_block(self.stop_on_block)
             .with_step_on_block(self.step_on_block)
             .with_step_on_decision(self.step_on_decision)
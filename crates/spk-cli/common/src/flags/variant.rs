@@ -64,7 +64,7 @@ impl FromStr for VariantSpec {
 }
 
 /// The location of the definition of a variant of a recipe.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum VariantLocation {
     /// The variant is defined in the recipe at the given index.
     Index(usize),
@@ -10,7 +10,7 @@ use spk_schema::ident::{PkgRequestOptionValue, VarRequest};
 use spk_schema::option_map::HOST_OPTIONS;
 use spk_solve::Solver;
 
-use crate::flags::{DecisionFormatterSettings, SolverToRun, SolverToShow};
+use crate::flags::{DecisionFormat, DecisionFormatterSettings, SolverToRun, SolverToShow};
 
 #[rstest]
 #[case(&["hello:world"], &[("hello", "world")])]
@@ -76,12 +76,16 @@ async fn test_get_solver_with_host_options(
             max_verbosity_increase_level: Default::default(),
             timeout: Default::default(),
             show_solution: Default::default(),
+            stream_solution: Default::default(),
+            solution_tree: Default::default(),
+            solution_markdown: Default::default(),
             long_solves: Default::default(),
             max_frequent_errors: Default::default(),
             status_bar: Default::default(),
             solver_to_run,
             solver_to_show,
             show_search_size: Default::default(),
+            decision_format: DecisionFormat::Text,
             compare_solvers: Default::default(),
             stop_on_block: Default::default(),
             step_on_block: Default::default(),
@@ -95,6 +99,7 @@ async fn test_get_solver_with_host_options(
         check_impossible_validation: false,
         check_impossible_builds: false,
         check_impossible_all: false,
+        prerelease_policies: Default::default(),
     };
 
     let solver = solver_flags.get_solver(&options_flags).await.unwrap();
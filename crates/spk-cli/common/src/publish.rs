@@ -195,7 +195,7 @@ impl Publisher {
                 })
                 .await?
             }
-            Some(build) => vec![pkg.to_build_ident(build.clone())],
+            Some(_) => vec![BuildIdent::try_from(pkg)?],
         };
 
         for build in builds.iter() {
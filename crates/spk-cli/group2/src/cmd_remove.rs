@@ -85,19 +85,14 @@ impl Run for Remove {
                 };
 
                 for version in versions {
-                    match version.into_inner() {
-                        (version, None) => {
-                            remove_all(repo_name, repo, &version).await?;
-
-                            updated_repos.insert(repo.name().into(), repo.clone());
-                        }
-                        (version, Some(build)) => {
-                            let ident = version.into_build_ident(build);
-                            remove_build(repo_name, repo, &ident).await?;
-
-                            updated_repos.insert(repo.name().into(), repo.clone());
-                        }
+                    if version.build().is_none() {
+                        let version = version.try_into_version_ident()?;
+                        remove_all(repo_name, repo, &version).await?;
+                    } else {
+                        let ident = version.try_into_build_ident()?;
+                        remove_build(repo_name, repo, &ident).await?;
                     }
+                    updated_repos.insert(repo.name().into(), repo.clone());
                 }
             }
         }
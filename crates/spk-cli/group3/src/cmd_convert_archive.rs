@@ -0,0 +1,86 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use clap::{Args, ValueHint};
+use colored::Colorize;
+use miette::{Result, bail};
+use spk_cli_common::{CommandArgs, Run};
+
+/// Convert an archive between the spk and OCI image layout formats
+///
+/// Archives created by `spk export` are converted into an OCI image
+/// layout tar file and OCI image layouts (as a directory or tar file)
+/// are converted back into archives that can be used with `spk import`.
+/// The direction of the conversion is determined by the input.
+#[derive(Args)]
+pub struct ConvertArchive {
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The archive to convert
+    #[arg(value_hint = ValueHint::AnyPath, value_name = "INPUT")]
+    pub input: std::path::PathBuf,
+
+    /// The file to write the converted archive into
+    #[arg(value_hint = ValueHint::FilePath, value_name = "OUTPUT")]
+    pub output: std::path::PathBuf,
+}
+
+#[async_trait::async_trait]
+impl Run for ConvertArchive {
+    type Output = i32;
+
+    async fn run(&mut self) -> Result<Self::Output> {
+        if self.output.exists() {
+            bail!("Output file already exists: {:?}", self.output);
+        }
+
+        let (format, res) = if spk_storage::is_oci_layout(&self.input)? {
+            (
+                "spk archive",
+                spk_storage::convert_oci_to_archive(&self.input, &self.output).await,
+            )
+        } else {
+            (
+                "OCI image layout",
+                spk_storage::convert_archive_to_oci(&self.input, &self.output).await,
+            )
+        };
+        if res.is_err()
+            && let Err(err) = std::fs::remove_file(&self.output)
+            && err.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(?err, path=?self.output, "failed to clean up incomplete archive");
+        }
+        let conversion = res?;
+
+        tracing::info!(
+            objects = conversion.objects,
+            payloads = conversion.payloads,
+            tags = conversion.tags,
+            "converted"
+        );
+        for item in conversion.not_converted.iter() {
+            tracing::warn!("Not converted: {item}");
+        }
+        println!("{} {format}: {:?}", "Created".green(), self.output);
+        if !conversion.not_converted.is_empty() {
+            println!(
+                "{} {} item(s) could not be converted, see above for details",
+                "Warning:".yellow(),
+                conversion.not_converted.len()
+            );
+        }
+        Ok(0)
+    }
+}
+
+impl CommandArgs for ConvertArchive {
+    fn get_positional_args(&self) -> Vec<String> {
+        vec![
+            self.input.display().to_string(),
+            self.output.display().to_string(),
+        ]
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::sync::Arc;
+
+use clap::{Args, ValueHint};
+use colored::Colorize;
+use miette::{Result, bail};
+use spk_cli_common::{CommandArgs, Run, current_env, flags};
+use spk_schema::Package;
+use spk_storage as storage;
+
+/// Export the current environment as a bundle that can be
+/// activated again on another host
+///
+/// The bundle holds every layer of the active runtime along with the
+/// packages that were resolved into it, so it can be carried to a
+/// host without access to the original repositories. Use
+/// 'spk import-env' to activate it.
+#[derive(Args)]
+pub struct ExportEnv {
+    #[clap(flatten)]
+    pub repos: flags::Repositories,
+
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The file to export into
+    #[arg(value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub filename: std::path::PathBuf,
+}
+
+#[async_trait::async_trait]
+impl Run for ExportEnv {
+    type Output = i32;
+
+    async fn run(&mut self) -> Result<Self::Output> {
+        let runtime = spfs::active_runtime().await?;
+        let solution = current_env().await?;
+        if runtime.is_dirty() {
+            tracing::warn!("The runtime has uncommitted changes, they will not be bundled");
+        }
+
+        let names_and_repos = self.repos.get_repos_for_non_destructive_operation().await?;
+        let repo_handles = names_and_repos
+            .into_iter()
+            .map(|(_, r)| Arc::new(r))
+            .collect::<Vec<_>>();
+        let repos = repo_handles
+            .iter()
+            .map(|repo| match &**repo {
+                storage::RepositoryHandle::SPFS(repo) => Ok(repo),
+                storage::RepositoryHandle::Mem(_)
+                | storage::RepositoryHandle::Runtime(_)
+                | storage::RepositoryHandle::Indexed(_) => {
+                    bail!("Only spfs repositories are supported")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let packages = solution
+            .items()
+            .map(|item| item.spec.ident().clone())
+            .collect::<Vec<_>>();
+        let res = storage::export_env_bundle(
+            repos.as_slice(),
+            &runtime.status.stack,
+            &packages,
+            &self.filename,
+        )
+        .await;
+        if res.is_err()
+            && let Err(err) = std::fs::remove_file(&self.filename)
+        {
+            tracing::warn!(?err, path=?self.filename, "failed to clean up incomplete bundle");
+        }
+        res?;
+        println!("{}: {:?}", "Created".green(), self.filename);
+        Ok(0)
+    }
+}
+
+impl CommandArgs for ExportEnv {
+    fn get_positional_args(&self) -> Vec<String> {
+        vec![format!("{}", self.filename.display())]
+    }
+}
@@ -3,9 +3,7 @@
 // https://github.com/spkenv/spk
 
 use clap::Args;
-use futures::TryStreamExt;
 use miette::{Context, Result};
-use spfs::storage::TagStorage;
 use spk_cli_common::{CommandArgs, Run};
 
 #[cfg(test)]
@@ -34,20 +32,9 @@ impl Run for Import {
         // be using this syncer to create more useful ones for each archive
         let syncer = self.sync.get_syncer(&local_repo, &local_repo);
         for filename in self.files.iter() {
-            let tar_repo = spfs::storage::tar::TarRepository::open(&filename).await?;
-            let tar_repo: spfs::storage::RepositoryHandle = tar_repo.into();
-            let env_spec = tar_repo
-                .iter_tags()
-                .map_ok(|(spec, _)| spec)
-                .try_collect()
+            summary += spk_storage::import_package(filename, &syncer)
                 .await
-                .wrap_err("Failed to collect tags from archive")?;
-            tracing::info!(archive = ?filename, "importing");
-            summary += syncer
-                .clone_with_source(&tar_repo)
-                .sync_env(env_spec)
-                .await
-                .wrap_err("Failed to sync archived data")?
+                .wrap_err("Failed to import archived data")?
                 .summary();
         }
         tracing::info!("{:#?}", summary);
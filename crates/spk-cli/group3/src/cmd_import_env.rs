@@ -0,0 +1,89 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+
+use clap::Args;
+use miette::{Context, Result};
+use spfs::storage::PlatformStorage;
+use spk_cli_common::{CommandArgs, Run, current_env, flags};
+
+#[cfg(test)]
+#[path = "./cmd_import_env_test.rs"]
+mod cmd_import_env_test;
+
+/// Import an environment bundle and activate it
+///
+/// The bundle must have been created with 'spk export-env'. All of
+/// its contents are verified to be present before the environment is
+/// activated. If no command is given, spawn a new shell
+#[derive(Args)]
+pub struct ImportEnv {
+    #[clap(flatten)]
+    pub runtime: flags::Runtime,
+
+    /// The bundle to import
+    #[clap(name = "FILE")]
+    pub filename: std::path::PathBuf,
+
+    /// An optional command to run in the imported environment.
+    ///
+    /// Use '--' to separate the command from the bundle. If no command
+    /// is given, spawn a new shell
+    #[clap(raw = true)]
+    pub command: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl Run for ImportEnv {
+    type Output = i32;
+
+    async fn run(&mut self) -> Result<Self::Output> {
+        let mut rt = self.runtime.ensure_active_runtime(&["import-env"]).await?;
+        rt.status.editable = self.runtime.editable();
+
+        let local_repo = spk_storage::local_repository().await?;
+        let platform = spk_storage::import_env_bundle(&self.filename, &local_repo)
+            .await
+            .wrap_err("Failed to import environment bundle")?;
+        rt.status.stack = local_repo.read_platform(platform).await?.to_stack();
+        rt.save_state_to_storage().await?;
+        spfs::remount_runtime(&rt).await?;
+
+        // the packages are read back out of the runtime so that
+        // the environment is set up just as `spk env` would
+        let solution = current_env().await?;
+        let env = solution.to_environment(Some(std::env::vars()));
+
+        let mut command = if self.command.is_empty() {
+            spfs::build_interactive_shell_command(&rt, None)?
+        } else {
+            let cmd = self.command.first().unwrap();
+            let args = &self.command[1..];
+            spfs::build_shell_initialized_command(&rt, None, cmd, args)?
+        };
+
+        let existing_new_vars = command.vars.iter().map(|(k, _)| k).collect::<HashSet<_>>();
+        command.vars.extend(
+            env.into_iter()
+                .filter_map(|(k, v)| {
+                    let k: OsString = k.into();
+                    (!existing_new_vars.contains(&k)).then(|| (k, v.into()))
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        command
+            .exec()
+            .map(|_| 0)
+            .wrap_err("Failed to execute runtime command")
+    }
+}
+
+impl CommandArgs for ImportEnv {
+    fn get_positional_args(&self) -> Vec<String> {
+        vec![format!("{}", self.filename.display())]
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use spfs::storage::PlatformStorage;
+use spfstest::spfstest;
+use spk_build::{BinaryPackageBuilder, BuildSource};
+use spk_schema::foundation::option_map;
+use spk_schema::{Package, Recipe, recipe};
+use spk_solve::SolverImpl;
+use spk_storage::fixtures::*;
+use spk_storage::{Repository, SpfsRepository};
+
+fn as_spfs(repo: &spk_solve::RepositoryHandle) -> &SpfsRepository {
+    match repo {
+        spk_solve::RepositoryHandle::SPFS(repo) => repo,
+        spk_solve::RepositoryHandle::Mem(_)
+        | spk_solve::RepositoryHandle::Runtime(_)
+        | spk_solve::RepositoryHandle::Indexed(_) => {
+            panic!("only spfs repositories are supported")
+        }
+    }
+}
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_env_bundle_round_trip() {
+    let rt = spfs_runtime().await;
+    let spec = recipe!(
+        {
+            "pkg": "spk-bundle-test/0.0.1",
+            "build": {"script": "touch /spfs/file.txt"},
+        }
+    );
+    rt.tmprepo.publish_recipe(&spec).await.unwrap();
+    let (spec, _) = BinaryPackageBuilder::from_recipe_with_solver(
+        spec,
+        SolverImpl::Step(spk_solve::StepSolver::default()),
+    )
+    .with_source(BuildSource::LocalPath(".".into()))
+    .build_and_publish(option_map! {}, &*rt.tmprepo)
+    .await
+    .unwrap();
+
+    let repo = as_spfs(&rt.tmprepo);
+    let components = repo.read_components(spec.ident()).await.unwrap();
+    let stack = spfs::graph::Stack::from_iter(components.values().copied());
+
+    let filename = rt.tmpdir.path().join("bundle.spk");
+    spk_storage::export_env_bundle(&[repo], &stack, &[spec.ident().clone()], &filename)
+        .await
+        .expect("failed to export bundle");
+
+    let dest = make_repo(RepoKind::Spfs).await;
+    let dest = as_spfs(&dest);
+    let platform = spk_storage::import_env_bundle(&filename, dest)
+        .await
+        .expect("failed to import bundle");
+    let imported = dest.read_platform(platform).await.unwrap().to_stack();
+    assert_eq!(imported, stack, "bundle should recreate the same stack");
+    dest.read_package(spec.ident())
+        .await
+        .expect("bundled packages should be imported");
+}
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_env_bundle_import_requires_bundle() {
+    let rt = spfs_runtime().await;
+    let spec = recipe!({"pkg": "spk-bundle-test/0.0.1"});
+    rt.tmprepo.publish_recipe(&spec).await.unwrap();
+
+    let repo = as_spfs(&rt.tmprepo);
+    let filename = rt.tmpdir.path().join("archive.spk");
+    spk_storage::export_package(&[repo], spec.ident().to_any_ident(None), &filename)
+        .await
+        .expect("failed to export");
+
+    let dest = make_repo(RepoKind::Spfs).await;
+    let result = spk_storage::import_env_bundle(&filename, as_spfs(&dest)).await;
+    assert!(
+        result.is_err(),
+        "a package archive is not an environment bundle"
+    );
+}
+
+#[spfstest]
+#[rstest]
+#[tokio::test]
+async fn test_env_bundle_import_twice_into_same_repo() {
+    let rt = spfs_runtime().await;
+    let repo = as_spfs(&rt.tmprepo);
+
+    let mut bundles = Vec::new();
+    for name in ["spk-bundle-one", "spk-bundle-two"] {
+        let spec = recipe!(
+            {
+                "pkg": format!("{name}/0.0.1"),
+                "build": {"script": format!("touch /spfs/{name}.txt")},
+            }
+        );
+        rt.tmprepo.publish_recipe(&spec).await.unwrap();
+        let (spec, _) = BinaryPackageBuilder::from_recipe_with_solver(
+            spec,
+            SolverImpl::Step(spk_solve::StepSolver::default()),
+        )
+        .with_source(BuildSource::LocalPath(".".into()))
+        .build_and_publish(option_map! {}, &*rt.tmprepo)
+        .await
+        .unwrap();
+
+        let components = repo.read_components(spec.ident()).await.unwrap();
+        let stack = spfs::graph::Stack::from_iter(components.values().copied());
+        let filename = rt.tmpdir.path().join(format!("{name}.spk"));
+        spk_storage::export_env_bundle(&[repo], &stack, &[spec.ident().clone()], &filename)
+            .await
+            .expect("failed to export bundle");
+        bundles.push((filename, stack));
+    }
+
+    // the second import must not activate the environment of the
+    // first bundle, which is already in the repo
+    let dest = make_repo(RepoKind::Spfs).await;
+    let dest = as_spfs(&dest);
+    for (filename, stack) in bundles {
+        let platform = spk_storage::import_env_bundle(&filename, dest)
+            .await
+            .expect("failed to import bundle");
+        let imported = dest.read_platform(platform).await.unwrap().to_stack();
+        assert_eq!(imported, stack, "each bundle should recreate its own stack");
+    }
+}
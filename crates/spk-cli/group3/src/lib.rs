@@ -2,5 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+pub mod cmd_convert_archive;
 pub mod cmd_export;
+pub mod cmd_export_env;
 pub mod cmd_import;
+pub mod cmd_import_env;
@@ -0,0 +1,144 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use clap::Args;
+use colored::Colorize;
+use miette::{Context, IntoDiagnostic, Result, bail};
+use spfs::prelude::*;
+use spk_build::{BuildEnvSnapshot, ValueChange, build_env_path};
+use spk_cli_common::{CommandArgs, Run, flags};
+use spk_schema::BuildIdent;
+use spk_schema::foundation::format::FormatIdent;
+use spk_schema::ident::parse_build_ident;
+use spk_storage::{self as storage, Repository};
+use tokio::io::AsyncReadExt;
+
+/// Compare the build environments of two builds
+///
+/// Each binary package build records the packages, options and
+/// environment variables that were present when it was built. This
+/// command shows what changed between two builds, which is useful
+/// to understand why two builds of the same version behave differently.
+#[derive(Args)]
+pub struct BuildDiff {
+    #[clap(flatten)]
+    pub repos: flags::Repositories,
+
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The first build to compare (eg: my-pkg/1.0.0/3I42H3S6)
+    build_a: String,
+
+    /// The second build to compare
+    build_b: String,
+}
+
+#[async_trait::async_trait]
+impl Run for BuildDiff {
+    type Output = i32;
+
+    async fn run(&mut self) -> Result<Self::Output> {
+        let repos = self.repos.get_repos_for_non_destructive_operation().await?;
+
+        let build_a = parse_build_ident(&self.build_a)?;
+        let build_b = parse_build_ident(&self.build_b)?;
+        let snapshot_a = read_snapshot(&repos, &build_a).await?;
+        let snapshot_b = read_snapshot(&repos, &build_b).await?;
+
+        let diff = snapshot_a.diff(&snapshot_b);
+        println!("--- {}", build_a.format_ident());
+        println!("+++ {}", build_b.format_ident());
+        if diff.is_empty() {
+            println!("No differences in build environment");
+            return Ok(0);
+        }
+
+        print_section("packages", &diff.packages, |i| i.format_ident());
+        print_section("options", &diff.options, |v| v.to_string());
+        print_section("environment", &diff.environment, |v| v.to_string());
+        Ok(0)
+    }
+}
+
+impl CommandArgs for BuildDiff {
+    fn get_positional_args(&self) -> Vec<String> {
+        vec![self.build_a.clone(), self.build_b.clone()]
+    }
+}
+
+/// Load the build environment snapshot of a build from the first
+/// repository that contains it along with its snapshot.
+async fn read_snapshot(
+    repos: &[(String, storage::RepositoryHandle)],
+    build: &BuildIdent,
+) -> Result<BuildEnvSnapshot> {
+    let mut found_in = Vec::new();
+    for (repo_name, repo) in repos.iter() {
+        let storage::RepositoryHandle::SPFS(repo) = repo else {
+            continue;
+        };
+        let Ok(components) = repo.read_components(build).await else {
+            continue;
+        };
+        let path = build_env_path(build);
+        // every component holds a copy of the package metadata
+        for digest in components.values() {
+            let object = repo.read_object(*digest).await?;
+            let manifest = spfs::compute_object_manifest(object, repo).await?;
+            let Some(entry) = manifest.get_path(path.as_str()) else {
+                continue;
+            };
+            let (mut reader, _) = repo.open_payload(entry.object).await?;
+            let mut json = String::new();
+            reader
+                .read_to_string(&mut json)
+                .await
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to read {path} from {repo_name}"))?;
+            return serde_json::from_str(&json)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Invalid build environment data for {build}"));
+        }
+        found_in.push(repo_name.as_str());
+    }
+    if !found_in.is_empty() {
+        bail!(
+            "{} was found in {} but has no recorded build environment, it may have been built by an older version of spk",
+            build.format_ident(),
+            found_in.join(", ")
+        );
+    }
+    bail!("Build not found: {}", build.format_ident())
+}
+
+fn print_section<K, V, F>(title: &str, changes: &BTreeMap<K, ValueChange<V>>, format: F)
+where
+    K: Display,
+    F: Fn(&V) -> String,
+{
+    if changes.is_empty() {
+        return;
+    }
+    println!("{}", title.bold());
+    for (name, change) in changes.iter() {
+        match change {
+            ValueChange::Added(value) => {
+                println!("  {}", format!("+ {name}: {}", format(value)).green());
+            }
+            ValueChange::Removed(value) => {
+                println!("  {}", format!("- {name}: {}", format(value)).red());
+            }
+            ValueChange::Changed(a, b) => {
+                println!(
+                    "  {}",
+                    format!("~ {name}: {} -> {}", format(a), format(b)).yellow()
+                );
+            }
+        }
+    }
+}
@@ -136,6 +136,11 @@ pub struct View {
     /// The default is to not do a full solve.
     #[clap(long)]
     full_solve: bool,
+
+    /// Check that the current environment's runtime contains exactly the
+    /// layers of its packages, eg: after making changes with 'spfs edit'
+    #[clap(long, conflicts_with_all = &["package", "pkg", "filepath", "variants_info"])]
+    verify: bool,
 }
 
 #[async_trait::async_trait]
@@ -170,6 +175,10 @@ impl Run for View {
                 // -p <pkg> option, e.g. 'spk info -p some_package
                 p
             }
+            (None, None, None) if self.verify => {
+                // e.g. 'spk info --verify'
+                return self.verify_current_env().await;
+            }
             (None, None, None) => {
                 // No package, filepath or pkg options given
                 // e.g. 'spk info'
@@ -494,6 +503,34 @@ impl View {
         Ok(0)
     }
 
+    async fn verify_current_env(&self) -> Result<i32> {
+        let solution = current_env().await?;
+        let runtime = spfs::active_runtime().await?;
+
+        let discrepancies = spk_exec::validate_solution_against_runtime(&solution, &runtime)?;
+        if discrepancies.is_empty() {
+            println!(
+                "{}",
+                "The runtime matches the packages in the current environment".green()
+            );
+            return Ok(0);
+        }
+
+        let count = discrepancies.len();
+        println!(
+            "{}",
+            format!(
+                "The runtime does not match the packages in the current environment, found {count} {}:",
+                "difference".pluralize(count)
+            )
+            .red()
+        );
+        for discrepancy in discrepancies {
+            println!("  {discrepancy}");
+        }
+        Ok(1)
+    }
+
     fn print_variants_info(
         &self,
         options: &OptionMap,
@@ -741,6 +778,7 @@ impl View {
                                 &FormatChangeOptions {
                                     verbosity: DONT_SHOW_DETAILED_SETTINGS,
                                     level: NOT_AN_INITIAL_REQUEST,
+                                    ..Default::default()
                                 }
                             )
                         )
@@ -801,6 +839,12 @@ impl View {
                 .wrap_err("Failed to serialize loaded spec")?,
             OutputFormat::Env => tracing::warn!(ENV_FORMAT_NOT_SUPPORTED_HERE),
         }
+        if !package_spec.ident().is_source() {
+            tracing::info!(
+                "Built with {}",
+                package_spec.metadata().format_build_provenance()
+            );
+        }
         Ok(0)
     }
 
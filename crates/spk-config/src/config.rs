@@ -80,6 +80,10 @@ pub struct Solver {
     /// build key order.
     pub build_key_name_order: String,
 
+    /// If true, prefer the oldest of otherwise equivalent builds,
+    /// based on their publish time, instead of the newest.
+    pub build_key_prefer_oldest: bool,
+
     /// Comma-separated list of option names to promote to the front of the
     /// resolve order.
     pub request_priority_order: String,
@@ -206,6 +210,30 @@ pub struct HostOptions {
     pub distro_rules: HashMap<String, DistroRule>,
 }
 
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Validation {
+    /// Overrides the severity of package validation errors, keyed by
+    /// error code (eg: "spk::build::validation::alter_existing_files").
+    ///
+    /// Each value is either "error", which fails the build, or "warning",
+    /// which only reports the issue. Any code not listed here is an error.
+    pub severity: HashMap<String, String>,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Build {
+    /// The compiler cache to make available to build scripts, either
+    /// "ccache" or "sccache". Compiler caching is disabled when empty.
+    pub compiler_cache: String,
+
+    /// The directory that the compiler cache is stored in and shared
+    /// between builds. It must be outside of /spfs so that it is never
+    /// collected into a package.
+    pub compiler_cache_dir: String,
+}
+
 /// Helper for the default kafka message channel name, when not
 /// specified in config.
 fn default_kafka_channel_name() -> String {
@@ -393,6 +421,8 @@ pub struct Config {
     pub host_options: HostOptions,
     pub messaging: Vec<MessageChannel>,
     pub indexers: HashMap<String, Indexer>,
+    pub validation: Validation,
+    pub build: Build,
 }
 
 impl Config {
@@ -5,6 +5,8 @@
 use miette::Diagnostic;
 use thiserror::Error;
 
+use crate::RuntimeDiscrepancy;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Diagnostic, Debug, Error)]
@@ -26,6 +28,12 @@ pub enum Error {
     #[error(transparent)]
     #[diagnostic(forward(0))]
     SpkStorageError(#[from] spk_storage::Error),
+    #[error(
+        "The current runtime cannot be updated to hold the new solution: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    #[diagnostic(help("Start a new environment for the solution instead of changing this one"))]
+    IncompatibleRuntime(Vec<RuntimeDiscrepancy>),
     #[error("Error: {0}")]
     String(String),
 }
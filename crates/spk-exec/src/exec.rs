@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_stream::try_stream;
@@ -15,6 +17,9 @@ use spfs::sync::reporter::SyncReporters;
 use spfs::tracking::{Entry, EntryKind};
 use spk_schema::foundation::format::{FormatIdent, FormatOptionMap};
 use spk_schema::foundation::ident_component::Component;
+use spk_schema::foundation::version::parse_version;
+use spk_schema::ident::RequestWithOptions;
+use spk_schema::name::{PkgName, PkgNameBuf};
 use spk_schema::prelude::*;
 use spk_schema::{Components, OptionValues, Spec};
 use spk_solve::solution::{PackageSource, SPK_SOLVE_EXTRA_DATA_KEY, Solution};
@@ -33,6 +38,74 @@ mod exec_test;
 #[derive(Eq, Hash, PartialEq)]
 pub struct ConflictingPackagePair(BuildIdent, BuildIdent);
 
+/// A difference between the layers needed by a solution and the
+/// contents of an spfs runtime.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RuntimeDiscrepancy {
+    /// A layer of the solution is not in the runtime
+    MissingLayer {
+        digest: Digest,
+        package: BuildIdent,
+        component: Component,
+    },
+    /// A layer in the runtime is not part of the solution
+    UnexpectedLayer(Digest),
+    /// The runtime has edits that are not stored in any layer
+    UncommittedChanges,
+}
+
+impl std::fmt::Display for RuntimeDiscrepancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingLayer {
+                digest,
+                package,
+                component,
+            } => write!(
+                f,
+                "missing layer {digest} for {}:{component}",
+                package.format_ident()
+            ),
+            Self::UnexpectedLayer(digest) => {
+                write!(f, "layer {digest} is not part of the solution")
+            }
+            Self::UncommittedChanges => f.write_str("runtime has uncommitted changes"),
+        }
+    }
+}
+
+/// A local directory to use in place of the layers of a resolved package.
+///
+/// This is typically the build output of a package that is being
+/// worked on, laid out as it would be under `/spfs`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageOverride {
+    pub name: PkgNameBuf,
+    pub path: PathBuf,
+}
+
+impl FromStr for PackageOverride {
+    type Err = Error;
+
+    /// Parse an override in the form `<name>=<path>`
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((name, path)) = s.split_once('=') else {
+            return Err(Error::String(format!(
+                "Invalid package override '{s}', expected <name>=<path>"
+            )));
+        };
+        if path.is_empty() {
+            return Err(Error::String(format!(
+                "Invalid package override '{s}', no path given"
+            )));
+        }
+        Ok(Self {
+            name: PkgNameBuf::try_from(name).map_err(|err| Error::String(err.to_string()))?,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
 /// A single layer of a resolved solution.
 #[derive(Clone)]
 pub struct ResolvedLayer {
@@ -89,6 +162,74 @@ impl ResolvedLayers {
         self.0.iter().map(|l| l.digest).collect()
     }
 
+    /// Replace all the layers of the named package with a single layer.
+    ///
+    /// The new layer is placed at the top of the stack so that its
+    /// contents take precedence over the rest of the solution. Returns
+    /// false if there were no layers for the package.
+    pub fn replace_package_layers(
+        &mut self,
+        name: &PkgName,
+        digest: Digest,
+        repo: Arc<RepositoryHandle>,
+    ) -> bool {
+        let Some(spec) = self
+            .0
+            .iter()
+            .find(|l| l.spec.name() == name)
+            .map(|l| Arc::clone(&l.spec))
+        else {
+            return false;
+        };
+        self.0.retain(|l| l.spec.name() != name);
+        self.0.push(ResolvedLayer {
+            digest,
+            spec,
+            component: Component::All,
+            repo,
+        });
+        true
+    }
+
+    /// Compare these layers to those in an spfs stack.
+    ///
+    /// Any layer missing from the stack is reported first, followed
+    /// by any layer in the stack that is not one of these, in stack
+    /// order. The order of the layers themselves is not compared.
+    pub fn compare_to_stack(&self, stack: &spfs::graph::Stack) -> Vec<RuntimeDiscrepancy> {
+        let in_stack = stack.iter_bottom_up().collect::<HashSet<_>>();
+        let expected = self.0.iter().map(|l| l.digest).collect::<HashSet<_>>();
+        let missing = self
+            .0
+            .iter()
+            .filter(|l| !in_stack.contains(&l.digest))
+            .map(|l| RuntimeDiscrepancy::MissingLayer {
+                digest: l.digest,
+                package: l.spec.ident().clone(),
+                component: l.component.clone(),
+            });
+        let unexpected = stack
+            .iter_bottom_up()
+            .filter(|digest| !expected.contains(digest))
+            .map(RuntimeDiscrepancy::UnexpectedLayer);
+        missing.chain(unexpected).collect()
+    }
+
+    /// Find the differences with an spfs stack that would prevent it
+    /// from being updated in place to hold these layers.
+    ///
+    /// Layers missing from the stack can be added, but any layer in
+    /// the stack that is not one of these would be dropped.
+    pub fn incompatibilities_with_stack(
+        &self,
+        stack: &spfs::graph::Stack,
+    ) -> Vec<RuntimeDiscrepancy> {
+        self.compare_to_stack(stack)
+            .into_iter()
+            .filter(|d| !matches!(d, RuntimeDiscrepancy::MissingLayer { .. }))
+            .collect()
+    }
+
     /// Compute a [`spfs::tracking::Manifest`] from a [`ResolvedLayers`].
     ///
     /// If any shadowed files are detected a warning will be logged. Because the
@@ -179,10 +320,37 @@ impl ResolvedLayers {
     }
 }
 
+/// Collect the components of each package that are needed by the
+/// install requirements of the packages in a solution.
+fn components_required_by_solution(
+    solution: &Solution,
+) -> HashMap<PkgNameBuf, BTreeSet<Component>> {
+    let mut required: HashMap<PkgNameBuf, BTreeSet<Component>> = HashMap::new();
+    for resolved in solution.items() {
+        for request in resolved.spec.runtime_requirements().iter() {
+            let RequestWithOptions::Pkg(request) = request else {
+                continue;
+            };
+            let components = required.entry(request.pkg.name.clone()).or_default();
+            if request.pkg.components.is_empty() {
+                components.insert(Component::default_for_run());
+            } else {
+                components.extend(request.pkg.components.iter().cloned());
+            }
+        }
+    }
+    required
+}
+
 /// Return the necessary layers to have all solution packages.
+///
+/// Along with the components that were requested for each package,
+/// any components that other packages in the solution require from
+/// it are also included, so that every dependency is satisfied.
 pub fn solution_to_resolved_runtime_layers(solution: &Solution) -> Result<ResolvedLayers> {
     let mut seen = HashSet::new();
     let mut stack = Vec::new();
+    let required_by_solution = components_required_by_solution(solution);
 
     for resolved in solution.items() {
         let (repo, components) = match &resolved.source {
@@ -213,6 +381,29 @@ pub fn solution_to_resolved_runtime_layers(solution: &Solution) -> Result<Resolv
         if desired_components.is_empty() || desired_components.remove(&Component::All) {
             desired_components.extend(components.keys().cloned());
         }
+        if let Some(required) = required_by_solution.get(&resolved.request.pkg.name) {
+            let mut required = required.clone();
+            if required.remove(&Component::All) {
+                required.extend(components.keys().cloned());
+            }
+            let missing = required
+                .difference(&desired_components)
+                .filter(|c| components.contains_key(*c))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                tracing::info!(
+                    "Adding components of {} needed by other packages: {}",
+                    resolved.spec.ident().format_ident(),
+                    missing
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                desired_components.extend(missing);
+            }
+        }
         desired_components = resolved
             .spec
             .components()
@@ -325,12 +516,153 @@ where
     Ok(stack)
 }
 
+/// Commit the contents of each override to the local repository
+/// and use them in place of the layers of the overridden packages.
+///
+/// Every overridden package must be part of the solution. A warning
+/// is logged if an override contains package metadata for versions
+/// other than the one that was resolved.
+pub async fn apply_package_overrides(
+    resolved_layers: &mut ResolvedLayers,
+    solution: &Solution,
+    overrides: &[PackageOverride],
+) -> Result<()> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+
+    let local_repo = storage::local_repository().await?;
+    let committer = spfs::Committer::new(&local_repo);
+    let local_handle = Arc::new(RepositoryHandle::from(local_repo.clone()));
+    for package_override in overrides {
+        let Some(resolved) = solution.get(package_override.name.as_str()) else {
+            return Err(Error::String(format!(
+                "Cannot override {}, it is not part of the solution",
+                package_override.name
+            )));
+        };
+        warn_on_override_version_skew(package_override, resolved.spec.ident());
+
+        tracing::info!(
+            "overriding {} with {}",
+            resolved.spec.ident().format_ident(),
+            package_override.path.display()
+        );
+        let manifest = committer.commit_dir(&package_override.path).await?;
+        if manifest.is_empty() {
+            tracing::warn!(
+                "Override for {} is empty: {}",
+                package_override.name,
+                package_override.path.display()
+            );
+        }
+        let layer = local_repo
+            .create_layer(&manifest.to_graph_manifest())
+            .await?;
+        if !resolved_layers.replace_package_layers(
+            &package_override.name,
+            layer.digest()?,
+            Arc::clone(&local_handle),
+        ) {
+            return Err(Error::String(format!(
+                "Cannot override {}, it has no layers in the solution",
+                package_override.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Warn if an override only contains package metadata for versions
+/// of the package other than the one that was resolved.
+fn warn_on_override_version_skew(package_override: &PackageOverride, resolved: &BuildIdent) {
+    let metadata_dir = package_override
+        .path
+        .join("spk")
+        .join("pkg")
+        .join(package_override.name.as_str());
+    let Ok(entries) = std::fs::read_dir(&metadata_dir) else {
+        return;
+    };
+    let versions = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| parse_version(entry.file_name().to_string_lossy()).ok())
+        .collect::<Vec<_>>();
+    if !versions.is_empty() && !versions.contains(resolved.version()) {
+        tracing::warn!(
+            "Override for {} contains version(s) {} but {} was resolved",
+            package_override.name,
+            versions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            resolved.version()
+        );
+    }
+}
+
+/// Check that a runtime contains exactly the layers of the given solution.
+///
+/// An empty result means that the runtime matches. Otherwise the
+/// differences are returned, including any uncommitted edits made in
+/// the runtime, such as with `spfs edit`.
+pub fn validate_solution_against_runtime(
+    solution: &Solution,
+    rt: &spfs::runtime::Runtime,
+) -> Result<Vec<RuntimeDiscrepancy>> {
+    let resolved = solution_to_resolved_runtime_layers(solution)?;
+    let mut discrepancies = resolved.compare_to_stack(&rt.status.stack);
+    if rt.is_dirty() {
+        discrepancies.push(RuntimeDiscrepancy::UncommittedChanges);
+    }
+    Ok(discrepancies)
+}
+
+/// Check that a runtime can be updated in place to hold the given solution.
+///
+/// Packages can be added to a runtime, but any layer in the runtime
+/// that is not part of the solution would be removed, and any uncommitted
+/// edits may no longer make sense once the layers under them change.
+/// These are reported as an [`Error::IncompatibleRuntime`].
+pub fn check_runtime_compatible(solution: &Solution, rt: &spfs::runtime::Runtime) -> Result<()> {
+    let resolved = solution_to_resolved_runtime_layers(solution)?;
+    let mut incompatible = resolved.incompatibilities_with_stack(&rt.status.stack);
+    if rt.is_dirty() {
+        incompatible.push(RuntimeDiscrepancy::UncommittedChanges);
+    }
+    if incompatible.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::IncompatibleRuntime(incompatible))
+    }
+}
+
 /// Modify the active spfs runtime to include exactly the packages in the given solution.
+///
+/// The runtime is left unchanged if it cannot be updated in place,
+/// see [`check_runtime_compatible`].
 pub async fn setup_current_runtime(solution: &Solution) -> Result<()> {
     let mut rt = spfs::active_runtime().await?;
+    check_runtime_compatible(solution, &rt)?;
     setup_runtime(&mut rt, solution).await
 }
 
+/// Create a new spfs runtime that includes the packages in the given solution.
+///
+/// The runtime is not entered or made active, it is expected to be
+/// started with a command such as [`spfs::build_command_for_runtime`].
+pub async fn setup_fresh_runtime(solution: &Solution) -> Result<spfs::runtime::Runtime> {
+    let mut rt = spfs::Config::current()?
+        .get_runtime_storage()
+        .await?
+        .create_transient_runtime()
+        .await?;
+    configure_runtime(&mut rt, solution, &[], SyncReporters::console).await?;
+    rt.save_state_to_storage().await?;
+    Ok(rt)
+}
+
 pub async fn setup_runtime(rt: &mut spfs::runtime::Runtime, solution: &Solution) -> Result<()> {
     setup_runtime_with_reporter(rt, solution, SyncReporters::console).await
 }
@@ -343,12 +675,46 @@ pub async fn setup_runtime_with_reporter<F>(
 where
     F: Fn() -> SyncReporters,
 {
-    let stack = resolve_runtime_layers_with_reporter(
-        rt.config.mount_backend.requires_localization(),
-        solution,
-        reporter,
-    )
-    .await?;
+    setup_runtime_with_overrides(rt, solution, &[], reporter).await
+}
+
+/// Modify a runtime to include the packages in the given solution,
+/// with the layers of any overridden packages replaced by the
+/// contents of their local directories.
+///
+/// See [`apply_package_overrides`].
+pub async fn setup_runtime_with_overrides<F>(
+    rt: &mut spfs::runtime::Runtime,
+    solution: &Solution,
+    overrides: &[PackageOverride],
+    reporter: F,
+) -> Result<()>
+where
+    F: Fn() -> SyncReporters,
+{
+    configure_runtime(rt, solution, overrides, reporter).await?;
+    rt.save_state_to_storage().await?;
+    spfs::remount_runtime(rt).await?;
+    Ok(())
+}
+
+/// Set the stack and solve data of a runtime without saving it.
+async fn configure_runtime<F>(
+    rt: &mut spfs::runtime::Runtime,
+    solution: &Solution,
+    overrides: &[PackageOverride],
+    reporter: F,
+) -> Result<()>
+where
+    F: Fn() -> SyncReporters,
+{
+    let mut resolved_layers = solution_to_resolved_runtime_layers(solution)?;
+    apply_package_overrides(&mut resolved_layers, solution, overrides).await?;
+    let stack = if rt.config.mount_backend.requires_localization() {
+        pull_resolved_runtime_layers_with_reporter(&resolved_layers, reporter).await?
+    } else {
+        resolved_layers.layers()
+    };
     rt.status.stack = spfs::graph::Stack::from_iter(stack);
 
     let spfs_config = spfs::Config::current()?;
@@ -366,8 +732,5 @@ where
         )
         .await?;
     }
-
-    rt.save_state_to_storage().await?;
-    spfs::remount_runtime(rt).await?;
     Ok(())
 }
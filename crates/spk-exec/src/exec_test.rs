@@ -2,19 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use rstest::{fixture, rstest};
+use spfs::encoding::{Digest, EMPTY_DIGEST, NULL_DIGEST};
 use spfstest::spfstest;
 use spk_cmd_build::build_package;
-use spk_schema::foundation::build_ident;
 use spk_schema::foundation::fixtures::*;
-use spk_solve::{DecisionFormatterBuilder, SolverExt, SolverMut, StepSolver};
+use spk_schema::foundation::ident_component::Component;
+use spk_schema::foundation::{build_ident, pkg_name};
+use spk_schema::ident::{PkgRequest, PkgRequestWithOptions, RequestedBy};
+use spk_schema::name::PkgNameBuf;
+use spk_schema::prelude::*;
+use spk_schema::spec;
+use spk_solve::solution::{PackageSource, Solution};
+use spk_solve::{DecisionFormatterBuilder, RepositoryHandle, SolverExt, SolverMut, StepSolver};
 use spk_solve_macros::pinned_request;
 use spk_storage::fixtures::*;
 
-use crate::solution_to_resolved_runtime_layers;
+use crate::{PackageOverride, RuntimeDiscrepancy, solution_to_resolved_runtime_layers};
 
 #[fixture]
 fn solver() -> StepSolver {
@@ -91,3 +98,182 @@ build:
     assert!(environment.get_path("subdir/one.txt").is_some());
     assert!(environment.get_path("subdir/two.txt").is_some());
 }
+
+#[rstest]
+fn test_components_required_by_solution_are_included() {
+    let lib = Arc::new(spec!({"pkg": "lib/1.0.0/3I42H3S6"}));
+    let app = Arc::new(spec!({
+        "pkg": "app/1.0.0/3I42H3S6",
+        "install": {"requirements": [{"pkg": "lib:run"}]},
+    }));
+    let repo = Arc::new(RepositoryHandle::new_mem());
+    let lib_components = HashMap::from([
+        (Component::Build, Digest::from(EMPTY_DIGEST)),
+        (Component::Run, Digest::from(NULL_DIGEST)),
+    ]);
+
+    let mut solution = Solution::default();
+    let mut lib_request =
+        PkgRequest::from_ident(lib.ident().to_any_ident(), RequestedBy::SpkInternalTest);
+    lib_request.pkg.components = BTreeSet::from([Component::Build]);
+    solution.add(
+        PkgRequestWithOptions {
+            pkg_request: lib_request,
+            options: Default::default(),
+        },
+        Arc::clone(&lib),
+        PackageSource::Repository {
+            repo: Arc::clone(&repo),
+            components: lib_components,
+        },
+    );
+    solution.add(
+        PkgRequestWithOptions {
+            pkg_request: PkgRequest::from_ident(
+                app.ident().to_any_ident(),
+                RequestedBy::SpkInternalTest,
+            ),
+            options: Default::default(),
+        },
+        Arc::clone(&app),
+        PackageSource::SpkInternalTest,
+    );
+
+    let resolved_layers = solution_to_resolved_runtime_layers(&solution).unwrap();
+    let layers = resolved_layers.layers();
+    assert!(
+        layers.contains(&Digest::from(EMPTY_DIGEST)),
+        "the requested build component should be included"
+    );
+    assert!(
+        layers.contains(&Digest::from(NULL_DIGEST)),
+        "the run component needed by app should also be included"
+    );
+}
+
+/// A solution with the run component of a single lib package, where
+/// the run component is the [`NULL_DIGEST`] layer.
+fn lib_solution() -> Solution {
+    let lib = Arc::new(spec!({"pkg": "lib/1.0.0/3I42H3S6"}));
+    let repo = Arc::new(RepositoryHandle::new_mem());
+    let lib_components = HashMap::from([
+        (Component::Build, Digest::from(EMPTY_DIGEST)),
+        (Component::Run, Digest::from(NULL_DIGEST)),
+    ]);
+
+    let mut solution = Solution::default();
+    let mut lib_request =
+        PkgRequest::from_ident(lib.ident().to_any_ident(), RequestedBy::SpkInternalTest);
+    lib_request.pkg.components = BTreeSet::from([Component::Run]);
+    solution.add(
+        PkgRequestWithOptions {
+            pkg_request: lib_request,
+            options: Default::default(),
+        },
+        Arc::clone(&lib),
+        PackageSource::Repository {
+            repo,
+            components: lib_components,
+        },
+    );
+    solution
+}
+
+#[rstest]
+#[case::matching(vec![NULL_DIGEST], vec![])]
+#[case::missing(vec![], vec![RuntimeDiscrepancy::MissingLayer {
+    digest: Digest::from(NULL_DIGEST),
+    package: build_ident!("lib/1.0.0/3I42H3S6"),
+    component: Component::Run,
+}])]
+#[case::unexpected(
+    vec![NULL_DIGEST, EMPTY_DIGEST],
+    vec![RuntimeDiscrepancy::UnexpectedLayer(Digest::from(EMPTY_DIGEST))]
+)]
+fn test_resolved_layers_compare_to_stack(
+    #[case] stack: Vec<[u8; spfs::encoding::DIGEST_SIZE]>,
+    #[case] expected: Vec<RuntimeDiscrepancy>,
+) {
+    let resolved_layers = solution_to_resolved_runtime_layers(&lib_solution()).unwrap();
+    let stack = spfs::graph::Stack::from_iter(stack.into_iter().map(Digest::from));
+    assert_eq!(resolved_layers.compare_to_stack(&stack), expected);
+}
+
+#[rstest]
+#[case::matching(vec![NULL_DIGEST], vec![])]
+#[case::missing(vec![], vec![])]
+#[case::unexpected(
+    vec![EMPTY_DIGEST],
+    vec![RuntimeDiscrepancy::UnexpectedLayer(Digest::from(EMPTY_DIGEST))]
+)]
+fn test_resolved_layers_incompatibilities_with_stack(
+    #[case] stack: Vec<[u8; spfs::encoding::DIGEST_SIZE]>,
+    #[case] expected: Vec<RuntimeDiscrepancy>,
+) {
+    let resolved_layers = solution_to_resolved_runtime_layers(&lib_solution()).unwrap();
+    let stack = spfs::graph::Stack::from_iter(stack.into_iter().map(Digest::from));
+    assert_eq!(
+        resolved_layers.incompatibilities_with_stack(&stack),
+        expected,
+        "layers missing from the stack can be added, but others would be lost"
+    );
+}
+
+#[rstest]
+#[case("my-pkg=/path/to/build", Some(("my-pkg", "/path/to/build")))]
+#[case("my-pkg=build=1", Some(("my-pkg", "build=1")))]
+#[case("my-pkg", None)]
+#[case("my-pkg=", None)]
+#[case("=/path/to/build", None)]
+#[case("My_Pkg=/path/to/build", None)]
+fn test_package_override_parse(#[case] input: &str, #[case] expected: Option<(&str, &str)>) {
+    let actual = input.parse::<PackageOverride>().ok();
+    let expected = expected.map(|(name, path)| PackageOverride {
+        name: PkgNameBuf::try_from(name).unwrap(),
+        path: path.into(),
+    });
+    assert_eq!(actual, expected);
+}
+
+#[rstest]
+fn test_resolved_layers_replace_package_layers() {
+    let lib = Arc::new(spec!({"pkg": "lib/1.0.0/3I42H3S6"}));
+    let app = Arc::new(spec!({"pkg": "app/1.0.0/3I42H3S6"}));
+    let repo = Arc::new(RepositoryHandle::new_mem());
+
+    let mut solution = Solution::default();
+    for (spec, digest) in [(&lib, EMPTY_DIGEST), (&app, NULL_DIGEST)] {
+        solution.add(
+            PkgRequestWithOptions {
+                pkg_request: PkgRequest::from_ident(
+                    spec.ident().to_any_ident(),
+                    RequestedBy::SpkInternalTest,
+                ),
+                options: Default::default(),
+            },
+            Arc::clone(spec),
+            PackageSource::Repository {
+                repo: Arc::clone(&repo),
+                components: HashMap::from([(Component::Run, Digest::from(digest))]),
+            },
+        );
+    }
+
+    let mut resolved_layers = solution_to_resolved_runtime_layers(&solution).unwrap();
+    let replacement = Digest::from_bytes(&[1; spfs::encoding::DIGEST_SIZE]).unwrap();
+    assert!(resolved_layers.replace_package_layers(
+        pkg_name!("lib"),
+        replacement,
+        Arc::clone(&repo)
+    ));
+    assert_eq!(
+        resolved_layers.layers(),
+        vec![Digest::from(NULL_DIGEST), replacement],
+        "the override should replace the package's layer at the top of the stack"
+    );
+
+    assert!(
+        !resolved_layers.replace_package_layers(pkg_name!("other"), replacement, repo),
+        "packages without layers cannot be replaced"
+    );
+}
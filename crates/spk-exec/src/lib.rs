@@ -8,14 +8,21 @@ mod exec;
 pub use error::{Error, Result};
 pub use exec::{
     ConflictingPackagePair,
+    PackageOverride,
     ResolvedLayer,
     ResolvedLayers,
+    RuntimeDiscrepancy,
+    apply_package_overrides,
+    check_runtime_compatible,
     pull_resolved_runtime_layers,
     pull_resolved_runtime_layers_with_reporter,
     resolve_runtime_layers,
     resolve_runtime_layers_with_reporter,
     setup_current_runtime,
+    setup_fresh_runtime,
     setup_runtime,
+    setup_runtime_with_overrides,
     setup_runtime_with_reporter,
     solution_to_resolved_runtime_layers,
+    validate_solution_against_runtime,
 };
@@ -6,12 +6,18 @@ mod error;
 
 pub use error::{Error, Result};
 
-use crate::name::{PkgName, RepositoryNameBuf};
+use crate::name::{PkgName, RepositoryName, RepositoryNameBuf};
 
 /// Helper to hold values that affect the formatting of a request
 pub struct FormatChangeOptions {
     pub verbosity: u8,
     pub level: u64,
+    /// How each kind of change is distinguished from the others
+    pub markers: ChangeMarkers,
+    /// Append what requested a package when formatting its request
+    pub show_requested_by: bool,
+    /// Append the repository that each resolved build came from
+    pub show_build_repository: bool,
 }
 
 impl Default for FormatChangeOptions {
@@ -19,12 +25,68 @@ impl Default for FormatChangeOptions {
         Self {
             verbosity: 0,
             level: u64::MAX,
+            markers: ChangeMarkers::default(),
+            show_requested_by: false,
+            show_build_repository: false,
+        }
+    }
+}
+
+/// How formatted changes indicate whether they add, remove
+/// or otherwise modify something.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeMarkers {
+    /// Distinguish changes by color only
+    #[default]
+    Color,
+    /// Prefix changes with `+`, `-` or `~` and use no color
+    Symbols,
+    /// Prefix changes with symbols and also use color
+    ColorAndSymbols,
+}
+
+impl ChangeMarkers {
+    /// Use color when writing to a terminal, and symbols when
+    /// output is redirected or the `NO_COLOR` variable is set.
+    ///
+    /// This inspects the current process, so it is meant to be
+    /// called by command line tools rather than library code.
+    pub fn detect() -> Self {
+        use std::io::IsTerminal;
+
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        if no_color || !std::io::stdout().is_terminal() {
+            Self::Symbols
+        } else {
+            Self::Color
+        }
+    }
+
+    /// Mark a change label with the given symbol and/or color
+    pub fn mark(&self, symbol: char, label: &str, color: colored::Color) -> String {
+        use colored::Colorize;
+
+        match self {
+            Self::Color => label.color(color).to_string(),
+            Self::Symbols => format!("{symbol} {label}"),
+            Self::ColorAndSymbols => format!("{symbol} {label}").color(color).to_string(),
         }
     }
 }
 
 pub trait FormatBuild {
     fn format_build(&self) -> String;
+
+    /// Format this build followed by the name of the repository
+    /// that it was found in, if one is given.
+    fn format_build_with_repository(&self, repository_name: Option<&RepositoryName>) -> String {
+        use colored::Colorize;
+
+        match repository_name {
+            Some(name) => format!("{} [{}]", self.format_build(), name.as_str().cyan()),
+            None => self.format_build(),
+        }
+    }
 }
 
 pub trait FormatChange {
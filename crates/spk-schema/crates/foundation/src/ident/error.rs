@@ -21,6 +21,32 @@ pub enum Error {
     InvalidInclusionPolicy(#[source] serde_yaml::Error),
     #[error("Invalid PinPolicy: {0}")]
     InvalidPinPolicy(#[source] serde_yaml::Error),
+    #[error("Invalid identifier '{input}': {reason} at position {offset}")]
+    #[diagnostic(code("spk::ident::invalid_ident"))]
+    InvalidIdent {
+        #[source_code]
+        input: String,
+        #[label("{reason}")]
+        span: miette::SourceSpan,
+        /// The byte offset of the problem within the input
+        offset: usize,
+        /// The token that the parser expected to find, if known
+        expected: Option<String>,
+        reason: String,
+    },
+    #[error("Expected a build identifier but none present in '{0}'")]
+    MissingBuild(String),
+    #[error("Expected a version identifier but '{0}' has a build")]
+    UnexpectedBuild(String),
+    #[error("Expected a repository name but none present in '{0}'")]
+    MissingRepository(String),
+    #[error("Invalid identifier list on line {line}: {source}")]
+    InvalidIdentListLine {
+        /// The line number where the error occurred, starting from 1
+        line: usize,
+        #[source]
+        source: Box<Error>,
+    },
     #[error(transparent)]
     #[diagnostic(forward(0))]
     SpkIdentBuildError(#[from] crate::ident_build::Error),
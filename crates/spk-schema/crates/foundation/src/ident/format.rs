@@ -6,6 +6,7 @@ use colored::Colorize;
 
 use crate::format::{FormatBuild, FormatIdent};
 use crate::ident::{AnyIdent, BuildIdent, LocatedBuildIdent, VersionIdent};
+use crate::name::RepositoryName;
 
 impl FormatIdent for AnyIdent {
     fn format_ident(&self) -> String {
@@ -42,11 +43,19 @@ impl FormatIdent for VersionIdent {
 
 impl FormatIdent for BuildIdent {
     fn format_ident(&self) -> String {
+        self.format_ident_with_repository(None)
+    }
+}
+
+impl BuildIdent {
+    /// Format this ident with the name of the repository that the
+    /// build came from shown next to the build, if one is given.
+    pub fn format_ident_with_repository(&self, repository_name: Option<&RepositoryName>) -> String {
         format!(
             "{}/{}/{}",
             self.name().as_str().bold(),
             self.version().to_string().bright_blue(),
-            self.build().format_build()
+            self.build().format_build_with_repository(repository_name)
         )
     }
 }
@@ -8,7 +8,7 @@ use std::str::FromStr;
 use relative_path::RelativePathBuf;
 
 use crate::ident::ident_version::VersionIdent;
-use crate::ident::{BuildIdent, Ident, LocatedBuildIdent, RangeIdent, Result, parsing};
+use crate::ident::{BuildIdent, Error, Ident, LocatedBuildIdent, RangeIdent, Result, parsing};
 use crate::ident_build::Build;
 use crate::ident_ops::parsing::IdentPartsBuf;
 use crate::ident_ops::{MetadataPath, TagPath};
@@ -56,6 +56,22 @@ impl AnyIdent {
         target.map(|build| BuildIdent::new(base, build))
     }
 
+    /// Convert this ident into a [`BuildIdent`], failing if it has no build.
+    pub fn try_into_build_ident(self) -> Result<BuildIdent> {
+        match self.into_inner() {
+            (base, Some(build)) => Ok(BuildIdent::new(base, build)),
+            (base, None) => Err(Error::MissingBuild(base.to_string())),
+        }
+    }
+
+    /// Convert this ident into a [`VersionIdent`], failing if it has a build.
+    pub fn try_into_version_ident(self) -> Result<VersionIdent> {
+        if self.build().is_some() {
+            return Err(Error::UnexpectedBuild(self.to_string()));
+        }
+        Ok(self.into_version_ident())
+    }
+
     /// Return if this identifier can possibly have embedded packages.
     pub fn can_embed(&self) -> bool {
         // Only builds can have embeds.
@@ -103,12 +119,8 @@ impl AnyIdent {
         self,
         repository_name: RepositoryNameBuf,
     ) -> Result<LocatedBuildIdent> {
-        let (version_ident, build) = self.into_inner();
-        build
-            .map(|build| {
-                LocatedBuildIdent::new(repository_name, BuildIdent::new(version_ident, build))
-            })
-            .ok_or_else(|| "Ident must contain a build to become a LocatedBuildIdent".into())
+        self.try_into_build_ident()
+            .map(|ident| LocatedBuildIdent::new(repository_name, ident))
     }
 }
 
@@ -182,12 +194,7 @@ impl FromStr for AnyIdent {
 
         all_consuming(parsing::ident::<nom_supreme::error::ErrorTree<_>>)(source)
             .map(|(_, ident)| ident)
-            .map_err(|err| match err {
-                nom::Err::Error(e) | nom::Err::Failure(e) => {
-                    crate::ident::Error::String(e.to_string())
-                }
-                nom::Err::Incomplete(_) => unreachable!(),
-            })
+            .map_err(|err| parsing::invalid_ident_error(source, err))
     }
 }
 
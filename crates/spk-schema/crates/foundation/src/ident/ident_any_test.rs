@@ -7,6 +7,7 @@ use std::str::FromStr;
 use rstest::rstest;
 
 use super::{AnyIdent, parse_ident};
+use crate::ident::{BuildIdent, VersionIdent};
 
 #[rstest]
 #[case("package")]
@@ -27,3 +28,33 @@ fn test_ident_to_string() {
     let out = ident.to_string();
     assert_eq!(&out, "package");
 }
+
+#[rstest]
+fn test_ident_try_into_build_ident() {
+    let ident = parse_ident("package/1.0.0/src").unwrap();
+    let build: BuildIdent = ident.try_into().unwrap();
+    assert_eq!(build.to_string(), "package/1.0.0/src");
+
+    let ident = parse_ident("package/1.0.0").unwrap();
+    let err = BuildIdent::try_from(&ident).expect_err("an ident without a build should fail");
+    assert!(
+        matches!(err, crate::ident::Error::MissingBuild(_)),
+        "expected a MissingBuild error, got: {err:?}"
+    );
+}
+
+#[rstest]
+fn test_ident_try_into_version_ident() {
+    let ident = parse_ident("package/1.0.0").unwrap();
+    let version: VersionIdent = ident.try_into().unwrap();
+    assert_eq!(version.to_string(), "package/1.0.0");
+
+    let ident = parse_ident("package/1.0.0/src").unwrap();
+    let err = ident
+        .try_into_version_ident()
+        .expect_err("an ident with a build should fail");
+    assert!(
+        matches!(err, crate::ident::Error::UnexpectedBuild(_)),
+        "expected an UnexpectedBuild error, got: {err:?}"
+    );
+}
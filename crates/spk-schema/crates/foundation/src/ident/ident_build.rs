@@ -53,9 +53,7 @@ impl TryFrom<EmbeddedSourcePackage> for BuildIdent {
                     "EmbeddedSourcePackage missing version".to_string(),
                 ))
             } else {
-                Err(Error::String(
-                    "EmbeddedSourcePackage missing build".to_string(),
-                ))
+                Err(Error::MissingBuild(value.ident.pkg_name))
             };
         };
         Ok(Self::new(
@@ -169,12 +167,7 @@ impl FromStr for BuildIdent {
 
         all_consuming(parsing::build_ident::<nom_supreme::error::ErrorTree<_>>)(source)
             .map(|(_, ident)| ident)
-            .map_err(|err| match err {
-                nom::Err::Error(e) | nom::Err::Failure(e) => {
-                    crate::ident::Error::String(e.to_string())
-                }
-                nom::Err::Incomplete(_) => unreachable!(),
-            })
+            .map_err(|err| parsing::invalid_ident_error(source, err))
     }
 }
 
@@ -201,14 +194,17 @@ impl TryFrom<&IdentPartsBuf> for BuildIdent {
             .map(|v| v.as_str().parse::<Version>())
             .transpose()?
             .unwrap_or_default();
-        let build = parts
+        let ident = VersionIdent::new(name, version);
+        let Some(build) = parts
             .build_str
             .as_ref()
             .map(|v| v.parse::<Build>())
             .transpose()?
-            .ok_or_else(|| Error::String("Ident must have an associated build id".into()))?;
+        else {
+            return Err(Error::MissingBuild(ident.to_string()));
+        };
 
-        Ok(VersionIdent::new(name, version).into_build_ident(build))
+        Ok(ident.into_build_ident(build))
     }
 }
 
@@ -260,10 +256,10 @@ impl TryFrom<RangeIdent> for BuildIdent {
     type Error = crate::ident::Error;
 
     fn try_from(ri: RangeIdent) -> Result<Self> {
+        let Some(build) = ri.build else {
+            return Err(Error::MissingBuild(ri.to_string()));
+        };
         let name = ri.name;
-        let build = ri
-            .build
-            .ok_or_else(|| Error::String("Build was required from range ident".into()))?;
         Ok(ri
             .version
             .try_into_version()
@@ -271,6 +267,22 @@ impl TryFrom<RangeIdent> for BuildIdent {
     }
 }
 
+impl TryFrom<AnyIdent> for BuildIdent {
+    type Error = crate::ident::Error;
+
+    fn try_from(ident: AnyIdent) -> Result<Self> {
+        ident.try_into_build_ident()
+    }
+}
+
+impl TryFrom<&AnyIdent> for BuildIdent {
+    type Error = crate::ident::Error;
+
+    fn try_from(ident: &AnyIdent) -> Result<Self> {
+        ident.clone().try_into_build_ident()
+    }
+}
+
 impl TryFrom<&RangeIdent> for BuildIdent {
     type Error = crate::ident::Error;
 
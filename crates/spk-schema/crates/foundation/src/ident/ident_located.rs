@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::fmt::Write;
+use std::str::FromStr;
+
 use relative_path::RelativePathBuf;
 
 use super::BuildIdent;
-use crate::ident::{AnyIdent, Ident, VersionIdent};
+use crate::ident::{AnyIdent, Error, Ident, RangeIdent, Result, VersionIdent};
 use crate::ident_build::Build;
 use crate::ident_ops::{MetadataPath, TagPath};
 use crate::name::{PkgName, PkgNameBuf, RepositoryName, RepositoryNameBuf};
@@ -89,6 +92,58 @@ impl TagPath for LocatedBuildIdent {
 
 impl std::fmt::Display for LocatedBuildIdent {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}/{}", self.base(), self.target())
+        self.base.fmt(f)?;
+        f.write_char('/')?;
+        self.target.fmt(f)
+    }
+}
+
+impl std::fmt::Display for LocatedVersionIdent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.base.fmt(f)?;
+        f.write_char('/')?;
+        self.target.fmt(f)
+    }
+}
+
+impl FromStr for LocatedBuildIdent {
+    type Err = Error;
+
+    /// Parse an identifier of the form `<REPO>/<NAME>/<VERSION>/<BUILD>`
+    fn from_str(source: &str) -> Result<Self> {
+        RangeIdent::from_str(source)?.try_into()
+    }
+}
+
+impl FromStr for LocatedVersionIdent {
+    type Err = Error;
+
+    /// Parse an identifier of the form `<REPO>/<NAME>/<VERSION>`
+    fn from_str(source: &str) -> Result<Self> {
+        RangeIdent::from_str(source)?.try_into()
+    }
+}
+
+impl TryFrom<RangeIdent> for LocatedBuildIdent {
+    type Error = Error;
+
+    fn try_from(mut ri: RangeIdent) -> Result<Self> {
+        let Some(repository_name) = ri.repository_name.take() else {
+            return Err(Error::MissingRepository(ri.to_string()));
+        };
+        BuildIdent::try_from(ri).map(|ident| ident.into_located(repository_name))
+    }
+}
+
+impl TryFrom<RangeIdent> for LocatedVersionIdent {
+    type Error = Error;
+
+    fn try_from(mut ri: RangeIdent) -> Result<Self> {
+        let Some(repository_name) = ri.repository_name.take() else {
+            return Err(Error::MissingRepository(ri.to_string()));
+        };
+        AnyIdent::try_from(ri)?
+            .try_into_version_ident()
+            .map(|ident| Self::new(repository_name, ident))
     }
 }
@@ -92,12 +92,7 @@ impl FromStr for OptVersionIdent {
 
         all_consuming(parsing::opt_version_ident::<nom_supreme::error::ErrorTree<_>>)(source)
             .map(|(_, ident)| ident)
-            .map_err(|err| match err {
-                nom::Err::Error(e) | nom::Err::Failure(e) => {
-                    crate::ident::Error::String(e.to_string())
-                }
-                nom::Err::Incomplete(_) => unreachable!(),
-            })
+            .map_err(|err| parsing::invalid_ident_error(source, err))
     }
 }
 
@@ -8,8 +8,10 @@ use std::str::FromStr;
 
 use rstest::rstest;
 
-use crate::ident::RangeIdent;
+use crate::format::FormatIdent;
+use crate::ident::{BuildIdent, RangeIdent};
 use crate::ident_build::Build;
+use crate::name::RepositoryName;
 use crate::version::{Version, parse_version};
 use crate::version_range::{CompatRange, VersionFilter, VersionRange};
 
@@ -106,3 +108,19 @@ fn test_parse_range_ident(#[case] input: &str, #[case] expected: RangeIdent) {
     let actual = RangeIdent::from_str(input).unwrap();
     assert_eq!(actual, expected);
 }
+
+#[rstest]
+fn test_format_ident_with_repository() {
+    colored::control::set_override(false);
+    let ident = BuildIdent::from_str("my-pkg/1.0.0/3I42H3S6").unwrap();
+    let repo = RepositoryName::new("origin").unwrap();
+    assert_eq!(
+        ident.format_ident_with_repository(None),
+        ident.format_ident(),
+        "no repository should match the default format"
+    );
+    assert_eq!(
+        ident.format_ident_with_repository(Some(repo)),
+        "my-pkg/1.0.0/3I42H3S6 [origin]"
+    );
+}
@@ -119,6 +119,14 @@ impl std::fmt::Display for VersionIdent {
     }
 }
 
+impl TryFrom<AnyIdent> for VersionIdent {
+    type Error = crate::ident::Error;
+
+    fn try_from(ident: AnyIdent) -> Result<Self> {
+        ident.try_into_version_ident()
+    }
+}
+
 impl FromStr for VersionIdent {
     type Err = crate::ident::Error;
 
@@ -128,12 +136,7 @@ impl FromStr for VersionIdent {
 
         all_consuming(parsing::version_ident::<nom_supreme::error::ErrorTree<_>>)(source)
             .map(|(_, ident)| ident)
-            .map_err(|err| match err {
-                nom::Err::Error(e) | nom::Err::Failure(e) => {
-                    crate::ident::Error::String(e.to_string())
-                }
-                nom::Err::Incomplete(_) => unreachable!(),
-            })
+            .map_err(|err| parsing::invalid_ident_error(source, err))
     }
 }
 
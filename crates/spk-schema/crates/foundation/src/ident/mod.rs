@@ -44,7 +44,12 @@ pub use pkg_request_with_options::{
     PkgRequestOptions,
     PkgRequestWithOptions,
 };
-pub use range_ident::{RangeIdent, parse_ident_range, parse_ident_range_list};
+pub use range_ident::{
+    RangeIdent,
+    parse_ident_range,
+    parse_ident_range_lines,
+    parse_ident_range_list,
+};
 pub use request_with_options::RequestWithOptions;
 pub use satisfy::Satisfy;
 
@@ -5,6 +5,8 @@
 mod ident;
 mod request;
 
+use nom_supreme::error::{BaseErrorKind, ErrorTree, GenericErrorTree};
+
 #[cfg(test)]
 #[path = "./parsing_test.rs"]
 mod parsing_test;
@@ -16,3 +18,55 @@ pub use request::{
     range_ident_version_filter,
     version_filter_and_build,
 };
+
+/// Convert a failure from one of the ident parsers into an error
+/// that identifies the position of the problem within `input`.
+pub(crate) fn invalid_ident_error(
+    input: &str,
+    err: nom::Err<ErrorTree<&str>>,
+) -> crate::ident::Error {
+    let tree = match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        nom::Err::Incomplete(_) => unreachable!(),
+    };
+    let (location, kind) = deepest_error(&tree);
+    // the location of each error is the remainder of the input
+    // at the point where the error was encountered
+    let offset = input.len().saturating_sub(location.len());
+    let len = input
+        .get(offset..)
+        .and_then(|rest| rest.chars().next())
+        .map(char::len_utf8)
+        .unwrap_or(0);
+    let (expected, reason) = match kind {
+        BaseErrorKind::Expected(expectation) => (Some(expectation.to_string()), kind.to_string()),
+        BaseErrorKind::Kind(_) => (None, kind.to_string()),
+        BaseErrorKind::External(err) => (None, err.to_string()),
+    };
+    crate::ident::Error::InvalidIdent {
+        input: input.to_owned(),
+        span: (offset, len).into(),
+        offset,
+        expected,
+        reason,
+    }
+}
+
+/// Find the error that was encountered furthest into the input,
+/// which is the most useful one to report when alternatives fail.
+fn deepest_error<'a, 't>(
+    tree: &'t ErrorTree<&'a str>,
+) -> (
+    &'a str,
+    &'t BaseErrorKind<&'static str, Box<dyn std::error::Error + Send + Sync + 'static>>,
+) {
+    match tree {
+        GenericErrorTree::Base { location, kind } => (*location, kind),
+        GenericErrorTree::Stack { base, .. } => deepest_error(base),
+        GenericErrorTree::Alt(siblings) => siblings
+            .iter()
+            .map(deepest_error)
+            .min_by_key(|(location, _)| location.len())
+            .expect("an alternative error always has at least one sibling"),
+    }
+}
@@ -13,6 +13,7 @@ use nom::error::ErrorKind;
 use proptest::collection::{btree_map, btree_set, vec};
 use proptest::option::weighted;
 use proptest::prelude::*;
+use rstest::rstest;
 
 use crate::ident::{AnyIdent, RangeIdent, VersionIdent, parse_ident, parse_ident_range_list};
 use crate::ident_build::{Build, EmbeddedSource, EmbeddedSourcePackage};
@@ -617,6 +618,30 @@ fn parse_ident_with_basic_errors() {
     assert!(r.is_ok(), "{}", r.unwrap_err());
 }
 
+#[rstest]
+#[case::ident("my-pkg/1.0.0 extra", 12)]
+#[case::bad_version("my-pkg/1.0.0$", 12)]
+fn test_parse_ident_error_position(#[case] source: &str, #[case] expected: usize) {
+    let err = parse_ident(source).expect_err("ident should fail to parse");
+    let crate::ident::Error::InvalidIdent { offset, .. } = err else {
+        panic!("expected an InvalidIdent error, got: {err:?}");
+    };
+    assert_eq!(offset, expected, "wrong error position for '{source}'");
+}
+
+#[rstest]
+fn test_parse_ident_range_list_error_position() {
+    let source = "python,maya/2022.3 ,zlib";
+    let err = parse_ident_range_list(source).expect_err("list should fail to parse");
+    let crate::ident::Error::InvalidIdent { offset, .. } = err else {
+        panic!("expected an InvalidIdent error, got: {err:?}");
+    };
+    assert_eq!(
+        offset, 18,
+        "error should point at the space within the list"
+    );
+}
+
 /// Fail if post-tags are specified before pre-tags.
 #[test]
 fn check_wrong_tag_order_is_a_parse_error() {
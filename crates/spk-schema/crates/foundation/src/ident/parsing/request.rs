@@ -140,8 +140,7 @@ pub fn range_ident_comma_separated_list(
         range_ident::<nom_supreme::error::ErrorTree<_>>(known_repositories),
     ))(input);
 
-    parsed_list.map(|(_, l)| l).map_err(|err| match err {
-        nom::Err::Error(e) | nom::Err::Failure(e) => crate::ident::Error::String(e.to_string()),
-        nom::Err::Incomplete(_) => unreachable!(),
-    })
+    parsed_list
+        .map(|(_, l)| l)
+        .map_err(|err| super::invalid_ident_error(input, err))
 }
@@ -99,6 +99,10 @@ impl std::str::FromStr for PreReleasePolicy {
 pub enum InclusionPolicy {
     #[default]
     Always,
+    /// Include the package when it can be resolved alongside the
+    /// rest of the environment, otherwise leave it out rather than
+    /// failing the solve.
+    IfCompatible,
     IfAlreadyPresent,
 }
 
@@ -1111,13 +1115,23 @@ impl PkgRequest {
     /// versions that are not going to satisfy the request without
     /// needing to load the whole package spec.
     pub fn is_version_applicable(&self, version: &Version) -> Compatibility {
+        let compat = self.is_prerelease_allowed(version);
+        if !compat.is_ok() {
+            return compat;
+        }
+        self.pkg.version.is_applicable(version)
+    }
+
+    /// Return true if this request's prerelease policy allows the given
+    /// version, without checking it against the requested range.
+    pub fn is_prerelease_allowed(&self, version: &Version) -> Compatibility {
         if (self.prerelease_policy.is_none()
             || self.prerelease_policy == Some(PreReleasePolicy::ExcludeAll))
             && !version.pre.is_empty()
         {
             Compatibility::Incompatible(IncompatibleReason::PrereleasesNotAllowed)
         } else {
-            self.pkg.version.is_applicable(version)
+            Compatibility::Compatible
         }
     }
 
@@ -1294,6 +1308,21 @@ impl FormatRequest for PkgRequest {
         }
         out.push('/');
         out.push_str(&versions.join(","));
+
+        if format_settings.show_requested_by {
+            let requesters = self.get_requesters();
+            if !requesters.is_empty() {
+                let _ = write!(
+                    out,
+                    " (requested by {})",
+                    requesters
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
         out
     }
 }
@@ -242,6 +242,27 @@ fn test_compat_and_equals_restrict() {
     InclusionPolicy::Always,
     None
 )]
+// An optional request is still optional when merged with a constraint
+#[case(
+    "{pkg: something/>1.0, include: IfCompatible}",
+    "{pkg: something/>2.0, include: IfAlreadyPresent}",
+    InclusionPolicy::IfCompatible,
+    Some(">2.0.0")
+)]
+// but not when merged with a firm request
+#[case(
+    "{pkg: something/>1.0, include: IfCompatible}",
+    "{pkg: something/>2.0, include: Always}",
+    InclusionPolicy::Always,
+    Some(">2.0.0")
+)]
+// Unlike `IfAlreadyPresent`, optional requests must still intersect
+#[case(
+    "{pkg: something/=1.0, include: IfCompatible}",
+    "{pkg: something/=2.0, include: IfCompatible}",
+    InclusionPolicy::IfCompatible,
+    None
+)]
 fn test_inclusion_policy_and_merge(
     #[case] a: &str,
     #[case] b: &str,
@@ -489,3 +510,33 @@ fn test_deserialize_pkg_pin_string_or_bool() {
         ]
     );
 }
+
+#[rstest]
+fn test_format_request_show_requested_by() {
+    use crate::format::{FormatChangeOptions, FormatRequest};
+
+    colored::control::set_override(false);
+    let requester = parse_build_ident("myapp/1.0.0/3I42H3S6").unwrap();
+    let request = super::PkgRequest::from_ident(
+        parse_build_ident("python/3.9.0/3I42H3S6")
+            .unwrap()
+            .to_any_ident(),
+        super::RequestedBy::PackageBuild(requester),
+    );
+
+    let terse = request.format_request(None, &request.pkg.name, &FormatChangeOptions::default());
+    assert!(
+        !terse.contains("requested by"),
+        "requesters should not be shown by default: {terse}"
+    );
+
+    let options = FormatChangeOptions {
+        show_requested_by: true,
+        ..Default::default()
+    };
+    let detailed = request.format_request(None, &request.pkg.name, &options);
+    assert!(
+        detailed.ends_with(" (requested by myapp/1.0.0/3I42H3S6)"),
+        "requester should be appended: {detailed}"
+    );
+}
@@ -10,7 +10,15 @@ use itertools::Itertools;
 use nom::combinator::all_consuming;
 use serde::{Deserialize, Serialize};
 
-use crate::ident::{AnyIdent, BuildIdent, LocatedBuildIdent, Result, Satisfy, VersionIdent};
+use crate::ident::{
+    AnyIdent,
+    BuildIdent,
+    LocatedBuildIdent,
+    LocatedVersionIdent,
+    Result,
+    Satisfy,
+    VersionIdent,
+};
 use crate::ident_build::Build;
 use crate::ident_component::{Component, Components};
 use crate::ident_ops::parsing::KNOWN_REPOSITORY_NAMES;
@@ -308,6 +316,20 @@ impl From<LocatedBuildIdent> for RangeIdent {
     }
 }
 
+impl From<LocatedVersionIdent> for RangeIdent {
+    fn from(ident: LocatedVersionIdent) -> Self {
+        let (repository_name, version_ident) = ident.into_inner();
+        let (name, version) = version_ident.into_inner();
+        Self {
+            repository_name: Some(repository_name),
+            name,
+            version: version.into(),
+            components: BTreeSet::default(),
+            build: None,
+        }
+    }
+}
+
 impl From<AnyIdent> for RangeIdent {
     fn from(ident: AnyIdent) -> Self {
         let (version_ident, build) = ident.into_inner();
@@ -357,10 +379,7 @@ impl FromStr for RangeIdent {
             nom_supreme::error::ErrorTree<_>,
         >(&KNOWN_REPOSITORY_NAMES))(s)
         .map(|(_, ident)| ident)
-        .map_err(|err| match err {
-            nom::Err::Error(e) | nom::Err::Failure(e) => crate::ident::Error::String(e.to_string()),
-            nom::Err::Incomplete(_) => unreachable!(),
-        })
+        .map_err(|err| crate::ident::parsing::invalid_ident_error(s, err))
     }
 }
 
@@ -424,3 +443,37 @@ pub fn parse_ident_range_list<S: AsRef<str>>(source: S) -> Result<Vec<RangeIdent
         source.as_ref(),
     )
 }
+
+/// Parse a list of package identifiers that each specify a range
+/// of versions, as read from a file.
+///
+/// Identifiers can be separated by commas or newlines. Blank lines
+/// are ignored, as is anything following a `#` on a line. Any error
+/// identifies the line number where it occurred.
+///
+/// ```txt
+/// # build dependencies
+/// python/3.9,cmake
+/// zlib/1.2.11  # pinned for abi compatibility
+/// ```
+pub fn parse_ident_range_lines<S: AsRef<str>>(source: S) -> Result<Vec<RangeIdent>> {
+    let mut idents = Vec::new();
+    for (index, line) in source.as_ref().lines().enumerate() {
+        let line_content = match line.split_once('#') {
+            Some((content, _comment)) => content,
+            None => line,
+        }
+        .trim();
+        if line_content.is_empty() {
+            continue;
+        }
+        let parsed = parse_ident_range_list(line_content).map_err(|err| {
+            crate::ident::Error::InvalidIdentListLine {
+                line: index + 1,
+                source: Box::new(err),
+            }
+        })?;
+        idents.extend(parsed);
+    }
+    Ok(idents)
+}
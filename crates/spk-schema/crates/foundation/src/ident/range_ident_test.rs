@@ -3,10 +3,12 @@
 // https://github.com/spkenv/spk
 
 use std::collections::BTreeSet;
+use std::str::FromStr;
 
 use rstest::rstest;
 
-use super::parse_ident_range;
+use super::{RangeIdent, parse_ident_range, parse_ident_range_lines};
+use crate::ident::{LocatedBuildIdent, LocatedVersionIdent};
 use crate::ident_component::Component;
 use crate::version_range::RestrictMode;
 
@@ -43,3 +45,58 @@ fn test_range_ident_restrict_components() {
         .unwrap();
     assert_eq!(first.components, expected.components);
 }
+
+#[rstest]
+fn test_parse_ident_range_lines() {
+    let source = "# build dependencies\n\
+                  python/3.9,cmake\n\
+                  \n\
+                  zlib/1.2.11  # pinned\n";
+    let names: Vec<_> = parse_ident_range_lines(source)
+        .unwrap()
+        .into_iter()
+        .map(|ident| ident.name.to_string())
+        .collect();
+    assert_eq!(names, ["python", "cmake", "zlib"]);
+}
+
+#[rstest]
+fn test_parse_ident_range_lines_error_line() {
+    let source = "python\n# comment\nzlib/1.2.11 extra\n";
+    let err = parse_ident_range_lines(source).expect_err("should fail to parse");
+    let crate::ident::Error::InvalidIdentListLine { line, .. } = err else {
+        panic!("expected an error with a line number, got: {err:?}");
+    };
+    assert_eq!(line, 3);
+}
+
+#[rstest]
+fn test_located_build_ident_round_trip() {
+    let source = "my-repo/my-pkg/1.0.0/3I42H3S6";
+    let ident = LocatedBuildIdent::from_str(source).unwrap();
+    assert_eq!(ident.repository_name().as_str(), "my-repo");
+    assert_eq!(ident.to_string(), source);
+
+    let range = RangeIdent::from(ident.clone());
+    assert_eq!(range.to_string(), source);
+    assert_eq!(LocatedBuildIdent::try_from(range).unwrap(), ident);
+
+    let yaml = serde_yaml::to_string(&ident).unwrap();
+    let deserialized: LocatedBuildIdent = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized, ident);
+}
+
+#[rstest]
+fn test_located_version_ident_round_trip() {
+    let source = "my-repo/my-pkg/1.0.0";
+    let ident = LocatedVersionIdent::from_str(source).unwrap();
+    assert_eq!(ident.repository_name().as_str(), "my-repo");
+    assert_eq!(RangeIdent::from(ident).to_string(), source);
+}
+
+#[rstest]
+#[case("my-pkg/1.0.0/3I42H3S6")]
+#[case("my-repo/my-pkg/1.0.0")]
+fn test_located_build_ident_requires_repo_and_build(#[case] source: &str) {
+    LocatedBuildIdent::from_str(source).expect_err("should not be a located build ident");
+}
@@ -60,11 +60,25 @@ macro_rules! opt_name {
 #[error("Invalid name: {message}")]
 pub struct InvalidNameError {
     pub message: String,
+    /// Advice on how to correct the name, if any
+    #[help]
+    pub help: Option<String>,
 }
 
 impl InvalidNameError {
     pub fn new_error(msg: String) -> Error {
-        Error::InvalidNameError(Self { message: msg })
+        Error::InvalidNameError(Self {
+            message: msg,
+            help: None,
+        })
+    }
+
+    /// Create an error that includes advice on how to correct the name
+    pub fn new_error_with_help(msg: String, help: String) -> Error {
+        Error::InvalidNameError(Self {
+            message: msg,
+            help: Some(help),
+        })
     }
 }
 
@@ -132,10 +146,13 @@ fn validate_pkg_name<S: AsRef<str>>(name: S) -> Result<()> {
         )));
     }
     if let Some('-') = name.as_ref().chars().next() {
-        return Err(InvalidNameError::new_error(format!(
-            "Invalid package name, must begin with a letter and not a hyphen, got {}",
-            name.as_ref()
-        )));
+        return Err(InvalidNameError::new_error_with_help(
+            format!(
+                "Invalid package name, must begin with a letter and not a hyphen, got {}",
+                name.as_ref()
+            ),
+            pkg_name_help(name.as_ref()),
+        ));
     }
     let index = validate_source_str(&name, is_valid_pkg_name_char);
     if index > -1 {
@@ -147,9 +164,10 @@ fn validate_pkg_name<S: AsRef<str>>(name: S) -> Result<()> {
             name.chars().nth(index).unwrap(),
             &name[(index + 1)..]
         );
-        Err(InvalidNameError::new_error(format!(
-            "Invalid package name at pos {index}: {err_str}"
-        )))
+        Err(InvalidNameError::new_error_with_help(
+            format!("Invalid package name at pos {index}: {err_str}"),
+            pkg_name_help(name),
+        ))
     } else {
         Ok(())
     }
@@ -159,6 +177,36 @@ fn is_valid_pkg_name_char(c: char) -> bool {
     c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'
 }
 
+/// Explain how an invalid package name can be corrected, suggesting
+/// a normalized name when a valid one can be derived from it.
+fn pkg_name_help(name: &str) -> String {
+    let normalized: String = name
+        .chars()
+        .map(|c| match c {
+            '_' | '.' | ' ' => '-',
+            c => c.to_ascii_lowercase(),
+        })
+        .filter(|c| is_valid_pkg_name_char(*c))
+        .collect();
+    let normalized = normalized.trim_start_matches('-');
+    if normalized != name && (PkgName::MIN_LEN..=PkgName::MAX_LEN).contains(&normalized.len()) {
+        return format!(
+            "Package names may only contain lowercase letters, digits and hyphens, try '{normalized}' instead"
+        );
+    }
+    match name.chars().find(|c| !is_valid_pkg_name_char(*c)) {
+        Some(c) if c.is_ascii_uppercase() => format!(
+            "Package names cannot contain uppercase letters, use '{}' instead of '{c}'",
+            c.to_ascii_lowercase()
+        ),
+        Some('_') => "Package names cannot contain underscores, use hyphens instead".to_string(),
+        Some(c) => format!(
+            "Package names cannot contain '{c}', only lowercase letters, digits and hyphens are allowed"
+        ),
+        None => "Package names must begin with a letter or digit".to_string(),
+    }
+}
+
 impl OptName {
     const SEP: char = '.';
     // all valid package names are assumed to/must be
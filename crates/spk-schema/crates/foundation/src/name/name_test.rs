@@ -22,6 +22,23 @@ fn test_pkg_validation(#[case] input: &str) {
     super::validate_opt_name(input).expect("all valid package names should be valid option names");
 }
 
+#[rstest]
+#[case("upperCase", "try 'uppercase' instead")]
+#[case("has_underscores", "try 'has-underscores' instead")]
+#[case("-leading", "try 'leading' instead")]
+#[case("My Package!", "try 'my-package' instead")]
+#[case("!!", "cannot contain '!'")]
+fn test_pkg_validation_help(#[case] input: &str, #[case] expected: &str) {
+    let Err(super::Error::InvalidNameError(err)) = super::validate_pkg_name(input) else {
+        panic!("expected '{input}' to be an invalid package name");
+    };
+    let help = err.help.expect("invalid package names should include help");
+    assert!(
+        help.contains(expected),
+        "expected help for '{input}' to contain \"{expected}\", got: {help}"
+    );
+}
+
 #[rstest]
 #[case("lowercase")]
 #[case("with-dashes")]
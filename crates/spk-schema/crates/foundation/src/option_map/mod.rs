@@ -29,12 +29,17 @@ mod option_map_test;
 
 /// Create a set of options from a simple mapping.
 ///
+/// Values can be any expression that implements [`ToString`].
+///
 /// ```
 /// # #[macro_use] extern crate spk_schema_foundation;
 /// # fn main() {
+/// let version = spk_schema_foundation::version::Version::new(3, 7, 0);
 /// option_map!{
 ///   "debug" => "on",
-///   "python.abi" => "cp37m"
+///   "python.abi" => "cp37m",
+///   "python" => version,
+///   "jobs" => 4,
 /// };
 /// # }
 /// ```
@@ -51,7 +56,7 @@ macro_rules! option_map {
         let mut opts = OptionMap::default();
         $(opts.insert(
             OptNameBuf::try_from($k).expect("invalid option name"),
-            $v.into()
+            ::std::string::ToString::to_string(&$v)
         );)*
         opts
     }};
@@ -238,6 +243,22 @@ impl OptionMap {
         options
     }
 
+    /// A digest of these options that is suitable for use as a cache key.
+    ///
+    /// The digest depends only on the names and values of the options,
+    /// not the order that they were added, and is the same across
+    /// processes and versions of spk.
+    pub fn stable_digest(&self) -> String {
+        let mut hasher = ring::digest::Context::new(&ring::digest::SHA256);
+        for (name, value) in self.iter().sorted_unstable_by_key(|(name, _)| *name) {
+            hasher.update(name.as_bytes());
+            hasher.update(b"=");
+            hasher.update(value.as_bytes());
+            hasher.update(&[0]);
+        }
+        data_encoding::BASE32_NOPAD.encode(hasher.finish().as_ref())
+    }
+
     /// Remove option-related values from the given environment variables
     pub fn clean_environment(env: &mut HashMap<String, String>) {
         let to_remove = env
@@ -25,6 +25,55 @@ fn test_package_options() {
     );
 }
 
+#[rstest]
+fn test_option_map_macro_computed_values() {
+    let version = crate::version::Version::new(1, 2, 3);
+    let name = String::from("world");
+    let options = option_map! {
+        "version" => version,
+        "message" => format!("hello, {name}"),
+        "name" => name,
+        "jobs" => 4,
+    };
+    let mut expected = OptionMap::default();
+    expected.insert(opt_name!("version").to_owned(), "1.2.3".into());
+    expected.insert(opt_name!("message").to_owned(), "hello, world".into());
+    expected.insert(opt_name!("name").to_owned(), "world".into());
+    expected.insert(opt_name!("jobs").to_owned(), "4".into());
+    assert_eq!(options, expected);
+}
+
+#[rstest]
+fn test_option_map_stable_digest() {
+    let mut first = OptionMap::default();
+    first.insert(opt_name!("debug").to_owned(), "on".into());
+    first.insert(opt_name!("python.abi").to_owned(), "cp37m".into());
+    let mut second = OptionMap::default();
+    second.insert(opt_name!("python.abi").to_owned(), "cp37m".into());
+    second.insert(opt_name!("debug").to_owned(), "on".into());
+    assert_eq!(
+        first.stable_digest(),
+        second.stable_digest(),
+        "insertion order should not change the digest"
+    );
+
+    assert_ne!(
+        first.stable_digest(),
+        option_map! {"debug" => "off", "python.abi" => "cp37m"}.stable_digest(),
+        "different values should change the digest"
+    );
+    assert_ne!(
+        option_map! {"a" => "b=c"}.stable_digest(),
+        option_map! {"a=b" => "c"}.stable_digest(),
+        "names and values should not run together"
+    );
+    assert_eq!(
+        first.stable_digest(),
+        "AZLYCSJRC7ENO7GYPL52B5YMW6YEZXOWCTUGF4CLGYQD4J7KBPSQ",
+        "the digest should not change between versions"
+    );
+}
+
 #[rstest]
 fn test_option_map_deserialize_scalar() {
     let opts: OptionMap =
@@ -56,3 +56,37 @@ fn test_render(#[case] compat: &str, #[case] v: &str, #[case] expected: &str) {
         .render(&parse_version(v).unwrap());
     assert_eq!(rendered, expected);
 }
+
+#[rstest]
+#[case("none", "x.x.x")]
+#[case("any", "ab.ab.ab")]
+fn test_parse_compat_shorthand(#[case] shorthand: &str, #[case] expanded: &str) {
+    let compat = parse_compat(shorthand).unwrap();
+    assert_eq!(compat, parse_compat(expanded).unwrap());
+    assert_eq!(
+        compat.to_string(),
+        expanded,
+        "shorthand should be written out in full"
+    );
+}
+
+#[rstest]
+fn test_compat_shorthand_decisions(
+    #[values(("none", "x.x.x"), ("any", "ab.ab.ab"))] compat: (&str, &str),
+    #[values("1.0.0", "1.2.3", "1.2.3-pre.1", "1.2.3+r.1")] a: &str,
+    #[values("0.9.0", "1.0.0", "1.2.4", "1.3.0", "2.0.0", "1.2.3.1")] b: &str,
+) {
+    let (shorthand, expanded) = compat;
+    let shorthand = parse_compat(shorthand).unwrap();
+    let expanded = parse_compat(expanded).unwrap();
+    let a = parse_version(a).unwrap();
+    let b = parse_version(b).unwrap();
+    assert_eq!(
+        shorthand.is_api_compatible(&a, &b).is_ok(),
+        expanded.is_api_compatible(&a, &b).is_ok(),
+    );
+    assert_eq!(
+        shorthand.is_binary_compatible(&a, &b).is_ok(),
+        expanded.is_binary_compatible(&a, &b).is_ok(),
+    );
+}
@@ -40,10 +40,12 @@ use crate::{IsDefault, version};
 #[path = "./compat_test.rs"]
 mod compat_test;
 
+pub const ANY_COMPAT_SHORTHAND: &str = "any";
 pub const API_COMPAT_STR: &str = "a";
 pub const API_STR: &str = "API";
 pub const BINARY_COMPAT_STR: &str = "b";
 pub const BINARY_STR: &str = "Binary";
+pub const NONE_COMPAT_SHORTHAND: &str = "none";
 pub const NONE_COMPAT_STR: &str = "x";
 pub const POST_DELIMITER_STR: &str = "+";
 pub const PRE_DELIMITER_STR: &str = "-";
@@ -609,6 +611,20 @@ impl FromStr for Compat {
         use nom::multi::{fold_many0, many1, separated_list1};
         use nom::sequence::preceded;
 
+        // The shorthands expand to a rule for each of the three
+        // positions, and are always written back out in full
+        match value {
+            NONE_COMPAT_SHORTHAND => {
+                let none = CompatRuleSet::single(CompatRule::None);
+                return Ok(Compat::triple(none.clone(), none.clone(), none));
+            }
+            ANY_COMPAT_SHORTHAND => {
+                let any = CompatRuleSet::double(CompatRule::API, CompatRule::Binary);
+                return Ok(Compat::triple(any.clone(), any.clone(), any));
+            }
+            _ => {}
+        }
+
         fn compat_none(s: &str) -> IResult<&str, CompatRule> {
             map(tag(NONE_COMPAT_STR), |_| CompatRule::None)(s)
         }
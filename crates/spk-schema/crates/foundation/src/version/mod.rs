@@ -42,7 +42,7 @@ pub use compat::{
 };
 pub use error::{Error, Result};
 use itertools::Itertools;
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use relative_path::RelativePathBuf;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
@@ -80,11 +80,35 @@ pub fn get_version_position_label(pos: usize) -> &'static str {
 #[error("Invalid version: {message}")]
 pub struct InvalidVersionError {
     pub message: String,
+    /// The version string that was being parsed, if known
+    #[source_code]
+    pub version: Option<String>,
+    /// The location of the problem within the version string
+    #[label("{message}")]
+    pub span: Option<SourceSpan>,
 }
 
 impl InvalidVersionError {
     pub fn new_error(msg: String) -> Error {
-        Error::InvalidVersionError(Self { message: msg })
+        Error::InvalidVersionError(Self {
+            message: msg,
+            version: None,
+            span: None,
+        })
+    }
+
+    /// Create an error that points to the invalid portion of a version string.
+    pub fn new_error_at<S: Into<SourceSpan>>(msg: String, version: &str, span: S) -> Error {
+        Error::InvalidVersionError(Self {
+            message: msg,
+            version: Some(version.to_owned()),
+            span: Some(span.into()),
+        })
+    }
+
+    /// The byte offset of the problem within the version string, if known.
+    pub fn offset(&self) -> Option<usize> {
+        self.span.map(|span| span.offset())
     }
 }
 
@@ -459,6 +483,39 @@ impl Version {
         }
     }
 
+    /// The next version that is still compatible with this one under
+    /// the given rule and the default compat of `x.a.b`.
+    ///
+    /// The most significant digit that is allowed to change is
+    /// increased and the digits after it are reset to zero, so an API
+    /// compatible bump of `1.2.3` is `1.3.0` and a binary compatible
+    /// bump is `1.2.4`. [`CompatRule::None`] makes no promise of
+    /// compatibility and bumps the major version. Any pre- or
+    /// post-release tags are dropped.
+    pub fn next_in_compat_range(&self, required: CompatRule) -> Version {
+        let compat = Compat::default();
+        // missing digits are treated as zeros, otherwise they would be
+        // seen as compatible with any value
+        let mut base = Version::from_parts(self.parts.iter().copied());
+        base.parts
+            .parts
+            .resize(self.parts.len().max(Self::MINIMUM_PARTS_FOR_DISPLAY), 0);
+        let bump = |position: usize| {
+            let mut parts = base.parts.parts.clone();
+            parts[position] += 1;
+            parts[position + 1..].fill(0);
+            Version::from_parts(parts)
+        };
+        (0..Self::MINIMUM_PARTS_FOR_DISPLAY)
+            .map(bump)
+            .find(|next| match required {
+                CompatRule::None => true,
+                CompatRule::API => compat.is_api_compatible(&base, next).is_ok(),
+                CompatRule::Binary => compat.is_binary_compatible(&base, next).is_ok(),
+            })
+            .unwrap_or_else(|| bump(Self::MINIMUM_PARTS_FOR_DISPLAY - 1))
+    }
+
     /// The base integer portion of this version as a string.
     ///
     /// The version number will be normalized to at least three parts.
@@ -680,28 +737,55 @@ pub fn parse_version<S: AsRef<str>>(version: S) -> Result<Version> {
         return Ok(Version::default());
     }
 
-    let (version, post) = break_string(version, "+");
+    let source = version;
+    let (version, post) = break_string(source, "+");
+    let post_offset = version.len() + 1;
     let (version, pre) = break_string(version, "-");
+    let pre_offset = version.len() + 1;
 
     let str_parts = version.split(VERSION_SEP);
     let mut parts = Vec::new();
+    let mut offset = 0;
     for (i, p) in str_parts.enumerate() {
         match p.parse() {
             Ok(p) => parts.push(p),
             Err(_) => {
-                return Err(InvalidVersionError::new_error(format!(
-                    "Version must be a sequence of integers, got '{p}' in position {i} [{version}]"
-                )));
+                // point at the first character that is not a digit, or
+                // at the start of the part if it was empty or too large
+                let bad = p.find(|c: char| !c.is_ascii_digit()).unwrap_or_default();
+                let len = p[bad..].chars().next().map(char::len_utf8).unwrap_or(0);
+                return Err(InvalidVersionError::new_error_at(
+                    format!(
+                        "Version must be a sequence of integers, got '{p}' in position {i} [{version}]"
+                    ),
+                    source,
+                    (offset + bad, len),
+                ));
             }
         }
+        offset += p.len() + VERSION_SEP.len();
     }
 
     let mut v = Version::from_parts(parts);
-    v.pre = parse_tag_set(pre)?;
-    v.post = parse_tag_set(post)?;
+    v.pre = parse_tag_set(pre).map_err(|err| locate_tag_set_error(err, source, pre_offset, pre))?;
+    v.post =
+        parse_tag_set(post).map_err(|err| locate_tag_set_error(err, source, post_offset, post))?;
     Ok(v)
 }
 
+/// Point an error from parsing one of the tag sets of a version at
+/// the location of that tag set within the whole version string.
+fn locate_tag_set_error(err: Error, version: &str, offset: usize, tags: &str) -> Error {
+    match err {
+        Error::InvalidVersionError(InvalidVersionError {
+            message,
+            span: None,
+            ..
+        }) => InvalidVersionError::new_error_at(message, version, (offset, tags.len())),
+        err => err,
+    }
+}
+
 impl Serialize for Version {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -6,7 +6,7 @@ use std::cmp::{Ord, Ordering};
 
 use rstest::rstest;
 
-use super::{TagSet, Version, parse_version};
+use super::{Compat, CompatRule, TagSet, Version, parse_version};
 
 #[rstest]
 fn test_version_nonzero() {
@@ -75,6 +75,18 @@ fn test_parse_version_invalid(#[case] string: &str) {
     }
 }
 
+#[rstest]
+#[case("1.a.0", 2)]
+#[case("1.20x.0", 4)]
+#[case("1.2.5-alpha.a", 6)]
+#[case("1.2.5-alpha.1+r", 14)]
+fn test_parse_version_invalid_position(#[case] string: &str, #[case] expected: usize) {
+    let Err(super::Error::InvalidVersionError(err)) = parse_version(string) else {
+        panic!("expected InvalidVersionError for '{string}'");
+    };
+    assert_eq!(err.offset(), Some(expected));
+}
+
 #[rstest]
 #[case("1.0.0")]
 #[case("0.0.0")]
@@ -108,3 +120,36 @@ fn test_parse_version_clone(#[case] string: &str) {
 fn test_tag_set_order(#[case] a: TagSet, #[case] b: TagSet, #[case] expected: Ordering) {
     assert_eq!(a.cmp(&b), expected);
 }
+
+#[rstest]
+#[case("1.2.3", CompatRule::None, "2.0.0")]
+#[case("1.2.3", CompatRule::API, "1.3.0")]
+#[case("1.2.3", CompatRule::Binary, "1.2.4")]
+#[case("1.2.3.4", CompatRule::Binary, "1.2.4.0")]
+#[case("1", CompatRule::API, "1.1.0")]
+#[case("1", CompatRule::Binary, "1.0.1")]
+#[case("1.2.3-pre.1", CompatRule::API, "1.3.0")]
+#[case("1.2.3-pre.1", CompatRule::Binary, "1.2.4")]
+#[case("1.2.3+r.1", CompatRule::Binary, "1.2.4")]
+fn test_next_in_compat_range(
+    #[case] version: &str,
+    #[case] required: CompatRule,
+    #[case] expected: &str,
+) {
+    let version = parse_version(version).unwrap();
+    let next = version.next_in_compat_range(required);
+    assert_eq!(next, parse_version(expected).unwrap());
+    assert!(next.pre.is_empty() && next.post.is_empty());
+    assert!(next > version, "{next} should be newer than {version}");
+
+    let compat = Compat::default();
+    let result = match required {
+        CompatRule::None | CompatRule::API => compat.is_api_compatible(&version, &next),
+        CompatRule::Binary => compat.is_binary_compatible(&version, &next),
+    };
+    assert_eq!(
+        result.is_ok(),
+        required != CompatRule::None,
+        "{next} compatibility with {version} == {result:?}"
+    );
+}
@@ -304,6 +304,7 @@ impl<T: Ranged> Ranged for &T {
 #[enum_dispatch(Ranged)]
 pub enum VersionRange {
     Compat(CompatRange),
+    CompatibleRelease(CompatibleReleaseRange),
     DoubleEquals(DoubleEqualsVersion),
     DoubleNotEquals(DoubleNotEqualsVersion),
     Equals(EqualsVersion),
@@ -322,6 +323,7 @@ impl Display for VersionRange {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             VersionRange::Compat(vr) => vr.fmt(f),
+            VersionRange::CompatibleRelease(vr) => vr.fmt(f),
             VersionRange::DoubleEquals(vr) => vr.fmt(f),
             VersionRange::DoubleNotEquals(vr) => vr.fmt(f),
             VersionRange::Equals(vr) => vr.fmt(f),
@@ -377,6 +379,30 @@ impl<T: Ranged> From<&T> for VersionRange {
     }
 }
 
+impl VersionRange {
+    /// Return the range of versions that are valid in both this range
+    /// and another.
+    ///
+    /// Rules that are made redundant by the other range are dropped, so
+    /// `>=1.0` and `>=2.0` narrow to just `>=2.0`. When both ranges are
+    /// still needed, as with `>=1.0` and `<2.0`, they are returned
+    /// together as a [`VersionFilter`]. Returns `None` if no version
+    /// can be valid in both ranges, as with `>=2.0` and `<1.0`.
+    pub fn intersect(&self, other: &VersionRange) -> Option<VersionRange> {
+        let mut filter = VersionFilter::new(self.rules());
+        if !filter
+            .restrict(other, RestrictMode::RequireIntersectingRanges)
+            .is_ok()
+        {
+            return None;
+        }
+        if filter.len() == 1 {
+            return filter.rules.into_iter().next();
+        }
+        Some(VersionRange::Filter(filter))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct SemverRange {
     minimum: Version,
@@ -643,6 +669,62 @@ impl Display for LowestSpecifiedRange {
     }
 }
 
+/// A compatible release range, as in `~=1.4.2`.
+///
+/// This follows the meaning of the same operator in python packaging,
+/// and matches the same versions as a [`LowestSpecifiedRange`]. The
+/// last specified component of the version may increase, so
+/// `~=1.4.2` is `>=1.4.2,<1.5.0` and `~=2.2` is `>=2.2,<3.0`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct CompatibleReleaseRange {
+    range: LowestSpecifiedRange,
+}
+
+impl CompatibleReleaseRange {
+    pub fn new(base: Version) -> Self {
+        Self {
+            range: LowestSpecifiedRange::new(base),
+        }
+    }
+
+    pub fn version(&self) -> Cow<'_, Version> {
+        self.range.version()
+    }
+}
+
+impl TryFrom<Version> for CompatibleReleaseRange {
+    type Error = Error;
+
+    fn try_from(base: Version) -> Result<Self> {
+        Ok(Self {
+            range: LowestSpecifiedRange::try_from(base)?,
+        })
+    }
+}
+
+impl Ranged for CompatibleReleaseRange {
+    fn greater_or_equal_to(&self) -> Option<Version> {
+        self.range.greater_or_equal_to()
+    }
+
+    fn less_than(&self) -> Option<Version> {
+        self.range.less_than()
+    }
+}
+
+impl Display for CompatibleReleaseRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let base_str = self.range.base.parts[..self.range.specified]
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec()
+            .join(VERSION_SEP);
+        f.write_str("~=")?;
+        f.write_str(&base_str)?;
+        self.range.base.format_tags(f)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct GreaterThanRange {
     bound: Version,
@@ -18,6 +18,7 @@ use crate::version::CompatRule;
 use crate::version::parsing::{version, version_str};
 use crate::version_range::{
     CompatRange,
+    CompatibleReleaseRange,
     DoubleEqualsVersion,
     DoubleNotEqualsVersion,
     EqualsVersion,
@@ -145,6 +146,9 @@ where
                     preceded(char('^'), cut(version_str)),
                     SemverRange::new_version_range,
                 ),
+                map_res(preceded(tag("~="), cut(version)), |v| {
+                    CompatibleReleaseRange::try_from(v).map(VersionRange::CompatibleRelease)
+                }),
                 map_res(preceded(char('~'), cut(version)), |v| {
                     LowestSpecifiedRange::try_from(v).map(VersionRange::LowestSpecified)
                 }),
@@ -15,7 +15,7 @@ pub struct Error {
     message: String,
     tpl: String,
     label: Option<String>,
-    location: miette::SourceOffset,
+    spans: Vec<miette::SourceSpan>,
     // kept around to determine the original source
     // of this error in the case where a template position
     // and error message was not discerned
@@ -43,7 +43,9 @@ impl miette::Diagnostic for Error {
     fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
         let label = self.label.as_ref()?;
         Some(Box::new(
-            [miette::LabeledSpan::at(self.location, label)].into_iter(),
+            self.spans
+                .iter()
+                .map(|span| miette::LabeledSpan::at(*span, label)),
         ))
     }
 
@@ -93,13 +95,56 @@ impl Error {
                 original = None;
             }
         }
-        let location = miette::SourceOffset::from_location(&tpl, line, column);
+        let mut spans = vec![miette::SourceOffset::from_location(&tpl, line, column).into()];
+        if label.is_none()
+            && let Some((filter, reason)) = original.as_deref().and_then(find_failed_filter)
+        {
+            // render errors do not include a position, but the filter
+            // that failed can still be located in the template
+            let filter_spans = find_filter_calls(&tpl, &filter);
+            if !filter_spans.is_empty() {
+                spans = filter_spans;
+                label = Some(reason);
+            }
+        }
         Error {
             message,
             tpl,
             label,
-            location,
+            spans,
             original,
         }
     }
 }
+
+/// Find the name of the filter that caused an error, along
+/// with the reason that it gave for failing.
+fn find_failed_filter(err: &tera::Error) -> Option<(String, String)> {
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(err) = current {
+        if let Some(tera::Error {
+            kind: tera::ErrorKind::CallFilter(name),
+            ..
+        }) = err.downcast_ref::<tera::Error>()
+        {
+            let reason = err
+                .source()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| err.to_string());
+            return Some((name.clone(), reason));
+        }
+        current = err.source();
+    }
+    None
+}
+
+/// Locate every place that the named filter is applied in a template.
+fn find_filter_calls(tpl: &str, filter: &str) -> Vec<miette::SourceSpan> {
+    let Ok(re) = Regex::new(&format!(r"\|\s*({})\b", regex::escape(filter))) else {
+        return Vec::new();
+    };
+    re.captures_iter(tpl)
+        .filter_map(|m| m.get(1))
+        .map(|m| miette::SourceSpan::new(m.start().into(), m.len()))
+        .collect()
+}
@@ -20,3 +20,31 @@ fn test_error_position_extraction() {
         "should capture original parsing error location"
     );
 }
+
+#[rstest]
+fn test_error_filter_position() {
+    // errors raised by a filter while rendering should point
+    // to where the filter is used in the template
+
+    static TPL: &str = r#"pkg: my-pkg/1.0.0
+build:
+  options:
+    - var: python
+  script:
+    - echo building
+{% if "3.9" | compare_version(op=">= not-a-version") %}
+    - echo new python
+{% endif %}
+"#;
+    let err = crate::render_template("test", TPL, &json!({}))
+        .expect_err("expected template render to fail");
+    let labels = err.labels().expect("labels").collect::<Vec<_>>();
+    assert_eq!(labels.len(), 1, "should label the failed filter");
+    let line = TPL[..labels[0].offset()].lines().count();
+    assert_eq!(line, 7, "should point to the line with the failed filter");
+    assert_eq!(
+        labels[0].label(),
+        Some("invalid comparison string"),
+        "should explain why the filter failed"
+    );
+}
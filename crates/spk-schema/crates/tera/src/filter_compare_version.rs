@@ -19,7 +19,10 @@ impl CompareVersion {
     pub const FILTER_NAME: &'static str = "compare_version";
     /// The comparison operation to perform
     pub const ARG_OPERATOR: &'static str = "op";
-    /// The version to compare with, if not part of the operator string
+    /// The version to compare with, if not part of the operator string.
+    ///
+    /// This can be a literal or a value from the template data, and
+    /// is either a string or a number.
     pub const ARG_RHS: &'static str = "rhs";
     pub const ARGS: &'static [&'static str] = &[Self::ARG_OPERATOR, Self::ARG_RHS];
 }
@@ -44,16 +47,28 @@ impl tera::Filter for CompareVersion {
                 Self::ARG_OPERATOR
             )));
         };
-        if let Some(rhs) = args.get(Self::ARG_RHS) {
-            if rhs.is_array() || rhs.is_object() {
+        match args.get(Self::ARG_RHS) {
+            None => {}
+            // strings are appended as-is, rather than in their quoted
+            // json form, so that a version stored in the template data
+            // can be used directly
+            Some(Value::String(rhs)) => range_str.push_str(rhs),
+            Some(Value::Number(rhs)) => range_str.push_str(&rhs.to_string()),
+            Some(Value::Null) => {
                 return Err(tera::Error::msg(format!(
-                    "{}: {} argument expected a scalar value, got {rhs:?}",
+                    "{}: {} argument has no value, was the variable it refers to left unset?",
                     Self::FILTER_NAME,
                     Self::ARG_RHS
                 )));
             }
-            range_str.push_str(&rhs.to_string());
-        };
+            Some(rhs) => {
+                return Err(tera::Error::msg(format!(
+                    "{}: {} argument expected a string or number value, got {rhs:?}",
+                    Self::FILTER_NAME,
+                    Self::ARG_RHS
+                )));
+            }
+        }
 
         match args.len() {
             1 => {}
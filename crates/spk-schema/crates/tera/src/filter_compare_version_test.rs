@@ -33,3 +33,41 @@ sources:
         crate::render_template("test", TPL, &options).expect("template should not fail to render");
     assert_eq!(rendered, EXPECTED);
 }
+
+#[rstest]
+#[case::literal(r#"{{ "3.9" | compare_version(op=">=3.7") }}"#)]
+#[case::literal_rhs(r#"{{ "3.9" | compare_version(op=">=", rhs="3.7") }}"#)]
+#[case::string_var(r#"{{ "3.9" | compare_version(op=">=", rhs=opt.python_abi_min) }}"#)]
+#[case::number_var(r#"{{ "3.9" | compare_version(op=">=", rhs=opt.python_min) }}"#)]
+#[case::concatenated(r#"{{ "3.9" | compare_version(op=">=" ~ opt.python_abi_min) }}"#)]
+fn test_compare_version_rhs(#[case] tpl: &str) {
+    // the version to compare with can come from the template data,
+    // either as a string or as a number
+
+    let options = json!({"opt": {"python_abi_min": "3.7", "python_min": 3.7}});
+    let rendered =
+        crate::render_template("test", tpl, &options).expect("template should not fail to render");
+    assert_eq!(rendered, "true");
+}
+
+#[rstest]
+#[case::missing("opt.python_abi_min", "opt.python_abi_min")]
+#[case::null("opt.python_unset", "rhs argument has no value")]
+fn test_compare_version_rhs_missing(#[case] var: &str, #[case] expected: &str) {
+    let options = json!({"opt": {"python_unset": null}});
+    let tpl = format!(r#"{{{{ "3.9" | compare_version(op=">=", rhs={var}) }}}}"#);
+    let err = crate::render_template("test", tpl, &options)
+        .expect_err("template should fail when the compared version is missing");
+
+    let mut messages = vec![err.to_string()];
+    let mut source = std::error::Error::source(&err);
+    while let Some(err) = source {
+        messages.push(err.to_string());
+        source = err.source();
+    }
+    let messages = messages.join("\n");
+    assert!(
+        messages.contains(expected),
+        "error should explain which value is missing ({expected:?}), got: {messages}"
+    );
+}
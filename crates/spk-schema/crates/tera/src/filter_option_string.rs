@@ -0,0 +1,76 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use serde_json::Value;
+
+#[cfg(test)]
+#[path = "./filter_option_string_test.rs"]
+mod filter_option_string_test;
+
+/// Formats a set of options as a sorted `name=value,name=value` string
+#[derive(Clone, Copy)]
+pub struct OptionString;
+
+impl OptionString {
+    pub const FILTER_NAME: &'static str = "option_string";
+    /// Include options with an empty value, which are skipped by default
+    pub const ARG_KEEP_EMPTY: &'static str = "keep_empty";
+    pub const ARGS: &'static [&'static str] = &[Self::ARG_KEEP_EMPTY];
+}
+
+impl tera::Filter for OptionString {
+    fn filter(
+        &self,
+        value: &Value,
+        args: &std::collections::HashMap<String, Value>,
+    ) -> tera::Result<Value> {
+        let keep_empty = match args.get(Self::ARG_KEEP_EMPTY) {
+            None => false,
+            Some(Value::Bool(keep_empty)) => *keep_empty,
+            Some(keep_empty) => {
+                return Err(tera::Error::msg(format!(
+                    "{}: {} argument expected a boolean value, got {keep_empty:?}",
+                    Self::FILTER_NAME,
+                    Self::ARG_KEEP_EMPTY
+                )));
+            }
+        };
+
+        if args.len() > Self::ARGS.len() {
+            return Err(tera::Error::msg(format!(
+                "{}: one or more unsupported arguments provided, supported args: {:?}",
+                Self::FILTER_NAME,
+                Self::ARGS
+            )));
+        }
+
+        let Value::Object(options) = value else {
+            return Err(tera::Error::msg(format!(
+                "{}: expected object input, got: {value:?}",
+                Self::FILTER_NAME
+            )));
+        };
+
+        let mut pairs = Vec::with_capacity(options.len());
+        for (name, value) in options {
+            let value_str = match value {
+                Value::Null => String::new(),
+                Value::Bool(b) => b.to_string(),
+                Value::Number(n) => n.to_string(),
+                Value::String(s) => s.clone(),
+                // there is no command line form for these, but they
+                // are kept as compact json rather than being dropped
+                Value::Array(_) | Value::Object(_) => value.to_string(),
+            };
+            if value_str.is_empty() && !keep_empty {
+                continue;
+            }
+            pairs.push(format!("{name}={value_str}"));
+        }
+        // the map may preserve insertion order, depending on the
+        // features enabled for serde_json, so always sort the output
+        pairs.sort();
+        Ok(pairs.join(",").into())
+    }
+}
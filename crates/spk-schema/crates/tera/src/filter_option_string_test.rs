@@ -0,0 +1,53 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use serde_json::json;
+
+#[rstest]
+#[case::sorted(
+    json!({"opt": {"python": "3.9", "debug": "off", "arch": "x86_64"}}),
+    "{{ opt | option_string }}",
+    "arch=x86_64,debug=off,python=3.9"
+)]
+#[case::skip_empty(
+    json!({"opt": {"python": "3.9", "empty": "", "null": null}}),
+    "{{ opt | option_string }}",
+    "python=3.9"
+)]
+#[case::keep_empty(
+    json!({"opt": {"python": "3.9", "empty": "", "null": null}}),
+    "{{ opt | option_string(keep_empty=true) }}",
+    "empty=,null=,python=3.9"
+)]
+#[case::non_string(
+    json!({"opt": {"count": 3, "ratio": 1.5, "debug": true}}),
+    "{{ opt | option_string }}",
+    "count=3,debug=true,ratio=1.5"
+)]
+#[case::nested(
+    json!({"opt": {"list": [1, "two"], "map": {"a": "1", "b": 2}}}),
+    "{{ opt | option_string }}",
+    r#"list=[1,"two"],map={"a":"1","b":2}"#
+)]
+#[case::empty(json!({"opt": {}}), "{{ opt | option_string }}", "")]
+fn test_option_string(
+    #[case] options: serde_json::Value,
+    #[case] tpl: &str,
+    #[case] expected: &str,
+) {
+    let rendered =
+        crate::render_template("test", tpl, &options).expect("template should not fail to render");
+    assert_eq!(rendered, expected);
+}
+
+#[rstest]
+#[case::input("{{ opt.python | option_string }}")]
+#[case::keep_empty("{{ opt | option_string(keep_empty=\"yes\") }}")]
+#[case::unknown_arg("{{ opt | option_string(keep_empty=true, sep=\";\") }}")]
+fn test_option_string_invalid(#[case] tpl: &str) {
+    let options = json!({"opt": {"python": "3.9"}});
+    crate::render_template("test", tpl, &options)
+        .expect_err("template should fail with invalid input or arguments");
+}
@@ -35,8 +35,17 @@ impl ParseVersion {
         Self::FIELD_POST,
     ];
 
+    pub const PARTS: &'static [&'static str] = &[
+        Self::FIELD_MAJOR,
+        Self::FIELD_MINOR,
+        Self::FIELD_PATCH,
+        Self::FIELD_PRE,
+        Self::FIELD_POST,
+    ];
+
     pub const ARG_FIELD: &'static str = "field";
-    pub const ARGS: &'static [&'static str] = &[Self::ARG_FIELD];
+    pub const ARG_PART: &'static str = "part";
+    pub const ARGS: &'static [&'static str] = &[Self::ARG_FIELD, Self::ARG_PART];
 }
 
 impl tera::Filter for ParseVersion {
@@ -67,7 +76,7 @@ impl tera::Filter for ParseVersion {
 
         match args.len() {
             0 => {}
-            1 if args.contains_key(Self::ARG_FIELD) => {}
+            1 if args.contains_key(Self::ARG_FIELD) || args.contains_key(Self::ARG_PART) => {}
             _ => {
                 return Err(tera::Error::msg(format!(
                     "{}: one or more unsupported arguments provided, supported args: {:?}",
@@ -94,6 +103,35 @@ impl tera::Filter for ParseVersion {
             return Ok(part);
         }
 
+        if let Some(part) = &args.get(Self::ARG_PART) {
+            let Value::String(part) = part else {
+                return Err(tera::Error::msg(format!(
+                    "{}: 'part' argument expected a string, got: {part:?}",
+                    Self::FILTER_NAME,
+                )));
+            };
+            let value =
+                match part.as_str() {
+                    Self::FIELD_MAJOR => version.parts.first().map(|n| Value::from(*n)),
+                    Self::FIELD_MINOR => version.parts.get(1).map(|n| Value::from(*n)),
+                    Self::FIELD_PATCH => version.parts.get(2).map(|n| Value::from(*n)),
+                    Self::FIELD_PRE => (!version.pre.tags.is_empty())
+                        .then(|| Value::String(version.pre.to_string())),
+                    Self::FIELD_POST => (!version.post.tags.is_empty())
+                        .then(|| Value::String(version.post.to_string())),
+                    _ => {
+                        return Err(tera::Error::msg(format!(
+                            "{}: spk version has no part {part:?}, available parts: {:?}",
+                            Self::FILTER_NAME,
+                            Self::PARTS
+                        )));
+                    }
+                };
+            // parts that are not in the version are empty rather than
+            // an error, so that templates can easily check for them
+            return Ok(value.unwrap_or_else(|| Value::String(String::new())));
+        }
+
         Ok(data)
     }
 }
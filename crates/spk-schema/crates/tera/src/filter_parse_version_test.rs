@@ -45,3 +45,37 @@ fn test_parse_version_access_block_params() {
         crate::render_template("test", TPL, &options).expect("template should not fail to render");
     assert_eq!(rendered.trim(), EXPECTED.trim());
 }
+
+#[rstest]
+#[case("major", "1")]
+#[case("minor", "2")]
+#[case("patch", "3")]
+#[case("pre", "beta.1")]
+#[case("post", "r.0")]
+fn test_parse_version_part(#[case] part: &str, #[case] expected: &str) {
+    let tpl = format!(r#"{{{{ "1.2.3.4-beta.1+r.0" | parse_version(part="{part}") }}}}"#);
+    let rendered = crate::render_template("test", tpl, &json!({}))
+        .expect("template should not fail to render");
+    assert_eq!(rendered, expected);
+}
+
+#[rstest]
+#[case("patch")]
+#[case("pre")]
+#[case("post")]
+fn test_parse_version_part_missing(#[case] part: &str) {
+    // parts that are not in the version are rendered as empty
+    let tpl = format!(r#"[{{{{ "1.2" | parse_version(part="{part}") }}}}]"#);
+    let rendered = crate::render_template("test", tpl, &json!({}))
+        .expect("template should not fail to render");
+    assert_eq!(rendered, "[]");
+}
+
+#[rstest]
+#[case(r#"{{ "1.2.3" | parse_version(part="base") }}"#)]
+#[case(r#"{{ "1.2.3" | parse_version(part=1) }}"#)]
+#[case(r#"{{ "1.2.3" | parse_version(field="major", part="major") }}"#)]
+fn test_parse_version_part_invalid(#[case] tpl: &str) {
+    crate::render_template("test", tpl, &json!({}))
+        .expect_err("template should fail to render with an invalid part");
+}
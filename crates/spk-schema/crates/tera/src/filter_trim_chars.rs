@@ -0,0 +1,66 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use serde_json::Value;
+
+#[cfg(test)]
+#[path = "./filter_trim_chars_test.rs"]
+mod filter_trim_chars_test;
+
+/// Like the built-in `trim` filter, but strips the given characters
+/// instead of whitespace.
+pub struct TrimChars;
+
+impl TrimChars {
+    pub const FILTER_NAME: &'static str = "trim_chars";
+
+    /// The characters to strip from both ends of the value
+    pub const ARG_CHARS: &'static str = "chars";
+    pub const ARGS: &'static [&'static str] = &[Self::ARG_CHARS];
+}
+
+impl tera::Filter for TrimChars {
+    fn filter(
+        &self,
+        value: &Value,
+        args: &std::collections::HashMap<String, Value>,
+    ) -> tera::Result<Value> {
+        let chars = match args.get(Self::ARG_CHARS) {
+            Some(Value::String(chars)) => chars,
+            None => {
+                return Err(tera::Error::msg(format!(
+                    "{}: missing required argument {}",
+                    Self::FILTER_NAME,
+                    Self::ARG_CHARS
+                )));
+            }
+            Some(chars) => {
+                return Err(tera::Error::msg(format!(
+                    "{}: {} argument expected a string value, got {chars:?}",
+                    Self::FILTER_NAME,
+                    Self::ARG_CHARS
+                )));
+            }
+        };
+
+        if args.len() > Self::ARGS.len() {
+            return Err(tera::Error::msg(format!(
+                "{}: one or more unsupported arguments provided, supported args: {:?}",
+                Self::FILTER_NAME,
+                Self::ARGS
+            )));
+        }
+
+        let Value::String(value) = value else {
+            return Err(tera::Error::msg(format!(
+                "{}: expected string input, got {:?}",
+                Self::FILTER_NAME,
+                value,
+            )));
+        };
+
+        let trimmed = value.trim_matches(|c| chars.contains(c));
+        Ok(Value::String(trimmed.to_string()))
+    }
+}
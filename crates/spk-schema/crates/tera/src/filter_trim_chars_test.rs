@@ -0,0 +1,38 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use serde_json::json;
+
+#[rstest]
+#[case::chars(r#"{{ "--1.2.3-" | trim_chars(chars="-") }}"#, "1.2.3")]
+#[case::many_chars(r#"{{ "v1.2.3/\n" | trim_chars(chars="v/\n") }}"#, "1.2.3")]
+#[case::inner_chars_kept(r#"{{ "x1x2x" | trim_chars(chars="x") }}"#, "1x2")]
+#[case::whitespace_kept(r#"{{ value | trim_chars(chars="-") }}"#, "\u{3000}1.2.3\u{3000}")]
+fn test_trim_chars(#[case] tpl: &str, #[case] expected: &str) {
+    let options = json!({"value": "-\u{3000}1.2.3\u{3000}-"});
+    let rendered =
+        crate::render_template("test", tpl, &options).expect("template should not fail to render");
+    assert_eq!(rendered, expected);
+}
+
+#[rstest]
+fn test_builtin_trim_is_unchanged() {
+    // the built-in filter also strips non-ascii whitespace
+    let options = json!({"value": "\u{3000} 1.2.3\n\u{3000}"});
+    let rendered = crate::render_template("test", "{{ value | trim }}", &options)
+        .expect("template should not fail to render");
+    assert_eq!(rendered, "1.2.3");
+}
+
+#[rstest]
+#[case::input(r#"{{ 1 | trim_chars(chars="-") }}"#)]
+#[case::missing_chars(r#"{{ "1.2.3" | trim_chars }}"#)]
+#[case::chars(r#"{{ "1.2.3" | trim_chars(chars=1) }}"#)]
+#[case::unknown_arg(r#"{{ "1.2.3" | trim_chars(chars="-", other="-") }}"#)]
+fn test_trim_chars_invalid(#[case] tpl: &str) {
+    let options = json!({});
+    crate::render_template("test", tpl, &options)
+        .expect_err("template should fail without chars, or with a non-string or unknown argument");
+}
@@ -7,11 +7,17 @@
 mod error;
 mod filter_compare_version;
 mod filter_default_options;
+mod filter_option_string;
 mod filter_parse_version;
 mod filter_replace_regex;
+mod filter_trim_chars;
 
 pub use error::Error;
 
+#[cfg(test)]
+#[path = "./lib_test.rs"]
+mod lib_test;
+
 /// Build the default template rendered for spk
 ///
 /// This parser has all configuration and extensions
@@ -34,6 +40,14 @@ pub fn default_renderer() -> tera::Tera {
         filter_default_options::DefaultOpts::FILTER_NAME,
         filter_default_options::DefaultOpts,
     );
+    renderer.register_filter(
+        filter_option_string::OptionString::FILTER_NAME,
+        filter_option_string::OptionString,
+    );
+    renderer.register_filter(
+        filter_trim_chars::TrimChars::FILTER_NAME,
+        filter_trim_chars::TrimChars,
+    );
     renderer
 }
 
@@ -44,12 +58,35 @@ where
     T: AsRef<str>,
     D: serde::Serialize,
 {
-    let tpl = tpl.as_ref();
     let mut tera = default_renderer();
+    render_template_to(&mut tera, filename, tpl, data)
+}
+
+/// Render a template using an existing renderer
+///
+/// This allows the same renderer to be reused when rendering many
+/// templates, along with any additional templates or filters that
+/// have been registered with it. The template is added under the
+/// given name, replacing any existing template of the same name,
+/// and is removed again once it has been rendered.
+pub fn render_template_to<N, T, D>(
+    tera: &mut tera::Tera,
+    filename: N,
+    tpl: T,
+    data: &D,
+) -> Result<String, Error>
+where
+    N: AsRef<str>,
+    T: AsRef<str>,
+    D: serde::Serialize,
+{
+    let filename = filename.as_ref();
+    let tpl = tpl.as_ref();
     let map_err = |err| Error::build(tpl.to_string(), err);
-    tera.add_raw_template(filename.as_ref(), tpl)
-        .map_err(map_err)?;
-    let context = tera::Context::from_serialize(data).map_err(map_err)?;
-    let rendered = tera.render(filename.as_ref(), &context).map_err(map_err)?;
-    Ok(rendered)
+    tera.add_raw_template(filename, tpl).map_err(map_err)?;
+    let result = tera::Context::from_serialize(data)
+        .and_then(|context| tera.render(filename, &context))
+        .map_err(map_err);
+    tera.templates.remove(filename);
+    result
 }
@@ -0,0 +1,56 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{default_renderer, render_template_to};
+
+#[rstest]
+fn test_render_template_to_shared_renderer() {
+    let mut tera = default_renderer();
+    tera.add_raw_template("greeting", "hello, {{ name }}")
+        .expect("partial template should be valid");
+
+    let first = render_template_to(
+        &mut tera,
+        "spec",
+        "{% include \"greeting\" %}",
+        &json!({"name": "world"}),
+    )
+    .expect("first template should render");
+    assert_eq!(first, "hello, world");
+
+    let second = render_template_to(
+        &mut tera,
+        "spec",
+        "{{ version | parse_version(field=\"major\") }}",
+        &json!({"version": "1.2.3"}),
+    )
+    .expect("second template should render with the same name");
+    assert_eq!(second, "1");
+
+    assert!(
+        tera.get_template_names().all(|name| name != "spec"),
+        "rendered templates should not be left in the renderer"
+    );
+    assert!(
+        tera.get_template_names().any(|name| name == "greeting"),
+        "other templates should be left in the renderer"
+    );
+}
+
+#[rstest]
+fn test_render_template_to_removes_failed_template() {
+    let mut tera = default_renderer();
+    let result = render_template_to(&mut tera, "spec", "{{ missing }}", &json!({}));
+    assert!(
+        result.is_err(),
+        "rendering an undefined variable should fail"
+    );
+    assert!(
+        tera.get_template_names().all(|name| name != "spec"),
+        "templates that fail to render should not be left in the renderer"
+    );
+}
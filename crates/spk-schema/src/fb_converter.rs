@@ -27,6 +27,7 @@ use spk_schema_foundation::option_map::OptionMap;
 use spk_schema_foundation::spec_ops::FileMatcher;
 use spk_schema_foundation::version_range::{
     CompatRange,
+    CompatibleReleaseRange,
     DoubleEqualsVersion,
     DoubleNotEqualsVersion,
     EqualsVersion,
@@ -105,6 +106,7 @@ pub fn fb_inclusion_policy_to_inclusion_policy(
     match fb_inclusion_policy {
         spk_proto::InclusionPolicy::Always => InclusionPolicy::Always,
         spk_proto::InclusionPolicy::IfAlreadyPresent => InclusionPolicy::IfAlreadyPresent,
+        spk_proto::InclusionPolicy::IfCompatible => InclusionPolicy::IfCompatible,
         _ => {
             // Covering up to ::MAX for the compiler, but this should not happen
             debug_assert!(
@@ -779,6 +781,12 @@ pub fn fb_version_filter_to_version_filter(
                     let required = fb_lone_compat_rule_to_lone_compat_rule(fb_rule.required());
                     VersionRange::Compat(CompatRange::new(base, required))
                 }
+                spk_proto::VersionRangeOperator::CompatibleRelease => {
+                    let base = fb_rule.version().map(|v| fb_version_to_version(v)).expect(
+                        "A VersionRangeOperator::CompatibleRelease should have a base in an index",
+                    );
+                    VersionRange::CompatibleRelease(CompatibleReleaseRange::new(base))
+                }
                 spk_proto::VersionRangeOperator::DoubleEquals => {
                     let version = fb_rule.version().map(|v| fb_version_to_version(v)).expect(
                         "A VersionRangeOperator::DoubleEquals should have a version in an index",
@@ -904,6 +912,11 @@ fn version_filter_to_fb_version_filter<'a>(
                 args.version = Some(fb_version);
                 args.required = fb_required;
             }
+            VersionRange::CompatibleRelease(value) => {
+                let fb_version = version_to_fb_version(builder, &value.version());
+                args.filter_op = spk_proto::VersionRangeOperator::CompatibleRelease;
+                args.version = Some(fb_version);
+            }
             VersionRange::DoubleEquals(value) => {
                 let fb_version = version_to_fb_version(builder, &value.version());
                 args.filter_op = spk_proto::VersionRangeOperator::DoubleEquals;
@@ -1102,6 +1115,7 @@ fn inclusion_policy_to_fb_inclusion_policy(
     match inclusion_policy {
         InclusionPolicy::Always => spk_proto::InclusionPolicy::Always,
         InclusionPolicy::IfAlreadyPresent => spk_proto::InclusionPolicy::IfAlreadyPresent,
+        InclusionPolicy::IfCompatible => spk_proto::InclusionPolicy::IfCompatible,
     }
 }
 
@@ -18,6 +18,7 @@ mod option;
 mod package;
 pub mod prelude;
 mod recipe;
+mod rename;
 mod requirements_list;
 mod source_spec;
 mod spec;
@@ -91,6 +92,7 @@ pub use package::{
     PackageMut,
 };
 pub use recipe::{BuildEnv, Recipe};
+pub use rename::Rename;
 pub use requirements_list::{RequirementsList, convert_requests_to_requests_with_options};
 pub use serde_json;
 pub use source_spec::{GitSource, LocalSource, ScriptSource, SourceSpec, TarSource};
@@ -5,9 +5,12 @@
 use std::collections::BTreeMap;
 use std::process::{Command, Stdio};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use spk_config::Metadata;
 use spk_schema_foundation::IsDefault;
+use spk_schema_foundation::name::{OptName, OptNameBuf};
+use spk_schema_foundation::option_map::HOST_OPTIONS;
 
 use crate::{Error, Result};
 
@@ -28,6 +31,16 @@ pub struct Meta {
 }
 
 impl Meta {
+    /// The label that holds the time that a package build was
+    /// published, as an RFC 3339 timestamp
+    pub const PUBLISHED_LABEL: &'static str = "spk:published";
+    /// The label that holds the version of spk that built a package
+    pub const BUILT_WITH_SPK_LABEL: &'static str = "spk:built-with-spk";
+    /// The label that holds the version of spfs that built a package
+    pub const BUILT_WITH_SPFS_LABEL: &'static str = "spk:built-with-spfs";
+    /// The label that holds the operating system that built a package
+    pub const BUILT_ON_OS_LABEL: &'static str = "spk:built-on-os";
+
     pub fn has_label_with_value(&self, label: &str, value: &str) -> bool {
         if let Some(label_value) = self.labels.get(label) {
             return *label_value == value;
@@ -35,6 +48,61 @@ impl Meta {
         false
     }
 
+    /// The time that this package was published, if it was recorded
+    /// in the [`Self::PUBLISHED_LABEL`] label and is a valid timestamp.
+    pub fn published(&self) -> Option<DateTime<Utc>> {
+        let value = self.labels.get(Self::PUBLISHED_LABEL)?;
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(published) => Some(published.with_timezone(&Utc)),
+            Err(err) => {
+                tracing::debug!(
+                    "Ignoring invalid {} label '{value}': {err}",
+                    Self::PUBLISHED_LABEL
+                );
+                None
+            }
+        }
+    }
+
+    /// Record the given time as when this package was published.
+    pub fn set_published(&mut self, published: DateTime<Utc>) {
+        self.labels
+            .insert(Self::PUBLISHED_LABEL.to_string(), published.to_rfc3339());
+    }
+
+    /// Record the versions of spk and spfs, and the operating system
+    /// of the current host, as the ones that built this package.
+    pub fn record_build_provenance(&mut self) {
+        self.labels.insert(
+            Self::BUILT_WITH_SPK_LABEL.to_string(),
+            env!("CARGO_PKG_VERSION").to_string(),
+        );
+        self.labels.insert(
+            Self::BUILT_WITH_SPFS_LABEL.to_string(),
+            spfs::VERSION.to_string(),
+        );
+        self.labels
+            .insert(Self::BUILT_ON_OS_LABEL.to_string(), host_os_description());
+    }
+
+    /// Describe the versions of spk and spfs, and the operating system,
+    /// that built this package, using "unknown" for any that were not
+    /// recorded, such as for packages built by older versions of spk.
+    pub fn format_build_provenance(&self) -> String {
+        let get = |label| {
+            self.labels
+                .get(label)
+                .map(String::as_str)
+                .unwrap_or("unknown")
+        };
+        format!(
+            "spk {}, spfs {}, os {}",
+            get(Self::BUILT_WITH_SPK_LABEL),
+            get(Self::BUILT_WITH_SPFS_LABEL),
+            get(Self::BUILT_ON_OS_LABEL),
+        )
+    }
+
     pub fn update_metadata(&mut self, global_config: &Metadata) -> Result<i32> {
         for config in global_config.global.iter() {
             let cmd = &config.command;
@@ -81,6 +149,28 @@ impl Meta {
     }
 }
 
+/// Describe the host operating system, including the distro and its
+/// version when they are known, eg: "linux (rocky 9.3)".
+fn host_os_description() -> String {
+    let Ok(options) = HOST_OPTIONS.get() else {
+        return std::env::consts::OS.to_string();
+    };
+    let os = options
+        .get(OptName::os())
+        .map(String::as_str)
+        .unwrap_or(std::env::consts::OS);
+    let Some(distro) = options.get(OptName::distro()) else {
+        return os.to_string();
+    };
+    let distro_version = OptNameBuf::try_from(distro.as_str())
+        .ok()
+        .and_then(|name| options.get(&name));
+    match distro_version {
+        Some(version) => format!("{os} ({distro} {version})"),
+        None => format!("{os} ({distro})"),
+    }
+}
+
 impl IsDefault for Meta {
     fn is_default(&self) -> bool {
         self == &Self::default()
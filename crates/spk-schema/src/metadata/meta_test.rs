@@ -54,3 +54,40 @@ fn test_custom_metadata() {
         assert!(spec.meta.labels.contains_key(*key));
     }
 }
+
+#[rstest]
+#[case("2024-05-01T12:30:00Z", Some(1714566600))]
+#[case("2024-05-01T14:30:00+02:00", Some(1714566600))]
+#[case("yesterday", None)]
+fn test_package_meta_published(#[case] value: &str, #[case] expected: Option<i64>) {
+    let mut meta = super::Meta::default();
+    assert!(meta.published().is_none());
+    meta.labels
+        .insert(super::Meta::PUBLISHED_LABEL.to_string(), value.to_string());
+    assert_eq!(meta.published().map(|p| p.timestamp()), expected);
+}
+
+#[rstest]
+fn test_package_meta_build_provenance() {
+    let mut meta = super::Meta::default();
+    assert_eq!(
+        meta.format_build_provenance(),
+        "spk unknown, spfs unknown, os unknown",
+        "packages without provenance should show it as unknown"
+    );
+
+    meta.record_build_provenance();
+    let provenance = meta.format_build_provenance();
+    assert!(
+        provenance.starts_with(&format!(
+            "spk {}, spfs {}, os ",
+            env!("CARGO_PKG_VERSION"),
+            spfs::VERSION
+        )),
+        "should record the current spk and spfs versions, got: {provenance}"
+    );
+    assert!(
+        !provenance.contains("unknown"),
+        "should record all provenance, got: {provenance}"
+    );
+}
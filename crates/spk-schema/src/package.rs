@@ -244,6 +244,9 @@ pub trait PackageMut: Package + DeprecateMut {
     /// restrictions of this one. Otherwise the new request is
     /// appended to the list.
     fn insert_or_merge_install_requirement(&mut self, req: PinnedRequest) -> crate::Result<()>;
+
+    /// Modify the additional metadata attached to this package
+    fn metadata_mut(&mut self) -> crate::Result<&mut crate::metadata::Meta>;
 }
 
 forward_to_impl!(Package, {
@@ -0,0 +1,32 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use crate::Result;
+use crate::foundation::name::PkgNameBuf;
+
+/// Can be given a new package name
+#[enum_dispatch::enum_dispatch]
+pub trait Rename {
+    /// Change the package name of this instance, keeping its
+    /// version and build
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()>;
+}
+
+impl<T> Rename for Box<T>
+where
+    T: Rename,
+{
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        (**self).set_name(name)
+    }
+}
+
+impl<T> Rename for &mut T
+where
+    T: Rename,
+{
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        (**self).set_name(name)
+    }
+}
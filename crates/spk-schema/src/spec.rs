@@ -9,6 +9,7 @@ use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use enum_dispatch::enum_dispatch;
 use format_serde_error::SerdeError;
 use serde::{Deserialize, Serialize};
@@ -45,6 +46,7 @@ use crate::{
     Package,
     PackageMut,
     Recipe,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -289,7 +291,7 @@ impl TemplateExt for SpecTemplate {
 /// file or machine-managed persistent storage.
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize)]
 #[serde(tag = "api")]
-#[enum_dispatch(Deprecate, DeprecateMut)]
+#[enum_dispatch(Deprecate, DeprecateMut, Rename)]
 pub enum SpecRecipe {
     #[serde(rename = "v0/package")]
     V0Package(super::v0::RecipeSpec),
@@ -640,7 +642,7 @@ impl Test for SpecTest {
 /// and deserialized from a `Repository`.
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 #[serde(tag = "api")]
-#[enum_dispatch(Deprecate, DeprecateMut)]
+#[enum_dispatch(Deprecate, DeprecateMut, Rename)]
 pub enum Spec {
     #[serde(rename = "v0/package")]
     V0Package(Box<super::v0::PackageSpec>),
@@ -650,6 +652,16 @@ pub enum Spec {
     V0IndexedPackage(Box<super::v0::IndexedPackage>),
 }
 
+impl Spec {
+    /// The time that this build was published, if it is known
+    pub fn published(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Spec::V0Package(spec) => spec.meta.published(),
+            Spec::V0IndexedPackage(spec) => spec.published(),
+        }
+    }
+}
+
 impl Components for Spec {
     type ComponentSpecT = ComponentSpec;
 
@@ -851,6 +863,13 @@ impl PackageMut for Spec {
             Spec::V0IndexedPackage(spec) => spec.insert_or_merge_install_requirement(req),
         }
     }
+
+    fn metadata_mut(&mut self) -> Result<&mut Meta> {
+        match self {
+            Spec::V0Package(spec) => spec.metadata_mut(),
+            Spec::V0IndexedPackage(spec) => spec.metadata_mut(),
+        }
+    }
 }
 
 impl FromYaml for Spec {
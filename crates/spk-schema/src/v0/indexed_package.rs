@@ -8,6 +8,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use arc_swap::{ArcSwap, ArcSwapOption};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use spk_schema_foundation::IsDefault;
 use spk_schema_foundation::ident::{
@@ -27,7 +28,7 @@ use spk_schema_foundation::version::{IncompatibleReason, VarOptionProblem};
 use super::check_package_spec_satisfies_pkg_request;
 use super::package_spec::build_requirements_from_build_options;
 use crate::fb_converter::fb_requirements_to_requirements;
-use crate::foundation::name::PkgName;
+use crate::foundation::name::{PkgName, PkgNameBuf};
 use crate::foundation::spec_ops::prelude::*;
 use crate::foundation::version::{Compat, Compatibility, Version};
 use crate::ident::{Satisfy, VarRequest};
@@ -49,6 +50,7 @@ use crate::{
     Opt,
     Package,
     PackageMut,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -159,6 +161,12 @@ impl IndexedPackage {
         }
     }
 
+    /// The time that this build was published, if it was stored in
+    /// the index, see [`crate::metadata::Meta::published`].
+    pub fn published(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.build_index().published()?, 0)
+    }
+
     // Helper function for gathering and filtering downstream
     // requirements trait implementation.
     fn downstream_requirements<F>(&self, filter: F) -> Cow<'_, RequirementsList<RequestWithOptions>>
@@ -237,6 +245,15 @@ impl DeprecateMut for IndexedPackage {
     }
 }
 
+impl Rename for IndexedPackage {
+    fn set_name(&mut self, _name: PkgNameBuf) -> Result<()> {
+        Err(Error::SpkIndexedPackageDoesNotImplement(
+            "Rename".to_string(),
+            "set_name".to_string(),
+        ))
+    }
+}
+
 impl Satisfy<PkgRequestWithOptions> for IndexedPackage {
     fn check_satisfies_request(&self, pkg_request: &PkgRequestWithOptions) -> Compatibility {
         check_package_spec_satisfies_pkg_request(self, pkg_request)
@@ -577,4 +594,11 @@ impl PackageMut for IndexedPackage {
             "insert_or_merge_install_requirement".to_string(),
         ))
     }
+
+    fn metadata_mut(&mut self) -> Result<&mut crate::metadata::Meta> {
+        Err(Error::SpkIndexedPackageDoesNotImplement(
+            "PackageMut".to_string(),
+            "metadata_mut".to_string(),
+        ))
+    }
 }
@@ -33,7 +33,7 @@ use super::TestSpec;
 use crate::build_spec::UncheckedBuildSpec;
 use crate::foundation::ident_build::Build;
 use crate::foundation::ident_component::Component;
-use crate::foundation::name::PkgName;
+use crate::foundation::name::{PkgName, PkgNameBuf};
 use crate::foundation::option_map::OptionMap;
 use crate::foundation::spec_ops::prelude::*;
 use crate::foundation::version::{Compat, CompatRule, Compatibility, Version};
@@ -68,6 +68,7 @@ use crate::{
     Opt,
     Package,
     PackageMut,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -319,6 +320,13 @@ impl DeprecateMut for PackageSpec {
     }
 }
 
+impl Rename for PackageSpec {
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        self.pkg.set_name(name);
+        Ok(())
+    }
+}
+
 impl HasBuild for PackageSpec {
     fn build(&self) -> &Build {
         self.pkg.build()
@@ -502,6 +510,10 @@ impl PackageMut for PackageSpec {
             Ok(())
         })
     }
+
+    fn metadata_mut(&mut self) -> Result<&mut Meta> {
+        Ok(&mut self.meta)
+    }
 }
 
 /// Shared implementation for Satisfy<PkgRequestWithOptions> for package-like types.
@@ -18,7 +18,7 @@ use spk_schema_foundation::ident::{
     VersionIdent,
 };
 use spk_schema_foundation::ident_build::{Build, BuildId};
-use spk_schema_foundation::name::PkgName;
+use spk_schema_foundation::name::{PkgName, PkgNameBuf};
 use spk_schema_foundation::option_map::{HOST_OPTIONS, OptionMap, Stringified};
 use spk_schema_foundation::spec_ops::{HasVersion, Named, Versioned};
 use spk_schema_foundation::version::Version;
@@ -39,6 +39,7 @@ use crate::{
     Opt,
     Package,
     Recipe,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -155,6 +156,13 @@ impl DeprecateMut for Platform {
     }
 }
 
+impl Rename for Platform {
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        self.platform.set_name(name);
+        Ok(())
+    }
+}
+
 impl HasVersion for Platform {
     fn version(&self) -> &Version {
         self.platform.version()
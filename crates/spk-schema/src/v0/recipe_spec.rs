@@ -29,7 +29,7 @@ use super::TestSpec;
 use super::variant_spec::VariantSpecEntryKey;
 use crate::foundation::ident_build::Build;
 use crate::foundation::ident_component::Component;
-use crate::foundation::name::{OptNameBuf, PkgName};
+use crate::foundation::name::{OptNameBuf, PkgName, PkgNameBuf};
 use crate::foundation::option_map::OptionMap;
 use crate::foundation::spec_ops::prelude::*;
 use crate::foundation::version::{Compat, Compatibility, Version};
@@ -58,6 +58,7 @@ use crate::{
     Opt,
     Package,
     Recipe,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -134,6 +135,13 @@ impl DeprecateMut for RecipeSpec {
     }
 }
 
+impl Rename for RecipeSpec {
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        self.pkg.set_name(name);
+        Ok(())
+    }
+}
+
 impl HasVersion for RecipeSpec {
     fn version(&self) -> &Version {
         self.pkg.version()
@@ -418,6 +426,7 @@ impl Recipe for RecipeSpec {
         if let Err(err) = recipe_meta.update_metadata(&config.metadata) {
             tracing::warn!("Failed to collect extra package metadata: {err}");
         }
+        recipe_meta.record_build_provenance();
 
         let mut missing_build_requirements = HashMap::new();
         let mut missing_runtime_requirements: HashMap<OptNameBuf, (String, Option<String>)> =
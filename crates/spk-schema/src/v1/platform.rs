@@ -23,7 +23,7 @@ use spk_schema_foundation::ident::{
 };
 use spk_schema_foundation::ident_build::{Build, BuildId};
 use spk_schema_foundation::ident_component::Component;
-use spk_schema_foundation::name::{OptName, PkgName};
+use spk_schema_foundation::name::{OptName, PkgName, PkgNameBuf};
 use spk_schema_foundation::option_map::{HOST_OPTIONS, OptionMap};
 use spk_schema_foundation::spec_ops::{HasVersion, Named, Versioned};
 use spk_schema_foundation::version::Version;
@@ -45,6 +45,7 @@ use crate::{
     Package,
     Recipe,
     RecipeComponentSpec,
+    Rename,
     RequirementsList,
     Result,
     RuntimeEnvironment,
@@ -97,6 +98,13 @@ impl DeprecateMut for Platform {
     }
 }
 
+impl Rename for Platform {
+    fn set_name(&mut self, name: PkgNameBuf) -> Result<()> {
+        self.platform.set_name(name);
+        Ok(())
+    }
+}
+
 impl HasVersion for Platform {
     fn version(&self) -> &Version {
         self.platform.version()
@@ -5,6 +5,7 @@
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Serialize};
 use spk_schema_foundation::IsDefault;
+use spk_schema_foundation::ident_component::Component;
 use spk_schema_foundation::name::{PkgName, PkgNameBuf};
 
 #[cfg(test)]
@@ -221,6 +222,10 @@ impl ValidationRule {
 #[strum_discriminants(derive(Hash, PartialOrd, Ord, strum::VariantNames, Deserialize, Serialize))]
 pub enum ValidationMatcher {
     EmptyPackage,
+    EmptyComponent {
+        components: Vec<Component>,
+        min_files: Option<usize>,
+    },
     CollectAllFiles,
     StrongInheritanceVarDescription,
     LongVarDescription,
@@ -236,6 +241,14 @@ pub enum ValidationMatcher {
         packages: Vec<PkgNameBuf>,
     },
     SpdxLicense,
+    ExternalCommand {
+        /// The command and arguments to run
+        command: Vec<String>,
+        /// Seconds to wait for the command before it is considered failed
+        timeout: Option<u64>,
+        /// Allow the command to access the network
+        allow_network: bool,
+    },
 }
 
 #[derive(
@@ -327,6 +340,26 @@ impl<'de> Deserialize<'de> for ValidationRule {
                 let kind = map.next_value::<Kind>()?;
                 match kind {
                     Kind::EmptyPackage => Ok(ValidationMatcher::EmptyPackage),
+                    Kind::EmptyComponent => {
+                        let mut components = Default::default();
+                        let mut min_files = None;
+                        while let Some(name) = map.next_key::<String>()? {
+                            match name.as_str() {
+                                "components" => components = map.next_value()?,
+                                "min_files" => min_files = map.next_value()?,
+                                unknown => {
+                                    return Err(serde::de::Error::unknown_field(
+                                        unknown,
+                                        &["components", "min_files"],
+                                    ));
+                                }
+                            }
+                        }
+                        Ok(ValidationMatcher::EmptyComponent {
+                            components,
+                            min_files,
+                        })
+                    }
                     Kind::SpdxLicense => Ok(ValidationMatcher::SpdxLicense),
                     Kind::CollectAllFiles => Ok(ValidationMatcher::EmptyPackage),
                     Kind::StrongInheritanceVarDescription => {
@@ -377,6 +410,37 @@ impl<'de> Deserialize<'de> for ValidationRule {
                         Ok(ValidationMatcher::CollectExistingFiles { packages })
                     }
                     Kind::RecursiveBuild => Ok(ValidationMatcher::RecursiveBuild),
+                    Kind::ExternalCommand => {
+                        let mut command = None;
+                        let mut timeout = None;
+                        let mut allow_network = false;
+                        while let Some(name) = map.next_key::<String>()? {
+                            match name.as_str() {
+                                "command" => command = Some(map.next_value()?),
+                                "timeout" => timeout = map.next_value()?,
+                                "allow_network" => allow_network = map.next_value()?,
+                                unknown => {
+                                    return Err(serde::de::Error::unknown_field(
+                                        unknown,
+                                        &["command", "timeout", "allow_network"],
+                                    ));
+                                }
+                            }
+                        }
+                        let command: Vec<String> =
+                            command.ok_or_else(|| serde::de::Error::missing_field("command"))?;
+                        if command.is_empty() {
+                            return Err(serde::de::Error::invalid_length(
+                                0,
+                                &"a command with at least one element",
+                            ));
+                        }
+                        Ok(ValidationMatcher::ExternalCommand {
+                            command,
+                            timeout,
+                            allow_network,
+                        })
+                    }
                 }
             }
         }
@@ -407,6 +471,17 @@ impl Serialize for ValidationRule {
                     map.serialize_entry("packages", packages)?;
                 }
             }
+            ValidationMatcher::EmptyComponent {
+                components,
+                min_files,
+            } => {
+                if !components.is_empty() {
+                    map.serialize_entry("components", components)?;
+                }
+                if let Some(min_files) = min_files {
+                    map.serialize_entry("min_files", min_files)?;
+                }
+            }
             ValidationMatcher::AlterExistingFiles { packages, action } => {
                 if !packages.is_empty() {
                     map.serialize_entry("packages", packages)?;
@@ -420,6 +495,19 @@ impl Serialize for ValidationRule {
                     map.serialize_entry("packages", packages)?;
                 }
             }
+            ValidationMatcher::ExternalCommand {
+                command,
+                timeout,
+                allow_network,
+            } => {
+                map.serialize_entry("command", command)?;
+                if let Some(timeout) = timeout {
+                    map.serialize_entry("timeout", timeout)?;
+                }
+                if *allow_network {
+                    map.serialize_entry("allow_network", allow_network)?;
+                }
+            }
         }
         map.end()
     }
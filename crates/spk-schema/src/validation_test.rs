@@ -16,3 +16,41 @@ fn test_validation_rule_expansion() {
         }
     }));
 }
+
+#[test]
+fn test_empty_component_rule_round_trip() {
+    let yaml = "{rules: [{deny: EmptyComponent, components: [run], min_files: 2}]}";
+    let spec: ValidationSpec = serde_yaml::from_str(yaml).unwrap();
+    let expected = super::ValidationRule::Deny {
+        condition: super::ValidationMatcher::EmptyComponent {
+            components: vec![spk_schema_foundation::ident_component::Component::Run],
+            min_files: Some(2),
+        },
+    };
+    assert!(spec.to_expanded_rules().contains(&expected));
+
+    let serialized = serde_yaml::to_string(&spec).unwrap();
+    let again: ValidationSpec = serde_yaml::from_str(&serialized).unwrap();
+    assert_eq!(spec, again);
+}
+
+#[test]
+fn test_external_command_rule_round_trip() {
+    let yaml = "{rules: [{deny: ExternalCommand, command: [license-scan, --strict], timeout: 30}]}";
+    let spec: ValidationSpec = serde_yaml::from_str(yaml).unwrap();
+    let expected = super::ValidationRule::Deny {
+        condition: super::ValidationMatcher::ExternalCommand {
+            command: vec!["license-scan".into(), "--strict".into()],
+            timeout: Some(30),
+            allow_network: false,
+        },
+    };
+    assert!(spec.to_expanded_rules().contains(&expected));
+
+    let serialized = serde_yaml::to_string(&spec).unwrap();
+    let again: ValidationSpec = serde_yaml::from_str(&serialized).unwrap();
+    assert_eq!(spec, again);
+
+    serde_yaml::from_str::<ValidationSpec>("{rules: [{deny: ExternalCommand}]}")
+        .expect_err("a command is required");
+}
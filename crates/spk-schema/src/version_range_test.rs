@@ -52,10 +52,45 @@ fn test_parse_version_range_tilde() {
     assert!(parse_version_range("~2").is_err());
 }
 
+#[rstest]
+#[case("~=1.4.2", "1.4.2", "1.5.0")]
+#[case("~=2.2", "2.2", "3.0")]
+#[case("~=1.4.2+r.1", "1.4.2+r.1", "1.5.0")]
+fn test_parse_version_range_compatible_release(
+    #[case] range: &str,
+    #[case] lower: &str,
+    #[case] upper: &str,
+) {
+    let vr = parse_version_range(range).unwrap();
+    assert!(
+        matches!(vr, VersionRange::CompatibleRelease(_)),
+        "expected a compatible release range, got: {vr:?}"
+    );
+    assert_eq!(vr.to_string(), range, "should round trip through Display");
+    assert_eq!(
+        vr.greater_or_equal_to().expect("some version"),
+        Version::from_str(lower).expect("valid version")
+    );
+    assert_eq!(
+        vr.less_than().expect("some version"),
+        Version::from_str(upper)
+            .expect("valid version")
+            .minus_epsilon()
+    );
+
+    assert!(parse_version_range("~=2").is_err());
+}
+
 #[rstest]
 #[case("~1.0.0", "1.0.0", true)]
 #[case("~1.0.0", "1.0.1", true)]
 #[case("~1.0.0", "1.2.1", false)]
+#[case("~=1.4.2", "1.4.2", true)]
+#[case("~=1.4.2", "1.4.9", true)]
+#[case("~=1.4.2", "1.4.1", false)]
+#[case("~=1.4.2", "1.5.0", false)]
+#[case("~=2.2", "2.9.1", true)]
+#[case("~=2.2", "3.0.0", false)]
 #[case("^1.0.0", "1.0.0", true)]
 #[case("^1.0.0", "1.1.0", true)]
 #[case("^1.0.0", "1.0.1", true)]
@@ -220,6 +255,31 @@ fn test_intersects(#[case] range1: &str, #[case] range2: &str, #[case] expected:
     assert_eq!(!&c, !expected, "b:{b} + a:{a} == {c:?}");
 }
 
+#[rstest]
+// overlapping
+#[case(">=1.0", "<2.0", Some(">=1.0,<2.0"))]
+#[case(">=1.0", ">=2.0", Some(">=2.0"))]
+#[case("<2.0", "<=1.0", Some("<=1.0"))]
+#[case(">1.0", ">=1.0", Some(">1.0"))]
+#[case(">=1.0", "=1.5", Some("=1.5"))]
+#[case("1.*", ">=1.2", Some(">=1.2,1.*"))]
+// touching at a point
+#[case(">=1.0", "<=1.0", Some(">=1.0,<=1.0"))]
+#[case("<=1.0", "=1.0", Some("=1.0"))]
+// disjoint
+#[case(">=2", "<1", None)]
+#[case(">=1.0", "<1.0", None)]
+#[case(">1.0", "<=1.0", None)]
+#[case("=1.0", "=1.1", None)]
+#[case("1.*", ">=2", None)]
+fn test_intersect(#[case] range1: &str, #[case] range2: &str, #[case] expected: Option<&str>) {
+    let a = parse_version_range(range1).unwrap();
+    let b = parse_version_range(range2).unwrap();
+    let expected = expected.map(|e| parse_version_range(e).unwrap());
+    assert_eq!(a.intersect(&b), expected, "a:{a} & b:{b}");
+    assert_eq!(b.intersect(&a), expected, "b:{b} & a:{a}");
+}
+
 prop_compose! {
     // XXX: The tagset is limited to a maximum of one entry because of
     // the ambiguous use of commas to delimit both tags and version filters.
@@ -10,16 +10,18 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use async_stream::stream;
-use colored::Colorize;
+use colored::Color;
 use futures::Stream;
 use miette::Diagnostic;
 use once_cell::sync::{Lazy, OnceCell};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use spk_schema::foundation::format::{FormatChange, FormatIdent, FormatOptionMap, FormatRequest};
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::name::{OptNameBuf, PkgName, PkgNameBuf};
 use spk_schema::foundation::option_map;
 use spk_schema::foundation::option_map::OptionMap;
-use spk_schema::foundation::version::Compatibility;
+use spk_schema::foundation::version::{Compatibility, IncompatibleReason};
 use spk_schema::ident::{
     InclusionPolicy,
     PinnedValue,
@@ -106,6 +108,8 @@ pub enum Change {
     SetPackage(Box<SetPackage>),
     SetPackageBuild(Box<SetPackageBuild>),
     StepBack(StepBack),
+    /// Removes an optional package request that could not be satisfied.
+    DropRequest(DropRequest),
 }
 
 impl Change {
@@ -117,6 +121,7 @@ impl Change {
             Change::SetPackage(sp) => sp.apply(parent, base),
             Change::SetPackageBuild(spb) => spb.apply(parent, base),
             Change::StepBack(sb) => sb.apply(parent, base),
+            Change::DropRequest(dr) => dr.apply(parent, base),
         }
     }
 
@@ -149,7 +154,11 @@ impl FormatChange for Change {
             RequestPackage(c) => {
                 format!(
                     "{} {}",
-                    Self::get_request_change_label(format_settings.level).blue(),
+                    format_settings.markers.mark(
+                        '+',
+                        Self::get_request_change_label(format_settings.level),
+                        Color::Blue
+                    ),
                     c.request.format_request(
                         c.request.pkg.repository_name.as_ref(),
                         &c.request.pkg.name,
@@ -160,7 +169,11 @@ impl FormatChange for Change {
             RequestVar(c) => {
                 format!(
                     "{} {}{}",
-                    Self::get_request_change_label(format_settings.level).blue(),
+                    format_settings.markers.mark(
+                        '+',
+                        Self::get_request_change_label(format_settings.level),
+                        Color::Blue
+                    ),
                     option_map! {c.request.var.clone() => c.request.value.to_string()}
                         .format_option_map(),
                     if format_settings.verbosity > PkgRequest::SHOW_REQUEST_DETAILS {
@@ -171,9 +184,20 @@ impl FormatChange for Change {
                 )
             }
             SetPackageBuild(c) => {
-                format!("{} {}", "BUILD".yellow(), c.spec.ident().format_ident())
+                format!(
+                    "{} {}",
+                    format_settings.markers.mark('+', "BUILD", Color::Yellow),
+                    c.spec.ident().format_ident()
+                )
             }
             SetPackage(c) => {
+                let ident = if format_settings.show_build_repository {
+                    c.spec
+                        .ident()
+                        .format_ident_with_repository(c.source.repository_name())
+                } else {
+                    c.spec.ident().format_ident()
+                };
                 if format_settings.verbosity > 0 {
                     // Work out who the requesters were, so this can show
                     // the resolved package and its requester(s)
@@ -210,20 +234,42 @@ impl FormatChange for Change {
                     // Show the resolved package and its requester(s)
                     format!(
                         "{} {}  (requested by {})",
-                        "RESOLVE".green(),
-                        c.spec.ident().format_ident(),
+                        format_settings.markers.mark('+', "RESOLVE", Color::Green),
+                        ident,
                         requested_by.join(", ")
                     )
                 } else {
                     // Just show the resolved package, don't show the requester(s)
-                    format!("{} {}", "RESOLVE".green(), c.spec.ident().format_ident())
+                    format!(
+                        "{} {}",
+                        format_settings.markers.mark('+', "RESOLVE", Color::Green),
+                        ident
+                    )
                 }
             }
             SetOptions(c) => {
-                format!("{} {}", "ASSIGN".cyan(), c.options.format_option_map())
+                format!(
+                    "{} {}",
+                    format_settings.markers.mark('~', "ASSIGN", Color::Cyan),
+                    c.options.format_option_map()
+                )
             }
             StepBack(c) => {
-                format!("{} {}", "BLOCKED".red(), c.cause)
+                format!(
+                    "{} {}",
+                    format_settings.markers.mark('-', "BLOCKED", Color::Red),
+                    c.cause
+                )
+            }
+            DropRequest(c) => {
+                format!(
+                    "{} {} - {}",
+                    format_settings
+                        .markers
+                        .mark('-', "DROP OPTIONAL", Color::Magenta),
+                    c.request.pkg,
+                    c.cause
+                )
             }
         }
     }
@@ -960,6 +1006,166 @@ pub enum Note {
     Other(String),
 }
 
+impl Note {
+    /// The stable code that identifies the kind of this note.
+    pub fn code(&self) -> NoteCode {
+        match self {
+            Note::SkipPackageNote(n) => n.code,
+            Note::Other(_) => NoteCode::Other,
+        }
+    }
+}
+
+impl Serialize for Note {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Note::SkipPackageNote(n) => n.serialize(serializer),
+            Note::Other(message) => {
+                let mut state = serializer.serialize_struct("Note", 2)?;
+                state.serialize_field("code", &NoteCode::Other)?;
+                state.serialize_field("message", message)?;
+                state.end()
+            }
+        }
+    }
+}
+
+/// A stable, machine-readable code for the reason behind a [`Note`].
+///
+/// Unlike the human-readable message of a note, these codes are
+/// intended to be aggregated across many solves and so existing
+/// codes must not be renamed or repurposed.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, strum::Display, strum::IntoStaticStr,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum NoteCode {
+    /// The package is already embedded by another package in the solve
+    SkipAlreadyEmbedded,
+    /// A build environment could not be resolved to build from source
+    SkipBuildEnvUnresolvable,
+    /// Building the package from source failed or is not allowed
+    SkipBuildFromSource,
+    /// The build id does not match what was requested
+    SkipBuildId,
+    /// The package does not provide the requested components
+    SkipComponentsMissing,
+    /// The package conflicts with an existing requirement
+    SkipConflictingRequirement,
+    /// The build or recipe is deprecated
+    SkipDeprecated,
+    /// The package conflicts with an embedded package
+    SkipEmbeddedConflict,
+    /// The package would add a request that cannot be satisfied
+    SkipImpossibleRequest,
+    /// The inclusion policy of the request is not compatible
+    SkipInclusionPolicy,
+    /// An internal error was encountered while checking the package
+    SkipInternalError,
+    /// None of the builds of the version are compatible
+    SkipNoCompatibleBuilds,
+    /// A build option or var request is not satisfied
+    SkipOption,
+    /// The package name or repository does not match the request
+    SkipPackageMismatch,
+    /// The version is a prerelease and prereleases are not allowed
+    SkipPrerelease,
+    /// The recipe for the package could not be found
+    SkipRecipeUnavailable,
+    /// The version is outside of the requested range
+    SkipVersionRange,
+    /// The package was skipped for some other reason
+    SkipOther,
+    /// A note that does not represent a skipped package
+    Other,
+}
+
+impl NoteCode {
+    /// Describe the builds that were skipped with this code, such
+    /// that it reads naturally after "all" or a number of builds.
+    pub fn skipped_builds_description(&self) -> &'static str {
+        match self {
+            NoteCode::SkipAlreadyEmbedded => "already embedded",
+            NoteCode::SkipBuildEnvUnresolvable => "without a resolvable build environment",
+            NoteCode::SkipBuildFromSource => "not buildable from source",
+            NoteCode::SkipBuildId => "not the requested build",
+            NoteCode::SkipComponentsMissing => "missing the requested components",
+            NoteCode::SkipConflictingRequirement => "conflicting with existing requirements",
+            NoteCode::SkipDeprecated => "deprecated",
+            NoteCode::SkipEmbeddedConflict => "conflicting with embedded packages",
+            NoteCode::SkipImpossibleRequest => "impossible",
+            NoteCode::SkipInclusionPolicy => "incompatible with the inclusion policy",
+            NoteCode::SkipInternalError => "failing with internal errors",
+            NoteCode::SkipNoCompatibleBuilds => "without compatible builds",
+            NoteCode::SkipOption => "filtered by options",
+            NoteCode::SkipPackageMismatch => "not from the requested package or repository",
+            NoteCode::SkipPrerelease => "prereleases",
+            NoteCode::SkipRecipeUnavailable => "missing their recipe",
+            NoteCode::SkipVersionRange => "outside of the requested version range",
+            NoteCode::SkipOther | NoteCode::Other => "skipped for other reasons",
+        }
+    }
+}
+
+impl From<&Compatibility> for NoteCode {
+    fn from(compat: &Compatibility) -> Self {
+        match compat {
+            Compatibility::Compatible => NoteCode::SkipOther,
+            Compatibility::Incompatible(reason) => reason.into(),
+        }
+    }
+}
+
+impl From<&IncompatibleReason> for NoteCode {
+    fn from(reason: &IncompatibleReason) -> Self {
+        use IncompatibleReason::*;
+        match reason {
+            AlreadyEmbeddedPackage { .. } => NoteCode::SkipAlreadyEmbedded,
+            BuildDeprecated | RecipeDeprecated => NoteCode::SkipDeprecated,
+            BuildFromSourceDisabled => NoteCode::SkipBuildFromSource,
+            BuildIdMismatch(_) | BuildIdNotSuperset(_) => NoteCode::SkipBuildId,
+            BuildOptionMismatch { .. }
+            | BuildOptionsMissing(_)
+            | OptionNotSatisfied { .. }
+            | OptionResolveError
+            | RequirementsNotSuperset { .. }
+            | VarOptionIllegalChoice { .. }
+            | VarOptionMismatch(_)
+            | VarOptionMissing(_)
+            | VarRequestNotSuperset(_)
+            | VarRequirementMismatch { .. } => NoteCode::SkipOption,
+            ComponentsMissing(_) => NoteCode::SkipComponentsMissing,
+            ConflictingEmbeddedPackage(_)
+            | ConflictingEmbeddedPackageRequirement(..)
+            | EmbeddedIncompatible { .. }
+            | PackageNotAnEmbeddedPackage => NoteCode::SkipEmbeddedConflict,
+            ConflictingRequirement(_) => NoteCode::SkipConflictingRequirement,
+            ImpossibleRequest(_) => NoteCode::SkipImpossibleRequest,
+            InclusionPolicyNotSuperset(_) => NoteCode::SkipInclusionPolicy,
+            InternalError(_) => NoteCode::SkipInternalError,
+            NoCompatibleBuilds { .. } => NoteCode::SkipNoCompatibleBuilds,
+            PackageNameMismatch(_) | PackageRepoMismatch(_) => NoteCode::SkipPackageMismatch,
+            PrereleasesNotAllowed => NoteCode::SkipPrerelease,
+            RangesDoNotIntersect { .. }
+            | RangeNotSuperset(_)
+            | VersionRangeInvalid { .. }
+            | VersionTooHigh(_)
+            | VersionTooLow(_)
+            | VersionNotCompatible(_)
+            | VersionNotDifferent(_)
+            | VersionNotEqual(_)
+            | VersionOutOfRange { .. } => NoteCode::SkipVersionRange,
+            // the restricted package is only context, the code
+            // comes from the underlying problem
+            Restrict { inner_reason, .. } => inner_reason.as_ref().into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RequestPackage {
     pub request: PkgRequestWithOptions,
@@ -1390,6 +1596,10 @@ pub struct State {
     // efficient for processing. This field does not contribute to the
     // state id. It is used to track the resolve order for a solution.
     packages_in_solve_order: Arc<Vec<Arc<Spec>>>,
+    // The optional requests that were dropped from the solve because
+    // they could not be satisfied. This field does not contribute to
+    // the state id.
+    dropped_requests: Arc<Vec<PkgRequest>>,
     options: Arc<BTreeMap<OptNameBuf, String>>,
     state_id: StateId,
     cached_option_map: Arc<OnceCell<OptionMap>>,
@@ -1429,6 +1639,7 @@ impl State {
             var_requests: Arc::new(var_requests),
             packages: Arc::new(BTreeMap::new()),
             packages_in_solve_order: Arc::new(Vec::new()),
+            dropped_requests: Arc::new(Vec::new()),
             options: Arc::new(options),
             state_id,
             cached_option_map: Arc::new(OnceCell::new()),
@@ -1457,6 +1668,9 @@ impl State {
                 .map_err(GraphError::RequestError)?;
             solution.add(req, Arc::clone(spec), source.clone());
         }
+        for request in self.dropped_requests.iter() {
+            solution.add_dropped_request(request.clone());
+        }
         Ok(solution)
     }
 
@@ -1569,6 +1783,7 @@ impl State {
         // TODO: consider changing the request list to only contain
         // requests that have not been satisfied, or only merged
         // requests, or both.
+        let mut optional_request = None;
         for request in self.pkg_requests.iter() {
             if self.packages.contains_key(&*request.pkg.name) {
                 continue;
@@ -1584,24 +1799,41 @@ impl State {
                 // expansion of dependencies.
                 continue;
             }
-            return Some(match self.get_merged_request(&request.pkg.name) {
-                Ok(request) => NextRequest::Request(request),
-                Err(super::error::GetMergedRequestError::Conflict { request, cause }) => {
-                    NextRequest::Conflict {
-                        request: *request,
-                        cause,
-                    }
+            if request.inclusion_policy == InclusionPolicy::IfCompatible {
+                // Optional requests are resolved after all the
+                // required ones, so that they are fitted into the
+                // environment rather than shaping it.
+                optional_request.get_or_insert(request);
+                continue;
+            }
+            return Some(self.next_request_for(&request.pkg.name));
+        }
+
+        optional_request.map(|request| self.next_request_for(&request.pkg.name))
+    }
+
+    fn next_request_for(&self, name: &PkgName) -> NextRequest {
+        match self.get_merged_request(name) {
+            Ok(request) => NextRequest::Request(request),
+            Err(super::error::GetMergedRequestError::Conflict { request, cause }) => {
+                NextRequest::Conflict {
+                    request: *request,
+                    cause,
                 }
-                Err(err) => NextRequest::Conflict {
-                    request: self
-                        .conflicting_request_for_package(&request.pkg.name)
-                        .expect("next unresolved request must exist"),
-                    cause: err.to_string(),
-                },
-            });
+            }
+            Err(err) => NextRequest::Conflict {
+                request: self
+                    .conflicting_request_for_package(name)
+                    .expect("next unresolved request must exist"),
+                cause: err.to_string(),
+            },
         }
+    }
 
-        None
+    /// The optional requests that were dropped from the solve
+    /// because they could not be satisfied.
+    pub fn get_dropped_requests(&self) -> &[PkgRequest] {
+        &self.dropped_requests
     }
 
     pub fn get_pkg_requests(&self) -> &Vec<Arc<CachedHash<PkgRequestWithOptions>>> {
@@ -1653,6 +1885,7 @@ impl State {
             var_requests: Arc::clone(&self.var_requests),
             packages: Arc::clone(&self.packages),
             packages_in_solve_order: Arc::clone(&self.packages_in_solve_order),
+            dropped_requests: Arc::clone(&self.dropped_requests),
             options: Arc::new(options),
             state_id,
             // options are changing
@@ -1682,6 +1915,7 @@ impl State {
             var_requests: Arc::clone(&self.var_requests),
             packages,
             packages_in_solve_order,
+            dropped_requests: Arc::clone(&self.dropped_requests),
             options: Arc::clone(&self.options),
             state_id,
             // options are the same
@@ -1703,6 +1937,7 @@ impl State {
             var_requests: Arc::clone(&self.var_requests),
             packages: Arc::clone(&self.packages),
             packages_in_solve_order: Arc::clone(&self.packages_in_solve_order),
+            dropped_requests: Arc::clone(&self.dropped_requests),
             options: Arc::clone(&self.options),
             state_id,
             // options are the same
@@ -1727,6 +1962,7 @@ impl State {
             var_requests,
             packages: Arc::clone(&self.packages),
             packages_in_solve_order: Arc::clone(&self.packages_in_solve_order),
+            dropped_requests: Arc::clone(&self.dropped_requests),
             options: Arc::new(options),
             state_id,
             // options are changing
@@ -1776,22 +2012,48 @@ impl std::fmt::Display for SkipPackageNoteReason {
 pub struct SkipPackageNote {
     pub pkg: AnyIdent,
     pub reason: SkipPackageNoteReason,
+    pub code: NoteCode,
 }
 
 impl SkipPackageNote {
     pub fn new(pkg: AnyIdent, reason: Compatibility) -> Self {
         SkipPackageNote {
             pkg,
+            code: NoteCode::from(&reason),
             reason: SkipPackageNoteReason::Compatibility(reason),
         }
     }
 
+    /// Create a note from a free-form message.
+    ///
+    /// The note will have the [`NoteCode::SkipOther`] code unless
+    /// one is provided with [`Self::with_code`].
     pub fn new_from_message<S: ToString>(pkg: AnyIdent, reason: S) -> Self {
         SkipPackageNote {
             pkg,
             reason: SkipPackageNoteReason::String(reason.to_string()),
+            code: NoteCode::SkipOther,
         }
     }
+
+    /// Replace the code of this note.
+    pub fn with_code(mut self, code: NoteCode) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+impl Serialize for SkipPackageNote {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SkipPackageNote", 3)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("pkg", &self.pkg.to_string())?;
+        state.serialize_field("message", &self.reason.to_string())?;
+        state.end()
+    }
 }
 
 impl IsSameReasonAs for SkipPackageNote {
@@ -1800,6 +2062,38 @@ impl IsSameReasonAs for SkipPackageNote {
     }
 }
 
+/// Leaves an optional package request out of the solve.
+///
+/// All of the `IfCompatible` requests for the package are removed
+/// from the state and recorded so that they can be reported with
+/// the solution. Any other requests for the package are kept.
+#[derive(Clone, Debug)]
+pub struct DropRequest {
+    pub request: PkgRequest,
+    pub cause: String,
+}
+
+impl DropRequest {
+    pub fn new(request: PkgRequest, cause: impl Into<String>) -> Self {
+        DropRequest {
+            request,
+            cause: cause.into(),
+        }
+    }
+
+    pub fn apply(&self, parent: &Arc<State>, base: &Arc<State>) -> Arc<State> {
+        let (dropped, new_requests): (Vec<_>, Vec<_>) =
+            base.pkg_requests.iter().cloned().partition(|request| {
+                request.pkg.name == self.request.pkg.name
+                    && request.inclusion_policy == InclusionPolicy::IfCompatible
+            });
+        let mut state = base.with_pkg_requests(parent, new_requests);
+        Arc::make_mut(&mut state.dropped_requests)
+            .extend(dropped.iter().map(|request| request.pkg_request.clone()));
+        Arc::new(state)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct StepBack {
     pub cause: String,
@@ -5,15 +5,16 @@
 use std::sync::Arc;
 
 use rstest::rstest;
-use spk_schema::foundation::format::{FormatChange, FormatChangeOptions};
+use spk_schema::foundation::format::{ChangeMarkers, FormatChange, FormatChangeOptions};
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::name::PkgName;
-use spk_schema::foundation::{opt_name, option_map};
+use spk_schema::foundation::version::{Compatibility, IncompatibleReason};
+use spk_schema::foundation::{build_ident, opt_name, option_map};
 use spk_schema::{recipe, spec};
 use spk_solve_solution::PackageSource;
 
 use super::DecisionBuilder;
-use crate::{Decision, graph};
+use crate::{Decision, Note, NoteCode, SkipPackageNote, graph};
 
 #[rstest]
 fn test_resolve_build_same_result() {
@@ -42,6 +43,7 @@ fn test_resolve_build_same_result() {
     let format_change_options = FormatChangeOptions {
         verbosity: 100,
         level: u64::MAX,
+        ..Default::default()
     };
 
     println!("resolve");
@@ -66,6 +68,37 @@ fn test_resolve_build_same_result() {
     );
 }
 
+#[rstest]
+#[case::color(ChangeMarkers::Color, false)]
+#[case::symbols(ChangeMarkers::Symbols, true)]
+#[case::color_and_symbols(ChangeMarkers::ColorAndSymbols, true)]
+fn test_format_change_markers(#[case] markers: ChangeMarkers, #[case] expect_symbol: bool) {
+    let spec = Arc::new(spec!({"pkg": "test/1.0.0/3I42H3S6"}));
+    let change = graph::Change::SetPackage(Box::new(graph::SetPackage::new(
+        spec,
+        PackageSource::SpkInternalTest,
+    )));
+    let format_change_options = FormatChangeOptions {
+        verbosity: 0,
+        markers,
+        ..Default::default()
+    };
+    let formatted = change.format_change(&format_change_options, None);
+    assert!(formatted.contains("RESOLVE"), "{formatted}");
+    assert_eq!(
+        formatted.contains("+ RESOLVE"),
+        expect_symbol,
+        "unexpected markers in {formatted:?}"
+    );
+}
+
+#[rstest]
+fn test_format_change_options_default_markers() {
+    // The default must not depend on the terminal that the
+    // process happens to be attached to
+    assert_eq!(FormatChangeOptions::default().markers, ChangeMarkers::Color);
+}
+
 #[rstest]
 fn test_empty_options_do_not_unset() {
     let state = graph::State::default_state();
@@ -142,3 +175,43 @@ fn test_request_default_component() {
         "default component should be injected when none specified"
     );
 }
+
+#[rstest]
+fn test_skip_package_note_serialized_code() {
+    let pkg = build_ident!("my-pkg/1.0.0/src").to_any_ident();
+
+    let note = Note::SkipPackageNote(Box::new(SkipPackageNote::new(
+        pkg.clone(),
+        Compatibility::Incompatible(IncompatibleReason::BuildDeprecated),
+    )));
+    assert_eq!(note.code(), NoteCode::SkipDeprecated);
+    let value = serde_json::to_value(&note).unwrap();
+    assert_eq!(value["code"], "SKIP_DEPRECATED");
+    assert_eq!(value["pkg"], "my-pkg/1.0.0/src");
+    assert_eq!(
+        value["message"],
+        "build is deprecated and not requested specifically"
+    );
+
+    let note = Note::SkipPackageNote(Box::new(
+        SkipPackageNote::new_from_message(pkg, "recipe not available")
+            .with_code(NoteCode::SkipRecipeUnavailable),
+    ));
+    let value = serde_json::to_value(&note).unwrap();
+    assert_eq!(value["code"], "SKIP_RECIPE_UNAVAILABLE");
+    assert_eq!(value["message"], "recipe not available");
+
+    let value = serde_json::to_value(Note::Other("something".into())).unwrap();
+    assert_eq!(value["code"], "OTHER");
+    assert_eq!(value["message"], "something");
+}
+
+#[rstest]
+fn test_note_code_uses_restricted_reason() {
+    let reason = IncompatibleReason::Restrict {
+        pkg: "my-pkg".parse().unwrap(),
+        inner_reason: Box::new(IncompatibleReason::PrereleasesNotAllowed),
+    };
+    assert_eq!(NoteCode::from(&reason), NoteCode::SkipPrerelease);
+    assert_eq!(NoteCode::SkipPrerelease.to_string(), "SKIP_PRERELEASE");
+}
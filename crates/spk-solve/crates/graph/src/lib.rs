@@ -19,16 +19,20 @@ pub use graph::{
     DEAD_STATE,
     DUPLICATE_REQUESTS_COUNT,
     Decision,
+    DropRequest,
     Graph,
     GraphError,
     NextRequest,
     Node,
     Note,
+    NoteCode,
     REQUESTS_FOR_SAME_PACKAGE_COUNT,
     RequestPackage,
     RequestVar,
     SetOptions,
+    SetPackage,
     SkipPackageNote,
+    SkipPackageNoteReason,
     State,
     StepBack,
 };
@@ -114,6 +114,10 @@ macro_rules! make_build {
 ///     a list of dependencies used to make the build (eg [depa, depb])
 ///     the options used to make the build (eg: {"debug" => "on"})
 ///     the list of component names to generate (eg: ["bin", "run"])
+///
+/// After all of those, `deprecated=true` can be given to mark the
+/// build as deprecated before it is published, eg:
+///     make_build_and_components!({"pkg": "mypkg/1.0.0"}, [], {}, [], deprecated=true)
 #[macro_export(local_inner_macros)]
 macro_rules! make_build_and_components {
     ($spec:tt) => {
@@ -129,6 +133,16 @@ macro_rules! make_build_and_components {
         let opts = spk_schema::foundation::option_map!{$($k => $v),*};
         make_build_and_components!($spec, [$($dep),*], opts, [$($component),*])
     }};
+    ($spec:tt, [$($dep:expr),*], { $($k:expr => $v:expr),* }, [$($component:expr),*], deprecated=$deprecated:expr) => {{
+        let opts = spk_schema::foundation::option_map!{$($k => $v),*};
+        make_build_and_components!($spec, [$($dep),*], opts, [$($component),*], deprecated=$deprecated)
+    }};
+    ($spec:tt, [$($dep:expr),*], $opts:expr, [$($component:expr),*], deprecated=$deprecated:expr) => {{
+        use spk_schema::DeprecateMut;
+        let (mut spec, components) = make_build_and_components!($spec, [$($dep),*], $opts, [$($component),*]);
+        spec.set_deprecated($deprecated).expect("Failed to set build deprecation");
+        (spec, components)
+    }};
     (recipe = $recipe:ident, [$($dep:expr),*], $opts:expr, [$($component:expr),*]) => {{
         use spk_schema::{Components, Package, Recipe};
         let mut components = std::collections::HashMap::<spk_schema::foundation::ident_component::Component, $crate::spfs::encoding::Digest>::new();
@@ -0,0 +1,52 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::sync::Arc;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use spk_schema::{Spec, spec};
+use spk_solve_package_iterator::BuildToSortedOptName;
+
+/// Make a unique, valid, build digest for the given number
+fn make_digest(n: usize) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    (0..8)
+        .map(|i| ALPHABET[(n >> (5 * i)) & 31] as char)
+        .collect()
+}
+
+pub fn sort_builds_benchmark(c: &mut Criterion) {
+    const NUM_BUILDS: usize = 256;
+    const NUM_OPTIONS: usize = 32;
+
+    // A wide set of builds of one version, with some options that
+    // vary across the builds and some that do not.
+    let builds: Vec<Arc<Spec>> = (0..NUM_BUILDS)
+        .map(|n| {
+            let options = (0..NUM_OPTIONS)
+                .map(|o| spk_schema::serde_json::json!({"var": format!("opt{o}/{}", n % (o + 1))}))
+                .collect::<Vec<_>>();
+            Arc::new(spec!({
+                "pkg": format!("wide/1.0.0/{}", make_digest(n)),
+                "build": {"options": options},
+            }))
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("sort builds");
+    group.throughput(Throughput::Elements(NUM_BUILDS as u64));
+    group.bench_function("BuildToSortedOptName::sort_builds", |b| {
+        b.iter(|| BuildToSortedOptName::sort_builds(builds.iter()))
+    });
+    // The same builds are sorted repeatedly during a solve, so all
+    // but the first iteration here reuse the cached result.
+    let sorted_opt_names = BuildToSortedOptName::default();
+    group.bench_function("BuildToSortedOptName::sort_builds_cached", |b| {
+        b.iter(|| sorted_opt_names.sort_builds_cached(builds.iter()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sort_builds_benchmark);
+criterion_main!(benches);
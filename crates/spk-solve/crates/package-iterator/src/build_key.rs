@@ -4,6 +4,7 @@
 
 //! This module defines the structures that make up compound build keys
 //! used in the 'by_build_option_values' build sorting method.
+use std::cmp::Reverse;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -94,6 +95,8 @@ mod build_key_test;
 ///     NotSet,
 ///     Text('apples'),
 ///     ...
+///     Published(Some(1714566600)),
+///     Text('BUILDDIGEST'),
 ///   ]
 /// )
 ///
@@ -134,11 +137,22 @@ impl BuildKey {
     /// for the matching build (pkg AnyIdent). If not, it will make a
     /// strange build key that could be unrelated to the build. See
     /// SortedBuildIterator for more details.
+    ///
+    /// The build's publish time, in seconds since the epoch, is used
+    /// to order builds that are otherwise equal. Newer builds are
+    /// preferred unless `prefer_oldest` is set.
+    ///
+    /// When `required_packages` is given, builds that require fewer
+    /// packages are put ahead of the others before their option
+    /// values are considered, see [`BuildKeyEntry::RequiredPackages`].
     pub fn new(
         pkg: &BuildIdent,
         ordering: &Vec<OptNameBuf>,
         name_values: &OptionMap,
         makes_an_impossible_request: bool,
+        required_packages: Option<usize>,
+        published: Option<i64>,
+        prefer_oldest: bool,
     ) -> BuildKey {
         if pkg.is_source() {
             // All '/src' builds use the same simplified key
@@ -150,16 +164,25 @@ impl BuildKey {
 
         // Binary builds (non-/src) use a compound key of option
         // values assembled using the given ordering (of option
-        // names). There are 2 extra special entries added, the first
-        // and the last. The first is for the impossible requests
-        // flag, and the last is for consistent tie-breaking.
-        let mut key_entries: Vec<BuildKeyEntry> = Vec::with_capacity(ordering.len() + 2);
+        // names). There are up to 4 extra special entries added, the
+        // first two and the last two. The first is for the impossible
+        // requests flag, the optional second is for the number of
+        // required packages, and the last two are for consistent
+        // tie-breaking.
+        let mut key_entries: Vec<BuildKeyEntry> = Vec::with_capacity(ordering.len() + 4);
 
         // The "does this request generate only possible requests?"
         // flag entry is first to give the most influence in the build key.
         let possible_requests = !makes_an_impossible_request;
         key_entries.push(BuildKeyEntry::PossibleRequests(possible_requests));
 
+        // The number of required packages goes ahead of the option
+        // values, otherwise builds with more dependencies would
+        // usually win on their extra, set, option values.
+        if let Some(required_packages) = required_packages {
+            key_entries.push(BuildKeyEntry::RequiredPackages(Reverse(required_packages)));
+        }
+
         for name in ordering {
             // Generate this entry based on the value for this name
             let entry: BuildKeyEntry = match name_values.get(name) {
@@ -189,6 +212,15 @@ impl BuildKey {
             key_entries.push(entry);
         }
 
+        // The publish time is added next so that otherwise identical
+        // builds are picked by age, rather than by their digests,
+        // which are effectively random. Builds are reverse sorted, so
+        // the time is negated to put older builds first when they
+        // are preferred. Builds without a publish time end up last
+        // either way.
+        let published = published.map(|t| if prefer_oldest { -t } else { t });
+        key_entries.push(BuildKeyEntry::Published(published));
+
         // The digest portion of the build's ident is added at the end
         // as a tie-breaker just in case two or more of the builds end
         // up with identical key entries up to this point. The digest
@@ -252,6 +284,15 @@ pub enum BuildKeyEntry {
     /// is used when the value was successfully expanded into a
     /// version range build key value, e.g. 6.3.1 or ~1.2.3
     ExpandedVersion(BuildKeyExpandedVersionRange),
+    /// This value is the time the build was published, in seconds
+    /// since the epoch, or negated if older builds are preferred. It
+    /// is None for builds that have no recorded publish time.
+    Published(Option<i64>),
+    /// This value is the number of packages the build requires, which
+    /// is reversed so builds that add fewer packages to a solve come
+    /// first. It is only present when the solver has been asked to
+    /// prefer the fewest packages.
+    RequiredPackages(Reverse<usize>),
 }
 
 impl std::fmt::Display for BuildKeyEntry {
@@ -261,6 +302,11 @@ impl std::fmt::Display for BuildKeyEntry {
             BuildKeyEntry::NotSet => f.write_str("NotSet"),
             BuildKeyEntry::Text(s) => f.write_str(s),
             BuildKeyEntry::ExpandedVersion(v) => f.write_str(&format!("{v}")),
+            BuildKeyEntry::Published(Some(t)) => f.write_str(&format!("Published: {t}")),
+            BuildKeyEntry::Published(None) => f.write_str("Published: unknown"),
+            BuildKeyEntry::RequiredPackages(Reverse(n)) => {
+                f.write_str(&format!("Required packages: {n}"))
+            }
         }
     }
 }
@@ -258,7 +258,15 @@ fn test_generating_build_key() {
     // Generate the build's key based on the ordering of option names
     let ordering = vec![name1, name2, name3, name4, name5];
     let impossible = false;
-    let key = BuildKey::new(a_build.ident(), &ordering, &resolved_options, impossible);
+    let key = BuildKey::new(
+        a_build.ident(),
+        &ordering,
+        &resolved_options,
+        impossible,
+        None,
+        None,
+        false,
+    );
 
     // Expected build key structure for this ordering and build options:
     // "alib", "somevalue", "notinthisbuild", "apkg", "versionbuild" build digest
@@ -313,6 +321,8 @@ fn test_generating_build_key() {
             vec![],
             true,
         )),
+        // no publish time
+        BuildKeyEntry::Published(None),
         // build digest as a string, it is always the last entry
         BuildKeyEntry::Text("TESTTEST".to_string()),
     ]);
@@ -347,10 +357,82 @@ fn test_generating_build_key_src_build() {
     // the ordering or option names in the key generation
     let ordering = vec![name1, name2, name3, name4];
     let impossible = true;
-    let key = BuildKey::new(a_build.ident(), &ordering, &resolved_options, impossible);
+    let key = BuildKey::new(
+        a_build.ident(),
+        &ordering,
+        &resolved_options,
+        impossible,
+        None,
+        None,
+        false,
+    );
 
     // Expected build key structure
     let expected = BuildKey::Src;
 
     assert_eq!(key, expected)
 }
+
+#[rstest]
+#[case::prefer_newest(false, vec!["NEWNEWNE", "OLDOLDOL", "NOTIMENO"])]
+#[case::prefer_oldest(true, vec!["OLDOLDOL", "NEWNEWNE", "NOTIMENO"])]
+fn test_build_key_publish_time_tie_breaker(
+    #[case] prefer_oldest: bool,
+    #[case] expected: Vec<&str>,
+) {
+    let builds = [
+        ("NOTIMENO", None),
+        ("OLDOLDOL", Some(1_600_000_000)),
+        ("NEWNEWNE", Some(1_700_000_000)),
+    ];
+    let options = OptionMap::default();
+    let mut keys = builds
+        .iter()
+        .map(|(digest, published)| {
+            let spec = spec!({"pkg": format!("testpackage/1.0.0/{digest}")});
+            let key = BuildKey::new(
+                spec.ident(),
+                &Vec::new(),
+                &options,
+                false,
+                None,
+                *published,
+                prefer_oldest,
+            );
+            (key, *digest)
+        })
+        .collect::<Vec<_>>();
+    // Builds are reverse sorted by their keys, see SortedBuildIterator
+    keys.sort_by(|a, b| b.0.cmp(&a.0));
+    let order = keys.into_iter().map(|(_, d)| d).collect::<Vec<_>>();
+    assert_eq!(order, expected);
+}
+
+#[rstest]
+fn test_build_key_required_packages_before_options() {
+    let name = opt_name!("flavor").to_owned();
+    let make_key = |digest: &str, flavor: &str, required_packages: Option<usize>| {
+        let spec = spec!({"pkg": format!("testpackage/1.0.0/{digest}")});
+        let mut options = OptionMap::default();
+        options.insert(name.clone(), flavor.to_string());
+        BuildKey::new(
+            spec.ident(),
+            &vec![name.clone()],
+            &options,
+            false,
+            required_packages,
+            None,
+            false,
+        )
+    };
+
+    // Builds are reverse sorted, see SortedBuildIterator
+    assert!(
+        make_key("STANDARD", "standard", None) > make_key("LITELITE", "lite", None),
+        "option values should decide when packages are not counted"
+    );
+    assert!(
+        make_key("LITELITE", "lite", Some(0)) > make_key("STANDARD", "standard", Some(1)),
+        "fewer required packages should win over the option values"
+    );
+}
@@ -7,15 +7,18 @@ mod error;
 mod package_iterator;
 mod promotion_patterns;
 
-pub use build_key::BuildKey;
+pub use build_key::{BuildKey, BuildKeyEntry};
 pub use error::{Error, Result};
 pub use package_iterator::{
     BUILD_SORT_TARGET,
     BuildIterator,
+    BuildKeyComparator,
     BuildToSortedOptName,
     EmptyBuildIterator,
+    EmptyBuildsReason,
     PackageIterator,
     RepositoryPackageIterator,
     SortedBuildIterator,
+    SortedOptNames,
 };
 pub use promotion_patterns::PromotionPatterns;
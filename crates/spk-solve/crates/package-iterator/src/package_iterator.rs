@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use dyn_clone::DynClone;
@@ -12,7 +12,7 @@ use once_cell::sync::Lazy;
 use spk_schema::foundation::name::{OptNameBuf, PkgNameBuf, RepositoryNameBuf};
 use spk_schema::foundation::option_map::OptionMap;
 use spk_schema::foundation::version::Version;
-use spk_schema::ident::{AsVersionIdent, VersionIdent};
+use spk_schema::ident::{AsVersionIdent, InclusionPolicy, RequestWithOptions, VersionIdent};
 use spk_schema::version::Compatibility;
 use spk_schema::{AnyIdent, BuildIdent, OptionValues, Package, Spec};
 use spk_solve_solution::PackageSource;
@@ -44,6 +44,14 @@ static BUILD_KEY_NAME_ORDER: Lazy<PromotionPatterns> = Lazy::new(|| {
     )
 });
 
+/// Whether to prefer the oldest, rather than the newest, of builds
+/// that are otherwise equivalent when ordering builds.
+static BUILD_KEY_PREFER_OLDEST: Lazy<bool> = Lazy::new(|| {
+    spk_config::get_config()
+        .map(|c| c.solver.build_key_prefer_oldest)
+        .unwrap_or_default()
+});
+
 type BuildWithRepos = HashMap<RepositoryNameBuf, (Arc<Spec>, PackageSource)>;
 
 #[async_trait::async_trait]
@@ -52,6 +60,10 @@ pub trait BuildIterator: DynClone + Send + Sync + std::fmt::Debug {
     fn is_sorted_build_iterator(&self) -> bool {
         false
     }
+    /// Why this iterator has no builds to give out, if it is known
+    fn empty_reason(&self) -> Option<&EmptyBuildsReason> {
+        None
+    }
     async fn next(&mut self) -> crate::Result<Option<BuildWithRepos>>;
     fn len(&self) -> usize;
 }
@@ -99,12 +111,61 @@ impl VersionIterator {
     fn new(versions: VecDeque<Arc<Version>>) -> Self {
         VersionIterator { versions }
     }
+
+    /// The versions that will be given out next, in order
+    fn upcoming(&self) -> impl Iterator<Item = &Arc<Version>> {
+        self.versions.iter()
+    }
 }
 
 type RepositoryByNameByVersion =
     HashMap<Arc<Version>, HashMap<RepositoryNameBuf, Arc<RepositoryHandle>>>;
 
+/// The number of upcoming versions whose builds are listed ahead of
+/// time by a [`RepositoryPackageIterator`].
+const PREFETCH_VERSIONS: usize = 4;
+
+/// The builds of a version being listed in the background.
+///
+/// The listing is cancelled if this is dropped before it is done.
+#[derive(Debug)]
+struct PrefetchedBuilds(tokio::task::JoinHandle<Result<RepositoryBuildIterator>>);
+
+impl PrefetchedBuilds {
+    fn spawn(
+        pkg: AnyIdent,
+        repos: HashMap<RepositoryNameBuf, Arc<RepositoryHandle>>,
+        embedded_stubs: bool,
+    ) -> Self {
+        Self(tokio::spawn(RepositoryBuildIterator::new(
+            pkg,
+            repos,
+            embedded_stubs,
+        )))
+    }
+
+    async fn join(mut self) -> Result<RepositoryBuildIterator> {
+        match (&mut self.0).await {
+            Ok(result) => result,
+            Err(err) => Err(Error::String(format!(
+                "Failed to list builds ahead of time: {err}"
+            ))),
+        }
+    }
+}
+
+impl Drop for PrefetchedBuilds {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 /// A stateful cursor yielding package builds from a set of repositories.
+///
+/// While the builds of one version are being used, the builds of the
+/// next few versions are listed in the background to hide the latency
+/// of remote repositories. Any listings still in progress are
+/// cancelled when the iterator is dropped.
 #[derive(Debug)]
 pub struct RepositoryPackageIterator {
     pub package_name: PkgNameBuf,
@@ -112,6 +173,7 @@ pub struct RepositoryPackageIterator {
     versions: Option<VersionIterator>,
     version_map: RepositoryByNameByVersion,
     builds_map: HashMap<Version, Arc<tokio::sync::Mutex<dyn BuildIterator + Send>>>,
+    prefetched: HashMap<Arc<Version>, PrefetchedBuilds>,
     active_version: Option<Arc<Version>>,
     embedded_stubs: bool,
 }
@@ -150,6 +212,7 @@ impl PackageIterator for RepositoryPackageIterator {
             version_map,
             // Python custom clone() doesn't clone the remaining fields
             builds_map: HashMap::default(),
+            prefetched: HashMap::default(),
             active_version: None,
             embedded_stubs: self.embedded_stubs,
         })
@@ -168,6 +231,7 @@ impl PackageIterator for RepositoryPackageIterator {
 
                 if self.active_version.is_none() {
                     self.active_version = self.versions.as_mut().and_then(|i| i.next());
+                    self.prefetch_builds();
                 }
                 let version = if let Some(active_version) = self.active_version.as_ref() {
                     active_version
@@ -179,6 +243,7 @@ impl PackageIterator for RepositoryPackageIterator {
                     // Clear the builds in order to repopulate them with stubs
                     // this time around.
                     self.builds_map.clear();
+                    self.prefetched.clear();
                     continue 'retry;
                 } else {
                     return Ok(None);
@@ -193,13 +258,18 @@ impl PackageIterator for RepositoryPackageIterator {
                 let pkg = VersionIdent::new(self.package_name.clone(), (**version).clone())
                     .into_any_ident(None);
                 if !self.builds_map.contains_key(version) {
-                    match RepositoryBuildIterator::new(
-                        pkg.clone(),
-                        repos.clone(),
-                        self.embedded_stubs,
-                    )
-                    .await
-                    {
+                    let builds = match self.prefetched.remove(version) {
+                        Some(prefetched) => prefetched.join().await,
+                        None => {
+                            RepositoryBuildIterator::new(
+                                pkg.clone(),
+                                repos.clone(),
+                                self.embedded_stubs,
+                            )
+                            .await
+                        }
+                    };
+                    match builds {
                         Ok(iter) => {
                             self.builds_map.insert(
                                 (**version).clone(),
@@ -244,11 +314,48 @@ impl RepositoryPackageIterator {
             versions: None,
             version_map: HashMap::default(),
             builds_map: HashMap::default(),
+            prefetched: HashMap::default(),
             active_version: None,
             embedded_stubs: false,
         }
     }
 
+    /// Start listing the builds of the next few versions in the
+    /// background, if they are not already known.
+    fn prefetch_builds(&mut self) {
+        let Some(versions) = self.versions.as_ref() else {
+            return;
+        };
+        let upcoming = versions
+            .upcoming()
+            .take(PREFETCH_VERSIONS)
+            .cloned()
+            .collect::<Vec<_>>();
+        // Listings for versions that are neither active nor upcoming
+        // are no longer needed
+        self.prefetched.retain(|version, _| {
+            self.active_version.as_ref() == Some(version) || upcoming.contains(version)
+        });
+        for version in upcoming {
+            if self.builds_map.contains_key(&*version) || self.prefetched.contains_key(&version) {
+                continue;
+            }
+            let Some(repos) = self.version_map.get(&version) else {
+                continue;
+            };
+            let pkg = VersionIdent::new(self.package_name.clone(), (*version).clone())
+                .into_any_ident(None);
+            let prefetched = PrefetchedBuilds::spawn(pkg, repos.clone(), self.embedded_stubs);
+            self.prefetched.insert(version, prefetched);
+        }
+    }
+
+    /// Cancel the listing of any builds that were started ahead of
+    /// time, e.g. because the solver will not need any more versions.
+    pub fn cancel_prefetch(&mut self) {
+        self.prefetched.clear();
+    }
+
     async fn build_version_map(&self) -> Result<RepositoryByNameByVersion> {
         let mut version_map: RepositoryByNameByVersion = HashMap::default();
         // Keep track of all the repos that possess this version so it is
@@ -409,8 +516,63 @@ impl RepositoryBuildIterator {
     }
 }
 
+/// Why none of the builds of a version could be used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EmptyBuildsReason {
+    /// The number of builds that the version had
+    pub num_builds: usize,
+    /// The number of builds skipped for each reason, keyed by a
+    /// description of the skipped builds, e.g. "deprecated"
+    pub skipped: BTreeMap<String, usize>,
+}
+
+impl EmptyBuildsReason {
+    pub fn new(num_builds: usize) -> Self {
+        Self {
+            num_builds,
+            skipped: BTreeMap::new(),
+        }
+    }
+
+    /// Count a build as skipped for the described reason
+    pub fn add_skipped<S: Into<String>>(&mut self, description: S) {
+        *self.skipped.entry(description.into()).or_default() += 1;
+    }
+}
+
+impl std::fmt::Display for EmptyBuildsReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let builds = if self.num_builds == 1 {
+            "build"
+        } else {
+            "builds"
+        };
+        write!(f, "had {} {builds}", self.num_builds)?;
+        match self.skipped.iter().collect::<Vec<_>>().as_slice() {
+            [] => Ok(()),
+            [(description, count)] if **count == self.num_builds => {
+                if self.num_builds == 1 {
+                    write!(f, ", {description}")
+                } else {
+                    write!(f, ", all {description}")
+                }
+            }
+            skipped => {
+                let skipped = skipped
+                    .iter()
+                    .map(|(description, count)| format!("{count} {description}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, ", none usable: {skipped}")
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
-pub struct EmptyBuildIterator {}
+pub struct EmptyBuildIterator {
+    reason: Option<EmptyBuildsReason>,
+}
 
 #[async_trait::async_trait]
 impl BuildIterator for EmptyBuildIterator {
@@ -418,6 +580,10 @@ impl BuildIterator for EmptyBuildIterator {
         true
     }
 
+    fn empty_reason(&self) -> Option<&EmptyBuildsReason> {
+        self.reason.as_ref()
+    }
+
     async fn next(&mut self) -> crate::Result<Option<BuildWithRepos>> {
         Ok(None)
     }
@@ -429,15 +595,88 @@ impl BuildIterator for EmptyBuildIterator {
 
 impl EmptyBuildIterator {
     pub fn new() -> Self {
-        EmptyBuildIterator {}
+        EmptyBuildIterator { reason: None }
+    }
+
+    /// Create an iterator for a version whose builds were all
+    /// passed over for the given reason
+    pub fn with_reason(reason: EmptyBuildsReason) -> Self {
+        EmptyBuildIterator {
+            reason: Some(reason),
+        }
     }
 }
 
+/// Iterates over the builds of a package version in order of their
+/// build keys, see [`BuildKey`].
+///
+/// The builds are kept in a heap rather than fully sorted up front,
+/// so each build is only put in its place when it is asked for. A
+/// solver that finds a usable build early on does not pay to sort
+/// the rest of them.
 #[derive(Clone, Debug)]
 pub struct SortedBuildIterator {
-    builds: VecDeque<BuildWithRepos>,
+    builds: BinaryHeap<SortedBuild>,
+}
+
+/// A caller supplied ordering of build keys for a
+/// [`SortedBuildIterator`], see [`SortedBuildIterator::new_with_comparator`].
+///
+/// Like the default ordering, the builds whose keys compare as the
+/// greatest are given out first.
+pub type BuildKeyComparator = dyn Fn(&BuildKey, &BuildKey) -> std::cmp::Ordering + Send + Sync;
+
+/// A build waiting in a [`SortedBuildIterator`].
+#[derive(Clone)]
+struct SortedBuild {
+    key: BuildKey,
+    /// The position the build was read from its source in, so that
+    /// builds with the same key come out in the order they went in
+    position: usize,
+    build: BuildWithRepos,
+    /// Orders the keys ahead of their own ordering, when given
+    comparator: Option<Arc<BuildKeyComparator>>,
+}
+
+impl std::fmt::Debug for SortedBuild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SortedBuild")
+            .field("key", &self.key)
+            .field("position", &self.position)
+            .field("build", &self.build)
+            .field("comparator", &self.comparator.is_some())
+            .finish()
+    }
+}
+
+impl Ord for SortedBuild {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // The heap gives out the greatest element first, which is
+        // the build with the highest key, and then the earliest read
+        let primary = match &self.comparator {
+            Some(comparator) => comparator(&self.key, &other.key),
+            None => std::cmp::Ordering::Equal,
+        };
+        primary
+            .then_with(|| self.key.cmp(&other.key))
+            .then_with(|| other.position.cmp(&self.position))
+    }
+}
+
+impl PartialOrd for SortedBuild {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortedBuild {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
 }
 
+impl Eq for SortedBuild {}
+
 #[async_trait::async_trait]
 impl BuildIterator for SortedBuildIterator {
     fn is_empty(&self) -> bool {
@@ -449,7 +688,7 @@ impl BuildIterator for SortedBuildIterator {
     }
 
     async fn next(&mut self) -> crate::Result<Option<BuildWithRepos>> {
-        Ok(self.builds.pop_front())
+        Ok(self.builds.pop().map(|sorted| sorted.build))
     }
 
     fn len(&self) -> usize {
@@ -468,12 +707,55 @@ struct ChangeCounter {
     pub use_it: bool,
 }
 
-pub struct BuildToSortedOptName {}
+/// The option names to use in build keys, in order of importance,
+/// and the option values of each binary build they were worked out
+/// from, see [`BuildToSortedOptName::sort_builds`].
+pub type SortedOptNames = (Vec<OptNameBuf>, HashMap<BuildIdent, OptionMap>);
+
+/// Works out the option names to use when making build keys for a
+/// set of builds.
+///
+/// An instance remembers the names it has worked out for each set of
+/// builds, so that sorting the same builds again, which happens often
+/// during a solve, does not have to look at all their options again.
+/// The remembered names are only valid for as long as the builds in
+/// the repositories do not change, so an instance should not be kept
+/// for longer than a single solve.
+#[derive(Debug, Default)]
+pub struct BuildToSortedOptName {
+    cache: Mutex<HashMap<Vec<BuildIdent>, Arc<SortedOptNames>>>,
+}
 
 impl BuildToSortedOptName {
-    pub fn sort_builds<'a>(
+    /// Like [`Self::sort_builds`], but reuses the result from an
+    /// earlier call for the same set of builds.
+    pub fn sort_builds_cached<'a>(
+        &self,
         builds: impl Iterator<Item = &'a Arc<Spec>>,
-    ) -> (Vec<OptNameBuf>, HashMap<BuildIdent, OptionMap>) {
+    ) -> Arc<SortedOptNames> {
+        let builds = builds.collect::<Vec<_>>();
+        // The result does not depend on the order of the builds
+        let mut key = builds.iter().map(|b| b.ident().clone()).collect::<Vec<_>>();
+        key.sort();
+
+        if let Some(sorted) = self
+            .cache
+            .lock()
+            .expect("sorted option names cache lock poisoned")
+            .get(&key)
+        {
+            return Arc::clone(sorted);
+        }
+
+        let sorted = Arc::new(Self::sort_builds(builds.into_iter()));
+        self.cache
+            .lock()
+            .expect("sorted option names cache lock poisoned")
+            .insert(key, Arc::clone(&sorted));
+        sorted
+    }
+
+    pub fn sort_builds<'a>(builds: impl Iterator<Item = &'a Arc<Spec>>) -> SortedOptNames {
         let mut number_non_src_builds: u64 = 0;
         let mut build_name_values: HashMap<BuildIdent, OptionMap> = HashMap::default();
         let mut changes: HashMap<OptNameBuf, ChangeCounter> = HashMap::new();
@@ -569,26 +851,78 @@ impl BuildToSortedOptName {
 }
 
 impl SortedBuildIterator {
+    /// Sort the builds from the source.
+    ///
+    /// If `prefer_fewest_packages` is set, builds that require fewer
+    /// packages are given out before builds with higher option values,
+    /// see [`SortedBuildIterator::count_required_packages`].
     pub async fn new(
         _options: OptionMap,
         source: Arc<tokio::sync::Mutex<dyn BuildIterator + Send>>,
         builds_with_impossible_requests: HashMap<BuildIdent, Compatibility>,
+        sorted_opt_names: &BuildToSortedOptName,
+        prefer_fewest_packages: bool,
     ) -> Result<Self> {
         // Note: _options is unused in this implementation, it was used
         // in the by_distance sorting implementation
-        let mut builds = VecDeque::<BuildWithRepos>::new();
-        {
-            let mut source_lock = source.lock().await;
-            while let Some(item) = source_lock.next().await? {
-                builds.push_back(item);
-            }
-        }
+        Ok(Self::sort_by_build_option_values(
+            Self::read_builds(source).await?,
+            builds_with_impossible_requests,
+            sorted_opt_names,
+            prefer_fewest_packages,
+            None,
+        ))
+    }
 
-        let mut sbi = SortedBuildIterator { builds };
+    /// Like [`Self::new`], but the builds are ordered by the given
+    /// comparator first.
+    ///
+    /// Builds that the comparator finds equal fall back to the usual
+    /// ordering of their keys.
+    pub async fn new_with_comparator(
+        _options: OptionMap,
+        source: Arc<tokio::sync::Mutex<dyn BuildIterator + Send>>,
+        builds_with_impossible_requests: HashMap<BuildIdent, Compatibility>,
+        sorted_opt_names: &BuildToSortedOptName,
+        prefer_fewest_packages: bool,
+        comparator: Box<BuildKeyComparator>,
+    ) -> Result<Self> {
+        Ok(Self::sort_by_build_option_values(
+            Self::read_builds(source).await?,
+            builds_with_impossible_requests,
+            sorted_opt_names,
+            prefer_fewest_packages,
+            Some(Arc::from(comparator)),
+        ))
+    }
 
-        sbi.sort_by_build_option_values(builds_with_impossible_requests)
-            .await;
-        Ok(sbi)
+    /// Read all the remaining builds out of the source
+    async fn read_builds(
+        source: Arc<tokio::sync::Mutex<dyn BuildIterator + Send>>,
+    ) -> Result<Vec<BuildWithRepos>> {
+        let mut builds = Vec::<BuildWithRepos>::new();
+        let mut source_lock = source.lock().await;
+        while let Some(item) = source_lock.next().await? {
+            builds.push(item);
+        }
+        Ok(builds)
+    }
+
+    /// The number of packages that resolving this build will add to
+    /// a solve.
+    ///
+    /// Requirements that are only used when their package is already
+    /// present are not counted.
+    pub fn count_required_packages(spec: &Spec) -> usize {
+        spec.runtime_requirements()
+            .iter()
+            .filter(|request| match request {
+                RequestWithOptions::Pkg(request) => {
+                    request.inclusion_policy == InclusionPolicy::Always
+                }
+                RequestWithOptions::Var(_) => false,
+            })
+            .count()
     }
 
     /// Helper for making BuildKey structures used in the sorting in
@@ -598,6 +932,7 @@ impl SortedBuildIterator {
         ordered_names: &Vec<OptNameBuf>,
         build_name_values: &HashMap<BuildIdent, OptionMap>,
         makes_an_impossible_request: bool,
+        prefer_fewest_packages: bool,
     ) -> BuildKey {
         let build_id = spec.ident();
         let empty = OptionMap::default();
@@ -610,45 +945,63 @@ impl SortedBuildIterator {
             ordered_names,
             name_values,
             makes_an_impossible_request,
+            prefer_fewest_packages.then(|| Self::count_required_packages(spec)),
+            spec.published().map(|p| p.timestamp()),
+            *BUILD_KEY_PREFER_OLDEST,
         )
     }
 
-    /// Sorts builds by keys based on ordered build option names and
+    /// Orders builds by keys based on ordered build option names and
     /// differing values in those options
-    async fn sort_by_build_option_values(
-        &mut self,
+    fn sort_by_build_option_values(
+        builds: Vec<BuildWithRepos>,
         builds_with_impossible_requests: HashMap<BuildIdent, Compatibility>,
-    ) {
+        sorted_opt_names: &BuildToSortedOptName,
+        prefer_fewest_packages: bool,
+        comparator: Option<Arc<BuildKeyComparator>>,
+    ) -> Self {
         let start = Instant::now();
 
-        let (key_entry_names, build_name_values) = BuildToSortedOptName::sort_builds(
-            self.builds
+        let sorted = sorted_opt_names.sort_builds_cached(
+            builds
                 .iter()
                 .flat_map(|hm| hm.values().map(|(spec, _src)| spec)),
         );
-
-        // Sort the builds by their generated keys generated from the
-        // ordered names and values worth including.
-        self.builds.make_contiguous().sort_by_cached_key(|hm| {
-            // Pull an arbitrary spec out from the hashmap
-            let spec = &hm.iter().next().expect("non-empty hashmap").1.0;
-            // Reverse the sort to get the build with the highest
-            // "numbers" in the earlier parts of its key to come first,
-            // which also reverse sorts the text values, i.e. "on" will
-            // come before "off".
-            std::cmp::Reverse(SortedBuildIterator::make_option_values_build_key(
-                spec,
-                &key_entry_names,
-                &build_name_values,
-                builds_with_impossible_requests.contains_key(&spec.ident().clone()),
-            ))
-        });
+        let (key_entry_names, build_name_values) = &*sorted;
+
+        // Generate each build's key from the ordered names and values
+        // worth including. The builds with the highest "numbers" in
+        // the earlier parts of their keys come out of the heap first,
+        // which also reverse sorts the text values, i.e. "on" will
+        // come before "off".
+        let builds = builds
+            .into_iter()
+            .enumerate()
+            .map(|(position, hm)| {
+                // Pull an arbitrary spec out from the hashmap
+                let spec = &hm.iter().next().expect("non-empty hashmap").1.0;
+                let key = SortedBuildIterator::make_option_values_build_key(
+                    spec,
+                    key_entry_names,
+                    build_name_values,
+                    builds_with_impossible_requests.contains_key(&spec.ident().clone()),
+                    prefer_fewest_packages,
+                );
+                SortedBuild {
+                    key,
+                    position,
+                    build: hm,
+                    comparator: comparator.clone(),
+                }
+            })
+            .collect::<BinaryHeap<_>>();
+        let sbi = SortedBuildIterator { builds };
 
         let duration: Duration = start.elapsed();
         tracing::info!(
             target: BUILD_SORT_TARGET,
             "Sort by build option values: {} builds in {} secs",
-            self.builds.len(),
+            sbi.builds.len(),
             duration.as_secs_f64()
         );
         tracing::debug!(
@@ -663,18 +1016,22 @@ impl SortedBuildIterator {
         tracing::debug!(
             target: BUILD_SORT_TARGET,
             "Keys by build option values: 'Build => Key : Options':\n {}",
-            self.builds
+            sbi.builds
+                .clone()
+                .into_sorted_vec()
                 .iter()
-                .flat_map(|hm| hm.values())
+                .rev()
+                .flat_map(|sorted| sorted.build.values())
                 .map(|(spec, _)| {
                     format!(
                         "{} = {} : {:?}",
                         spec.ident(),
                         SortedBuildIterator::make_option_values_build_key(
                             spec,
-                            &key_entry_names,
-                            &build_name_values,
+                            key_entry_names,
+                            build_name_values,
                             builds_with_impossible_requests.contains_key(&spec.ident().clone()),
+                            prefer_fewest_packages,
                         ),
                         spec.option_values(),
                     )
@@ -682,5 +1039,6 @@ impl SortedBuildIterator {
                 .collect::<Vec<String>>()
                 .join("\n ")
         );
+        sbi
     }
 }
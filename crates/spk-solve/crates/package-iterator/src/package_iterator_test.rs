@@ -2,18 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
-use std::collections::HashMap;
+use std::collections::{BinaryHeap, HashMap};
 use std::sync::Arc;
 
 use rstest::rstest;
 use spk_schema::foundation::name::PkgName;
-use spk_schema::foundation::option_map;
 use spk_schema::foundation::option_map::OptionMap;
 use spk_schema::foundation::version::Compatibility;
+use spk_schema::foundation::{opt_name, option_map};
 use spk_schema::{BuildIdent, OptionValues, Package, Spec, recipe, spec};
 use spk_solve_macros::{make_build, make_repo};
 
-use super::{BuildIterator, PackageIterator, RepositoryPackageIterator, SortedBuildIterator};
+use super::{
+    BuildIterator,
+    BuildKeyComparator,
+    BuildToSortedOptName,
+    EmptyBuildsReason,
+    PackageIterator,
+    RepositoryPackageIterator,
+    SortedBuild,
+    SortedBuildIterator,
+};
+use crate::build_key::{BuildKey, BuildKeyEntry};
 
 #[rstest]
 #[tokio::test]
@@ -187,6 +197,8 @@ async fn test_solver_sorted_build_iterator_sort_by_option_values() {
             OptionMap::default(),
             builds,
             builds_with_impossible_requests.clone(),
+            &BuildToSortedOptName::default(),
+            false,
         )
         .await
         .unwrap();
@@ -220,3 +232,186 @@ async fn test_solver_sorted_build_iterator_sort_by_option_values() {
         }
     }
 }
+
+#[rstest]
+fn test_sort_builds_cached_reuses_result() {
+    let builds = [
+        Arc::new(spec!({
+            "pkg": "mypkg/1.0.0/AAAAAAAA",
+            "build": {"options": [{"var": "debug/on"}, {"var": "same/value"}]},
+        })),
+        Arc::new(spec!({
+            "pkg": "mypkg/1.0.0/BBBBBBBB",
+            "build": {"options": [{"var": "debug/off"}, {"var": "same/value"}]},
+        })),
+    ];
+    let sorted_opt_names = BuildToSortedOptName::default();
+
+    let first = sorted_opt_names.sort_builds_cached(builds.iter());
+    assert_eq!(*first, BuildToSortedOptName::sort_builds(builds.iter()));
+    assert_eq!(first.0, vec![opt_name!("debug").to_owned()]);
+
+    let reversed = sorted_opt_names.sort_builds_cached(builds.iter().rev());
+    assert!(
+        Arc::ptr_eq(&first, &reversed),
+        "the same builds, in any order, should reuse the cached result"
+    );
+
+    let subset = sorted_opt_names.sort_builds_cached(builds[..1].iter());
+    assert!(!Arc::ptr_eq(&first, &subset));
+    assert!(subset.0.is_empty());
+}
+
+#[rstest]
+fn test_sorted_build_heap_matches_stable_sort() {
+    let keys = [
+        BuildKey::Src,
+        BuildKey::Binary(vec![]),
+        BuildKey::Src,
+        BuildKey::Binary(vec![BuildKeyEntry::NotSet]),
+        BuildKey::Src,
+    ];
+    let mut heap = keys
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(position, key)| SortedBuild {
+            key,
+            position,
+            build: HashMap::new(),
+            comparator: None,
+        })
+        .collect::<BinaryHeap<_>>();
+
+    // The eager sort that the heap replaced was a stable sort of
+    // the reversed keys
+    let mut expected = keys.iter().enumerate().collect::<Vec<_>>();
+    expected.sort_by_key(|(_, key)| std::cmp::Reverse(*key));
+
+    let mut order = Vec::new();
+    while let Some(sorted) = heap.pop() {
+        order.push(sorted.position);
+    }
+    assert_eq!(
+        order,
+        expected.into_iter().map(|(p, _)| p).collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+#[case::no_builds(0, &[], "had 0 builds")]
+#[case::single(1, &["deprecated"], "had 1 build, deprecated")]
+#[case::all_same(4, &["deprecated"; 4], "had 4 builds, all deprecated")]
+#[case::mixed(
+    3,
+    &["deprecated", "filtered by options", "deprecated"],
+    "had 3 builds, none usable: 2 deprecated, 1 filtered by options"
+)]
+fn test_empty_builds_reason_display(
+    #[case] num_builds: usize,
+    #[case] skipped: &[&str],
+    #[case] expected: &str,
+) {
+    let mut reason = EmptyBuildsReason::new(num_builds);
+    for description in skipped {
+        reason.add_skipped(*description);
+    }
+    assert_eq!(reason.to_string(), expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_repository_package_iterator_prefetch_keeps_order() {
+    let repo = make_repo!([
+        {"pkg": "my-pkg/1.0.0"},
+        {"pkg": "my-pkg/1.1.0"},
+        {"pkg": "my-pkg/1.2.0"},
+        {"pkg": "my-pkg/2.0.0"},
+        {"pkg": "my-pkg/2.1.0"},
+        {"pkg": "my-pkg/3.0.0"},
+        {"pkg": "my-pkg/3.1.0"},
+    ]);
+    let repos = vec![Arc::new(repo)];
+    let pkg_name = PkgName::new("my-pkg").unwrap();
+
+    let mut iterator = RepositoryPackageIterator::new(pkg_name.to_owned(), repos.clone());
+    let mut versions = Vec::new();
+    while let Some((pkg, _builds)) = iterator.next().await.unwrap() {
+        versions.push(pkg.version().to_string());
+    }
+    assert_eq!(
+        versions,
+        [
+            "3.1.0", "3.0.0", "2.1.0", "2.0.0", "1.2.0", "1.1.0", "1.0.0"
+        ],
+        "versions should be iterated from highest to lowest"
+    );
+
+    // Stopping early, with listings still in progress, is fine
+    let mut iterator = RepositoryPackageIterator::new(pkg_name.to_owned(), repos);
+    let (pkg, _) = iterator.next().await.unwrap().unwrap();
+    assert_eq!(pkg.version().to_string(), "3.1.0");
+    iterator.cancel_prefetch();
+    let (pkg, _) = iterator.next().await.unwrap().unwrap();
+    assert_eq!(pkg.version().to_string(), "3.0.0");
+}
+
+#[rstest]
+#[case::default_order(None, &["3.0.0", "2.0.0", "1.0.0"])]
+#[case::inverted(
+    Some(Box::new(|a: &BuildKey, b: &BuildKey| b.cmp(a)) as Box<BuildKeyComparator>),
+    &["1.0.0", "2.0.0", "3.0.0"]
+)]
+#[tokio::test]
+async fn test_sorted_build_iterator_with_comparator(
+    #[case] comparator: Option<Box<BuildKeyComparator>>,
+    #[case] expected: &[&str],
+) {
+    let repo = make_repo!([
+        {"pkg": "my-pkg/1.0.0", "build": {"options": [{"var": "toolchain/1.0.0"}]}},
+        {"pkg": "my-pkg/1.0.0", "build": {"options": [{"var": "toolchain/3.0.0"}]}},
+        {"pkg": "my-pkg/1.0.0", "build": {"options": [{"var": "toolchain/2.0.0"}]}},
+    ]);
+    let pkg_name = PkgName::new("my-pkg").unwrap();
+    let mut rp_iterator = RepositoryPackageIterator::new(pkg_name.to_owned(), vec![Arc::new(repo)]);
+    let (_pkg, builds) = rp_iterator.next().await.unwrap().unwrap();
+
+    let mut iterator = match comparator {
+        None => {
+            SortedBuildIterator::new(
+                OptionMap::default(),
+                builds,
+                HashMap::new(),
+                &BuildToSortedOptName::default(),
+                false,
+            )
+            .await
+        }
+        Some(comparator) => {
+            SortedBuildIterator::new_with_comparator(
+                OptionMap::default(),
+                builds,
+                HashMap::new(),
+                &BuildToSortedOptName::default(),
+                false,
+                comparator,
+            )
+            .await
+        }
+    }
+    .unwrap();
+
+    let mut order = Vec::new();
+    while let Some(hm) = iterator.next().await.unwrap() {
+        for (build, _) in hm.values() {
+            order.push(
+                build
+                    .option_values()
+                    .get(opt_name!("toolchain"))
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+        }
+    }
+    assert_eq!(order, expected);
+}
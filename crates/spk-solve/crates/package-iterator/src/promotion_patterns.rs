@@ -13,6 +13,10 @@ pub struct PromotionPatterns(Vec<Pattern>);
 
 impl PromotionPatterns {
     /// Parse a comma-separated string into a list of patterns.
+    ///
+    /// Patterns use shell-style globs (`*`, `?`, `[abc]`), so a
+    /// pattern without any wildcards only matches the exact name.
+    /// Patterns that are not valid globs are ignored.
     pub fn new(comma_separated_patterns: &str) -> Self {
         Self(
             comma_separated_patterns
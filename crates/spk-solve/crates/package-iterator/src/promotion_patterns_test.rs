@@ -11,6 +11,12 @@ use crate::PromotionPatterns;
 #[case::pattern_order_matters_1("gcc,python", &["a", "python", "b", "gcc"], &["gcc", "python", "a", "b"])]
 #[case::pattern_order_matters_2("python,gcc", &["a", "python", "b", "gcc"], &["python", "gcc", "a", "b"])]
 #[case::pattern_glob("*platform*,python,gcc", &["a", "python", "b", "gcc", "spi-platform"], &["spi-platform", "python", "gcc", "a", "b"])]
+#[case::pattern_prefix("gcc*", &["a", "clang", "gcc-arch", "b", "gcc"], &["gcc-arch", "gcc", "a", "clang", "b"])]
+#[case::pattern_single_char("py?", &["python", "pyq", "a", "py3"], &["pyq", "py3", "python", "a"])]
+#[case::pattern_char_class("vfx[0-9]*", &["vfxplatform", "vfx2023", "a", "vfx9"], &["vfx2023", "vfx9", "vfxplatform", "a"])]
+#[case::pattern_exact_no_wildcard("gcc", &["gcc-arch", "gcc", "gcc6"], &["gcc", "gcc-arch", "gcc6"])]
+#[case::pattern_first_match_wins("gcc,g*,*", &["b", "gnu", "gcc", "a"], &["gcc", "gnu", "b", "a"])]
+#[case::pattern_invalid_ignored("[,gcc", &["a", "gcc"], &["gcc", "a"])]
 fn test_promote_names(#[case] patterns: &str, #[case] input: &[&str], #[case] expected: &[&str]) {
     let patterns = PromotionPatterns::new(patterns);
     let mut subject = input.to_owned();
@@ -12,6 +12,8 @@ pub use solution::{
     LayerPackageAndComponents,
     PackageSource,
     Solution,
+    SolutionTable,
+    SolveStats,
     SolvedRequest,
     find_highest_package_version,
     get_spfs_layers_to_packages,
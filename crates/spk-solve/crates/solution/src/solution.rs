@@ -19,8 +19,14 @@ use spk_schema::foundation::format::{
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::option_map::OptionMap;
 use spk_schema::foundation::version::VERSION_SEP;
-use spk_schema::ident::{InitialRawRequest, PkgRequest, PkgRequestWithOptions, RequestedBy};
-use spk_schema::name::{PkgNameBuf, RepositoryNameBuf};
+use spk_schema::ident::{
+    InitialRawRequest,
+    PkgRequest,
+    PkgRequestWithOptions,
+    PreReleasePolicy,
+    RequestedBy,
+};
+use spk_schema::name::{PkgName, PkgNameBuf, RepositoryName, RepositoryNameBuf};
 use spk_schema::prelude::*;
 use spk_schema::version::Version;
 use spk_schema::{
@@ -37,9 +43,15 @@ use spk_storage::RepositoryHandle;
 
 use crate::{Error, PackageSolveData, PackagesToSolveData, Result};
 
+#[cfg(test)]
+#[path = "./solution_test.rs"]
+mod solution_test;
+
 const SOLUTION_FORMAT_EMPTY_REPORT: &str = "Nothing Installed";
 const SOLUTION_FORMAT_HEADING: &str = "Installed Packages:\n";
 const SOLUTION_FORMAT_FOOTER: &str = "Number of Packages:";
+const SOLUTION_FORMAT_DROPPED: &str = "Dropped Optional Requests:";
+const SOLUTION_TREE_SEEN_MARKER: &str = "(*)";
 
 const PACKAGE_COLUMN: usize = 0;
 const VERSION_COLUMN: usize = 1;
@@ -74,6 +86,14 @@ impl PackageSource {
         matches!(self, Self::BuildFromSource { .. })
     }
 
+    /// The name of the repository that this package was found in, if any
+    pub fn repository_name(&self) -> Option<&RepositoryName> {
+        match self {
+            PackageSource::Repository { repo, .. } => Some(repo.name()),
+            _ => None,
+        }
+    }
+
     pub async fn read_recipe(&self, ident: &VersionIdent) -> Result<Arc<SpecRecipe>> {
         match self {
             PackageSource::BuildFromSource { recipe } => Ok(Arc::clone(recipe)),
@@ -162,11 +182,7 @@ impl SolvedRequest {
             PkgRequest::from_ident(self.spec.ident().to_any_ident(), RequestedBy::DoesNotMatter);
         installed.pkg.components = self.selected_components();
 
-        let repo_name = if let PackageSource::Repository { repo, .. } = &self.source {
-            Some(repo.name().to_owned())
-        } else {
-            None
-        };
+        let repo_name = self.source.repository_name().map(ToOwned::to_owned);
 
         // Pass zero verbosity to format_request(), via the format
         // change options, to stop it outputting the internal details.
@@ -195,6 +211,21 @@ impl SolvedRequest {
         options.format_option_map()
     }
 
+    /// The prerelease policy that was in effect when resolving this
+    /// request.
+    pub fn prerelease_policy(&self) -> PreReleasePolicy {
+        self.request.prerelease_policy.unwrap_or_default()
+    }
+
+    /// Format the prerelease policy for this solved request, if it
+    /// is not the default.
+    pub(crate) fn format_prerelease_policy(&self) -> String {
+        match self.prerelease_policy() {
+            PreReleasePolicy::ExcludeAll => String::new(),
+            policy => format!(" {{prereleasePolicy: {policy}}}"),
+        }
+    }
+
     /// Returns the spfs layers in a resolved request, from its source
     /// repo, in a mapping of components to layers. This results in an
     /// error for packages without a source repo: an
@@ -323,11 +354,29 @@ pub fn get_spfs_layers_to_packages<'a>(
     Ok(layers_to_packages)
 }
 
+/// Counts of the work a solver did to produce a [`Solution`].
+///
+/// Solvers that do not track a counter leave it at zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// The number of decisions applied to the solve graph
+    pub decisions: u64,
+    /// The number of times the solver stepped back after a conflict
+    pub steps_back: u64,
+    /// The number of requests made for a package that was already
+    /// requested, possibly at another version
+    pub requests_for_same_package: u64,
+    /// The number of requests that were identical to an existing one
+    pub duplicate_requests: u64,
+}
+
 /// Represents a set of resolved packages.
 #[derive(Clone, Debug, Default)]
 pub struct Solution {
     options: OptionMap,
     resolved: Vec<SolvedRequest>,
+    dropped: Vec<PkgRequest>,
+    stats: SolveStats,
 }
 
 impl Solution {
@@ -335,6 +384,8 @@ impl Solution {
         Self {
             options,
             resolved: Default::default(),
+            dropped: Default::default(),
+            stats: Default::default(),
         }
     }
 
@@ -380,6 +431,30 @@ impl Solution {
         }
     }
 
+    /// Record an optional request that was left out of this solution
+    /// because it could not be satisfied.
+    pub fn add_dropped_request(&mut self, request: PkgRequest) {
+        if !self.dropped.contains(&request) {
+            self.dropped.push(request);
+        }
+    }
+
+    /// The optional requests that were left out of this solution
+    /// because they could not be satisfied.
+    pub fn dropped_requests(&self) -> &[PkgRequest] {
+        &self.dropped
+    }
+
+    /// The work done by the solver to find this solution
+    pub fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    /// Record the work done by the solver to find this solution
+    pub fn set_stats(&mut self, stats: SolveStats) {
+        self.stats = stats;
+    }
+
     /// Return the set of repositories in this solution.
     pub fn repositories(&self) -> Vec<Arc<RepositoryHandle>> {
         let mut seen = HashSet::new();
@@ -507,6 +582,35 @@ impl Solution {
             .await)
     }
 
+    /// Format the resolved packages as a markdown table.
+    ///
+    /// The output is plain text without any terminal colors, so
+    /// that it can be included in documentation or posted as a
+    /// comment, eg: by CI.
+    pub fn format_solution_as_markdown(&self) -> String {
+        if self.is_empty() {
+            return SOLUTION_FORMAT_EMPTY_REPORT.to_string();
+        }
+
+        let mut out = String::from("| Name | Version | Build | Components |\n");
+        out.push_str("| --- | --- | --- | --- |\n");
+
+        let mut required_items = self.resolved.clone();
+        required_items.sort_by(|a, b| a.spec.name().cmp(b.spec.name()));
+        for req in required_items {
+            let ident = req.spec.ident();
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                markdown_cell(ident.name()),
+                markdown_cell(ident.version()),
+                markdown_cell(ident.build()),
+                markdown_cell(req.selected_components().iter().join(", ")),
+            );
+        }
+        out
+    }
+
     fn format_solution_without_padding_or_highest(&self, verbosity: u8) -> String {
         let mut out = SOLUTION_FORMAT_HEADING.to_string();
 
@@ -527,16 +631,108 @@ impl Solution {
 
                 if verbosity > 1 {
                     // Show the options for this package (build)
-                    let _ = write!(out, " {}", req.format_package_options());
+                    let _ = write!(
+                        out,
+                        " {}{}",
+                        req.format_package_options(),
+                        req.format_prerelease_policy()
+                    );
                 }
             }
             out.push('\n');
         }
 
         let _ = write!(out, " {SOLUTION_FORMAT_FOOTER} {number_of_packages}");
+        self.format_dropped_requests(&mut out);
+        out
+    }
+
+    /// Format the solution as a tree of the packages that required
+    /// each other.
+    ///
+    /// Packages that were not required by another package in the
+    /// solution, such as those requested on the command line, are at
+    /// the top level. Packages required by more than one other are
+    /// only expanded the first time that they appear, and are marked
+    /// with `(*)` everywhere else.
+    pub fn format_solution_tree(&self) -> String {
+        if self.is_empty() {
+            return SOLUTION_FORMAT_EMPTY_REPORT.to_string();
+        }
+
+        let resolved = self
+            .resolved
+            .iter()
+            .map(|req| (req.spec.name(), req))
+            .collect::<BTreeMap<_, _>>();
+        let mut dependencies: BTreeMap<&PkgName, BTreeSet<&PkgName>> = BTreeMap::new();
+        let mut required = HashSet::new();
+        for (name, req) in resolved.iter() {
+            for requester in req.request.get_requesters() {
+                let parent = match &requester {
+                    RequestedBy::PackageBuild(ident) | RequestedBy::Embedded(ident) => ident.name(),
+                    _ => continue,
+                };
+                if let Some((parent, _)) = resolved.get_key_value(parent)
+                    && parent != name
+                {
+                    dependencies.entry(*parent).or_default().insert(*name);
+                    required.insert(*name);
+                }
+            }
+        }
+
+        let mut out = String::from(SOLUTION_FORMAT_HEADING);
+        let mut seen = HashSet::new();
+        let roots = resolved
+            .keys()
+            .filter(|name| !required.contains(*name))
+            .copied()
+            .collect_vec();
+        for root in roots {
+            format_solution_tree_node(
+                &mut out,
+                root,
+                &resolved,
+                &dependencies,
+                &mut seen,
+                "  ",
+                "",
+            );
+        }
+        // packages that only require each other have no root,
+        // so start from the first of them that was not shown
+        for name in resolved.keys() {
+            if !seen.contains(name) {
+                format_solution_tree_node(
+                    &mut out,
+                    *name,
+                    &resolved,
+                    &dependencies,
+                    &mut seen,
+                    "  ",
+                    "",
+                );
+            }
+        }
+
+        let _ = write!(out, " {SOLUTION_FORMAT_FOOTER} {}", resolved.len());
+        self.format_dropped_requests(&mut out);
         out
     }
 
+    /// Append the dropped optional requests, if there are any.
+    fn format_dropped_requests(&self, out: &mut String) {
+        if self.dropped.is_empty() {
+            return;
+        }
+        let _ = write!(
+            out,
+            "\n {SOLUTION_FORMAT_DROPPED} {}",
+            self.dropped.iter().map(|r| r.pkg.to_string()).join(", ")
+        );
+    }
+
     async fn format_solution_with_padding_and_highest(
         &self,
         verbosity: u8,
@@ -551,176 +747,22 @@ impl Solution {
             self.items().collect_vec().into_iter()
         };
 
-        let number_of_packages = required_items.len();
-        let mut data: Vec<Vec<(usize, String)>> = Vec::with_capacity(number_of_packages + 1);
-
-        let package_heading = String::from("Package");
-        let version_heading = String::from("Version");
-        let highest_heading = String::from("Newest");
-        let size_heading = String::from("Size");
-        let package_heading_width = console::measure_text_width(&package_heading);
-        let version_heading_width = console::measure_text_width(&version_heading);
-        let highest_heading_width = console::measure_text_width(&highest_heading);
-        let size_heading_width = console::measure_text_width(&size_heading);
-
-        let mut header: Vec<(usize, String)> = vec![
-            (package_heading_width, package_heading),
-            (version_heading_width, version_heading),
-            (highest_heading_width, highest_heading),
-        ];
-        if verbosity >= 1 {
-            header.push((size_heading_width, size_heading));
-        }
-
-        if verbosity == 1 {
-            // Zero because not padding this column
-            header.push((0, String::from("Requested by")));
-        }
-        if verbosity > 1 {
-            // Zero because not padding this column
-            header.push((0, String::from("Requested by w/ {build options}")));
-        }
-        data.push(header);
-
-        let mut max_widths: Vec<usize> = vec![
-            package_heading_width,
-            version_heading_width,
-            highest_heading_width,
-            size_heading_width,
-            0,
-            0,
-        ];
-
-        let mut total_size = 0;
-
-        // This only pads the first 2 columns at the moment: the
-        // packages and the highest_versions. The remaining columns
-        // are unpadded.
+        let mut table =
+            SolutionTable::new(verbosity).with_highest_versions(highest_versions.clone());
+        let mut data: Vec<TableRow> = Vec::with_capacity(required_items.len() + 1);
+        data.push(table.header_row());
         for req in required_items {
-            // Work out the disk usage size of the resolved build components.
-            let size = match &req.source {
-                PackageSource::Repository { repo, components } => {
-                    match spk_storage::get_components_disk_usage(
-                        repo.clone(),
-                        Arc::new(req.spec.ident().clone()),
-                        components,
-                    )
-                    .await
-                    {
-                        Ok(disk_usage) => disk_usage.size,
-                        Err(err) => {
-                            tracing::warn!(
-                                "Problem working out disk size of {}: {err}",
-                                req.spec.ident().to_string()
-                            );
-                            0
-                        }
-                    }
-                }
-                // Other package sources are ignored for disk usage
-                _ => 0,
-            };
-            total_size += size;
-
-            // Assemble the output line for this resolved request
-            let mut line: Vec<(usize, String)> = Vec::new();
-
-            // Get installed request with components and repo name included
-            let package = req.format_as_installed_package();
-
-            let l = console::measure_text_width(&package);
-            if l > max_widths[PACKAGE_COLUMN] {
-                max_widths[PACKAGE_COLUMN] = l;
-            }
-            line.push((l, package));
-
-            // Get the request's solved version
-            let version = req.spec.ident().version().to_string();
-
-            let l = console::measure_text_width(&version);
-            if l > max_widths[VERSION_COLUMN] {
-                max_widths[VERSION_COLUMN] = l;
-            }
-            line.push((l, version));
-
-            // Add whether this request is for the highest version of
-            // the package, or what the highest version of the package is.
-            let highest_label = match highest_versions.get(req.spec.name()) {
-                Some(highest_version) => {
-                    if *req.spec.ident().version() == **highest_version {
-                        "-".dimmed()
-                    } else {
-                        highest_version.to_string().dimmed()
-                    }
-                }
-                None => "".black(),
-            };
-
-            let l = console::measure_text_width(&highest_label);
-            if l > max_widths[HIGHEST_VERSION_COLUMN] {
-                max_widths[HIGHEST_VERSION_COLUMN] = l;
-            }
-            line.push((l, highest_label.to_string()));
-
-            // Optionally, add the last 3 columns: the disk usage, the
-            // things that requested this package, and the package's
-            // build options.
-            if verbosity > 0 {
-                let mut disk_usage = if size < 1024 {
-                    format!("{} ", spk_storage::human_readable(size))
-                } else {
-                    spk_storage::human_readable(size)
-                };
-                // Right aligning the size column by using a minimum
-                // width of 8 because the human readable format, after
-                // the adjustment above, is at least "ddd.d UU" = 8
-                let delta = 8usize.saturating_sub(disk_usage.len());
-                if delta > 0 {
-                    let padding = " ".repeat(delta);
-                    disk_usage = format!("{padding}{disk_usage}");
-                }
-
-                let l = console::measure_text_width(&disk_usage);
-                if l > max_widths[DISK_SIZE_COLUMN] {
-                    max_widths[DISK_SIZE_COLUMN] = l;
-                }
-                line.push((l, disk_usage));
-
-                // Zero because not padding this value's column
-                line.push((0, req.format_package_requesters()));
-
-                if verbosity > 1 {
-                    // Zero because not padding this value's column
-                    line.push((0, req.format_package_options()));
-                }
-            }
-
-            data.push(line);
+            data.push(table.row(req).await);
         }
 
-        // Output the data, one package per line, with padding between
-        // the values on each line.
+        // Output the data, one package per line, once all the rows
+        // are known so that every line is padded to the same widths.
         for line in data {
-            out.push_str("  ");
-
-            for (col_index, (length, value)) in line.into_iter().enumerate() {
-                let mut max_width = max_widths[col_index];
-                if max_width == 0 {
-                    max_width = length
-                }
-                let padding = " ".repeat(max_width - length);
-
-                let _ = write!(out, "{value}{padding}  ");
-            }
-
+            out.push_str(&table.format_row(line));
             out.push('\n');
         }
 
-        let human_readable_size = spk_storage::human_readable(total_size);
-        let _ = write!(
-            out,
-            " {SOLUTION_FORMAT_FOOTER} {number_of_packages}   (Total Size: {human_readable_size})"
-        );
+        out.push_str(&table.format_footer(self));
         out
     }
 
@@ -766,6 +808,59 @@ impl BuildEnv for Solution {
     }
 }
 
+/// Append one package of a solution tree, along with all of the
+/// packages that it required.
+fn format_solution_tree_node<'a>(
+    out: &mut String,
+    name: &'a PkgName,
+    resolved: &BTreeMap<&'a PkgName, &'a SolvedRequest>,
+    dependencies: &BTreeMap<&'a PkgName, BTreeSet<&'a PkgName>>,
+    seen: &mut HashSet<&'a PkgName>,
+    prefix: &str,
+    connector: &str,
+) {
+    let Some(req) = resolved.get(name) else {
+        return;
+    };
+    let children = dependencies.get(name);
+    let first_time = seen.insert(name);
+    let _ = write!(
+        out,
+        "{prefix}{connector}{}",
+        req.format_as_installed_package()
+    );
+    if !first_time && children.is_some() {
+        let _ = write!(out, " {SOLUTION_TREE_SEEN_MARKER}");
+    }
+    out.push('\n');
+    let Some(children) = children.filter(|_| first_time) else {
+        return;
+    };
+
+    let child_prefix = match connector {
+        "├── " => format!("{prefix}│   "),
+        "└── " => format!("{prefix}    "),
+        _ => prefix.to_string(),
+    };
+    let last = children.len() - 1;
+    for (index, child) in children.iter().enumerate() {
+        let connector = if index == last {
+            "└── "
+        } else {
+            "├── "
+        };
+        format_solution_tree_node(
+            out,
+            child,
+            resolved,
+            dependencies,
+            seen,
+            &child_prefix,
+            connector,
+        );
+    }
+}
+
 impl FormatSolution for Solution {
     fn format_solution(&self, verbosity: u8) -> String {
         if self.is_empty() {
@@ -776,6 +871,285 @@ impl FormatSolution for Solution {
     }
 }
 
+/// The values of one line of the solution table, with their display widths
+type TableRow = Vec<(usize, String)>;
+
+/// Formats resolved packages as the lines of the solution table.
+///
+/// The same table is used to format a complete solution at once and
+/// to show each package as soon as the solver resolves it. When the
+/// packages are shown one at a time, each line can only be padded to
+/// the widest values seen so far.
+#[derive(Clone, Debug)]
+pub struct SolutionTable {
+    verbosity: u8,
+    highest_versions: HashMap<PkgNameBuf, Arc<Version>>,
+    max_widths: Vec<usize>,
+    sizes: HashMap<PkgNameBuf, u64>,
+}
+
+impl SolutionTable {
+    const PACKAGE_HEADING: &str = "Package";
+    const VERSION_HEADING: &str = "Version";
+    const HIGHEST_HEADING: &str = "Newest";
+    const SIZE_HEADING: &str = "Size";
+
+    pub fn new(verbosity: u8) -> Self {
+        Self {
+            verbosity,
+            highest_versions: HashMap::new(),
+            max_widths: vec![
+                console::measure_text_width(Self::PACKAGE_HEADING),
+                console::measure_text_width(Self::VERSION_HEADING),
+                console::measure_text_width(Self::HIGHEST_HEADING),
+                console::measure_text_width(Self::SIZE_HEADING),
+                0,
+                0,
+            ],
+            sizes: HashMap::new(),
+        }
+    }
+
+    /// Use these highest available versions for the newest version column
+    pub fn with_highest_versions(
+        mut self,
+        highest_versions: HashMap<PkgNameBuf, Arc<Version>>,
+    ) -> Self {
+        self.highest_versions = highest_versions;
+        self
+    }
+
+    /// The heading lines that come before any packages in the table
+    pub fn format_heading(&self) -> String {
+        format!(
+            "{}{}",
+            SOLUTION_FORMAT_HEADING,
+            self.format_row(self.header_row())
+        )
+    }
+
+    /// Format the line for a single resolved package.
+    ///
+    /// The highest version of the package is looked up in the
+    /// given repositories if it is not already known.
+    pub async fn format_package(
+        &mut self,
+        req: &SolvedRequest,
+        repos: &[Arc<RepositoryHandle>],
+    ) -> String {
+        let name = req.spec.name();
+        if !repos.is_empty() && !self.highest_versions.contains_key(name) {
+            match find_highest_package_version(name.to_owned(), repos).await {
+                Ok(version) => {
+                    self.highest_versions.insert(name.to_owned(), version);
+                }
+                Err(err) => {
+                    tracing::warn!("Problem finding the highest version of {name}: {err}");
+                }
+            }
+        }
+        let row = self.row(req).await;
+        self.format_row(row)
+    }
+
+    /// Forget the packages that are not resolved in the given state
+    /// of the solve, returning the names of those that were removed.
+    ///
+    /// This keeps the total size correct when the solver steps back
+    /// and unsets packages that were already shown.
+    pub fn retain_packages<F>(&mut self, mut keep: F) -> Vec<PkgNameBuf>
+    where
+        F: FnMut(&PkgName) -> bool,
+    {
+        let removed = self
+            .sizes
+            .keys()
+            .filter(|name| !keep(name))
+            .cloned()
+            .sorted()
+            .collect_vec();
+        for name in removed.iter() {
+            self.sizes.remove(name);
+        }
+        removed
+    }
+
+    /// The line that ends the table, summarizing the given solution
+    pub fn format_footer(&self, solution: &Solution) -> String {
+        if solution.is_empty() {
+            return SOLUTION_FORMAT_EMPTY_REPORT.to_string();
+        }
+        let total_size: u64 = solution
+            .items()
+            .filter_map(|item| self.sizes.get(item.spec.name()))
+            .sum();
+        let human_readable_size = spk_storage::human_readable(total_size);
+        let mut out = format!(
+            " {SOLUTION_FORMAT_FOOTER} {}   (Total Size: {human_readable_size})",
+            solution.len()
+        );
+        solution.format_dropped_requests(&mut out);
+        out
+    }
+
+    fn header_row(&self) -> TableRow {
+        let mut header: TableRow = [
+            Self::PACKAGE_HEADING,
+            Self::VERSION_HEADING,
+            Self::HIGHEST_HEADING,
+        ]
+        .into_iter()
+        .map(|heading| (console::measure_text_width(heading), heading.to_string()))
+        .collect();
+        if self.verbosity >= 1 {
+            header.push((
+                console::measure_text_width(Self::SIZE_HEADING),
+                Self::SIZE_HEADING.to_string(),
+            ));
+        }
+
+        if self.verbosity == 1 {
+            // Zero because not padding this column
+            header.push((0, String::from("Requested by")));
+        }
+        if self.verbosity > 1 {
+            // Zero because not padding this column
+            header.push((0, String::from("Requested by w/ {build options}")));
+        }
+        header
+    }
+
+    /// Assemble the values for a resolved package, and widen the
+    /// columns to fit them.
+    ///
+    /// This only pads the first 4 columns at the moment: the
+    /// package, version, highest version, and disk size. The
+    /// remaining columns are unpadded.
+    async fn row(&mut self, req: &SolvedRequest) -> TableRow {
+        // Work out the disk usage size of the resolved build components.
+        let size = match &req.source {
+            PackageSource::Repository { repo, components } => {
+                match spk_storage::get_components_disk_usage(
+                    repo.clone(),
+                    Arc::new(req.spec.ident().clone()),
+                    components,
+                )
+                .await
+                {
+                    Ok(disk_usage) => disk_usage.size,
+                    Err(err) => {
+                        tracing::warn!(
+                            "Problem working out disk size of {}: {err}",
+                            req.spec.ident().to_string()
+                        );
+                        0
+                    }
+                }
+            }
+            // Other package sources are ignored for disk usage
+            _ => 0,
+        };
+        self.sizes.insert(req.spec.name().to_owned(), size);
+
+        // Assemble the output line for this resolved request
+        let mut line: TableRow = Vec::new();
+
+        // Get installed request with components and repo name included
+        let package = req.format_as_installed_package();
+
+        let l = console::measure_text_width(&package);
+        if l > self.max_widths[PACKAGE_COLUMN] {
+            self.max_widths[PACKAGE_COLUMN] = l;
+        }
+        line.push((l, package));
+
+        // Get the request's solved version
+        let version = req.spec.ident().version().to_string();
+
+        let l = console::measure_text_width(&version);
+        if l > self.max_widths[VERSION_COLUMN] {
+            self.max_widths[VERSION_COLUMN] = l;
+        }
+        line.push((l, version));
+
+        // Add whether this request is for the highest version of
+        // the package, or what the highest version of the package is.
+        let highest_label = match self.highest_versions.get(req.spec.name()) {
+            Some(highest_version) => {
+                if *req.spec.ident().version() == **highest_version {
+                    "-".dimmed()
+                } else {
+                    highest_version.to_string().dimmed()
+                }
+            }
+            None => "".black(),
+        };
+
+        let l = console::measure_text_width(&highest_label);
+        if l > self.max_widths[HIGHEST_VERSION_COLUMN] {
+            self.max_widths[HIGHEST_VERSION_COLUMN] = l;
+        }
+        line.push((l, highest_label.to_string()));
+
+        // Optionally, add the last 3 columns: the disk usage, the
+        // things that requested this package, and the package's
+        // build options.
+        if self.verbosity > 0 {
+            let mut disk_usage = if size < 1024 {
+                format!("{} ", spk_storage::human_readable(size))
+            } else {
+                spk_storage::human_readable(size)
+            };
+            // Right aligning the size column by using a minimum
+            // width of 8 because the human readable format, after
+            // the adjustment above, is at least "ddd.d UU" = 8
+            let delta = 8usize.saturating_sub(disk_usage.len());
+            if delta > 0 {
+                let padding = " ".repeat(delta);
+                disk_usage = format!("{padding}{disk_usage}");
+            }
+
+            let l = console::measure_text_width(&disk_usage);
+            if l > self.max_widths[DISK_SIZE_COLUMN] {
+                self.max_widths[DISK_SIZE_COLUMN] = l;
+            }
+            line.push((l, disk_usage));
+
+            // Zero because not padding this value's column
+            line.push((0, req.format_package_requesters()));
+
+            if self.verbosity > 1 {
+                // Zero because not padding this value's column
+                line.push((
+                    0,
+                    format!(
+                        "{}{}",
+                        req.format_package_options(),
+                        req.format_prerelease_policy()
+                    ),
+                ));
+            }
+        }
+
+        line
+    }
+
+    /// Output the values of one line, with padding between them.
+    fn format_row(&self, line: TableRow) -> String {
+        let mut out = String::from("  ");
+        for (col_index, (length, value)) in line.into_iter().enumerate() {
+            let mut max_width = self.max_widths[col_index];
+            if max_width == 0 {
+                max_width = length
+            }
+            let padding = " ".repeat(max_width.saturating_sub(length));
+
+            let _ = write!(out, "{value}{padding}  ");
+        }
+        out
+    }
+}
+
 /// Helper for finding the highest version number of a package in a list of repositories
 pub async fn find_highest_package_version(
     name: PkgNameBuf,
@@ -791,3 +1165,9 @@ pub async fn find_highest_package_version(
     }
     Ok(max_version)
 }
+
+/// Format a value for use in a markdown table cell, escaping any
+/// characters that would otherwise end the cell early.
+fn markdown_cell(value: impl std::fmt::Display) -> String {
+    value.to_string().replace('|', "\\|")
+}
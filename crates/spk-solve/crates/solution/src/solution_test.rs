@@ -0,0 +1,15 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+
+use super::markdown_cell;
+
+#[rstest]
+#[case::plain("my-pkg", "my-pkg")]
+#[case::pipe("run|build", "run\\|build")]
+#[case::many_pipes("|a||b|", "\\|a\\|\\|b\\|")]
+fn test_markdown_cell_escapes_pipes(#[case] value: &str, #[case] expected: &str) {
+    assert_eq!(markdown_cell(value), expected);
+}
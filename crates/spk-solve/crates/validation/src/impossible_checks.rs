@@ -8,7 +8,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use dashmap::DashMap;
 use futures::stream::{FuturesUnordered, StreamExt};
-use spk_schema::foundation::format::{FormatChangeOptions, FormatRequest};
+use spk_schema::foundation::format::{ChangeMarkers, FormatChangeOptions, FormatRequest};
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::name::{PkgName, PkgNameBuf};
 use spk_schema::foundation::version::Compatibility;
@@ -51,6 +51,10 @@ const TASK_CHANNEL_MESSAGE_CAPACITY: usize = 128;
 const REQUEST_FORMAT_OPTIONS: FormatChangeOptions = FormatChangeOptions {
     verbosity: 100,
     level: 100,
+    // only used for formatting requests, which are unmarked
+    markers: ChangeMarkers::Color,
+    show_requested_by: false,
+    show_build_repository: false,
 };
 
 /// The default set of validators used for impossible version request
@@ -424,6 +428,18 @@ impl ImpossibleRequestsChecker {
                 continue;
             }
 
+            if combined_request.inclusion_policy == InclusionPolicy::IfCompatible {
+                // IfCompatible requests are dropped by the solver
+                // when they cannot be satisfied, so they never make
+                // a package impossible to use.
+                tracing::debug!(
+                    target: IMPOSSIBLE_CHECKS_TARGET,
+                    "Combined request: {} has `IfCompatible` set, so it's possible",
+                    combined_request.pkg_request,
+                );
+                continue;
+            }
+
             if self.impossible_requests.contains_key(&combined_request.pkg) {
                 tracing::debug!(
                     target: IMPOSSIBLE_CHECKS_TARGET,
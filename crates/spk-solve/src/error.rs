@@ -3,13 +3,22 @@
 // https://github.com/spkenv/spk
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use colored::Colorize;
 use miette::Diagnostic;
 use spk_schema::VersionIdent;
 use spk_schema::foundation::format::FormatError;
+use spk_schema::foundation::name::OptNameBuf;
+use spk_schema::foundation::version::{
+    Compatibility,
+    IncompatibleReason,
+    VarOptionProblem,
+    parse_version,
+};
 use spk_schema::ident::PkgRequest;
-use spk_solve_graph::Note;
+use spk_solve_graph::{Note, SkipPackageNoteReason, State};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -25,10 +34,16 @@ pub enum Error {
     #[error(transparent)]
     #[diagnostic(forward(0))]
     OutOfOptions(Box<OutOfOptions>),
+    #[error(transparent)]
+    #[diagnostic(forward(0))]
+    UnsatisfiableVarRequest(Box<UnsatisfiableVarRequest>),
     #[error("Solver interrupted: {0}")]
     SolverInterrupted(String),
     #[error(transparent)]
     #[diagnostic(forward(0))]
+    SolverTimedOut(Box<SolverTimedOut>),
+    #[error(transparent)]
+    #[diagnostic(forward(0))]
     SpkIdentComponentError(#[from] spk_schema::foundation::ident_component::Error),
     #[error(transparent)]
     #[diagnostic(forward(0))]
@@ -53,8 +68,16 @@ pub enum Error {
     SpkSpecError(Box<spk_schema::Error>),
     #[error("Status bar IO error: {0}")]
     StatusBarIOError(#[source] std::io::Error),
-    #[error("Initial requests contain {0} impossible request{plural}.", plural = if *.0 == 1 { "" } else { "s" } )]
-    InitialRequestsContainImpossibleError(usize),
+    #[error(
+        "Initial requests contain {count} impossible request{plural}:{list}",
+        count = .0.len(),
+        plural = if .0.len() == 1 { "" } else { "s" },
+        list = ImpossibleRequest::format_list(.0),
+    )]
+    InitialRequestsContainImpossibleError(Vec<ImpossibleRequest>),
+    #[error(transparent)]
+    #[diagnostic(forward(0))]
+    FailedToResolveImpossibleRequests(Box<FailedToResolveImpossibleRequests>),
     #[error(transparent)]
     #[diagnostic(forward(0))]
     SpkStorageError(#[from] spk_storage::Error),
@@ -97,6 +120,167 @@ pub struct OutOfOptions {
     pub notes: Vec<Note>,
 }
 
+/// A request that the impossible request checks found can never be
+/// satisfied by the builds in the repositories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImpossibleRequest {
+    /// The request, or the build whose requirements would make it
+    pub request: String,
+    pub reason: IncompatibleReason,
+}
+
+impl ImpossibleRequest {
+    /// Format each request on its own indented line
+    fn format_list(requests: &[ImpossibleRequest]) -> String {
+        requests
+            .iter()
+            .map(|request| format!("\n - {request}"))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ImpossibleRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is impossible: {}", self.request, self.reason)
+    }
+}
+
+/// The solver failed to find a solution, and the impossible request
+/// checks had found requests that could never be satisfied, which
+/// are the likely cause.
+#[derive(Diagnostic, Debug, Error)]
+#[diagnostic(
+    code(spk::solve::impossible_requests),
+    help("No build satisfies these requests together, check the requirements that made them")
+)]
+#[error(
+    "Failed to resolve, found {count} impossible request{plural}:{list}",
+    count = .impossible_requests.len(),
+    plural = if .impossible_requests.len() == 1 { "" } else { "s" },
+    list = ImpossibleRequest::format_list(.impossible_requests),
+)]
+pub struct FailedToResolveImpossibleRequests {
+    #[source]
+    pub source: spk_solve_graph::Error,
+    pub impossible_requests: Vec<ImpossibleRequest>,
+}
+
+/// The solver ran past its configured timeout before finding a solution.
+#[derive(Diagnostic, Debug, Error)]
+#[diagnostic(
+    code(spk::solve::timed_out),
+    help("Raise the solver timeout, or inspect the partial state to see where it got stuck")
+)]
+#[error(
+    "Solver timed out after {elapsed:?} with {resolved} package{plural} resolved",
+    resolved = .deepest_state.get_resolved_packages().len(),
+    plural = if .deepest_state.get_resolved_packages().len() == 1 { "" } else { "s" }
+)]
+pub struct SolverTimedOut {
+    pub elapsed: Duration,
+    /// The deepest state reached before the timeout, holding the
+    /// packages that had been resolved so far
+    pub deepest_state: Arc<State>,
+}
+
+/// A package ran out of options because none of its builds had the
+/// value requested for one of its options.
+#[derive(Diagnostic, Debug, Error)]
+#[diagnostic(
+    code(spk::solve::unsatisfiable_var_request),
+    help("Request one of the available values, or build the package with the requested one")
+)]
+#[error(
+    "option {var}={requested} requested but only {{{available}}} available",
+    available = .available.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
+)]
+pub struct UnsatisfiableVarRequest {
+    pub request: PkgRequest,
+    pub var: OptNameBuf,
+    pub requested: String,
+    /// The values of the option in the builds that were skipped,
+    /// in version order
+    pub available: Vec<String>,
+    pub notes: Vec<Note>,
+}
+
+impl UnsatisfiableVarRequest {
+    /// Identify when every build skipped in `err` failed to satisfy
+    /// the same option request, and nothing else.
+    pub fn from_out_of_options(err: &OutOfOptions) -> Option<Self> {
+        let mut found: Option<Self> = None;
+        for note in err.notes.iter() {
+            let Note::SkipPackageNote(note) = note else {
+                continue;
+            };
+            let SkipPackageNoteReason::Compatibility(Compatibility::Incompatible(reason)) =
+                &note.reason
+            else {
+                continue;
+            };
+            let (var, available, requested) = Self::var_mismatch(reason)?;
+            let found = found.get_or_insert_with(|| Self {
+                request: err.request.clone(),
+                var: var.clone(),
+                requested: requested.to_string(),
+                available: Vec::new(),
+                notes: err.notes.clone(),
+            });
+            if found.var != *var || found.requested != requested {
+                return None;
+            }
+            if !available.is_empty() && !found.available.iter().any(|v| v == available) {
+                found.available.push(available.to_string());
+            }
+        }
+        if let Some(found) = found.as_mut() {
+            found.available.sort_by(|a, b| compare_option_values(a, b));
+        }
+        found
+    }
+
+    /// The option name, the value of the build and the requested
+    /// value when `reason` is a mismatched option request.
+    fn var_mismatch(reason: &IncompatibleReason) -> Option<(&OptNameBuf, &str, &str)> {
+        let IncompatibleReason::OptionNotSatisfied { inner_reason } = reason else {
+            return None;
+        };
+        let IncompatibleReason::VarOptionMismatch(problem) = &**inner_reason else {
+            return None;
+        };
+        match problem {
+            VarOptionProblem::IncompatibleBuildOption {
+                var_request,
+                exact,
+                request_value,
+            }
+            | VarOptionProblem::IncompatibleBuildOptionWithContext {
+                var_request,
+                exact,
+                request_value,
+                ..
+            } => Some((var_request, exact, request_value)),
+            VarOptionProblem::IncompatibleBuildOptionInvalidVersion {
+                var_request,
+                base,
+                request_value,
+            } => Some((var_request, base, request_value)),
+            _ => None,
+        }
+    }
+}
+
+/// Order option values as versions when they can be parsed as
+/// versions, so that "9" comes before "11", and as strings otherwise.
+fn compare_option_values(a: &str, b: &str) -> std::cmp::Ordering {
+    match (parse_version(a), parse_version(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
 #[async_trait::async_trait]
 impl FormatError for Error {
     async fn format_error(&self, verbosity: u8) -> String {
@@ -111,6 +295,12 @@ impl FormatError for Error {
                 msg.push_str(err.as_str());
             }
             Error::GraphError(err) => return err.format_error(verbosity).await,
+            Error::FailedToResolveImpossibleRequests(err) => {
+                let mut msg = err.source.format_error(verbosity).await;
+                msg.push_str("\n * impossible requests found during the solve:");
+                msg.push_str(&ImpossibleRequest::format_list(&err.impossible_requests));
+                return msg;
+            }
             err => {
                 msg.push_str("\n * ");
                 msg.push_str(err.to_string().as_str());
@@ -20,7 +20,9 @@ use futures::stream::FuturesUnordered;
 use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use spk_schema::foundation::format::{
+    ChangeMarkers,
     FormatChange,
     FormatChangeOptions,
     FormatIdent,
@@ -40,9 +42,18 @@ use spk_solve_graph::{
     State,
 };
 
+use crate::solution::{SolutionTable, SolvedRequest};
 use crate::solvers::step::ErrorFreq;
 use crate::solvers::{StepSolver, StepSolverRuntime};
-use crate::{Error, Result, Solution, Solver, StatusLine, show_search_space_stats};
+use crate::{
+    Error,
+    RepositoryHandle,
+    Result,
+    Solution,
+    Solver,
+    StatusLine,
+    show_search_space_stats,
+};
 #[cfg(feature = "statsd")]
 use crate::{
     SPK_SOLUTION_PACKAGE_COUNT_METRIC,
@@ -100,6 +111,122 @@ pub fn format_note(note: &Note) -> String {
     }
 }
 
+/// How solver decisions are written out by a [`DecisionFormatter`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Human-readable, colored lines
+    #[default]
+    Text,
+    /// One [`DecisionRecord`] per line, as json
+    Json,
+}
+
+/// The kind of change or note that a [`DecisionRecord`] describes.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionRecordKind {
+    RequestPackage,
+    RequestVar,
+    SetOptions,
+    SetPackage,
+    SetPackageBuild,
+    StepBack,
+    DropRequest,
+    SkipPackage,
+    Note,
+}
+
+/// A single change or note from a solver decision, as written
+/// out with [`OutputFormat::Json`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DecisionRecord {
+    /// The depth of the decision in the solve
+    pub level: u64,
+    pub kind: DecisionRecordKind,
+    /// The package or request involved, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pkg: Option<String>,
+    /// Any additional value, such as a var request or options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Why the change was made or the package was skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The stable code of a note, see [`spk_solve_graph::NoteCode`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl DecisionRecord {
+    fn new(level: u64, kind: DecisionRecordKind) -> Self {
+        Self {
+            level,
+            kind,
+            pkg: None,
+            detail: None,
+            reason: None,
+            code: None,
+        }
+    }
+
+    /// The records for the notes and then the changes of a decision.
+    pub fn from_decision(level: u64, decision: &Decision) -> Vec<Self> {
+        use DecisionRecordKind as Kind;
+        let notes = decision.notes.iter().map(|note| {
+            let mut record = match note {
+                Note::SkipPackageNote(n) => Self {
+                    pkg: Some(n.pkg.to_string()),
+                    reason: Some(n.reason.to_string()),
+                    ..Self::new(level, Kind::SkipPackage)
+                },
+                Note::Other(message) => Self {
+                    reason: Some(message.clone()),
+                    ..Self::new(level, Kind::Note)
+                },
+            };
+            record.code = Some(note.code().to_string());
+            record
+        });
+        let changes = decision.changes.iter().map(|change| match change {
+            Change::RequestPackage(c) => Self {
+                pkg: Some(c.request.pkg.to_string()),
+                ..Self::new(level, Kind::RequestPackage)
+            },
+            Change::RequestVar(c) => Self {
+                detail: Some(c.request.to_string()),
+                ..Self::new(level, Kind::RequestVar)
+            },
+            Change::SetOptions(c) => Self {
+                detail: Some(
+                    c.options
+                        .iter()
+                        .map(|(name, value)| format!("{name}={value}"))
+                        .join(", "),
+                ),
+                ..Self::new(level, Kind::SetOptions)
+            },
+            Change::SetPackage(c) => Self {
+                pkg: Some(c.spec.ident().to_string()),
+                ..Self::new(level, Kind::SetPackage)
+            },
+            Change::SetPackageBuild(c) => Self {
+                pkg: Some(c.spec.ident().to_string()),
+                ..Self::new(level, Kind::SetPackageBuild)
+            },
+            Change::StepBack(c) => Self {
+                reason: Some(c.cause.clone()),
+                ..Self::new(level, Kind::StepBack)
+            },
+            Change::DropRequest(c) => Self {
+                pkg: Some(c.request.pkg.to_string()),
+                reason: Some(c.cause.clone()),
+                ..Self::new(level, Kind::DropRequest)
+            },
+        });
+        notes.chain(changes).collect()
+    }
+}
+
 /// How long to wait before showing the solver status bar.
 const STATUS_BAR_DELAY: Duration = Duration::from_secs(5);
 
@@ -123,6 +250,10 @@ where
     settings: DecisionFormatterSettings,
     status_bar: StatusBarStatus,
     status_line_rendered_hash: u64,
+    // For showing each package as it is resolved, when streaming the solution
+    solution_table: Option<SolutionTable>,
+    streamed_heading: bool,
+    repos: Vec<Arc<RepositoryHandle>>,
 }
 
 impl<I> FormattedDecisionsIter<I>
@@ -145,8 +276,78 @@ where
             } else {
                 StatusBarStatus::Disabled
             },
+            solution_table: (settings.show_solution
+                && settings.stream_solution
+                && !settings.solution_tree
+                && !settings.solution_markdown
+                && settings.output_format != OutputFormat::Json)
+                .then(|| SolutionTable::new(settings.verbosity)),
             settings,
             status_line_rendered_hash: 0,
+            streamed_heading: false,
+            repos: Vec::new(),
+        }
+    }
+
+    /// Look up the newest versions of streamed solution packages in
+    /// these repositories.
+    pub fn with_repositories(mut self, repos: Vec<Arc<RepositoryHandle>>) -> Self {
+        self.repos = repos;
+        self
+    }
+
+    /// Take the table used to show the solution as it was resolved,
+    /// if the solution is being streamed.
+    pub(crate) fn take_solution_table(&mut self) -> Option<SolutionTable> {
+        self.solution_table.take()
+    }
+
+    /// Show a newly resolved package as the next line of the solution table.
+    async fn stream_resolved_package(
+        &mut self,
+        state: &State,
+        change: &spk_solve_graph::SetPackage,
+    ) {
+        let Some(table) = &mut self.solution_table else {
+            return;
+        };
+        let request = match state.get_merged_request(change.spec.name()) {
+            Ok(request) => request,
+            Err(err) => {
+                tracing::debug!("Not showing {}: {err}", change.spec.ident());
+                return;
+            }
+        };
+        if !self.streamed_heading {
+            self.output_queue.push_back(format!(
+                "{}{}",
+                self.settings.heading_prefix,
+                table.format_heading()
+            ));
+            self.streamed_heading = true;
+        }
+        let solved = SolvedRequest {
+            request,
+            spec: Arc::clone(&change.spec),
+            source: change.source.clone(),
+        };
+        let line = table.format_package(&solved, &self.repos).await;
+        self.output_queue.push_back(line);
+    }
+
+    /// Note any already shown packages that the solver has unset by
+    /// stepping back to an earlier state.
+    fn stream_step_back(&mut self, destination: &State) {
+        let Some(table) = &mut self.solution_table else {
+            return;
+        };
+        let resolved = destination.get_resolved_packages();
+        let removed = table.retain_packages(|name| resolved.contains_key(name));
+        if !removed.is_empty() {
+            self.output_queue.push_back(format!(
+                "  {}",
+                format!("(stepped back, unset: {})", removed.iter().join(", ")).dimmed()
+            ));
         }
     }
 
@@ -250,6 +451,8 @@ where
                     &FormatChangeOptions {
                         verbosity: self.verbosity,
                         level: self.level,
+                        markers: self.settings.change_markers,
+                        ..Default::default()
                     },
                 )
             })
@@ -373,6 +576,7 @@ where
             RequestVar(_) => 2,
             SetOptions(_) => 3,
             SetPackageBuild(_) => 1,
+            DropRequest(_) => 1,
         };
         self.verbosity >= relevant_level
     }
@@ -436,7 +640,22 @@ where
 
                     self.render_statusbar(&node)?;
 
-                    if self.verbosity > 5 {
+                    let json = self.settings.output_format == OutputFormat::Json;
+                    if json {
+                        for record in DecisionRecord::from_decision(self.level, &decision) {
+                            match serde_json::to_string(&record) {
+                                Ok(line) => self.output_queue.push_back(line),
+                                Err(err) => {
+                                    yield Err(Error::String(format!(
+                                        "Failed to serialize solver decision: {err}"
+                                    )));
+                                    continue 'outer;
+                                }
+                            }
+                        }
+                    }
+
+                    if self.verbosity > 5 && !json {
                         // Show the state's package requests and resolved
                         // packages. This does not use indentation to make
                         // this "State ...:" debugging output stand out from
@@ -452,7 +671,10 @@ where
                                     &r.pkg.name,
                                     &FormatChangeOptions {
                                         verbosity: self.verbosity,
-                                        level: self.level
+                                        level: self.level,
+                                        show_requested_by: self.settings.show_requested_by,
+                                        show_build_repository: self.settings.show_build_repository,
+                                        markers: self.settings.change_markers,
                                     },
                                 ))
                                 .collect::<Vec<String>>()
@@ -470,7 +692,7 @@ where
                         ));
                     }
 
-                    if self.verbosity > 9 {
+                    if self.verbosity > 9 && !json {
                         // Show the state's var requests and resolved options
                         self.output_queue.push_back(format!(
                             "{} {:?}",
@@ -489,7 +711,7 @@ where
                         ));
                     }
 
-                    if self.verbosity > 1 {
+                    if self.verbosity > 1 && !json {
                         let prefix: String = if self.verbosity > 2 && self.level > 5 {
                             let level_text = self.level.to_string();
                             let prefix_width = level_text.len() + 1;
@@ -515,9 +737,11 @@ where
                                 } else {
                                     fill = ">";
                                 }
+                                self.stream_resolved_package(&node.state, change).await;
                             }
                             StepBack(spk_solve_graph::StepBack { destination, .. }) => {
                                 fill = "!";
+                                self.stream_step_back(destination);
                                 new_level = destination.state_depth;
                                 // Ensures the solver will stop before the next
                                 // decision because of this (BLOCKED) change, if
@@ -530,7 +754,7 @@ where
                             }
                         }
 
-                        if !self.change_is_relevant_at_verbosity(change) {
+                        if json || !self.change_is_relevant_at_verbosity(change) {
                             continue;
                         }
 
@@ -549,7 +773,10 @@ where
                                 change.format_change(
                                     &FormatChangeOptions {
                                         verbosity: self.verbosity,
-                                        level: self.level
+                                        level: self.level,
+                                        show_requested_by: self.settings.show_requested_by,
+                                        show_build_repository: self.settings.show_build_repository,
+                                        markers: self.settings.change_markers,
                                     },
                                     Some(&node.state)
                                 )
@@ -563,7 +790,10 @@ where
                                 change.format_change(
                                     &FormatChangeOptions {
                                         verbosity: self.verbosity,
-                                        level: self.level
+                                        level: self.level,
+                                        show_requested_by: self.settings.show_requested_by,
+                                        show_build_repository: self.settings.show_build_repository,
+                                        markers: self.settings.change_markers,
                                     },
                                     Some(&node.state)
                                 )
@@ -651,6 +881,13 @@ pub struct DecisionFormatterBuilder {
     output_to_dir: Option<PathBuf>,
     output_to_dir_min_verbosity: u8,
     output_file_prefix: String,
+    show_requested_by: bool,
+    show_build_repository: bool,
+    stream_solution: bool,
+    solution_tree: bool,
+    solution_markdown: bool,
+    output_format: OutputFormat,
+    change_markers: ChangeMarkers,
 }
 
 impl Default for DecisionFormatterBuilder {
@@ -676,6 +913,13 @@ impl Default for DecisionFormatterBuilder {
             output_to_dir: None,
             output_to_dir_min_verbosity: 2,
             output_file_prefix: String::from(DEFAULT_SOLVER_RUN_FILE_PREFIX),
+            show_requested_by: false,
+            show_build_repository: false,
+            stream_solution: false,
+            solution_tree: false,
+            solution_markdown: false,
+            output_format: OutputFormat::default(),
+            change_markers: ChangeMarkers::default(),
         }
     }
 }
@@ -731,6 +975,55 @@ impl DecisionFormatterBuilder {
         self
     }
 
+    /// Include what requested each package when showing requests
+    pub fn with_show_requested_by(&mut self, show_requested_by: bool) -> &mut Self {
+        self.show_requested_by = show_requested_by;
+        self
+    }
+
+    /// Include the repository that each resolved build came from
+    pub fn with_show_build_repository(&mut self, show_build_repository: bool) -> &mut Self {
+        self.show_build_repository = show_build_repository;
+        self
+    }
+
+    /// Show each package of the solution as it is resolved, rather
+    /// than as a single aligned table once the solve is complete
+    pub fn with_stream_solution(&mut self, stream_solution: bool) -> &mut Self {
+        self.stream_solution = stream_solution;
+        self
+    }
+
+    /// Show the solution as a tree of the packages that required
+    /// each other, rather than as a list
+    pub fn with_solution_tree(&mut self, solution_tree: bool) -> &mut Self {
+        self.solution_tree = solution_tree;
+        self
+    }
+
+    /// Show the solution as a markdown table without any colors,
+    /// rather than as a list
+    pub fn with_solution_markdown(&mut self, solution_markdown: bool) -> &mut Self {
+        self.solution_markdown = solution_markdown;
+        self
+    }
+
+    /// Write each solver decision in the given format
+    ///
+    /// With [`OutputFormat::Json`], every change and note is written,
+    /// regardless of the verbosity.
+    pub fn with_output_format(&mut self, output_format: OutputFormat) -> &mut Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Control how each kind of change is marked in the text output,
+    /// see [`ChangeMarkers::detect`] for picking one from the terminal
+    pub fn with_change_markers(&mut self, change_markers: ChangeMarkers) -> &mut Self {
+        self.change_markers = change_markers;
+        self
+    }
+
     pub fn with_header<S: Into<String>>(&mut self, heading: S) -> &mut Self {
         self.heading_prefix = heading.into();
         self
@@ -848,6 +1141,13 @@ impl DecisionFormatterBuilder {
                 output_to_dir: self.output_to_dir.clone(),
                 output_to_dir_min_verbosity: self.output_to_dir_min_verbosity,
                 output_file_prefix: self.output_file_prefix.clone(),
+                show_requested_by: self.show_requested_by,
+                show_build_repository: self.show_build_repository,
+                stream_solution: self.stream_solution,
+                solution_tree: self.solution_tree,
+                solution_markdown: self.solution_markdown,
+                output_format: self.output_format,
+                change_markers: self.change_markers,
             },
         }
     }
@@ -973,6 +1273,19 @@ impl OutputKind {
         }
     }
 
+    /// Make an output kind for reporting the stats and solution of a
+    /// solve whose decisions are written as json. Anything that would
+    /// be printed is logged instead, keeping stdout for json records.
+    fn for_json_reports(&self) -> Self {
+        match self {
+            OutputKind::Println
+            | OutputKind::Tracing
+            | OutputKind::PrintlnAndToFile(_)
+            | OutputKind::TracingAndToFile(_) => OutputKind::Tracing,
+            OutputKind::LogFile(f) => OutputKind::LogFile(f.clone()),
+        }
+    }
+
     /// Flush the output kind's output file, if any. Any errors that
     /// occur while flushing the file are logged but not returned
     /// because we don't want them to interrupt the solve.
@@ -1025,12 +1338,21 @@ pub(crate) struct DecisionFormatterSettings {
     pub(crate) output_to_dir: Option<PathBuf>,
     pub(crate) output_to_dir_min_verbosity: u8,
     pub(crate) output_file_prefix: String,
+    pub(crate) show_requested_by: bool,
+    pub(crate) show_build_repository: bool,
+    pub(crate) stream_solution: bool,
+    pub(crate) solution_tree: bool,
+    pub(crate) solution_markdown: bool,
+    pub(crate) output_format: OutputFormat,
+    pub(crate) change_markers: ChangeMarkers,
 }
 
 enum LoopOutcome {
     Interrupted(String),
     Failed(Box<Error>),
-    Success,
+    /// The solve finished, and holds the table of packages that were
+    /// shown as they were resolved when the solution is streamed
+    Success(Option<SolutionTable>),
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Default, strum::Display)]
@@ -1144,6 +1466,13 @@ impl DecisionFormatter {
                 output_to_dir: None,
                 output_to_dir_min_verbosity: 2,
                 output_file_prefix: String::from(DEFAULT_SOLVER_TEST_FILE_PREFIX),
+                show_requested_by: false,
+                show_build_repository: false,
+                stream_solution: false,
+                solution_tree: false,
+                solution_markdown: false,
+                output_format: OutputFormat::Text,
+                change_markers: ChangeMarkers::Color,
             },
         }
     }
@@ -1385,10 +1714,15 @@ impl DecisionFormatter {
             let builds = "build".pluralize(total_builds);
             let steps = "step".pluralize(num_steps);
 
-            println!(
+            let line = format!(
                 "{solver_kind}{padding}: {solved} in {seconds:.6} seconds, {num_steps} {steps} ({num_steps_back} back), {total_builds} {builds} at {:.3} builds/sec",
                 total_builds as f64 / seconds
             );
+            if self.settings.output_format == OutputFormat::Json {
+                tracing::info!("{line}");
+            } else {
+                println!("{line}");
+            }
         }
     }
 
@@ -1512,28 +1846,26 @@ impl DecisionFormatter {
         runtime: &mut StepSolverRuntime,
         mut output_location: OutputKind,
     ) -> LoopOutcome {
+        let repos = runtime.solver.repositories().to_vec();
         let decisions = runtime.iter();
-        let mut formatted_decisions = self.formatted_decisions_iter(decisions);
-        let iter = formatted_decisions.iter();
-        tokio::pin!(iter);
-        #[allow(clippy::never_loop)]
-        'outer: loop {
+        let mut formatted_decisions = self
+            .formatted_decisions_iter(decisions)
+            .with_repositories(repos);
+        {
+            let iter = formatted_decisions.iter();
+            tokio::pin!(iter);
             while let Some(line) = iter.next().await {
                 match line {
                     Ok(message) => output_location.output_message(message),
-                    Err(e) => {
-                        match e {
-                            Error::SolverInterrupted(mesg) => {
-                                break 'outer LoopOutcome::Interrupted(mesg);
-                            }
-                            _ => break 'outer LoopOutcome::Failed(Box::new(e)),
-                        };
+                    Err(Error::SolverInterrupted(mesg)) => {
+                        return LoopOutcome::Interrupted(mesg);
                     }
+                    Err(e) => return LoopOutcome::Failed(Box::new(e)),
                 };
             }
-
-            break LoopOutcome::Success;
         }
+
+        LoopOutcome::Success(formatted_decisions.take_solution_table())
     }
 
     async fn check_and_output_solver_results(
@@ -1541,9 +1873,16 @@ impl DecisionFormatter {
         loop_outcome: LoopOutcome,
         solve_time: Duration,
         runtime: &mut StepSolverRuntime,
-        mut output_location: OutputKind,
+        output_location: OutputKind,
     ) -> Result<(Solution, Arc<tokio::sync::RwLock<Graph>>)> {
-        match loop_outcome {
+        // The json decision records must be the only thing written to
+        // stdout, so the stats and solution are logged instead.
+        let mut report_location = if self.settings.output_format == OutputFormat::Json {
+            output_location.for_json_reports()
+        } else {
+            output_location.clone()
+        };
+        let streamed_table = match loop_outcome {
             LoopOutcome::Interrupted(mesg) => {
                 // The solve was interrupted, record time taken and
                 // other the details in sentry for later analysis.
@@ -1560,12 +1899,12 @@ impl DecisionFormatter {
                     },
                 );
 
-                output_location.output_message(format!("{}", mesg.yellow()));
+                report_location.output_message(format!("{}", mesg.yellow()));
                 // Show the solver stats after an interruption, unless
                 // it was due to being BLOCKED with stop-on-block
                 // being set without report-time also being set.
                 if !self.settings.stop_on_block || self.settings.report_time {
-                    output_location
+                    report_location
                         .output_message(self.format_solve_stats(&runtime.solver, solve_time));
                 }
 
@@ -1591,7 +1930,7 @@ impl DecisionFormatter {
 
                 return Err(*e);
             }
-            LoopOutcome::Success => {}
+            LoopOutcome::Success(table) => table,
         };
 
         if solve_time > Duration::from_secs(self.settings.long_solves_threshold) {
@@ -1610,7 +1949,7 @@ impl DecisionFormatter {
         // Note: this time includes the output time because the solver is
         // run in the iterator in the format_decisions_iter() loop above
         if self.settings.report_time {
-            output_location.output_message(self.format_solve_stats(&runtime.solver, solve_time));
+            report_location.output_message(self.format_solve_stats(&runtime.solver, solve_time));
         }
 
         let solution = runtime.current_solution().await;
@@ -1621,9 +1960,34 @@ impl DecisionFormatter {
         }
 
         if self.settings.show_solution
+            && self.settings.solution_tree
+            && let Ok(ref s) = solution
+        {
+            report_location.output_message(format!(
+                "{}{}",
+                self.settings.heading_prefix,
+                s.format_solution_tree()
+            ));
+        } else if self.settings.show_solution
+            && self.settings.solution_markdown
+            && let Ok(ref s) = solution
+        {
+            report_location.output_message(s.format_solution_as_markdown());
+        } else if self.settings.show_solution
+            && let Some(table) = &streamed_table
+            && let Ok(ref s) = solution
+        {
+            // The packages were already shown as they were resolved
+            let footer = table.format_footer(s);
+            if s.is_empty() {
+                report_location.output_message(format!("{}{footer}", self.settings.heading_prefix));
+            } else {
+                report_location.output_message(footer);
+            }
+        } else if self.settings.show_solution
             && let Ok(ref s) = solution
         {
-            output_location.output_message(format!(
+            report_location.output_message(format!(
                 "{}{}",
                 self.settings.heading_prefix,
                 s.format_solution_with_highest_versions(
@@ -6,6 +6,8 @@ mod error;
 mod io;
 #[cfg(feature = "statsd")]
 mod metrics;
+mod prerelease_policies;
+mod repository_ordering;
 mod search_space;
 mod solver;
 mod solvers;
@@ -16,7 +18,10 @@ pub use io::{
     DEFAULT_SOLVER_RUN_FILE_PREFIX,
     DecisionFormatter,
     DecisionFormatterBuilder,
+    DecisionRecord,
+    DecisionRecordKind,
     MultiSolverKind,
+    OutputFormat,
 };
 #[cfg(feature = "statsd")]
 pub use metrics::{
@@ -31,13 +36,15 @@ pub use metrics::{
     SPK_SOLVER_SOLUTION_SIZE_METRIC,
     get_metrics_client,
 };
+pub use prerelease_policies::PreReleasePolicies;
+pub use repository_ordering::{ConfiguredOrder, PreferRepositories, RepositoryOrdering};
 pub(crate) use search_space::show_search_space_stats;
 pub use serde;
 pub use serde_json;
 pub use solver::{Solver, SolverExt, SolverImpl, SolverMut};
 // Publicly exported ResolvoSolver to stop dead code warnings
 pub use solvers::ResolvoSolver;
-pub use solvers::{StepSolver, StepSolverRuntime};
+pub use solvers::{StepSolver, StepSolverRuntime, VersionCompatCache};
 pub use spfs;
 pub use spk_schema::foundation::ident_build::Build;
 pub use spk_schema::foundation::ident_component::Component;
@@ -55,7 +62,7 @@ pub use spk_schema::{Package, Recipe, Spec, SpecRecipe, recipe, spec, v0};
 pub use spk_solve_graph as graph;
 pub use spk_solve_package_iterator as package_iterator;
 pub use spk_solve_solution as solution;
-pub use spk_solve_solution::{PackageSource, Solution};
+pub use spk_solve_solution::{PackageSource, Solution, SolveStats};
 pub use spk_solve_validation as validation;
 pub use spk_storage::RepositoryHandle;
 pub(crate) use status_line::StatusLine;
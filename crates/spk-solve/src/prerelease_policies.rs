@@ -0,0 +1,73 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::BTreeMap;
+
+use spk_schema::ident::{PkgRequest, PreReleasePolicy};
+use spk_schema::name::{PkgName, PkgNameBuf};
+
+#[cfg(test)]
+#[path = "./prerelease_policies_test.rs"]
+mod prerelease_policies_test;
+
+/// The prerelease policies used for the requests in a solve that
+/// do not set a policy of their own.
+///
+/// A policy set on a request always takes precedence. Otherwise the
+/// policy configured for the requested package is used, and then
+/// the default policy for the solve.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreReleasePolicies {
+    default: Option<PreReleasePolicy>,
+    packages: BTreeMap<PkgNameBuf, PreReleasePolicy>,
+}
+
+impl PreReleasePolicies {
+    pub fn new(default: Option<PreReleasePolicy>) -> Self {
+        Self {
+            default,
+            packages: BTreeMap::new(),
+        }
+    }
+
+    /// Use the given policy for all requests for the named package.
+    pub fn with_package(mut self, name: PkgNameBuf, policy: PreReleasePolicy) -> Self {
+        self.packages.insert(name, policy);
+        self
+    }
+
+    /// True if no policies have been configured.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.packages.is_empty()
+    }
+
+    /// The policy for requests of the named package that do not set
+    /// their own, if one was configured.
+    pub fn policy_for(&self, name: &PkgName) -> Option<PreReleasePolicy> {
+        self.packages.get(name).copied().or(self.default)
+    }
+
+    /// The policy that applies to the given request.
+    pub fn effective_policy(&self, request: &PkgRequest) -> PreReleasePolicy {
+        request
+            .prerelease_policy
+            .or_else(|| self.policy_for(&request.pkg.name))
+            .unwrap_or_default()
+    }
+
+    /// Fill in the policy for a request that does not set its own.
+    ///
+    /// Requests without a policy already exclude prereleases, so
+    /// they are only changed when prereleases are to be included.
+    /// Leaving them unset means that a policy set on another request
+    /// for the same package still takes precedence when the two are
+    /// merged.
+    pub fn apply(&self, request: &mut PkgRequest) {
+        if request.prerelease_policy.is_none()
+            && self.policy_for(&request.pkg.name) == Some(PreReleasePolicy::IncludeAll)
+        {
+            request.prerelease_policy = Some(PreReleasePolicy::IncludeAll);
+        }
+    }
+}
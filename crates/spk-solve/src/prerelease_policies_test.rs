@@ -0,0 +1,101 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use spk_schema::foundation::pkg_name;
+use spk_schema::ident::{PkgRequest, PreReleasePolicy, RequestedBy, parse_ident_range};
+
+use super::PreReleasePolicies;
+
+fn request(range: &str, policy: Option<PreReleasePolicy>) -> PkgRequest {
+    PkgRequest::new(
+        parse_ident_range(range).unwrap(),
+        RequestedBy::DoesNotMatter,
+    )
+    .with_prerelease(policy)
+}
+
+#[rstest]
+// nothing configured
+#[case(None, None, "my-pkg", None, PreReleasePolicy::ExcludeAll)]
+// the default applies to every package
+#[case(
+    Some(PreReleasePolicy::IncludeAll),
+    None,
+    "my-pkg",
+    None,
+    PreReleasePolicy::IncludeAll
+)]
+// a package policy is used over the default
+#[case(
+    Some(PreReleasePolicy::ExcludeAll),
+    Some(PreReleasePolicy::IncludeAll),
+    "my-pkg",
+    None,
+    PreReleasePolicy::IncludeAll
+)]
+#[case(
+    Some(PreReleasePolicy::IncludeAll),
+    Some(PreReleasePolicy::ExcludeAll),
+    "my-pkg",
+    None,
+    PreReleasePolicy::ExcludeAll
+)]
+// but only for that package
+#[case(
+    Some(PreReleasePolicy::ExcludeAll),
+    Some(PreReleasePolicy::IncludeAll),
+    "other-pkg",
+    None,
+    PreReleasePolicy::ExcludeAll
+)]
+// a policy on the request is used over both
+#[case(
+    Some(PreReleasePolicy::IncludeAll),
+    Some(PreReleasePolicy::IncludeAll),
+    "my-pkg",
+    Some(PreReleasePolicy::ExcludeAll),
+    PreReleasePolicy::ExcludeAll
+)]
+fn test_prerelease_policy_precedence(
+    #[case] default: Option<PreReleasePolicy>,
+    #[case] package: Option<PreReleasePolicy>,
+    #[case] requested: &str,
+    #[case] request_policy: Option<PreReleasePolicy>,
+    #[case] expected: PreReleasePolicy,
+) {
+    let mut policies = PreReleasePolicies::new(default);
+    if let Some(package) = package {
+        policies = policies.with_package(pkg_name!("my-pkg").to_owned(), package);
+    }
+    let request = request(requested, request_policy);
+    assert_eq!(policies.effective_policy(&request), expected);
+}
+
+#[rstest]
+fn test_prerelease_policy_apply() {
+    let policies = PreReleasePolicies::new(Some(PreReleasePolicy::IncludeAll))
+        .with_package(pkg_name!("stable").to_owned(), PreReleasePolicy::ExcludeAll);
+
+    let mut included = request("my-pkg", None);
+    policies.apply(&mut included);
+    assert_eq!(
+        included.prerelease_policy,
+        Some(PreReleasePolicy::IncludeAll)
+    );
+
+    let mut excluded = request("stable", None);
+    policies.apply(&mut excluded);
+    assert_eq!(
+        excluded.prerelease_policy, None,
+        "excluding prereleases is left unset so that other requests can still include them"
+    );
+
+    let mut explicit = request("my-pkg", Some(PreReleasePolicy::ExcludeAll));
+    policies.apply(&mut explicit);
+    assert_eq!(
+        explicit.prerelease_policy,
+        Some(PreReleasePolicy::ExcludeAll)
+    );
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::sync::Arc;
+
+use spk_schema::name::{PkgName, RepositoryNameBuf};
+use spk_storage::RepositoryHandle;
+
+#[cfg(test)]
+#[path = "./repository_ordering_test.rs"]
+mod repository_ordering_test;
+
+/// Decides the order that a solver consults its repositories in
+/// when looking for the builds of a package.
+///
+/// The order is decided separately for each package, so a strategy
+/// can prefer different repositories depending on what is being
+/// requested, such as a repository that is known to hold a cached
+/// copy of the package.
+pub trait RepositoryOrdering: Send + Sync {
+    /// Return the repositories to search for the named package,
+    /// in the order that they should be consulted.
+    fn order_repositories(
+        &self,
+        package: &PkgName,
+        repos: &[Arc<RepositoryHandle>],
+    ) -> Vec<Arc<RepositoryHandle>>;
+}
+
+/// Consults repositories in the order they were added to the solver.
+///
+/// This is the default strategy for all solvers.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConfiguredOrder;
+
+impl RepositoryOrdering for ConfiguredOrder {
+    fn order_repositories(
+        &self,
+        _package: &PkgName,
+        repos: &[Arc<RepositoryHandle>],
+    ) -> Vec<Arc<RepositoryHandle>> {
+        repos.to_vec()
+    }
+}
+
+/// Consults the named repositories first, in the given order,
+/// followed by any others in the order they were added to the solver.
+#[derive(Debug, Default, Clone)]
+pub struct PreferRepositories {
+    preferred: Vec<RepositoryNameBuf>,
+}
+
+impl PreferRepositories {
+    pub fn new<I>(preferred: I) -> Self
+    where
+        I: IntoIterator<Item = RepositoryNameBuf>,
+    {
+        Self {
+            preferred: preferred.into_iter().collect(),
+        }
+    }
+}
+
+impl RepositoryOrdering for PreferRepositories {
+    fn order_repositories(
+        &self,
+        _package: &PkgName,
+        repos: &[Arc<RepositoryHandle>],
+    ) -> Vec<Arc<RepositoryHandle>> {
+        let mut ordered = repos.to_vec();
+        // a stable sort keeps the configured order for repositories
+        // with the same preference
+        ordered.sort_by_key(|repo| {
+            self.preferred
+                .iter()
+                .position(|name| name.as_str() == repo.name().as_str())
+                .unwrap_or(self.preferred.len())
+        });
+        ordered
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::sync::Arc;
+
+use rstest::rstest;
+use spk_schema::foundation::pkg_name;
+use spk_storage::RepositoryHandle;
+
+use super::{ConfiguredOrder, PreferRepositories, RepositoryOrdering};
+
+fn names(repos: &[Arc<RepositoryHandle>]) -> Vec<String> {
+    repos.iter().map(|r| r.name().to_string()).collect()
+}
+
+#[rstest]
+fn test_configured_order_is_unchanged() {
+    let repos: Vec<Arc<RepositoryHandle>> = (0..3)
+        .map(|_| Arc::new(RepositoryHandle::new_mem()))
+        .collect();
+    let ordered = ConfiguredOrder.order_repositories(pkg_name!("my-pkg"), &repos);
+    assert_eq!(names(&ordered), names(&repos));
+}
+
+#[rstest]
+fn test_prefer_repositories() {
+    let repos: Vec<Arc<RepositoryHandle>> = (0..4)
+        .map(|_| Arc::new(RepositoryHandle::new_mem()))
+        .collect();
+    let strategy =
+        PreferRepositories::new([repos[2].name().to_owned(), repos[1].name().to_owned()]);
+    let ordered = strategy.order_repositories(pkg_name!("my-pkg"), &repos);
+    let expected = names(&[
+        Arc::clone(&repos[2]),
+        Arc::clone(&repos[1]),
+        Arc::clone(&repos[0]),
+        Arc::clone(&repos[3]),
+    ]);
+    assert_eq!(
+        names(&ordered),
+        expected,
+        "preferred repositories should come first, followed by the rest in their original order"
+    );
+}
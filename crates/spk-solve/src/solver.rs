@@ -12,7 +12,7 @@ use spk_solve_solution::Solution;
 use spk_storage::RepositoryHandle;
 use variantly::Variantly;
 
-use crate::{DecisionFormatter, Result};
+use crate::{DecisionFormatter, PreReleasePolicies, RepositoryOrdering, Result};
 
 #[enum_dispatch(Solver, SolverExt, SolverMut)]
 // Don't derive Default. If some code is generic on Solver and is given one of
@@ -99,6 +99,19 @@ pub trait SolverMut: Solver {
     /// build environments are fully resolved and dependencies included
     fn set_binary_only(&mut self, binary_only: bool);
 
+    /// Set the strategy used to decide the order that repositories
+    /// are consulted in for each package.
+    ///
+    /// By default, repositories are consulted in the order that
+    /// they were added to the solver.
+    fn set_repository_ordering(&mut self, ordering: Arc<dyn RepositoryOrdering>);
+
+    /// Set the prerelease policies used for the requests in a solve
+    /// that do not set a policy of their own.
+    ///
+    /// See [`PreReleasePolicies`] for how they take precedence.
+    fn set_prerelease_policies(&mut self, policies: PreReleasePolicies);
+
     /// Run the solver as configured.
     async fn solve(&mut self) -> Result<Solution>;
 
@@ -172,6 +185,14 @@ where
         T::set_binary_only(self, binary_only)
     }
 
+    fn set_repository_ordering(&mut self, ordering: Arc<dyn RepositoryOrdering>) {
+        T::set_repository_ordering(self, ordering)
+    }
+
+    fn set_prerelease_policies(&mut self, policies: PreReleasePolicies) {
+        T::set_prerelease_policies(self, policies)
+    }
+
     async fn solve(&mut self) -> Result<Solution> {
         T::solve(self).await
     }
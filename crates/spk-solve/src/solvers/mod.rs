@@ -8,7 +8,7 @@ pub(crate) mod resolvo;
 pub(crate) mod step;
 
 pub use resolvo::Solver as ResolvoSolver;
-pub use step::{Solver as StepSolver, SolverRuntime as StepSolverRuntime};
+pub use step::{Solver as StepSolver, SolverRuntime as StepSolverRuntime, VersionCompatCache};
 
 // Public to allow other tests to use its macros
 #[cfg(test)]
@@ -30,13 +30,14 @@ use spk_schema::ident::{
     PinnedValue,
     PkgRequest,
     PkgRequestWithOptions,
+    PreReleasePolicy,
     RangeIdent,
     RequestWithOptions,
     RequestedBy,
     VarRequest,
 };
 use spk_schema::ident_component::Component;
-use spk_schema::name::{OptNameBuf, PkgNameBuf};
+use spk_schema::name::{OptNameBuf, PkgName, PkgNameBuf};
 use spk_schema::prelude::{HasVersion, Named, Versioned};
 use spk_schema::version_range::VersionFilter;
 use spk_schema::{OptionMap, Package, Spec};
@@ -46,15 +47,27 @@ use spk_storage::RepositoryHandle;
 
 use crate::solver::Solver as SolverTrait;
 use crate::solvers::resolvo::pkg_request_version_set::LocatedBuildIdentWithComponent;
-use crate::{DecisionFormatter, Error, Result, SolverExt, SolverMut, show_search_space_stats};
+use crate::{
+    ConfiguredOrder,
+    DecisionFormatter,
+    Error,
+    PreReleasePolicies,
+    RepositoryOrdering,
+    Result,
+    SolverExt,
+    SolverMut,
+    show_search_space_stats,
+};
 
 #[cfg(test)]
 #[path = "resolvo_tests.rs"]
 mod resolvo_tests;
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Solver {
     repos: Vec<Arc<RepositoryHandle>>,
+    repository_ordering: Arc<dyn RepositoryOrdering>,
+    prerelease_policies: PreReleasePolicies,
     requests: Vec<RequestWithOptions>,
     options: OptionMap,
     binary_only: bool,
@@ -62,10 +75,27 @@ pub struct Solver {
     build_from_source_trail: HashSet<LocatedBuildIdent>,
 }
 
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            repository_ordering: Arc::new(ConfiguredOrder),
+            prerelease_policies: PreReleasePolicies::default(),
+            requests: Vec::new(),
+            options: Default::default(),
+            binary_only: false,
+            _validators: Default::default(),
+            build_from_source_trail: HashSet::new(),
+        }
+    }
+}
+
 impl Solver {
     pub fn new(repos: Vec<Arc<RepositoryHandle>>, validators: Cow<'static, [Validators]>) -> Self {
         Self {
             repos,
+            repository_ordering: Arc::new(ConfiguredOrder),
+            prerelease_policies: PreReleasePolicies::default(),
             requests: Vec::new(),
             options: Default::default(),
             binary_only: true,
@@ -142,6 +172,23 @@ impl Solver {
             .collect()
     }
 
+    /// The prerelease policy in effect for the named package, as set
+    /// on the requests for it or filled in by the prerelease policies.
+    fn prerelease_policy_for(&self, name: &PkgName) -> Option<PreReleasePolicy> {
+        let requested = self
+            .requests
+            .iter()
+            .filter_map(|r| r.pkg_ref())
+            .filter(|r| r.pkg.name == *name)
+            .filter_map(|r| r.prerelease_policy)
+            .min();
+        requested.or_else(|| {
+            self.prerelease_policies
+                .policy_for(name)
+                .filter(|policy| *policy == PreReleasePolicy::IncludeAll)
+        })
+    }
+
     pub(crate) fn set_build_from_source_trail(&mut self, trail: HashSet<LocatedBuildIdent>) {
         self.build_from_source_trail = trail;
     }
@@ -169,18 +216,28 @@ impl Solver {
         }
 
         let repos = self.repos.clone();
-        let requests = self.requests.clone();
+        let repository_ordering = Arc::clone(&self.repository_ordering);
+        let mut requests = self.requests.clone();
+        for request in requests.iter_mut() {
+            if let RequestWithOptions::Pkg(request) = request {
+                self.prerelease_policies.apply(&mut request.pkg_request);
+            }
+        }
+        let prerelease_policies = self.prerelease_policies.clone();
         let binary_only = self.binary_only;
         let build_from_source_trail = self.build_from_source_trail.clone();
         // Use a blocking thread so resolvo can call `block_on` on the runtime.
-        let solvables = tokio::task::spawn_blocking(move || {
+        let (solvables, dropped_requests) = tokio::task::spawn_blocking(move || {
             let mut provider = Some(SpkProvider::new(
                 repos.clone(),
+                repository_ordering,
+                prerelease_policies,
                 known_global_vars.clone(),
                 binary_only,
                 build_from_source_trail,
             ));
             let mut loop_counter = 0;
+            let mut dropped_requests = Vec::new();
             let (solver, solved) = loop {
                 loop_counter += 1;
                 let mut this_iter_provider = provider.take().expect("provider is always Some");
@@ -211,6 +268,24 @@ impl Solver {
                             tracing::info!("Solver retry {loop_counter}");
                             continue;
                         }
+                        // Drop optional requests one at a time, most
+                        // recently added first, until a solution is found.
+                        if let Some(index) = requests.iter().rposition(|r| {
+                            matches!(r, RequestWithOptions::Pkg(p) if p.inclusion_policy == InclusionPolicy::IfCompatible)
+                        }) {
+                            let RequestWithOptions::Pkg(request) = requests.remove(index) else {
+                                unreachable!("position was found for a pkg request");
+                            };
+                            let mut next_provider = solver.provider().reset();
+                            next_provider.drop_root_pkg_request(request.pkg.name());
+                            provider = Some(next_provider);
+                            tracing::info!(
+                                "Solver retry {loop_counter}: dropped optional request {}",
+                                request.pkg
+                            );
+                            dropped_requests.push(request.pkg_request);
+                            continue;
+                        }
                         return Err(Error::FailedToResolve(format!(
                             "{}",
                             conflict.display_user_friendly(&solver)
@@ -220,13 +295,14 @@ impl Solver {
             };
 
             let pool = &solver.provider().pool;
-            Ok(solved
+            let solvables = solved
                 .into_iter()
                 .map(|solvable_id| {
                     let solvable = pool.resolve_solvable(solvable_id);
                     solvable.record.clone()
                 })
-                .collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            Ok((solvables, dropped_requests))
         })
         .await
         .map_err(|err| Error::String(format!("Tokio panicked? {err}")))??;
@@ -293,7 +369,7 @@ impl Solver {
                         version: VersionFilter::default(),
                         build: None,
                     },
-                    prerelease_policy: None,
+                    prerelease_policy: self.prerelease_policy_for(ident.name()),
                     inclusion_policy: InclusionPolicy::default(),
                     pin: None,
                     pin_policy: PinPolicy::default(),
@@ -394,6 +470,9 @@ impl Solver {
         for (pkg_request, package, source) in solution_adds {
             solution.add(pkg_request, package, source);
         }
+        for request in dropped_requests {
+            solution.add_dropped_request(request);
+        }
         Ok(solution)
     }
 }
@@ -442,6 +521,7 @@ impl SolverMut for Solver {
     fn reset(&mut self) {
         self.repos.truncate(0);
         self.requests.truncate(0);
+        self.prerelease_policies = PreReleasePolicies::default();
         self._validators = Cow::from(default_validators());
     }
 
@@ -492,6 +572,14 @@ impl SolverMut for Solver {
         self.binary_only = binary_only;
     }
 
+    fn set_repository_ordering(&mut self, ordering: Arc<dyn RepositoryOrdering>) {
+        self.repository_ordering = ordering;
+    }
+
+    fn set_prerelease_policies(&mut self, policies: PreReleasePolicies) {
+        self.prerelease_policies = policies;
+    }
+
     async fn solve(&mut self) -> Result<Solution> {
         Solver::solve(self).await
     }
@@ -40,7 +40,7 @@ use spk_schema::ident::{
 };
 use spk_schema::ident_build::{Build, EmbeddedSource, EmbeddedSourcePackage};
 use spk_schema::ident_component::Component;
-use spk_schema::name::{OptNameBuf, PkgNameBuf};
+use spk_schema::name::{OptNameBuf, PkgName, PkgNameBuf};
 use spk_schema::prelude::{HasVersion, Named};
 use spk_schema::version_range::{DoubleEqualsVersion, Ranged, VersionFilter, parse_version_range};
 use spk_schema::{
@@ -65,7 +65,7 @@ use super::pkg_request_version_set::{
     SyntheticComponent,
     VarValue,
 };
-use crate::SolverMut;
+use crate::{PreReleasePolicies, RepositoryOrdering, SolverMut};
 
 // Using just the package name as a Resolvo "package name" prevents multiple
 // components from the same package from existing in the same solution, since
@@ -154,7 +154,10 @@ impl ResolvoPackageName {
                     None
                 };
 
-                for repo in &provider.repos {
+                let repos = provider
+                    .repository_ordering
+                    .order_repositories(&pkg_name.name, &provider.repos);
+                for repo in &repos {
                     let versions = repo
                         .list_package_versions(&pkg_name.name)
                         .await
@@ -487,6 +490,10 @@ where
 pub(crate) struct SpkProvider {
     pub(crate) pool: Pool<RequestVS, ResolvoPackageName>,
     repos: Vec<Arc<RepositoryHandle>>,
+    /// Decides the order that repos are searched in for each package
+    repository_ordering: Arc<dyn RepositoryOrdering>,
+    /// Fills in the prerelease policy of requests that don't set one
+    prerelease_policies: PreReleasePolicies,
     /// Global package requests. These can be used to constrain the candidates
     /// returned for these packages.
     global_pkg_requests: HashMap<PkgNameBuf, PkgRequestWithOptions>,
@@ -507,6 +514,9 @@ pub(crate) struct SpkProvider {
     /// When recursively exploring building packages from source, track chain
     /// of packages to detect cycles.
     build_from_source_trail: RefCell<HashSet<LocatedBuildIdent>>,
+    /// The option names worked out for sorting each set of builds,
+    /// reused whenever the same builds are sorted again.
+    sorted_opt_names: BuildToSortedOptName,
 }
 
 impl SpkProvider {
@@ -560,6 +570,8 @@ impl SpkProvider {
         for variant in variants.iter() {
             let mut solver = super::Solver::new(self.repos.clone(), Cow::Borrowed(&[]));
             solver.set_binary_only(false);
+            solver.set_repository_ordering(Arc::clone(&self.repository_ordering));
+            solver.set_prerelease_policies(self.prerelease_policies.clone());
             solver.set_build_from_source_trail(HashSet::from_iter(
                 self.build_from_source_trail
                     .borrow()
@@ -609,6 +621,8 @@ impl SpkProvider {
 
     pub fn new(
         repos: Vec<Arc<RepositoryHandle>>,
+        repository_ordering: Arc<dyn RepositoryOrdering>,
+        prerelease_policies: PreReleasePolicies,
         known_global_vars: HashMap<OptNameBuf, HashSet<VarValue>>,
         binary_only: bool,
         build_from_source_trail: HashSet<LocatedBuildIdent>,
@@ -618,6 +632,8 @@ impl SpkProvider {
         Self {
             pool: Pool::new(),
             repos,
+            repository_ordering,
+            prerelease_policies,
             global_pkg_requests: Default::default(),
             global_var_requests: Default::default(),
             interned_solvables: Default::default(),
@@ -626,6 +642,7 @@ impl SpkProvider {
             cancel_solving: Default::default(),
             binary_only,
             build_from_source_trail: RefCell::new(build_from_source_trail),
+            sorted_opt_names: Default::default(),
         }
     }
 
@@ -681,9 +698,15 @@ impl SpkProvider {
                 pkg_request_with_component.pkg_request.prerelease_policy =
                     Some(PreReleasePolicy::IncludeAll);
             }
+            self.prerelease_policies
+                .apply(&mut pkg_request_with_component.pkg_request);
             pkg_request_with_component.pkg.components = BTreeSet::from_iter([component]);
             match pkg_request.inclusion_policy {
-                spk_schema::ident::InclusionPolicy::Always => {
+                // Only optional root requests can be dropped by the solver,
+                // see `Solver::solve`, so optional dependencies of
+                // packages are required like any other.
+                spk_schema::ident::InclusionPolicy::Always
+                | spk_schema::ident::InclusionPolicy::IfCompatible => {
                     let dep_vs = self.pool.intern_version_set(
                         dep_name,
                         RequestVS::SpkRequest(RequestWithOptions::Pkg(pkg_request_with_component)),
@@ -743,6 +766,11 @@ impl SpkProvider {
             .collect()
     }
 
+    /// Forget a root request that has been dropped from the solve.
+    pub(crate) fn drop_root_pkg_request(&mut self, name: &PkgName) {
+        self.global_pkg_requests.remove(name);
+    }
+
     pub fn is_canceled(&self) -> bool {
         self.cancel_solving.borrow().is_some()
     }
@@ -859,6 +887,8 @@ impl SpkProvider {
         Self {
             pool: Pool::new(),
             repos: self.repos.clone(),
+            repository_ordering: Arc::clone(&self.repository_ordering),
+            prerelease_policies: self.prerelease_policies.clone(),
             global_pkg_requests: self.global_pkg_requests.clone(),
             global_var_requests: self.global_var_requests.clone(),
             interned_solvables: Default::default(),
@@ -867,6 +897,7 @@ impl SpkProvider {
             cancel_solving: Default::default(),
             binary_only: self.binary_only,
             build_from_source_trail: self.build_from_source_trail.clone(),
+            sorted_opt_names: Default::default(),
         }
     }
 
@@ -973,6 +1004,22 @@ impl DependencyProvider for SpkProvider {
                         continue;
                     };
 
+                    // A request that names a repository can only be
+                    // satisfied by builds from that repository.
+                    if let Some(repository_name) = &pkg_request.pkg.repository_name
+                        && repository_name.as_str()
+                            != located_build_ident_with_component
+                                .ident
+                                .repository_name()
+                                .as_str()
+                    {
+                        tracing::trace!(pkg_request = %pkg_request.pkg, build = %located_build_ident_with_component.ident, "build is not from the requested repository");
+                        if inverse {
+                            selected.push(*candidate);
+                        }
+                        continue;
+                    }
+
                     let compatible = pkg_request
                         .is_version_applicable(located_build_ident_with_component.ident.version());
                     if compatible.is_ok() {
@@ -1265,14 +1312,17 @@ impl DependencyProvider for SpkProvider {
 
         // Find runs of the same package version.
         for version_run in SpkProvider::find_version_runs(&build_solvables_and_specs) {
-            let (ordered_names, build_name_values) =
-                BuildToSortedOptName::sort_builds(version_run.iter().map(|(_, _, spec)| spec));
+            let sorted = self
+                .sorted_opt_names
+                .sort_builds_cached(version_run.iter().map(|(_, _, spec)| spec));
+            let (ordered_names, build_name_values) = &*sorted;
 
             for (solvable_id, _, spec) in version_run {
                 let build_key = SortedBuildIterator::make_option_values_build_key(
                     spec,
-                    &ordered_names,
-                    &build_name_values,
+                    ordered_names,
+                    build_name_values,
+                    false,
                     false,
                 );
                 build_key_index.insert(*solvable_id, build_key);
@@ -4,15 +4,17 @@
 
 use std::sync::Arc;
 
+use futures::StreamExt;
 use rstest::{fixture, rstest};
 use spfs::encoding::EMPTY_DIGEST;
 use spk_schema::foundation::fixtures::*;
 use spk_schema::foundation::ident_component::Component;
-use spk_schema::foundation::{build_ident, opt_name, version_ident};
+use spk_schema::foundation::{build_ident, opt_name, pkg_name, version_ident};
 use spk_schema::ident::{
     InitialRawRequest,
     PinnedRequest,
     PkgRequest,
+    PreReleasePolicy,
     RangeIdent,
     RequestedBy,
     VarRequest,
@@ -34,10 +36,20 @@ use spk_storage::RepositoryHandle;
 use spk_storage::fixtures::*;
 use tap::prelude::*;
 
-use crate::io::DecisionFormatterBuilder;
+use crate::io::{DecisionFormatterBuilder, DecisionRecord, DecisionRecordKind, OutputFormat};
 use crate::solver::{SolverExt, SolverImpl, SolverMut};
 use crate::solvers::step::{ErrorDetails, ErrorFreq};
-use crate::{Error, ResolvoSolver, Result, Solution, StepSolver, option_map, spec};
+use crate::{
+    Error,
+    PreReleasePolicies,
+    ResolvoSolver,
+    Result,
+    Solution,
+    StepSolver,
+    StepSolverRuntime,
+    option_map,
+    spec,
+};
 
 #[fixture]
 fn solver() -> StepSolver {
@@ -310,6 +322,47 @@ async fn test_solver_package_with_no_recipe_from_cmd_line(
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_solver_publish_time_tie_breaker(
+    mut solver: StepSolver,
+    #[values(true, false)] use_index: bool,
+) {
+    // The two builds are otherwise identical, and the digest
+    // tie-breaker alone would pick the older one. Indexed repos
+    // must order them the same way as the others.
+    let repo = RepositoryHandle::new_mem();
+    let builds = [
+        ("ZZZZZZZZ", "2020-01-01T00:00:00Z"),
+        ("BBBBBBBB", "2024-01-01T00:00:00Z"),
+    ];
+    for (digest, published) in builds {
+        let spec = spec!({
+            "pkg": format!("my-pkg/1.0.0/{digest}"),
+            "meta": {"labels": {"spk:published": published}},
+        });
+        let components = vec![
+            (Component::Run, EMPTY_DIGEST.into()),
+            (Component::Build, EMPTY_DIGEST.into()),
+        ]
+        .into_iter()
+        .collect();
+        repo.publish_package(&spec, &components).await.unwrap();
+    }
+    let repo = Arc::new(wrap_repo_for_test(repo, use_index).await);
+
+    solver.add_repository(repo);
+    solver.add_request(pinned_request!("my-pkg"));
+
+    let solution = solver.solve().await.unwrap();
+    let resolved = solution.get("my-pkg").unwrap();
+    assert_eq!(
+        resolved.spec.ident().build().to_string(),
+        "BBBBBBBB",
+        "the most recently published build should be resolved"
+    );
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
@@ -376,6 +429,33 @@ async fn test_solver_single_package_no_deps(
     assert_ne!(resolved.spec.ident().build(), &Build::Source);
 }
 
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_request_from_named_repository(#[case] mut solver: SolverImpl) {
+    let repo_a = Arc::new(make_repo!([{"pkg": "my-pkg/1.0.0"}]));
+    let repo_b = Arc::new(make_repo!([{"pkg": "my-pkg/1.0.0"}]));
+
+    solver.add_repository(repo_a.clone());
+    solver.add_repository(repo_b.clone());
+    let mut ident = parse_ident_range("my-pkg/1.0.0").unwrap();
+    ident.repository_name = Some(repo_b.name().to_owned());
+    solver.add_request(
+        PinnedRequest::Pkg(PkgRequest::new(ident, RequestedBy::SpkInternalTest)).into(),
+    );
+
+    let packages = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+    let pkg = packages
+        .get("my-pkg")
+        .expect("expected package to be in solution");
+    assert_eq!(
+        pkg.source.repository_name(),
+        Some(repo_b.name()),
+        "the package should come from the requested repository, even though another repository was consulted first"
+    );
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
@@ -797,6 +877,50 @@ async fn test_solver_pre_release_config_with_requirements(
     assert_resolved!(solution, "my-pkg", "1.0.0-pre.2");
 }
 
+/// Test that a prerelease policy for a single package takes precedence
+/// over the default policy for the solve
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_pre_release_policy_per_package(
+    #[case] mut solver: SolverImpl,
+    #[values(true, false)] use_index: bool,
+) {
+    let repo = make_repo!(
+        [
+            {"pkg": "my-pkg/1.0.0"},
+            {"pkg": "my-pkg/2.0.0-pre.1"},
+            {"pkg": "my-lib/1.0.0"},
+            {"pkg": "my-lib/2.0.0-pre.1"},
+            // my-lib is only requested as a dependency, without an
+            // explicit prereleasePolicy
+            {
+                "pkg": "my-tool/1.0.0",
+                "install": {"requirements": [{"pkg": "my-lib"}]},
+            },
+        ]
+    );
+    let repo = wrap_repo_for_test(repo, use_index).await;
+
+    solver.add_repository(Arc::new(repo));
+    solver.set_prerelease_policies(
+        PreReleasePolicies::new(Some(PreReleasePolicy::ExcludeAll))
+            .with_package(pkg_name!("my-lib").to_owned(), PreReleasePolicy::IncludeAll),
+    );
+    solver.add_request(pinned_request!("my-pkg"));
+    solver.add_request(pinned_request!("my-tool"));
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+    assert_resolved!(solution, "my-pkg", "1.0.0");
+    assert_resolved!(solution, "my-lib", "2.0.0-pre.1");
+    assert_eq!(
+        solution.get("my-lib").unwrap().prerelease_policy(),
+        PreReleasePolicy::IncludeAll,
+        "the effective policy should be recorded in the solution"
+    );
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
@@ -872,6 +996,527 @@ async fn test_solver_constraint_and_request(
     assert_resolved!(solution, "python", "3.7.3");
 }
 
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_optional_request_included(
+    #[case] mut solver: SolverImpl,
+    #[values(true, false)] use_index: bool,
+) {
+    // test what happens when an optional request can be satisfied
+    // - the package is resolved as normal
+    // - nothing is recorded as dropped
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-app/1.0.0",
+                "install": {"requirements": [{"pkg": "python/3.7"}]},
+            },
+            {
+                "pkg": "my-tool/1.0.0",
+                "install": {"requirements": [{"pkg": "python/3"}]},
+            },
+            {"pkg": "python/3.7.3"},
+        ]
+    );
+    let repo = wrap_repo_for_test(repo, use_index).await;
+
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-app"));
+    solver.add_request(pinned_request!({"pkg": "my-tool", "include": "IfCompatible"}));
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+
+    assert_resolved!(solution, "my-tool", "1.0.0");
+    assert!(solution.dropped_requests().is_empty());
+}
+
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_optional_request_dropped(
+    #[case] mut solver: SolverImpl,
+    #[values(true, false)] use_index: bool,
+) {
+    // test what happens when an optional request conflicts with
+    // the rest of the environment
+    // - the solve still succeeds without the package
+    // - the request is recorded as dropped in the solution
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-app/1.0.0",
+                "install": {"requirements": [{"pkg": "python/3.7"}]},
+            },
+            {
+                "pkg": "my-tool/1.0.0",
+                "install": {"requirements": [{"pkg": "python/3.8"}]},
+            },
+            {"pkg": "python/3.7.3"},
+            {"pkg": "python/3.8.1"},
+        ]
+    );
+    let repo = wrap_repo_for_test(repo, use_index).await;
+
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-app"));
+    solver.add_request(pinned_request!({"pkg": "my-tool", "include": "IfCompatible"}));
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+
+    assert_resolved!(solution, "python", "3.7.3");
+    assert!(solution.get("my-tool").is_none());
+    let dropped: Vec<_> = solution
+        .dropped_requests()
+        .iter()
+        .map(|r| r.pkg.name.as_str())
+        .collect();
+    assert_eq!(dropped, vec!["my-tool"]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solution_streamed_while_solving(mut solver: StepSolver) {
+    // when streaming, each package is shown as the solver resolves it,
+    // and the lines have the same content as the table that is shown
+    // once the solve is complete
+
+    let repo = Arc::new(make_repo!(
+        [
+            {
+                "pkg": "my-app/1.0.0",
+                "install": {"requirements": [{"pkg": "python/3.7"}]},
+            },
+            {"pkg": "python/3.7.3"},
+            {"pkg": "python/3.8.1"},
+        ]
+    ));
+    solver.add_repository(repo.clone());
+    solver.add_request(pinned_request!("my-app"));
+
+    let formatter = DecisionFormatterBuilder::default()
+        .with_verbosity(0)
+        .with_solution(true)
+        .with_stream_solution(true)
+        .build();
+    let mut runtime = solver.run();
+    let (lines, table) = {
+        let mut decisions = formatter
+            .formatted_decisions_iter(runtime.iter())
+            .with_repositories(vec![repo.clone()]);
+        let lines = decisions.iter().collect::<Vec<_>>().await;
+        (lines, decisions.take_solution_table())
+    };
+    let lines = lines
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .expect("formatting should not fail");
+    let table = table.expect("a table is used when streaming the solution");
+
+    let solution = runtime.current_solution().await.unwrap();
+    assert_eq!(
+        lines.len(),
+        1 + solution.len(),
+        "expected a heading and one line per package, got {lines:#?}"
+    );
+
+    let streamed = format!("{}\n{}", lines.join("\n"), table.format_footer(&solution));
+    let batch = solution
+        .format_solution_with_highest_versions(0, &[repo], false)
+        .await
+        .unwrap();
+    let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert_eq!(normalize(&streamed), normalize(&batch));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solution_tree_format(mut solver: StepSolver) {
+    // the solution tree shows which packages required which,
+    // only expanding packages required more than once the first
+    // time that they are shown
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-app/1.0.0",
+                "install": {"requirements": [{"pkg": "my-lib"}, {"pkg": "my-tool"}]},
+            },
+            {
+                "pkg": "my-tool/1.0.0",
+                "install": {"requirements": [{"pkg": "my-lib"}]},
+            },
+            {
+                "pkg": "my-lib/1.0.0",
+                "install": {"requirements": [{"pkg": "my-base"}]},
+            },
+            {"pkg": "my-base/1.0.0"},
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-app"));
+
+    let solution = solver.solve().await.unwrap();
+    let package = |name: &str| solution.get(name).unwrap().format_as_installed_package();
+    let expected = [
+        "Installed Packages:".to_string(),
+        format!("  {}", package("my-app")),
+        format!("  ├── {}", package("my-lib")),
+        format!("  │   └── {}", package("my-base")),
+        format!("  └── {}", package("my-tool")),
+        format!("      └── {} (*)", package("my-lib")),
+        " Number of Packages: 4".to_string(),
+    ]
+    .join("\n");
+    assert_eq!(solution.format_solution_tree(), expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solution_markdown_format(mut solver: StepSolver) {
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-app/1.0.0",
+                "install": {"requirements": [{"pkg": "my-lib:run"}]},
+            },
+            {"pkg": "my-lib/2.1.0"},
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-app:run"));
+
+    let solution = solver.solve().await.unwrap();
+    let build = |name: &str| solution.get(name).unwrap().spec.ident().build().to_string();
+    let expected = format!(
+        "| Name | Version | Build | Components |\n\
+         | --- | --- | --- | --- |\n\
+         | my-app | 1.0.0 | {} | run |\n\
+         | my-lib | 2.1.0 | {} | run |\n",
+        build("my-app"),
+        build("my-lib"),
+    );
+    assert_eq!(solution.format_solution_as_markdown(), expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_decision_formatter_json_output(mut solver: StepSolver) {
+    // each change in the solve is written as a json record, in order,
+    // instead of the human readable lines
+
+    let repo = make_repo!([{"pkg": "my-pkg/1.0.0"}]);
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-pkg"));
+
+    let formatter = DecisionFormatterBuilder::default()
+        .with_output_format(OutputFormat::Json)
+        .build();
+    let mut runtime = solver.run();
+    let lines = {
+        let mut decisions = formatter.formatted_decisions_iter(runtime.iter());
+        decisions.iter().collect::<Vec<_>>().await
+    };
+
+    let records = lines
+        .into_iter()
+        .map(|line| {
+            let line = line.expect("formatting should not fail");
+            serde_json::from_str::<DecisionRecord>(&line)
+                .unwrap_or_else(|err| panic!("every line should be a record: {line}: {err}"))
+        })
+        .filter(|record| {
+            !matches!(
+                record.kind,
+                DecisionRecordKind::SkipPackage | DecisionRecordKind::Note
+            )
+        })
+        .collect::<Vec<_>>();
+    let solution = runtime.current_solution().await.unwrap();
+    let expected = vec![
+        DecisionRecord {
+            level: 0,
+            kind: DecisionRecordKind::RequestPackage,
+            pkg: Some("my-pkg".to_string()),
+            detail: None,
+            reason: None,
+            code: None,
+        },
+        DecisionRecord {
+            level: 1,
+            kind: DecisionRecordKind::SetPackage,
+            pkg: Some(solution.get("my-pkg").unwrap().spec.ident().to_string()),
+            detail: None,
+            reason: None,
+            code: None,
+        },
+    ];
+    assert_eq!(records, expected);
+}
+
+#[rstest]
+#[case::highest_options(false, true)]
+#[case::fewest_packages(true, false)]
+#[tokio::test]
+async fn test_solver_prefer_fewest_packages(
+    mut solver: StepSolver,
+    #[case] prefer_fewest_packages: bool,
+    #[case] expect_extra: bool,
+) {
+    // the 'standard' build is picked by its option value unless
+    // builds that pull in fewer packages are preferred
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-pkg/1.0.0",
+                "build": {"options": [{"var": "flavor/standard"}]},
+                "install": {"requirements": [{"pkg": "extra-lib"}]},
+            },
+            {
+                "pkg": "my-pkg/1.0.0",
+                "build": {"options": [{"var": "flavor/lite"}]},
+            },
+            {"pkg": "extra-lib/1.0.0"},
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-pkg"));
+    solver.set_prefer_fewest_packages(prefer_fewest_packages);
+
+    let solution = solver.solve().await.unwrap();
+    assert_eq!(solution.get("extra-lib").is_some(), expect_extra);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solver_stats_count_steps_back(mut solver: StepSolver) {
+    // resolving some-library conflicts with the maya that was already
+    // chosen, so the solver has to step back before it can succeed
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "my-plugin/1.0.0",
+                "install": {
+                    "requirements": [{"pkg": "maya/2019"}, {"pkg": "some-library/1"}]
+                },
+            },
+            {"pkg": "maya/2019.2.0"},
+            {"pkg": "maya/2019.0.0"},
+            {
+                "pkg": "some-library/1.0.0",
+                "install": {"requirements": [{"pkg": "maya/~2019.0.0"}]},
+            },
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-plugin"));
+
+    let solution = solver.run().solution().await.unwrap();
+    assert_resolved!(solution, "maya", "2019.0.0");
+    let stats = solution.stats();
+    assert!(stats.steps_back > 0, "expected the solve to step back");
+    assert!(
+        stats.decisions > stats.steps_back,
+        "every step back is also a decision"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solver_timeout_returns_partial_state(mut solver: StepSolver) {
+    // a solve that runs past its timeout should report the deepest
+    // state it reached, rather than only that it gave up
+
+    let repo = make_repo!(
+        [
+            {"pkg": "pkg-a/1.0.0", "install": {"requirements": [{"pkg": "pkg-b"}]}},
+            {"pkg": "pkg-b/1.0.0", "install": {"requirements": [{"pkg": "pkg-c"}]}},
+            {"pkg": "pkg-c/1.0.0", "install": {"requirements": [{"pkg": "pkg-d"}]}},
+            {"pkg": "pkg-d/1.0.0", "install": {"requirements": [{"pkg": "pkg-e"}]}},
+            {"pkg": "pkg-e/1.0.0", "install": {"requirements": [{"pkg": "pkg-f"}]}},
+            {"pkg": "pkg-f/1.0.0"},
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("pkg-a"));
+
+    let mut runtime = solver.run();
+    {
+        // step until at least one package has been resolved
+        let iter = runtime.iter();
+        tokio::pin!(iter);
+        while let Some(item) = iter.next().await {
+            let (node, _) = item.expect("solve should not fail before the timeout");
+            if !node.state.get_resolved_packages().is_empty() {
+                break;
+            }
+        }
+    }
+    runtime.set_timeout(Some(std::time::Duration::ZERO));
+
+    let err = runtime.solution().await.expect_err("solve should time out");
+    let Error::SolverTimedOut(err) = err else {
+        panic!("expected a timeout error, got: {err}");
+    };
+    let resolved = err.deepest_state.get_resolved_packages();
+    assert!(
+        resolved.contains_key(pkg_name!("pkg-a")),
+        "the partial state should hold the packages resolved so far"
+    );
+    assert!(
+        !resolved.contains_key(pkg_name!("pkg-f")),
+        "the solve should have stopped before resolving everything"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solver_reset_clears_timeout(mut solver: StepSolver) {
+    let repo = make_repo!([{"pkg": "pkg-a/1.0.0"}]);
+    solver.set_timeout(Some(std::time::Duration::ZERO));
+    solver.reset();
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("pkg-a"));
+
+    solver
+        .solve()
+        .await
+        .expect("a reset solver should not keep the earlier timeout");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solver_version_compat_cache_hit_rate(mut solver: StepSolver) {
+    // every version of app but the oldest requires a version of base
+    // that no version of lib can be used with, so the solver checks
+    // the same versions of lib against the same range over and over
+
+    let repo = make_repo!(
+        [
+            {
+                "pkg": "app/3.0.0",
+                "install": {"requirements": [{"pkg": "lib/1"}, {"pkg": "base/2"}]},
+            },
+            {
+                "pkg": "app/2.0.0",
+                "install": {"requirements": [{"pkg": "lib/1"}, {"pkg": "base/2"}]},
+            },
+            {
+                "pkg": "app/1.0.0",
+                "install": {"requirements": [{"pkg": "lib/1"}]},
+            },
+            {"pkg": "base/2.0.0"},
+            {"pkg": "base/1.0.0"},
+            {"pkg": "lib/2.1.0"},
+            {"pkg": "lib/2.0.0"},
+            {
+                "pkg": "lib/1.7.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.6.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.5.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.4.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.3.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.2.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.1.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+            {
+                "pkg": "lib/1.0.0",
+                "install": {"requirements": [{"pkg": "base/1"}]},
+            },
+        ]
+    );
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("app"));
+    solver.set_resolve_validation_impossible_checks(false);
+    solver.set_version_compat_cache(true);
+
+    let mut runtime = solver.run();
+    let solution = runtime.solution().await.unwrap();
+    assert_resolved!(solution, "app", "1.0.0");
+
+    let cache = runtime
+        .solver
+        .version_compat_cache()
+        .expect("the cache should be enabled");
+    assert!(
+        cache.hits() > 0,
+        "expected repeated checks to hit the cache"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_solver_version_compat_cache_not_shared_between_solves(mut solver: StepSolver) {
+    let repo = Arc::new(make_repo!([{"pkg": "lib/1.0.0"}]));
+    solver.add_repository(repo);
+    solver.add_request(pinned_request!("lib/1"));
+    solver.set_version_compat_cache(true);
+
+    let mut runtime = solver.run();
+    runtime.solution().await.unwrap();
+    assert_eq!(runtime.solver.version_compat_cache().unwrap().misses(), 1);
+
+    // reusing the solver of a finished runtime should still start
+    // with an empty cache
+    let mut runtime = StepSolverRuntime::new(runtime.solver);
+    runtime.solution().await.unwrap();
+    let second = runtime.solver.version_compat_cache().unwrap();
+    assert_eq!(
+        second.hits(),
+        0,
+        "a new solve should not reuse the results of an earlier one"
+    );
+    assert_eq!(second.misses(), 1);
+}
+
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_optional_request_missing(
+    #[case] mut solver: SolverImpl,
+    #[values(true, false)] use_index: bool,
+) {
+    // an optional request for a package that does not exist is
+    // dropped rather than failing the solve
+
+    let repo = make_repo!([{"pkg": "my-app/1.0.0"}]);
+    let repo = wrap_repo_for_test(repo, use_index).await;
+
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("my-app"));
+    solver.add_request(pinned_request!({"pkg": "my-tool", "include": "IfCompatible"}));
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+
+    assert_resolved!(solution, "my-app", "1.0.0");
+    assert_eq!(solution.dropped_requests().len(), 1);
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
@@ -1324,6 +1969,52 @@ async fn test_solver_deprecated_build(
     );
 }
 
+#[rstest]
+#[case::step(step_solver())]
+#[case::resolvo(resolvo_solver())]
+#[tokio::test]
+async fn test_solver_deprecated_newer_version(#[case] mut solver: SolverImpl) {
+    let spec = recipe!({"pkg": "my-pkg/2.0.0"});
+    let (deprecated, deprecated_cmpts) =
+        make_build_and_components!(spec, [], {}, [], deprecated = true);
+    let deprecated_build = deprecated.ident().clone();
+    let repo = make_repo!([
+        {"pkg": "my-pkg/1.0.0"},
+        (deprecated, deprecated_cmpts),
+    ]);
+    repo.publish_recipe(&spec).await.unwrap();
+    let repo = Arc::new(repo);
+
+    solver.add_repository(repo.clone());
+    solver.add_request(pinned_request!("my-pkg"));
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+    assert_resolved!(
+        solution,
+        "my-pkg",
+        "1.0.0",
+        "should skip the deprecated newer version by default"
+    );
+
+    solver.reset();
+    solver.add_repository(repo);
+    solver.add_request(
+        PkgRequest::from_ident(
+            deprecated_build.to_any_ident(),
+            RequestedBy::SpkInternalTest,
+        )
+        .into(),
+    );
+
+    let solution = run_and_print_resolve_for_tests(&mut solver).await.unwrap();
+    assert_resolved!(
+        solution,
+        "my-pkg",
+        "2.0.0",
+        "should be able to resolve exact deprecated build"
+    );
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
@@ -2117,6 +2808,41 @@ async fn test_solver_impossible_request_but_embedded_package_makes_solvable(
     };
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_solver_error_reports_impossible_requests(mut solver: StepSolver) {
+    // pkg-a needs lib/>=1 and lib/<2 is also requested, each can be
+    // satisfied on its own but no build satisfies both, and the error
+    // should say so
+    init_logging();
+    let repo = make_repo!(
+        [
+            {"pkg": "pkg-a/1.0.0", "install": {"requirements": [{"pkg": "lib/>=1"}]}},
+            {"pkg": "lib/0.5.0"},
+            {"pkg": "lib/3.0.0"},
+        ]
+    );
+
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!("pkg-a"));
+    solver.add_request(pinned_request!("lib/<2"));
+    solver.set_resolve_validation_impossible_checks(true);
+
+    let err = solver
+        .run()
+        .solution()
+        .await
+        .expect_err("requests should be impossible to satisfy");
+    let Error::FailedToResolveImpossibleRequests(err) = err else {
+        panic!("expected the impossible requests to be reported, got: {err}");
+    };
+    let message = err.to_string();
+    assert!(
+        message.contains("pkg-a") && message.contains(">=1") && message.contains("<2"),
+        "error should name both requests: {message}"
+    );
+}
+
 /// When multiple packages try to embed the same package the solver doesn't
 /// panic.
 #[rstest]
@@ -2172,6 +2898,8 @@ async fn test_multiple_packages_embed_same_package(
         // step solver's error
         Err(Error::GraphError(ref graph_err))
             if matches!(&**graph_err, spk_solve_graph::Error::FailedToResolve(_)) => {}
+        // step solver's error, when impossible requests were found
+        Err(Error::FailedToResolveImpossibleRequests(_)) => {}
         // resolvo solver's error
         Err(Error::FailedToResolve(_)) => {}
         Ok(_) => {
@@ -2344,6 +3072,40 @@ async fn test_solver_unknown_package_options(
     run_and_print_resolve_for_tests(&mut solver).await.unwrap();
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_solver_unsatisfiable_var_request(mut solver: StepSolver) {
+    // test when no build of a package has the requested option value
+    // - the failure names the option and the values that were available
+
+    let spec = recipe!({
+        "pkg": "my-lib/1.0.0",
+        "build": {"options": [{"var": "gcc"}]},
+    });
+    let gcc9 = make_build!(spec, [], { "gcc" => "9" });
+    let gcc11 = make_build!(spec, [], { "gcc" => "11" });
+    let gcc12 = make_build!(spec, [], { "gcc" => "12" });
+    let repo = make_repo!([gcc9, gcc11, gcc12]);
+    repo.publish_recipe(&spec).await.unwrap();
+
+    solver.add_repository(Arc::new(repo));
+    solver.add_request(pinned_request!({"var": "my-lib.gcc/13"}));
+    solver.add_request(pinned_request!("my-lib"));
+
+    let mut runtime = solver.run();
+    let res = runtime.solution().await;
+    assert!(res.is_err(), "no build has the requested option value");
+
+    let expected =
+        "could not satisfy 'my-lib': option my-lib.gcc=13 requested but only {9, 11, 12} available";
+    let errors = runtime.solver.error_frequency();
+    assert!(
+        errors.contains_key(expected),
+        "expected the option mismatch to be reported, got: {:?}",
+        errors.keys().collect::<Vec<_>>()
+    );
+}
+
 #[rstest]
 #[case::step(step_solver())]
 #[case::resolvo(resolvo_solver())]
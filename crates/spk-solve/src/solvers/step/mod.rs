@@ -10,7 +10,9 @@
 //! then its mostly brute force approach gets overwhelmed.
 
 mod solver;
+mod version_compat_cache;
 
 #[cfg(test)]
 pub(crate) use solver::ErrorDetails;
 pub use solver::{ErrorFreq, Solver, SolverRuntime};
+pub use version_compat_cache::VersionCompatCache;
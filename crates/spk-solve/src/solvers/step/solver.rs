@@ -7,7 +7,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::mem::take;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_stream::stream;
 use futures::stream::{FuturesUnordered, StreamExt};
@@ -19,6 +19,7 @@ use spk_schema::foundation::name::{PkgName, PkgNameBuf};
 use spk_schema::foundation::version::Compatibility;
 use spk_schema::ident::{
     AsVersionIdent,
+    InclusionPolicy,
     PinnedValue,
     PkgRequest,
     PkgRequestWithOptions,
@@ -44,11 +45,15 @@ use spk_schema::{
 use spk_solve_graph::{
     Change,
     DEAD_STATE,
+    DUPLICATE_REQUESTS_COUNT,
     Decision,
+    DropRequest,
     Graph,
     NextRequest,
     Node,
     Note,
+    NoteCode,
+    REQUESTS_FOR_SAME_PACKAGE_COUNT,
     RequestPackage,
     RequestVar,
     SetOptions,
@@ -58,12 +63,14 @@ use spk_solve_graph::{
 };
 use spk_solve_package_iterator::{
     BuildIterator,
+    BuildToSortedOptName,
     EmptyBuildIterator,
+    EmptyBuildsReason,
     PackageIterator,
     RepositoryPackageIterator,
     SortedBuildIterator,
 };
-use spk_solve_solution::{PackageSource, Solution};
+use spk_solve_solution::{PackageSource, Solution, SolveStats};
 use spk_solve_validation::validators::BinaryOnlyValidator;
 use spk_solve_validation::{
     IMPOSSIBLE_CHECKS_TARGET,
@@ -74,10 +81,20 @@ use spk_solve_validation::{
 };
 use spk_storage::RepositoryHandle;
 
-use crate::error::{self, OutOfOptions};
+use crate::error::{self, ImpossibleRequest, OutOfOptions, SolverTimedOut};
 use crate::option_map::OptionMap;
 use crate::solver::Solver as SolverTrait;
-use crate::{DecisionFormatter, Error, Result, SolverExt, SolverMut};
+use crate::solvers::step::VersionCompatCache;
+use crate::{
+    ConfiguredOrder,
+    DecisionFormatter,
+    Error,
+    PreReleasePolicies,
+    RepositoryOrdering,
+    Result,
+    SolverExt,
+    SolverMut,
+};
 
 /// Structure to hold whether the three kinds of impossible checks are
 /// enabled or disabled in a solver.
@@ -109,6 +126,10 @@ impl Default for ImpossibleChecksSettings {
 #[derive(Clone)]
 pub struct Solver {
     repos: Vec<Arc<RepositoryHandle>>,
+    // Decides the order that repos are searched in for each package
+    repository_ordering: Arc<dyn RepositoryOrdering>,
+    // Fills in the prerelease policy of requests that don't set one
+    prerelease_policies: PreReleasePolicies,
     initial_state_builders: Vec<Change>,
     validators: Cow<'static, [Validators]>,
     // For validating candidate requests and builds by checking the
@@ -142,12 +163,27 @@ pub struct Solver {
     // Set of package/versions the solver has decided to try to build
     // from source as part of a solve
     new_builds_started: HashSet<VersionIdent>,
+    // For reusing the option names worked out for sorting a set of
+    // builds whenever the same builds are sorted again in the solve
+    sorted_opt_names: Arc<BuildToSortedOptName>,
+    // The wall-clock time a solve may run for before giving up
+    timeout: Option<Duration>,
+    // Whether to try builds that require fewer packages first
+    prefer_fewest_packages: bool,
+    // The requests found to be impossible during the solve, reported
+    // if the solve fails
+    impossible_requests_found: Vec<ImpossibleRequest>,
+    // Remembers version compatibility checks, when enabled. This is
+    // replaced with an empty cache at the start of every solve.
+    version_compat_cache: Option<VersionCompatCache>,
 }
 
 impl Default for Solver {
     fn default() -> Self {
         Self {
             repos: Vec::default(),
+            repository_ordering: Arc::new(ConfiguredOrder),
+            prerelease_policies: PreReleasePolicies::default(),
             initial_state_builders: Vec::default(),
             validators: Cow::from(default_validators()),
             request_validator: Arc::new(ImpossibleRequestsChecker::default()),
@@ -161,6 +197,11 @@ impl Default for Solver {
             error_frequency: HashMap::new(),
             problem_packages: HashMap::new(),
             new_builds_started: HashSet::new(),
+            sorted_opt_names: Arc::new(BuildToSortedOptName::default()),
+            timeout: None,
+            prefer_fewest_packages: false,
+            impossible_requests_found: Vec::new(),
+            version_compat_cache: None,
         }
     }
 }
@@ -234,12 +275,28 @@ impl Solver {
     pub fn get_initial_state(&self) -> Arc<State> {
         let mut state = None;
         let base = State::default_state();
-        for change in self.initial_state_builders.iter() {
+        let mut changes = self.initial_state_builders.clone();
+        self.apply_prerelease_policies(&mut changes);
+        for change in changes.iter() {
             state = Some(change.apply(&base, state.as_ref().unwrap_or(&base)));
         }
         state.unwrap_or(base)
     }
 
+    /// Fill in the prerelease policy of any package requests made by
+    /// the given changes that do not set one of their own.
+    fn apply_prerelease_policies(&self, changes: &mut [Change]) {
+        if self.prerelease_policies.is_empty() {
+            return;
+        }
+        for change in changes.iter_mut() {
+            if let Change::RequestPackage(request) = change {
+                self.prerelease_policies
+                    .apply(&mut request.request.pkg_request);
+            }
+        }
+    }
+
     /// Increment the number of occurrences of the given error message
     pub(crate) fn increment_error_count(&mut self, error_message: ErrorDetails) {
         match error_message {
@@ -324,8 +381,11 @@ impl Solver {
         package_name: PkgNameBuf,
     ) -> Arc<tokio::sync::Mutex<Box<dyn PackageIterator + Send>>> {
         debug_assert!(!self.repos.is_empty());
+        let repos = self
+            .repository_ordering
+            .order_repositories(&package_name, &self.repos);
         Arc::new(tokio::sync::Mutex::new(Box::new(
-            RepositoryPackageIterator::new(package_name, self.repos.clone()),
+            RepositoryPackageIterator::new(package_name, repos),
         )))
     }
 
@@ -366,6 +426,8 @@ impl Solver {
 
         let mut solver = Solver {
             repos: self.repos.clone(),
+            repository_ordering: Arc::clone(&self.repository_ordering),
+            prerelease_policies: self.prerelease_policies.clone(),
             ..Default::default()
         };
         solver.update_options(opts.clone());
@@ -546,7 +608,10 @@ impl Solver {
                 Err(e) => return Err(e.into()),
             };
 
-            let mut compat = request.is_version_applicable(pkg.version());
+            let mut compat = match self.version_compat_cache.as_mut() {
+                Some(cache) => cache.is_version_applicable(&request, pkg.version()),
+                None => request.is_version_applicable(pkg.version()),
+            };
             if !&compat {
                 // Count this version and its builds as incompatible
                 self.number_incompat_versions += 1;
@@ -564,6 +629,20 @@ impl Solver {
                 continue;
             }
 
+            let empty_reason = builds.lock().await.empty_reason().cloned();
+            if let Some(reason) = empty_reason {
+                // None of this version's builds could be used the
+                // last time they were tried
+                notes.push(Note::SkipPackageNote(Box::new(
+                    SkipPackageNote::new_from_message(
+                        pkg.clone(),
+                        format!("version {} {reason}", pkg.version()),
+                    )
+                    .with_code(NoteCode::SkipNoCompatibleBuilds),
+                )));
+                continue;
+            }
+
             let builds: Arc<tokio::sync::Mutex<dyn BuildIterator + Send>> = if !builds
                 .lock()
                 .await
@@ -598,6 +677,8 @@ impl Solver {
                         node.state.get_option_map().clone(),
                         builds.clone(),
                         builds_with_impossible_requests,
+                        &self.sorted_opt_names,
+                        self.prefer_fewest_packages,
                     )
                     .await?,
                 ));
@@ -607,11 +688,17 @@ impl Solver {
                 builds
             };
 
+            // For explaining why this version was passed over, if
+            // none of its builds can be used
+            let mut empty_reason = EmptyBuildsReason::default();
+
             while let Some(hm) = builds.lock().await.next().await? {
                 // Now add this build to the total considered during
                 // this overall step
 
                 self.number_total_builds += 1;
+                empty_reason.num_builds += 1;
+                let first_build_note = notes.len();
 
                 // Try all the hash map values to check all repos.
                 for (spec, source) in hm.values() {
@@ -637,6 +724,10 @@ impl Solver {
                                         // This build would add an impossible request,
                                         // which is a bad choice for any solve, so
                                         // discard this build and try another.
+                                        self.record_impossible_request(
+                                            spec.ident().to_string(),
+                                            &compat,
+                                        );
                                         notes.push(Note::SkipPackageNote(Box::new(
                                             SkipPackageNote::new(
                                                 spec.ident().to_any_ident(),
@@ -857,7 +948,8 @@ impl Solver {
                                 SkipPackageNote::new_from_message(
                                     spec.ident().to_any_ident(),
                                     &compat,
-                                ),
+                                )
+                                .with_code(NoteCode::SkipBuildFromSource),
                             )));
                             self.number_builds_skipped += 1;
                             continue;
@@ -868,7 +960,8 @@ impl Solver {
                                     SkipPackageNote::new_from_message(
                                         pkg.clone(),
                                         "cannot build from source, version is deprecated",
-                                    ),
+                                    )
+                                    .with_code(NoteCode::SkipDeprecated),
                                 )));
                                 continue;
                             }
@@ -880,7 +973,8 @@ impl Solver {
                                     SkipPackageNote::new_from_message(
                                         *pkg,
                                         "cannot build from source, recipe not available",
-                                    ),
+                                    )
+                                    .with_code(NoteCode::SkipRecipeUnavailable),
                                 )));
                                 continue;
                             }
@@ -891,7 +985,7 @@ impl Solver {
                             notes.push(Note::SkipPackageNote(Box::new(SkipPackageNote::new_from_message(
                                 spec.ident().to_any_ident(),
                                 format!("building from source is not possible with this recipe: {compat}"),
-                            ))));
+                            ).with_code(NoteCode::from(&compat)))));
                             self.number_builds_skipped += 1;
                             continue;
                         }
@@ -902,7 +996,8 @@ impl Solver {
                                     SkipPackageNote::new_from_message(
                                         spec.ident().to_any_ident(),
                                         format!("cannot resolve build env for source build: {err}"),
-                                    ),
+                                    )
+                                    .with_code(NoteCode::SkipBuildEnvUnresolvable),
                                 )));
                                 self.number_builds_skipped += 1;
                                 continue;
@@ -919,7 +1014,8 @@ impl Solver {
                                 SkipPackageNote::new_from_message(
                                     spec.ident().to_any_ident(),
                                     format!("building from source not possible: {compat}"),
-                                ),
+                                )
+                                .with_code(NoteCode::from(&compat)),
                             )));
                             self.number_builds_skipped += 1;
                             continue;
@@ -935,7 +1031,8 @@ impl Solver {
                                     SkipPackageNote::new_from_message(
                                         spec.ident().to_any_ident(),
                                         format!("cannot build package from source: {err}"),
-                                    ),
+                                    )
+                                    .with_code(NoteCode::SkipBuildFromSource),
                                 )));
                                 self.number_builds_skipped += 1;
                                 continue;
@@ -953,13 +1050,82 @@ impl Solver {
                     decision.add_notes(notes.iter().cloned());
                     return Ok(Some(decision));
                 }
+
+                // This build was skipped in every repo, the last
+                // note made for it holds the final reason why
+                let code = notes[first_build_note..]
+                    .iter()
+                    .rev()
+                    .find_map(|note| match note {
+                        Note::SkipPackageNote(note) => Some(note.code),
+                        _ => None,
+                    })
+                    .unwrap_or(NoteCode::SkipOther);
+                empty_reason.add_skipped(code.skipped_builds_description());
+            }
+
+            if empty_reason.num_builds > 0 {
+                notes.push(Note::SkipPackageNote(Box::new(
+                    SkipPackageNote::new_from_message(
+                        pkg.clone(),
+                        format!("version {} {empty_reason}", pkg.version()),
+                    )
+                    .with_code(NoteCode::SkipNoCompatibleBuilds),
+                )));
+                iterator_lock.set_builds(
+                    pkg.version(),
+                    Arc::new(tokio::sync::Mutex::new(EmptyBuildIterator::with_reason(
+                        empty_reason,
+                    ))),
+                );
             }
         }
 
-        Err(error::Error::OutOfOptions(Box::new(error::OutOfOptions {
+        let err = error::OutOfOptions {
             request: request.pkg_request,
             notes,
-        })))
+        };
+        match error::UnsatisfiableVarRequest::from_out_of_options(&err) {
+            Some(err) => Err(Error::UnsatisfiableVarRequest(Box::new(err))),
+            None => Err(Error::OutOfOptions(Box::new(err))),
+        }
+    }
+
+    /// Drop the optional requests for a package that could not be
+    /// resolved, rather than failing.
+    ///
+    /// Only the `IfCompatible` requests for the package are dropped,
+    /// so if it is also required the failure is found again, and
+    /// handled as normal, on the next step.
+    fn drop_unsatisfiable_optional_request(
+        state: &State,
+        result: Result<Option<Decision>>,
+    ) -> Result<Option<Decision>> {
+        let (request, cause) = match &result {
+            Err(Error::OutOfOptions(err)) => (&err.request, "could not be satisfied"),
+            Err(Error::UnsatisfiableVarRequest(err)) => (&err.request, "could not be satisfied"),
+            Err(Error::GraphError(err)) => match &**err {
+                spk_solve_graph::Error::PackageNotFoundDuringSolve(request) => {
+                    (&**request, "package not found")
+                }
+                _ => return result,
+            },
+            _ => return result,
+        };
+        let is_optional = state.get_pkg_requests().iter().any(|r| {
+            r.pkg.name == request.pkg.name && r.inclusion_policy == InclusionPolicy::IfCompatible
+        });
+        if !is_optional {
+            return result;
+        }
+        let mut decision =
+            Change::DropRequest(DropRequest::new(request.clone(), cause)).as_decision();
+        match result {
+            Err(Error::OutOfOptions(err)) => decision.add_notes(err.notes),
+            Err(Error::UnsatisfiableVarRequest(err)) => decision.add_notes(err.notes),
+            _ => {}
+        }
+        Ok(Some(decision))
     }
 
     /// Detect when applying `decision` would tighten the request for an
@@ -1098,7 +1264,7 @@ impl Solver {
         &mut self,
         initial_state: &State,
     ) -> Result<()> {
-        let mut impossible_request_count = 0;
+        let mut impossible_requests = Vec::new();
 
         let tasks = FuturesUnordered::new();
 
@@ -1176,7 +1342,7 @@ impl Solver {
                     return Err(crate::Error::String(err.to_string()));
                 }
             };
-            if !compat.is_ok() {
+            if let Compatibility::Incompatible(reason) = compat {
                 tracing::warn!(
                     "Impossible initial request, no builds in the repos [{}] satisfy: {}",
                     self.repos
@@ -1186,20 +1352,38 @@ impl Solver {
                         .join(", "),
                     checked_req
                 );
-                impossible_request_count += 1;
+                impossible_requests.push(ImpossibleRequest {
+                    request: checked_req.to_string(),
+                    reason,
+                });
             }
         }
 
         // Error if any initial request is impossible
-        if impossible_request_count > 0 {
+        if !impossible_requests.is_empty() {
             return Err(Error::InitialRequestsContainImpossibleError(
-                impossible_request_count,
+                impossible_requests,
             ));
         }
 
         Ok(())
     }
 
+    /// Remember that the given request was found to be impossible,
+    /// so it can be reported if the solve fails
+    fn record_impossible_request(&mut self, request: String, compat: &Compatibility) {
+        let Compatibility::Incompatible(reason) = compat else {
+            return;
+        };
+        let impossible = ImpossibleRequest {
+            request,
+            reason: reason.clone(),
+        };
+        if !self.impossible_requests_found.contains(&impossible) {
+            self.impossible_requests_found.push(impossible);
+        }
+    }
+
     /// Run this solver
     pub fn run(&self) -> SolverRuntime {
         SolverRuntime::new(self.clone())
@@ -1211,6 +1395,41 @@ impl Solver {
         self.new_builds_started.extend(new_builds);
     }
 
+    /// Limit how long a solve may run for.
+    ///
+    /// Once the timeout has passed, the solve stops with a
+    /// [`Error::SolverTimedOut`] holding the deepest state it reached.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Prefer the builds that require the fewest packages.
+    ///
+    /// This keeps the number of packages in a solution, and so the
+    /// number of layers in its runtime, down. It is weighed before the
+    /// option values of the builds, so it can pick a build made
+    /// against lower versions of its dependencies, or one without an
+    /// optional dependency, over the build that would otherwise be
+    /// picked.
+    pub fn set_prefer_fewest_packages(&mut self, prefer: bool) {
+        self.prefer_fewest_packages = prefer;
+    }
+
+    /// Enable or disable remembering the results of checking package
+    /// versions against the version ranges of requests.
+    ///
+    /// The same versions are often checked against the same ranges
+    /// many times over when the solver has to step back. The cache
+    /// only lives for a single solve.
+    pub fn set_version_compat_cache(&mut self, enabled: bool) {
+        self.version_compat_cache = enabled.then(VersionCompatCache::default);
+    }
+
+    /// The version compatibility cache of the current solve, if enabled
+    pub fn version_compat_cache(&self) -> Option<&VersionCompatCache> {
+        self.version_compat_cache.as_ref()
+    }
+
     /// Enable or disable running impossible checks on the initial requests
     /// before the solve starts
     pub fn set_initial_request_impossible_checks(&mut self, enabled: bool) {
@@ -1321,6 +1540,9 @@ impl SolverMut for Solver {
 
     fn reset(&mut self) {
         self.repos.truncate(0);
+        self.repository_ordering = Arc::new(ConfiguredOrder);
+        self.prerelease_policies = PreReleasePolicies::default();
+        self.timeout = None;
         self.initial_state_builders.truncate(0);
         self.validators = Cow::from(default_validators());
         (*self.request_validator).reset();
@@ -1334,6 +1556,10 @@ impl SolverMut for Solver {
         self.error_frequency.clear();
         self.problem_packages.clear();
         self.new_builds_started.clear();
+        self.sorted_opt_names = Arc::new(BuildToSortedOptName::default());
+        self.prefer_fewest_packages = false;
+        self.impossible_requests_found.clear();
+        self.version_compat_cache = None;
     }
 
     async fn run_and_log_resolve(&mut self, formatter: &DecisionFormatter) -> Result<Solution> {
@@ -1374,6 +1600,14 @@ impl SolverMut for Solver {
         }
     }
 
+    fn set_repository_ordering(&mut self, ordering: Arc<dyn RepositoryOrdering>) {
+        self.repository_ordering = ordering;
+    }
+
+    fn set_prerelease_policies(&mut self, policies: PreReleasePolicies) {
+        self.prerelease_policies = policies;
+    }
+
     async fn solve(&mut self) -> Result<Solution> {
         let mut runtime = self.run();
         {
@@ -1429,20 +1663,67 @@ pub struct SolverRuntime {
     history: SolverHistory,
     current_node: Option<Arc<tokio::sync::RwLock<Arc<Node>>>>,
     decision: Option<Arc<Decision>>,
+    // When this runtime was first iterated, for enforcing the timeout
+    started: Option<Instant>,
+    // The deepest state reached so far, reported if the solve times out
+    deepest_state: Option<Arc<State>>,
+    // For counting the decisions applied to the graph by this runtime
+    number_of_decisions: u64,
+    // The graph's process-wide request counters when this runtime
+    // was created, so only the requests made by this solve are reported
+    initial_requests_for_same_package: u64,
+    initial_duplicate_requests: u64,
 }
 
 impl SolverRuntime {
-    pub fn new(solver: Solver) -> Self {
-        let initial_decision = Decision::new(solver.initial_state_builders.clone());
+    pub fn new(mut solver: Solver) -> Self {
+        if solver.version_compat_cache.is_some() {
+            // results must not be carried over from any earlier solve
+            solver.version_compat_cache = Some(VersionCompatCache::default());
+        }
+        let mut initial_changes = solver.initial_state_builders.clone();
+        solver.apply_prerelease_policies(&mut initial_changes);
+        let initial_decision = Decision::new(initial_changes);
         Self {
             solver,
             graph: Arc::new(tokio::sync::RwLock::new(Graph::new())),
             history: SolverHistory::default(),
             current_node: None,
             decision: Some(Arc::new(initial_decision)),
+            started: None,
+            deepest_state: None,
+            number_of_decisions: 0,
+            initial_requests_for_same_package: REQUESTS_FOR_SAME_PACKAGE_COUNT
+                .load(Ordering::SeqCst),
+            initial_duplicate_requests: DUPLICATE_REQUESTS_COUNT.load(Ordering::SeqCst),
+        }
+    }
+
+    /// The work done by this runtime so far.
+    ///
+    /// The request counts come from counters shared by every solve in
+    /// the process, so they will include the requests of any other
+    /// solves running at the same time.
+    pub fn stats(&self) -> SolveStats {
+        SolveStats {
+            decisions: self.number_of_decisions,
+            steps_back: self.solver.get_number_of_steps_back(),
+            requests_for_same_package: REQUESTS_FOR_SAME_PACKAGE_COUNT
+                .load(Ordering::SeqCst)
+                .saturating_sub(self.initial_requests_for_same_package),
+            duplicate_requests: DUPLICATE_REQUESTS_COUNT
+                .load(Ordering::SeqCst)
+                .saturating_sub(self.initial_duplicate_requests),
         }
     }
 
+    /// Limit how long this runtime may run for.
+    ///
+    /// See [`Solver::set_timeout`].
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.solver.set_timeout(timeout);
+    }
+
     /// A reference to the solve graph being built by this runtime
     pub fn graph(&self) -> Arc<tokio::sync::RwLock<Graph>> {
         self.graph.clone()
@@ -1481,9 +1762,21 @@ impl SolverRuntime {
             .get_pkg_requests()
             .is_empty();
         if is_dead && !is_empty {
-            Err(spk_solve_graph::Error::FailedToResolve((*self.graph).read().await.clone()).into())
+            let err = spk_solve_graph::Error::FailedToResolve((*self.graph).read().await.clone());
+            if self.solver.impossible_requests_found.is_empty() {
+                Err(err.into())
+            } else {
+                Err(Error::FailedToResolveImpossibleRequests(Box::new(
+                    error::FailedToResolveImpossibleRequests {
+                        source: err,
+                        impossible_requests: self.solver.impossible_requests_found.clone(),
+                    },
+                )))
+            }
         } else {
-            current_node_lock.state.as_solution().map_err(Into::into)
+            let mut solution = current_node_lock.state.as_solution()?;
+            solution.set_stats(self.stats());
+            Ok(solution)
         }
     }
 
@@ -1550,6 +1843,7 @@ impl SolverRuntime {
     pub fn iter(&mut self) -> impl Stream<Item = Result<(Arc<Node>, Arc<Decision>)>> + Send {
         stream! {
             let mut first_iter = true;
+            let started = *self.started.get_or_insert_with(Instant::now);
             'outer: loop {
                 if self.decision.is_none()
                     || (self.current_node.is_some()
@@ -1565,6 +1859,20 @@ impl SolverRuntime {
                     break 'outer;
                 }
 
+                if let Some(timeout) = self.solver.timeout
+                    && started.elapsed() > timeout
+                {
+                    let deepest_state = match self.deepest_state.as_ref() {
+                        Some(state) => Arc::clone(state),
+                        None => Arc::clone(&self.graph.read().await.root.read().await.state),
+                    };
+                    yield Err(Error::SolverTimedOut(Box::new(SolverTimedOut {
+                        elapsed: started.elapsed(),
+                        deepest_state,
+                    })));
+                    break 'outer;
+                }
+
                 let to_yield = (
                     // A clone of Some(current_node) or the root node
                     {
@@ -1578,6 +1886,7 @@ impl SolverRuntime {
                     self.decision.as_ref().expect("decision is some").clone(),
                 );
 
+                self.number_of_decisions += 1;
                 self.current_node = Some({
                     let mut sg = self.graph.write().await;
                     let root_id = sg.root.read().await.id();
@@ -1609,6 +1918,13 @@ impl SolverRuntime {
                     .expect("current_node always `is_some` here");
                 let mut current_node_lock = current_node.write().await;
                 let current_level = current_node_lock.state.state_depth;
+                if self
+                    .deepest_state
+                    .as_ref()
+                    .is_none_or(|deepest| deepest.state_depth < current_level)
+                {
+                    self.deepest_state = Some(Arc::clone(&current_node_lock.state));
+                }
 
                 if first_iter {
                     // Check for impossible requests only the first
@@ -1624,13 +1940,27 @@ impl SolverRuntime {
                         }
                 }
 
-                self.decision = match self.solver.step_state(&self.graph, &mut current_node_lock).await
+                let result = self.solver.step_state(&self.graph, &mut current_node_lock).await.map(|decision| {
+                    decision.map(|mut decision| {
+                        self.solver.apply_prerelease_policies(&mut decision.changes);
+                        decision
+                    })
+                });
+                self.decision = match Solver::drop_unsatisfiable_optional_request(&current_node_lock.state, result)
                 {
                     Ok(decision) => decision.map(Arc::new),
-                    Err(crate::Error::OutOfOptions(ref err)) => {
+                    Err(err @ (crate::Error::OutOfOptions(_) | crate::Error::UnsatisfiableVarRequest(_))) => {
+                        let (request, notes, var_error) = match &err {
+                            crate::Error::OutOfOptions(err) => (&err.request, &err.notes, None),
+                            crate::Error::UnsatisfiableVarRequest(err) => {
+                                (&err.request, &err.notes, Some(err.to_string()))
+                            }
+                            _ => unreachable!(),
+                        };
+
                         // Add to problem package counts based on what made
                         // the request for the blocked package.
-                        let requested_by = err.request.get_requesters();
+                        let requested_by = request.get_requesters();
                         for req in &requested_by {
                             if let RequestedBy::PackageBuild(problem_package) = req {
                                 self.solver
@@ -1641,11 +1971,14 @@ impl SolverRuntime {
                         // Add the requirers to the output so where the
                         // requests came from is more visible to the user.
                         let requirers: Vec<String> = requested_by.iter().map(ToString::to_string).collect();
-                        let cause = format!(
+                        let mut cause = format!(
                             "could not satisfy '{}' as required by: {}",
-                            err.request.pkg,
+                            request.pkg,
                             requirers.join(", ")
                         );
+                        if let Some(var_error) = &var_error {
+                            cause = format!("{cause}: {var_error}");
+                        }
 
                         SolverRuntime::take_a_step_back(
                             &mut self.history,
@@ -1654,7 +1987,12 @@ impl SolverRuntime {
                             &cause,
                         ).await;
 
-                        self.solver.increment_error_count(ErrorDetails::CouldNotSatisfy(err.request.pkg.to_string(), requested_by));
+                        // Option mismatches are counted separately so the
+                        // available values are visible in the summary.
+                        match var_error {
+                            Some(var_error) => self.solver.increment_error_count(ErrorDetails::Message(format!("could not satisfy '{}': {var_error}", request.pkg))),
+                            None => self.solver.increment_error_count(ErrorDetails::CouldNotSatisfy(request.pkg.to_string(), requested_by)),
+                        }
 
                         if let Some(d) = self.decision.as_mut() {
                             'added_notes: {
@@ -1662,8 +2000,8 @@ impl SolverRuntime {
                                 // were skipped for the same reason, then
                                 // replace the individual skip notes with a
                                 // single summary note.
-                                if let Some(first) = err.notes.first()
-                                    && err.notes.iter().all(|n| {
+                                if let Some(first) = notes.first()
+                                    && notes.iter().all(|n| {
                                         match (n, first) {
                                             (Note::SkipPackageNote(n), Note::SkipPackageNote(first)) => n.is_same_reason_as(first),
                                             (Note::Other(n), Note::Other(first)) => n == first,
@@ -1681,7 +2019,7 @@ impl SolverRuntime {
                                         break 'added_notes;
                                     }
 
-                                Arc::make_mut(d).add_notes(err.notes.iter().cloned());
+                                Arc::make_mut(d).add_notes(notes.iter().cloned());
                             }
                         }
                         yield Ok(to_yield);
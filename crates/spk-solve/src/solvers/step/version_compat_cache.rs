@@ -0,0 +1,78 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use std::collections::HashMap;
+
+use spk_schema::foundation::version::{Compatibility, Version};
+use spk_schema::foundation::version_range::Ranged;
+use spk_schema::ident::PkgRequest;
+
+#[cfg(test)]
+#[path = "./version_compat_cache_test.rs"]
+mod version_compat_cache_test;
+
+/// Remembers the results of checking package versions against the
+/// version ranges of requests during a single solve.
+///
+/// Checking a version against a range has no side effects, so the
+/// result for the same version and range is always the same. Ranges
+/// are keyed by their string form, which includes every rule they
+/// were built from, so ranges made with either
+/// [`RestrictMode`](spk_schema::foundation::version_range::RestrictMode)
+/// are only ever matched with identical ranges.
+///
+/// A cache should not be kept for longer than a single solve.
+#[derive(Clone, Debug, Default)]
+pub struct VersionCompatCache {
+    results: HashMap<(Version, String), Compatibility>,
+    hits: u64,
+    misses: u64,
+}
+
+impl VersionCompatCache {
+    /// Like [`PkgRequest::is_version_applicable`], but
+    /// reuses the result of any earlier check of the same version
+    /// against the same range.
+    pub fn is_version_applicable(
+        &mut self,
+        request: &PkgRequest,
+        version: &Version,
+    ) -> Compatibility {
+        // The prerelease policy is part of the request and not the
+        // range, and is cheap to check, so it is not cached
+        let compat = request.is_prerelease_allowed(version);
+        if !compat.is_ok() {
+            return compat;
+        }
+
+        let key = (version.clone(), request.pkg.version.to_string());
+        if let Some(compat) = self.results.get(&key) {
+            self.hits += 1;
+            return compat.clone();
+        }
+        self.misses += 1;
+        let compat = request.pkg.version.is_applicable(version);
+        self.results.insert(key, compat.clone());
+        compat
+    }
+
+    /// The number of checks answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of checks that had to be worked out
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The fraction of checks answered from the cache, between 0 and 1
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
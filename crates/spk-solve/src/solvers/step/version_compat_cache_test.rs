@@ -0,0 +1,70 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use spk_schema::foundation::version::parse_version;
+use spk_schema::foundation::version_range::RestrictMode;
+use spk_schema::ident::{PkgRequest, PreReleasePolicy, RequestedBy, parse_ident_range};
+
+use super::VersionCompatCache;
+
+fn request(range: &str) -> PkgRequest {
+    PkgRequest::new(
+        parse_ident_range(range).unwrap(),
+        RequestedBy::SpkInternalTest,
+    )
+}
+
+#[rstest]
+#[case("lib/1", "1.2.0")]
+#[case("lib/1", "2.0.0")]
+#[case("lib/>=1,<2", "1.5.0")]
+#[case("lib/=1.0.0", "1.0.1")]
+fn test_version_compat_cache_matches_request(#[case] range: &str, #[case] version: &str) {
+    let mut cache = VersionCompatCache::default();
+    let request = request(range);
+    let version = parse_version(version).unwrap();
+    let expected = request.is_version_applicable(&version);
+
+    let first = cache.is_version_applicable(&request, &version);
+    let second = cache.is_version_applicable(&request, &version);
+    assert_eq!(first.is_ok(), expected.is_ok());
+    assert_eq!(second.is_ok(), expected.is_ok());
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1);
+}
+
+#[rstest]
+fn test_version_compat_cache_non_intersecting_restrict() {
+    // a range restricted without requiring the ranges to intersect
+    // matches nothing, and must not reuse a result of either of the
+    // ranges it was made from
+    let mut cache = VersionCompatCache::default();
+    let version = parse_version("1.0.0").unwrap();
+    let mut req = request("lib/>=1");
+    assert!(cache.is_version_applicable(&req, &version).is_ok());
+
+    let other = parse_ident_range("lib/<1").unwrap();
+    assert!(
+        req.pkg
+            .restrict(&other, RestrictMode::AllowNonIntersectingRanges)
+            .is_ok()
+    );
+    assert!(!cache.is_version_applicable(&req, &version).is_ok());
+    assert_eq!(cache.hits(), 0);
+}
+
+#[rstest]
+fn test_version_compat_cache_prerelease_policy() {
+    // the prerelease policy of a request applies even when the same
+    // version and range are already cached
+    let mut cache = VersionCompatCache::default();
+    let version = parse_version("1.0.0-pre.1").unwrap();
+    let include = request("lib/>=0.1").with_prerelease(Some(PreReleasePolicy::IncludeAll));
+    let exclude = request("lib/>=0.1").with_prerelease(Some(PreReleasePolicy::ExcludeAll));
+
+    assert!(cache.is_version_applicable(&include, &version).is_ok());
+    assert!(!cache.is_version_applicable(&exclude, &version).is_ok());
+    assert!(cache.is_version_applicable(&include, &version).is_ok());
+}
@@ -3,7 +3,8 @@
 // https://github.com/spkenv/spk
 
 use miette::Diagnostic;
-use spk_schema::{AnyIdent, VersionIdent};
+use spk_schema::foundation::name::PkgNameBuf;
+use spk_schema::{AnyIdent, BuildIdent, VersionIdent};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -37,14 +38,28 @@ pub enum Error {
     FileOpenError(std::path::PathBuf, #[source] std::io::Error),
     #[error("Failed to read file {0}")]
     FileReadError(std::path::PathBuf, #[source] std::io::Error),
+    #[error("Failed to write file {0}")]
+    FileWriteError(std::path::PathBuf, #[source] std::io::Error),
     #[error("{0}")]
     InvalidPackageSpec(Box<InvalidPackageSpec>),
+    #[error("Package spec file is empty: {0}")]
+    #[diagnostic(help(
+        "The file may have been truncated by an interrupted build, try rebuilding the package"
+    ))]
+    EmptyPackageSpec(std::path::PathBuf),
     #[error("Invalid repository metadata: {0}")]
     InvalidRepositoryMetadata(#[source] serde_yaml::Error),
     #[error("Package not found: {0}")]
     PackageNotFound(Box<AnyIdent>),
     #[error("Version exists: {0}")]
     VersionExists(VersionIdent),
+    #[error("Package exists: {0}")]
+    PackageExists(PkgNameBuf),
+    #[error(
+        "Package was published under its new name, but removing the old package failed after {} builds were removed",
+        .0.len()
+    )]
+    PackageRenameIncomplete(Vec<BuildIdent>, #[source] Box<Error>),
     #[error(transparent)]
     #[diagnostic(forward(0))]
     SPFS(#[from] spfs::Error),
@@ -23,7 +23,9 @@ pub use disk_usage::{
 };
 pub use error::{Error, InvalidPackageSpec, Result};
 pub use storage::{
+    ArchiveConversion,
     CachePolicy,
+    ENV_BUNDLE_TAG,
     FlatBufferRepoIndex,
     IndexedRepository,
     MemRepository,
@@ -35,12 +37,20 @@ pub use storage::{
     RuntimeRepository,
     SpfsRepository,
     Storage,
+    convert_archive_to_oci,
+    convert_oci_to_archive,
+    export_env_bundle,
     export_package,
+    export_package_excluding_components,
     find_path_providers,
+    import_env_bundle,
+    import_package,
     inject_path_repo_into_spfs_config,
+    is_oci_layout,
     local_repository,
     pretty_print_filepath,
     remote_repository,
+    repository_address,
     run_index_update_server,
 };
 pub use walker::{RepoWalker, RepoWalkerBuilder, RepoWalkerItem};
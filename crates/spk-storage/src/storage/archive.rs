@@ -3,9 +3,12 @@
 // https://github.com/spkenv/spk
 
 use std::convert::TryFrom;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use futures::TryStreamExt;
 use itertools::{Itertools, Position};
+use spfs::prelude::{DatabaseView, PlatformStorage, TagStorage};
+use spk_schema::foundation::ident_component::Component;
 use spk_schema::ident::AsVersionIdent;
 use spk_schema::{AnyIdent, BuildIdent, VersionIdent};
 use variantly::Variantly;
@@ -13,12 +16,16 @@ use variantly::Variantly;
 use super::{Repository, SpfsRepository};
 use crate::{Error, NameAndRepository, Result};
 
-pub async fn export_package(
-    source_repos: &[&SpfsRepository],
-    pkg: impl AsRef<AnyIdent>,
-    filename: impl AsRef<Path>,
-) -> Result<()> {
-    let pkg = pkg.as_ref();
+/// The tag that identifies the runtime stack stored in an
+/// environment bundle, see [`export_env_bundle`].
+pub const ENV_BUNDLE_TAG: &str = "spk/bundle/env";
+
+/// Create a new, empty archive at the given path, replacing any
+/// existing file.
+///
+/// Returns the absolute path of the archive and a repository
+/// that can be used to write into it.
+async fn create_archive(filename: impl AsRef<Path>) -> Result<(PathBuf, SpfsRepository)> {
     // Make filename absolute as spfs::runtime::makedirs_with_perms does not handle
     // relative paths properly.
     let filename = std::env::current_dir()
@@ -46,7 +53,7 @@ pub async fn export_package(
             repository: "<TAR Archive>".into(),
             source,
         })?;
-    // Package exports should not include the top-level directory for
+    // Archives should not include the top-level directory for
     // durable runtime upperdir edits.
     tar_repo.remove_durable_dir().await?;
 
@@ -54,6 +61,33 @@ pub async fn export_package(
         "archive",
         spfs::storage::RepositoryHandle::from(tar_repo),
     ))?;
+    Ok((filename, target_repo))
+}
+
+pub async fn export_package(
+    source_repos: &[&SpfsRepository],
+    pkg: impl AsRef<AnyIdent>,
+    filename: impl AsRef<Path>,
+) -> Result<()> {
+    export_package_excluding_components(source_repos, pkg, &[], filename).await
+}
+
+/// Like [`export_package`], but leaves the given components of each
+/// build out of the archive.
+///
+/// The layers of excluded components are not copied, and the builds
+/// are published into the archive without them, so the archive can
+/// still be imported as usual. Builds that are left with no
+/// components at all, such as source builds when excluding
+/// [`Component::Source`], are left out entirely.
+pub async fn export_package_excluding_components(
+    source_repos: &[&SpfsRepository],
+    pkg: impl AsRef<AnyIdent>,
+    excluded: &[Component],
+    filename: impl AsRef<Path>,
+) -> Result<()> {
+    let pkg = pkg.as_ref();
+    let (filename, target_repo) = create_archive(filename).await?;
 
     // these are sorted to ensure that the recipe is published
     // before any build - it's only an error in testing, but still best practice
@@ -90,7 +124,7 @@ pub async fn export_package(
         let mut all_errors_are_build_not_found = true;
 
         for (position, repo) in source_repos.iter().with_position() {
-            let err = match copy_any(transfer_pkg.clone(), repo, &target_repo).await {
+            let err = match copy_any(transfer_pkg.clone(), repo, &target_repo, excluded).await {
                 Ok(_) => continue 'pkg,
                 Err(Error::PackageNotFound(ident)) => {
                     if ident.build().is_some() {
@@ -141,8 +175,152 @@ pub async fn export_package(
     }
 
     tracing::info!(path=?filename, "building archive");
+    flush_archive(&target_repo)
+}
+
+/// Export a runtime stack and the packages that it was built from
+/// into a single archive that can be moved to another host.
+///
+/// The layers of the stack are stored as a platform tagged with
+/// [`ENV_BUNDLE_TAG`], so that the environment can be recreated
+/// exactly, see [`import_env_bundle`]. The packages are included so
+/// that they can be inspected once the environment is active again.
+pub async fn export_env_bundle(
+    source_repos: &[&SpfsRepository],
+    stack: &spfs::graph::Stack,
+    packages: &[BuildIdent],
+    filename: impl AsRef<Path>,
+) -> Result<()> {
+    let (filename, target_repo) = create_archive(filename).await?;
+
+    let mut to_transfer = std::collections::BTreeSet::new();
+    for pkg in packages.iter().filter(|pkg| !pkg.is_embedded()) {
+        let pkg = pkg.to_any_ident();
+        to_transfer.insert(pkg.with_build(None));
+        to_transfer.insert(pkg);
+    }
+
+    'pkg: for transfer_pkg in to_transfer.into_iter() {
+        for repo in source_repos.iter() {
+            match copy_any(transfer_pkg.clone(), repo, &target_repo, &[]).await {
+                Ok(_) => continue 'pkg,
+                Err(Error::PackageNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        if transfer_pkg.build().is_none() {
+            // the recipe is not needed to recreate the environment
+            tracing::warn!(pkg=%transfer_pkg, "recipe not found, it will not be bundled");
+            continue;
+        }
+        return Err(Error::PackageNotFound(Box::new(transfer_pkg)));
+    }
+
+    'layer: for digest in stack.iter_bottom_up() {
+        for repo in source_repos.iter() {
+            if !repo.has_object(digest).await {
+                continue;
+            }
+            tracing::info!(%digest, "exporting layer");
+            spfs::Syncer::new(repo, &target_repo)
+                .with_reporter(spfs::sync::reporter::SyncReporters::console())
+                .sync_digest(digest)
+                .await?;
+            continue 'layer;
+        }
+        return Err(Error::String(format!(
+            "Runtime layer {digest} was not found in any repository"
+        )));
+    }
+    let platform = target_repo.create_platform(stack.clone()).await?;
+    let tag = spfs::tracking::TagSpec::parse(ENV_BUNDLE_TAG)?;
+    target_repo.push_tag(&tag, &platform.digest()?).await?;
+
+    tracing::info!(path=?filename, "building archive");
+    flush_archive(&target_repo)
+}
+
+/// Import an archive created by [`export_package`].
+///
+/// The archive is used as the source of the given syncer, and all of
+/// its tagged packages are synced into the syncer's destination.
+pub async fn import_package(
+    filename: impl AsRef<Path>,
+    syncer: &spfs::Syncer<'_, '_>,
+) -> Result<spfs::sync::SyncEnvResult> {
+    let filename = filename.as_ref();
+    let tar_repo = spfs::storage::tar::TarRepository::open(filename).await?;
+    let tar_repo: spfs::storage::RepositoryHandle = tar_repo.into();
+    let env_spec = tar_repo
+        .iter_tags()
+        .map_ok(|(spec, _)| spec)
+        .try_collect()
+        .await?;
+    tracing::info!(archive = ?filename, "importing");
+    Ok(syncer
+        .clone_with_source(&tar_repo)
+        .sync_env(env_spec)
+        .await?)
+}
+
+/// Import an archive created by [`export_env_bundle`] into the
+/// given repository.
+///
+/// Every object needed by the bundled environment is checked for
+/// before returning, so that an incomplete bundle is caught before
+/// any attempt is made to activate it. Returns the digest of the
+/// platform that holds the bundled runtime stack.
+pub async fn import_env_bundle(
+    filename: impl AsRef<Path>,
+    dst_repo: &SpfsRepository,
+) -> Result<spfs::encoding::Digest> {
+    let filename = filename.as_ref();
+    let dst_repo: &spfs::storage::RepositoryHandle = dst_repo;
+    let tar_repo = spfs::storage::tar::TarRepository::open(filename).await?;
+    let tar_repo: spfs::storage::RepositoryHandle = tar_repo.into();
+
+    // The bundle tag is read from the bundle itself, as the destination
+    // may already hold one from a bundle that was imported before
+    let tag = spfs::tracking::TagSpec::parse(ENV_BUNDLE_TAG)?;
+    let platform = match tar_repo.resolve_tag(&tag).await {
+        Ok(tag) => tag.target,
+        Err(spfs::Error::UnknownReference(_)) => {
+            return Err(Error::String(format!(
+                "{filename:?} is not an environment bundle, it has no {ENV_BUNDLE_TAG} tag"
+            )));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let env_spec = tar_repo
+        .iter_tags()
+        .map_ok(|(spec, _)| spec)
+        .try_filter(|spec| futures::future::ready(spec.path() != tag.path()))
+        .try_collect()
+        .await?;
+    tracing::info!(archive = ?filename, "importing");
+    let syncer = spfs::Syncer::new(&tar_repo, dst_repo)
+        .with_reporter(spfs::sync::reporter::SyncReporters::console());
+    syncer.sync_env(env_spec).await?;
+    syncer.sync_digest(platform).await?;
+    dst_repo.push_tag(&tag, &platform).await?;
+
+    let summary = spfs::Checker::new(dst_repo)
+        .check_digest(platform)
+        .await?
+        .summary();
+    let missing = summary.missing_objects.len() + summary.missing_payloads.len();
+    if missing > 0 {
+        return Err(Error::String(format!(
+            "Environment bundle {filename:?} is incomplete, {missing} objects are missing"
+        )));
+    }
+    Ok(platform)
+}
+
+fn flush_archive(repo: &SpfsRepository) -> Result<()> {
     use std::ops::Deref;
-    if let spfs::storage::RepositoryHandle::Tar(tar) = target_repo.deref() {
+    if let spfs::storage::RepositoryHandle::Tar(tar) = repo.deref() {
         tar.flush()?;
     }
     Ok(())
@@ -152,11 +330,12 @@ async fn copy_any(
     pkg: AnyIdent,
     src_repo: &SpfsRepository,
     dst_repo: &SpfsRepository,
+    excluded: &[Component],
 ) -> Result<()> {
     match pkg.into_inner() {
         (base, None) => copy_recipe(&base, src_repo, dst_repo).await,
         (base, Some(build)) => {
-            copy_package(&BuildIdent::new(base, build), src_repo, dst_repo).await
+            copy_package(&BuildIdent::new(base, build), src_repo, dst_repo, excluded).await
         }
     }
 }
@@ -176,9 +355,15 @@ async fn copy_package(
     pkg: &BuildIdent,
     src_repo: &SpfsRepository,
     dst_repo: &SpfsRepository,
+    excluded: &[Component],
 ) -> Result<()> {
     let spec = src_repo.read_package(pkg).await?;
-    let components = src_repo.read_components(pkg).await?;
+    let mut components = src_repo.read_components(pkg).await?;
+    components.retain(|component, _| !excluded.contains(component));
+    if components.is_empty() {
+        tracing::debug!(%pkg, "all components are excluded, skipping");
+        return Ok(());
+    }
     tracing::info!(%pkg, "exporting");
     let syncer = spfs::Syncer::new(src_repo, dst_repo)
         .with_reporter(spfs::sync::reporter::SyncReporters::console());
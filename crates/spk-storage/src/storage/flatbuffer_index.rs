@@ -1139,6 +1139,8 @@ impl BuildInfo {
         let store_deprecated_as_placeholders = false;
         //let store_deprecated_as_placeholders = build_spec.is_deprecated();
 
+        let published = build_spec.published().map(|p| p.timestamp());
+
         let fb_build_index_args = if store_deprecated_as_placeholders {
             let fb_compat = compat_to_fb_compat(builder, &build_spec.compat());
 
@@ -1155,6 +1157,7 @@ impl BuildInfo {
                 embedded: None,
                 component_specs: None,
                 published_components: None,
+                published,
             }
         } else {
             let fb_compat = compat_to_fb_compat(builder, &build_spec.compat());
@@ -1186,6 +1189,7 @@ impl BuildInfo {
                 embedded: fb_embedded_specs,
                 component_specs: fb_component_specs,
                 published_components: fb_published_components,
+                published,
             }
         };
 
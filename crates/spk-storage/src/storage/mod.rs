@@ -8,18 +8,27 @@ mod handle;
 mod indexed;
 mod mem;
 mod messaging;
+mod oci;
 mod repository;
 mod repository_index;
 mod runtime;
 mod spfs;
 
-pub use archive::export_package;
+pub use archive::{
+    ENV_BUNDLE_TAG,
+    export_env_bundle,
+    export_package,
+    export_package_excluding_components,
+    import_env_bundle,
+    import_package,
+};
 pub use flatbuffer_index::FlatBufferRepoIndex;
 pub use handle::RepositoryHandle;
 pub use indexed::IndexedRepository;
 pub use mem::MemRepository;
 pub(crate) use messaging::announce_package_event;
 pub use messaging::{PackageEvent, run_index_update_server};
+pub use oci::{ArchiveConversion, convert_archive_to_oci, convert_oci_to_archive, is_oci_layout};
 pub use repository::{CachePolicy, Repository, Storage};
 pub use repository_index::{RepoIndex, RepositoryIndex, RepositoryIndexMut};
 pub use runtime::{RuntimeRepository, find_path_providers, pretty_print_filepath};
@@ -30,4 +39,5 @@ pub use self::spfs::{
     inject_path_repo_into_spfs_config,
     local_repository,
     remote_repository,
+    repository_address,
 };
@@ -0,0 +1,550 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+//! Conversion between spk archives and OCI image layouts.
+//!
+//! An spk archive is a tar file containing an spfs repository. The
+//! same data is stored in an OCI image layout by writing each spfs
+//! object, payload and tag as an OCI blob. Payloads are addressed by
+//! the sha256 of their contents in both formats and so keep the same
+//! digest. Objects and tags are stored in their encoded form, with the
+//! original spfs digest recorded in an annotation so that it can be
+//! verified when converting back.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use spfs::encoding::{Digest, Hasher};
+use spfs::prelude::*;
+use tokio::io::AsyncWriteExt;
+
+use crate::{Error, Result};
+
+#[cfg(test)]
+#[path = "./oci_test.rs"]
+mod oci_test;
+
+const OCI_LAYOUT_FILE: &str = "oci-layout";
+const OCI_LAYOUT_VERSION: &str = "1.0.0";
+const OCI_INDEX_FILE: &str = "index.json";
+const OCI_INDEX_MEDIA_TYPE: &str = "application/vnd.oci.image.index.v1+json";
+const OCI_MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+const OCI_EMPTY_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+const OCI_REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+
+const SPFS_ARTIFACT_TYPE: &str = "application/vnd.spkenv.spfs.repository.v1";
+const SPFS_OBJECT_MEDIA_TYPE: &str = "application/vnd.spkenv.spfs.object.v1";
+const SPFS_PAYLOAD_MEDIA_TYPE: &str = "application/vnd.spkenv.spfs.payload.v1";
+const SPFS_TAG_MEDIA_TYPE: &str = "application/vnd.spkenv.spfs.tag.v1";
+const SPFS_DIGEST_ANNOTATION: &str = "dev.spkenv.spfs.digest";
+
+/// The reference name of the manifest holding all spfs objects and payloads
+const SPFS_OBJECTS_REF_NAME: &str = "spfs-objects";
+
+/// A summary of the data copied when converting an archive.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchiveConversion {
+    pub objects: usize,
+    pub payloads: usize,
+    pub tags: usize,
+    /// Descriptions of any data in the source that could not
+    /// be represented in the converted archive
+    pub not_converted: Vec<String>,
+}
+
+/// True if the given path is an OCI image layout, either as
+/// a directory or a tar archive of one.
+pub fn is_oci_layout(path: impl AsRef<Path>) -> Result<bool> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        return Ok(path.join(OCI_LAYOUT_FILE).is_file());
+    }
+    let file = std::fs::File::open(path).map_err(|err| Error::FileOpenError(path.into(), err))?;
+    let mut archive = tar::Archive::new(file);
+    let entries = archive
+        .entries()
+        .map_err(|err| Error::FileReadError(path.into(), err))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::FileReadError(path.into(), err))?;
+        let entry_path = entry
+            .path()
+            .map_err(|err| Error::FileReadError(path.into(), err))?;
+        if entry_path
+            .components()
+            .eq(Path::new(OCI_LAYOUT_FILE).components())
+            || entry_path == Path::new(".").join(OCI_LAYOUT_FILE)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Write the full contents of an spk archive into a new OCI
+/// image layout tar archive.
+pub async fn convert_archive_to_oci(
+    archive: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<ArchiveConversion> {
+    let archive = archive.as_ref();
+    let output = output.as_ref();
+    let repo = spfs::storage::tar::TarRepository::open(archive)
+        .await
+        .map_err(|source| spfs::Error::FailedToOpenRepository {
+            repository: "<TAR Archive>".into(),
+            source,
+        })?;
+    let repo = spfs::storage::RepositoryHandle::from(repo);
+
+    let staging = staging_dir(output)?;
+    let layout = LayoutWriter::create(staging.path())?;
+    let mut conversion = ArchiveConversion::default();
+    let mut manifests = Vec::new();
+
+    let mut blobs = Vec::new();
+    let mut objects = repo.iter_objects();
+    while let Some((digest, object)) = objects.try_next().await? {
+        let data = object.encode_to_bytes()?;
+        let mut descriptor = layout.write_blob(SPFS_OBJECT_MEDIA_TYPE, &data)?;
+        descriptor
+            .annotations
+            .insert(SPFS_DIGEST_ANNOTATION.into(), digest.to_string());
+        blobs.push(descriptor);
+        conversion.objects += 1;
+    }
+    drop(objects);
+
+    let mut payloads = repo.iter_payload_digests();
+    while let Some(digest) = payloads.try_next().await? {
+        let (reader, _) = repo.open_payload(digest).await?;
+        let mut descriptor = layout
+            .write_blob_from(SPFS_PAYLOAD_MEDIA_TYPE, reader)
+            .await?;
+        if descriptor.digest != oci_digest(&digest) {
+            return Err(Error::String(format!(
+                "Payload {digest} in {archive:?} does not match its content digest"
+            )));
+        }
+        descriptor
+            .annotations
+            .insert(SPFS_DIGEST_ANNOTATION.into(), digest.to_string());
+        blobs.push(descriptor);
+        conversion.payloads += 1;
+    }
+    drop(payloads);
+    manifests.push(layout.write_manifest(SPFS_OBJECTS_REF_NAME, blobs)?);
+
+    let mut tag_streams = repo.iter_tag_streams();
+    while let Some((spec, stream)) = tag_streams.try_next().await? {
+        // tag streams are newest first, but are stored oldest first
+        // so that they can be re-inserted in their original order
+        let mut history: Vec<_> = stream.try_collect().await?;
+        history.reverse();
+        let mut blobs = Vec::with_capacity(history.len());
+        for tag in history {
+            let data = tag.encode_to_bytes()?;
+            let mut descriptor = layout.write_blob(SPFS_TAG_MEDIA_TYPE, &data)?;
+            descriptor
+                .annotations
+                .insert(SPFS_DIGEST_ANNOTATION.into(), tag.target.to_string());
+            blobs.push(descriptor);
+        }
+        conversion.tags += 1;
+        manifests.push(layout.write_manifest(spec.path().as_str(), blobs)?);
+    }
+    drop(tag_streams);
+
+    layout.finish(manifests)?;
+    write_tar(staging.path(), output)?;
+    Ok(conversion)
+}
+
+/// Write the spfs data from an OCI image layout, either a directory
+/// or tar archive of one, into a new spk archive.
+///
+/// Any content in the layout that was not created from an spk archive
+/// is skipped and listed in the returned [`ArchiveConversion`].
+pub async fn convert_oci_to_archive(
+    layout: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<ArchiveConversion> {
+    let layout = layout.as_ref();
+    let output = output.as_ref();
+    let unpacked;
+    let root = if layout.is_dir() {
+        layout.to_owned()
+    } else {
+        unpacked = staging_dir(output)?;
+        let file =
+            std::fs::File::open(layout).map_err(|err| Error::FileOpenError(layout.into(), err))?;
+        tar::Archive::new(file)
+            .unpack(unpacked.path())
+            .map_err(|err| Error::FileReadError(layout.into(), err))?;
+        unpacked.path().to_owned()
+    };
+    let reader = LayoutReader::open(&root)?;
+
+    if output.exists() {
+        return Err(Error::String(format!(
+            "Output file already exists: {output:?}"
+        )));
+    }
+    let tar_repo = spfs::storage::tar::TarRepository::create(output)
+        .await
+        .map_err(|source| spfs::Error::FailedToOpenRepository {
+            repository: "<TAR Archive>".into(),
+            source,
+        })?;
+    // Archives should not include the top-level directory for
+    // durable runtime upperdir edits.
+    tar_repo.remove_durable_dir().await?;
+    let repo = spfs::storage::RepositoryHandle::from(tar_repo);
+
+    let mut conversion = ArchiveConversion::default();
+    for entry in reader.index.manifests.iter() {
+        let ref_name = entry
+            .annotations
+            .get(OCI_REF_NAME_ANNOTATION)
+            .cloned()
+            .unwrap_or_else(|| entry.digest.clone());
+        if entry.media_type != OCI_MANIFEST_MEDIA_TYPE {
+            conversion.not_converted.push(format!(
+                "{ref_name}: unsupported media type {}",
+                entry.media_type
+            ));
+            continue;
+        }
+        let manifest: ImageManifest = reader.read_json(&entry.digest)?;
+        if manifest.artifact_type.as_deref() != Some(SPFS_ARTIFACT_TYPE) {
+            conversion
+                .not_converted
+                .push(format!("{ref_name}: not an spfs artifact"));
+            continue;
+        }
+        for (name, _) in manifest
+            .annotations
+            .iter()
+            .filter(|(name, _)| name.as_str() != OCI_REF_NAME_ANNOTATION)
+        {
+            conversion
+                .not_converted
+                .push(format!("{ref_name}: manifest annotation {name}"));
+        }
+        for layer in manifest.layers.iter() {
+            let Some(spfs_digest) = layer.annotations.get(SPFS_DIGEST_ANNOTATION) else {
+                conversion.not_converted.push(format!(
+                    "{ref_name}: layer {} has no spfs digest",
+                    layer.digest
+                ));
+                continue;
+            };
+            let spfs_digest = Digest::parse(spfs_digest).map_err(|err| {
+                Error::String(format!("Invalid spfs digest in {ref_name}: {err}"))
+            })?;
+            match layer.media_type.as_str() {
+                SPFS_OBJECT_MEDIA_TYPE => {
+                    let data = reader.read_blob(&layer.digest)?;
+                    let object = spfs::graph::Object::decode(&mut data.as_slice())?;
+                    let digest = object.digest()?;
+                    if digest != spfs_digest {
+                        return Err(Error::String(format!(
+                            "Object digest changed during conversion: expected {spfs_digest}, got {digest}"
+                        )));
+                    }
+                    repo.write_object(&object).await?;
+                    conversion.objects += 1;
+                }
+                SPFS_PAYLOAD_MEDIA_TYPE => {
+                    let path = reader.blob_path(&layer.digest)?;
+                    let file = tokio::fs::File::open(&path)
+                        .await
+                        .map_err(|err| Error::FileOpenError(path.clone(), err))?;
+                    let payload = Box::pin(tokio::io::BufReader::new(file));
+                    // Safety: the objects that track each payload are
+                    // copied along with them from the same layout
+                    let (digest, _) = unsafe { repo.write_data(payload).await? };
+                    if digest != spfs_digest {
+                        return Err(Error::String(format!(
+                            "Payload digest changed during conversion: expected {spfs_digest}, got {digest}"
+                        )));
+                    }
+                    conversion.payloads += 1;
+                }
+                SPFS_TAG_MEDIA_TYPE => {
+                    let data = reader.read_blob(&layer.digest)?;
+                    let tag = spfs::tracking::Tag::decode(&mut data.as_slice())?;
+                    repo.insert_tag(&tag).await?;
+                }
+                other => {
+                    conversion.not_converted.push(format!(
+                        "{ref_name}: layer with unsupported media type {other}"
+                    ));
+                }
+            }
+        }
+        if manifest
+            .layers
+            .iter()
+            .any(|l| l.media_type == SPFS_TAG_MEDIA_TYPE)
+        {
+            conversion.tags += 1;
+        }
+    }
+
+    if let spfs::storage::RepositoryHandle::Tar(tar) = &repo {
+        tar.flush()?;
+    }
+    Ok(conversion)
+}
+
+/// The OCI digest string for the given spfs digest, which
+/// are both sha256 hashes
+fn oci_digest(digest: &Digest) -> String {
+    format!(
+        "sha256:{}",
+        data_encoding::HEXLOWER.encode(digest.as_bytes())
+    )
+}
+
+/// Create a temporary directory next to the given output file
+fn staging_dir(output: &Path) -> Result<tempfile::TempDir> {
+    let parent = match output.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_owned(),
+        _ => PathBuf::from("."),
+    };
+    std::fs::create_dir_all(&parent)
+        .map_err(|err| Error::DirectoryCreateError(parent.clone(), err))?;
+    tempfile::Builder::new()
+        .prefix(".spk-convert-")
+        .tempdir_in(&parent)
+        .map_err(|err| Error::DirectoryCreateError(parent, err))
+}
+
+fn write_tar(source: &Path, output: &Path) -> Result<()> {
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(output)
+        .map_err(|err| Error::FileOpenError(output.into(), err))?;
+    let mut builder = tar::Builder::new(file);
+    builder
+        .append_dir_all(".", source)
+        .and_then(|_| builder.finish())
+        .map_err(|err| Error::FileWriteError(output.into(), err))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageManifest {
+    schema_version: u32,
+    #[serde(default)]
+    media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artifact_type: Option<String>,
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageIndex {
+    schema_version: u32,
+    #[serde(default)]
+    media_type: Option<String>,
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageLayout {
+    image_layout_version: String,
+}
+
+/// Writes blobs and manifests into an OCI image layout directory
+struct LayoutWriter {
+    root: PathBuf,
+    empty_config: Descriptor,
+}
+
+impl LayoutWriter {
+    fn create(root: &Path) -> Result<Self> {
+        let blobs = root.join("blobs").join("sha256");
+        std::fs::create_dir_all(&blobs).map_err(|err| Error::DirectoryCreateError(blobs, err))?;
+        let mut writer = Self {
+            root: root.to_owned(),
+            empty_config: Descriptor {
+                media_type: String::new(),
+                digest: String::new(),
+                size: 0,
+                annotations: Default::default(),
+            },
+        };
+        writer.empty_config = writer.write_blob(OCI_EMPTY_MEDIA_TYPE, b"{}")?;
+        Ok(writer)
+    }
+
+    fn blob_path(&self, digest: &str) -> PathBuf {
+        let hex = digest.trim_start_matches("sha256:");
+        self.root.join("blobs").join("sha256").join(hex)
+    }
+
+    fn write_blob(&self, media_type: &str, data: &[u8]) -> Result<Descriptor> {
+        let digest = data.digest().map_err(spfs::Error::from)?;
+        let descriptor = Descriptor {
+            media_type: media_type.to_string(),
+            digest: oci_digest(&digest),
+            size: data.len() as u64,
+            annotations: Default::default(),
+        };
+        let path = self.blob_path(&descriptor.digest);
+        std::fs::write(&path, data).map_err(|err| Error::FileWriteError(path, err))?;
+        Ok(descriptor)
+    }
+
+    async fn write_blob_from(
+        &self,
+        media_type: &str,
+        mut reader: std::pin::Pin<Box<dyn spfs::tracking::BlobRead>>,
+    ) -> Result<Descriptor> {
+        let working = self.root.join("blobs").join(".incoming");
+        let file = tokio::fs::File::create(&working)
+            .await
+            .map_err(|err| Error::FileOpenError(working.clone(), err))?;
+        let mut hasher = Hasher::with_target(file);
+        let size = tokio::io::copy(&mut reader, &mut hasher)
+            .await
+            .map_err(|err| Error::FileWriteError(working.clone(), err))?;
+        hasher
+            .flush()
+            .await
+            .map_err(|err| Error::FileWriteError(working.clone(), err))?;
+        let descriptor = Descriptor {
+            media_type: media_type.to_string(),
+            digest: oci_digest(&hasher.digest()),
+            size,
+            annotations: Default::default(),
+        };
+        let path = self.blob_path(&descriptor.digest);
+        tokio::fs::rename(&working, &path)
+            .await
+            .map_err(|err| Error::FileWriteError(path, err))?;
+        Ok(descriptor)
+    }
+
+    fn write_json<T: Serialize>(&self, media_type: &str, value: &T) -> Result<Descriptor> {
+        let data = serde_json::to_vec(value)
+            .map_err(|err| Error::String(format!("Failed to serialize OCI data: {err}")))?;
+        self.write_blob(media_type, &data)
+    }
+
+    fn write_manifest(&self, ref_name: &str, layers: Vec<Descriptor>) -> Result<Descriptor> {
+        let manifest = ImageManifest {
+            schema_version: 2,
+            media_type: Some(OCI_MANIFEST_MEDIA_TYPE.to_string()),
+            artifact_type: Some(SPFS_ARTIFACT_TYPE.to_string()),
+            config: self.empty_config.clone(),
+            layers,
+            annotations: Default::default(),
+        };
+        let mut descriptor = self.write_json(OCI_MANIFEST_MEDIA_TYPE, &manifest)?;
+        descriptor
+            .annotations
+            .insert(OCI_REF_NAME_ANNOTATION.into(), ref_name.to_string());
+        Ok(descriptor)
+    }
+
+    fn finish(self, manifests: Vec<Descriptor>) -> Result<()> {
+        let index = ImageIndex {
+            schema_version: 2,
+            media_type: Some(OCI_INDEX_MEDIA_TYPE.to_string()),
+            manifests,
+        };
+        let layout = ImageLayout {
+            image_layout_version: OCI_LAYOUT_VERSION.to_string(),
+        };
+        for (name, data) in [
+            (OCI_INDEX_FILE, serde_json::to_vec(&index)),
+            (OCI_LAYOUT_FILE, serde_json::to_vec(&layout)),
+        ] {
+            let data =
+                data.map_err(|err| Error::String(format!("Failed to serialize OCI data: {err}")))?;
+            let path = self.root.join(name);
+            std::fs::write(&path, data).map_err(|err| Error::FileWriteError(path, err))?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads blobs and manifests from an OCI image layout directory
+struct LayoutReader {
+    root: PathBuf,
+    index: ImageIndex,
+}
+
+impl LayoutReader {
+    fn open(root: &Path) -> Result<Self> {
+        let layout_path = root.join(OCI_LAYOUT_FILE);
+        let data = std::fs::read(&layout_path)
+            .map_err(|err| Error::FileReadError(layout_path.clone(), err))?;
+        let layout: ImageLayout = serde_json::from_slice(&data)
+            .map_err(|err| Error::String(format!("Invalid {layout_path:?}: {err}")))?;
+        if layout.image_layout_version != OCI_LAYOUT_VERSION {
+            return Err(Error::String(format!(
+                "Unsupported OCI image layout version: {}",
+                layout.image_layout_version
+            )));
+        }
+        let index_path = root.join(OCI_INDEX_FILE);
+        let data = std::fs::read(&index_path)
+            .map_err(|err| Error::FileReadError(index_path.clone(), err))?;
+        let index = serde_json::from_slice(&data)
+            .map_err(|err| Error::String(format!("Invalid {index_path:?}: {err}")))?;
+        Ok(Self {
+            root: root.to_owned(),
+            index,
+        })
+    }
+
+    fn blob_path(&self, digest: &str) -> Result<PathBuf> {
+        let Some(hex) = digest.strip_prefix("sha256:") else {
+            return Err(Error::String(format!("Unsupported OCI digest: {digest}")));
+        };
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::String(format!("Invalid OCI digest: {digest}")));
+        }
+        Ok(self.root.join("blobs").join("sha256").join(hex))
+    }
+
+    fn read_blob(&self, digest: &str) -> Result<Vec<u8>> {
+        let path = self.blob_path(digest)?;
+        let data = std::fs::read(&path).map_err(|err| Error::FileReadError(path, err))?;
+        let actual = oci_digest(&data.as_slice().digest().map_err(spfs::Error::from)?);
+        if actual != digest {
+            return Err(Error::String(format!(
+                "OCI blob does not match its digest: expected {digest}, got {actual}"
+            )));
+        }
+        Ok(data)
+    }
+
+    fn read_json<T: serde::de::DeserializeOwned>(&self, digest: &str) -> Result<T> {
+        let data = self.read_blob(digest)?;
+        serde_json::from_slice(&data)
+            .map_err(|err| Error::String(format!("Invalid OCI blob {digest}: {err}")))
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use futures::TryStreamExt;
+use rstest::rstest;
+use spfs::graph::DigestSearchCriteria;
+use spfs::prelude::*;
+use spfs::storage::RepositoryHandle;
+use spfs::storage::tar::TarRepository;
+use spfs::tracking::TagSpec;
+
+use super::{convert_archive_to_oci, convert_oci_to_archive, is_oci_layout};
+
+async fn open_archive(path: &std::path::Path) -> RepositoryHandle {
+    TarRepository::open(path).await.unwrap().into()
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_archive_oci_round_trip() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let data_dir = tmpdir.path().join("data");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    std::fs::write(data_dir.join("file.txt"), "hello, world").unwrap();
+
+    let original = tmpdir.path().join("original.spk");
+    let repo: RepositoryHandle = TarRepository::create(&original).await.unwrap().into();
+    let manifest = spfs::Committer::new(&repo)
+        .commit_dir(&data_dir)
+        .await
+        .unwrap();
+    let layer = repo
+        .create_layer(&manifest.to_graph_manifest())
+        .await
+        .unwrap();
+    let tag = TagSpec::parse("spk/pkg/my-pkg/1.0.0/3I42H3S6/run").unwrap();
+    repo.push_tag(&tag, &layer.digest().unwrap()).await.unwrap();
+    let RepositoryHandle::Tar(tar) = &repo else {
+        unreachable!()
+    };
+    tar.flush().unwrap();
+
+    let oci = tmpdir.path().join("converted.tar");
+    let conversion = convert_archive_to_oci(&original, &oci).await.unwrap();
+    assert_eq!(conversion.tags, 1);
+    assert!(conversion.not_converted.is_empty());
+    assert!(is_oci_layout(&oci).unwrap());
+    assert!(!is_oci_layout(&original).unwrap());
+
+    let restored = tmpdir.path().join("restored.spk");
+    let back = convert_oci_to_archive(&oci, &restored).await.unwrap();
+    assert_eq!(back.objects, conversion.objects);
+    assert_eq!(back.payloads, conversion.payloads);
+    assert_eq!(back.tags, conversion.tags);
+    assert!(back.not_converted.is_empty(), "{:?}", back.not_converted);
+
+    let restored = open_archive(&restored).await;
+    let restored_tag = restored.resolve_tag(&tag).await.unwrap();
+    assert_eq!(restored_tag.target, layer.digest().unwrap());
+    let mut expected: Vec<_> = repo
+        .find_digests(DigestSearchCriteria::All)
+        .try_collect()
+        .await
+        .unwrap();
+    let mut actual: Vec<_> = restored
+        .find_digests(DigestSearchCriteria::All)
+        .try_collect()
+        .await
+        .unwrap();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected, "all digests should be preserved");
+}
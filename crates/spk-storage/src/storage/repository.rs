@@ -3,8 +3,11 @@
 // https://github.com/spkenv/spk
 
 use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
 use std::sync::Arc;
 
+use async_stream::try_stream;
+use futures::Stream;
 use relative_path::RelativePathBuf;
 use spfs::find_path::ObjectPathEntry;
 use spk_schema::foundation::ident_component::Component;
@@ -12,7 +15,7 @@ use spk_schema::foundation::name::{PkgName, PkgNameBuf, RepositoryName};
 use spk_schema::foundation::version::Version;
 use spk_schema::ident_build::{Build, EmbeddedSource, InvalidBuildError};
 use spk_schema::option_map::get_host_options_filters;
-use spk_schema::{BuildIdent, Components, Deprecate, Package, PackageMut, VersionIdent};
+use spk_schema::{BuildIdent, Components, Deprecate, Package, PackageMut, Rename, VersionIdent};
 
 use self::internal::RepositoryExt;
 use super::{PackageEvent, announce_package_event};
@@ -336,6 +339,26 @@ pub trait Repository: Storage + Sync {
             .map(|vec| vec.into_iter().map(|(ident, _)| ident).collect())
     }
 
+    /// Return the builds for the given package name and version as they
+    /// are found, instead of all at once like [`Self::list_package_builds`].
+    ///
+    /// Embedded builds are only looked up once all the other builds
+    /// have been yielded, so for spfs repositories the tags holding
+    /// them are only listed if the stream is read that far.
+    fn stream_package_builds<'a>(
+        &'a self,
+        pkg: &'a VersionIdent,
+    ) -> Pin<Box<dyn Stream<Item = Result<BuildIdent>> + Send + 'a>> {
+        Box::pin(try_stream! {
+            for build in self.get_concrete_package_builds(pkg).await? {
+                yield build;
+            }
+            for build in self.get_embedded_package_builds(pkg).await? {
+                yield build;
+            }
+        })
+    }
+
     /// Return the set of builds for the given package name and version, paired
     /// with the tag spec from where the build was found, if available.
     async fn list_package_builds_with_tag_specs(
@@ -437,6 +460,21 @@ pub trait Repository: Storage + Sync {
             )));
         }
 
+        // Record when the package was first published, which is used
+        // to order otherwise equivalent builds. Packages being copied
+        // from another repository keep their original publish time.
+        let mut package = package.clone();
+        if package.metadata().published().is_none() {
+            match package.metadata_mut() {
+                Ok(meta) => meta.set_published(chrono::Utc::now()),
+                Err(err) => tracing::debug!(
+                    "Not recording the publish time of {}: {err}",
+                    package.ident()
+                ),
+            }
+        }
+        let package = &package;
+
         self.publish_package_to_storage(package, components).await?;
 
         // After successfully publishing a package, also publish stubs for any
@@ -601,6 +639,105 @@ pub trait Repository: Storage + Sync {
         announce_package_event(PackageEvent::Removed, self.address(), self.name(), pkg).await
     }
 
+    /// Move every version and build of a package to a new name.
+    ///
+    /// Recipes and builds are published again under the new name
+    /// with the same component digests, so no payloads are copied,
+    /// and are only removed from under the old name once all of them
+    /// have been published. Embedded stubs for the package are left
+    /// alone, they belong to the packages that embed it.
+    ///
+    /// # Errors:
+    /// - PackageExists: if a package with the new name already exists
+    /// - PackageNotFound: if there is no package with the old name
+    /// - PackageRenameIncomplete: if everything was published under the
+    ///   new name but could not be removed from under the old name, along
+    ///   with the old builds that were removed before the failure
+    async fn rename_package(&self, from: &PkgName, to: &PkgName) -> Result<()>
+    where
+        Self::Recipe: Rename,
+        <Self::Recipe as spk_schema::Recipe>::Output: Rename,
+        Self::Package: PackageMut,
+    {
+        if !self.list_package_versions(to).await?.is_empty() {
+            return Err(Error::PackageExists(to.to_owned()));
+        }
+        let versions = self.list_package_versions(from).await?;
+        if versions.is_empty() {
+            return Err(Error::PackageNotFound(from.to_owned().into()));
+        }
+
+        let mut recipes = Vec::new();
+        let mut builds = Vec::new();
+        let published: Result<()> = async {
+            for version in versions.iter() {
+                let ident = VersionIdent::new(from.to_owned(), (**version).clone());
+                match self.read_recipe(&ident).await {
+                    Ok(recipe) => {
+                        let mut recipe = (*recipe).clone();
+                        recipe.set_name(to.to_owned())?;
+                        // recorded before publishing so that a partial
+                        // publish is also cleaned up below
+                        recipes.push(ident.clone());
+                        self.publish_recipe(&recipe).await?;
+                    }
+                    Err(Error::PackageNotFound(_)) => {}
+                    Err(err) => return Err(err),
+                }
+                for build in self.list_package_builds(&ident).await? {
+                    if build.is_embedded() {
+                        continue;
+                    }
+                    let components = self.read_components(&build).await?;
+                    let mut spec = (*self.read_package(&build).await?).clone();
+                    spec.set_name(to.to_owned())?;
+                    builds.push(build);
+                    self.publish_package(&spec, &components).await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(err) = published {
+            // Nothing has been removed yet, so undo what was published
+            // to leave the package where it was. The last build or recipe
+            // may not have been published at all.
+            for build in builds.iter() {
+                let build = build.with_name(to.to_owned());
+                match self.remove_package(&build).await {
+                    Ok(()) | Err(Error::PackageNotFound(_)) => {}
+                    Err(err) => {
+                        tracing::warn!(%build, "failed to clean up renamed build: {err}");
+                    }
+                }
+            }
+            for recipe in recipes.iter() {
+                let recipe = recipe.with_name(to.to_owned());
+                match self.remove_recipe(&recipe).await {
+                    Ok(()) | Err(Error::PackageNotFound(_)) => {}
+                    Err(err) => {
+                        tracing::warn!(%recipe, "failed to clean up renamed recipe: {err}");
+                    }
+                }
+            }
+            return Err(err);
+        }
+
+        let mut removed = Vec::with_capacity(builds.len());
+        for build in builds.into_iter() {
+            if let Err(err) = self.remove_package(&build).await {
+                return Err(Error::PackageRenameIncomplete(removed, Box::new(err)));
+            }
+            removed.push(build);
+        }
+        for recipe in recipes.iter() {
+            if let Err(err) = self.remove_recipe(recipe).await {
+                return Err(Error::PackageRenameIncomplete(removed, Box::new(err)));
+            }
+        }
+        Ok(())
+    }
+
     /// Identify the payloads for this identified package's components.
     async fn read_components(
         &self,
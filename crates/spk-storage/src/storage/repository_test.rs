@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/spkenv/spk
 
+use std::collections::HashSet;
 use std::str::FromStr;
 
+use futures::TryStreamExt;
 use rstest::rstest;
 use spk_schema::foundation::ident_component::Component;
 use spk_schema::foundation::spec_ops::Named;
@@ -137,6 +139,40 @@ async fn test_repo_publish_recipe(#[case] repo: RepoKind) {
         .expect("force publish should ignore existing version");
 }
 
+#[rstest]
+#[case::mem(RepoKind::Mem)]
+#[case::spfs(RepoKind::Spfs)]
+#[tokio::test]
+async fn test_repo_publish_package_records_published_time(#[case] repo: RepoKind) {
+    let repo = make_repo(repo).await;
+    let spec = spec!({"pkg": "my-pkg/1.0.0/3I42H3S6"});
+    assert!(spec.metadata().published().is_none());
+    let components = vec![(Component::Run, empty_layer_digest())]
+        .into_iter()
+        .collect();
+    repo.publish_package(&spec, &components).await.unwrap();
+    let published = repo
+        .read_package(spec.ident())
+        .await
+        .unwrap()
+        .metadata()
+        .published()
+        .expect("publishing should record the publish time");
+
+    // publishing the package again, eg: when copying it into another
+    // repository, keeps the time that it was first published
+    let spec = repo.read_package(spec.ident()).await.unwrap();
+    repo.publish_package(&spec, &components).await.unwrap();
+    assert_eq!(
+        repo.read_package(spec.ident())
+            .await
+            .unwrap()
+            .metadata()
+            .published(),
+        Some(published)
+    );
+}
+
 #[rstest]
 #[case::mem(RepoKind::Mem)]
 #[case::spfs(RepoKind::Spfs)]
@@ -474,3 +510,130 @@ async fn test_repo_remove_package_removes_embed_stubs(#[case] repo: RepoKind) {
             .any(|pkg| pkg == "my-embedded-pkg")
     );
 }
+
+#[rstest]
+#[case::mem(RepoKind::Mem)]
+#[case::spfs(RepoKind::Spfs)]
+#[tokio::test]
+async fn test_repo_rename_package(#[case] repo: RepoKind) {
+    let repo = make_repo(repo).await;
+    let components = vec![(Component::Run, empty_layer_digest())]
+        .into_iter()
+        .collect();
+    for version in ["1.0.0", "2.0.0"] {
+        let recipe = recipe!({ "pkg": format!("my-pkg/{version}") });
+        repo.publish_recipe(&recipe).await.unwrap();
+        let spec = spec!({ "pkg": format!("my-pkg/{version}/3I42H3S6") });
+        repo.publish_package(&spec, &components).await.unwrap();
+    }
+
+    repo.rename_package(pkg_name!("my-pkg"), pkg_name!("new-pkg"))
+        .await
+        .unwrap();
+
+    let mut versions = (*repo
+        .list_package_versions(pkg_name!("new-pkg"))
+        .await
+        .unwrap())
+    .clone();
+    versions.sort();
+    assert_eq!(
+        versions.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+        vec!["1.0.0", "2.0.0"]
+    );
+    for version in ["1.0.0", "2.0.0"] {
+        let ident = parse_version_ident(&format!("new-pkg/{version}")).unwrap();
+        let recipe = repo.read_recipe(&ident).await.unwrap();
+        assert_eq!(recipe.name(), pkg_name!("new-pkg"));
+        let build = parse_build_ident(&format!("new-pkg/{version}/3I42H3S6")).unwrap();
+        let spec = repo.read_package(&build).await.unwrap();
+        assert_eq!(spec.ident(), &build);
+        assert_eq!(
+            repo.read_components(&build).await.unwrap(),
+            components,
+            "renamed builds should keep their component digests"
+        );
+    }
+    assert!(
+        repo.list_package_versions(pkg_name!("my-pkg"))
+            .await
+            .unwrap()
+            .is_empty(),
+        "nothing should be left under the old name"
+    );
+    assert!(
+        repo.read_package(&parse_build_ident("my-pkg/1.0.0/3I42H3S6").unwrap())
+            .await
+            .is_err()
+    );
+}
+
+#[rstest]
+#[case::mem(RepoKind::Mem)]
+#[case::spfs(RepoKind::Spfs)]
+#[tokio::test]
+async fn test_repo_rename_package_to_existing(#[case] repo: RepoKind) {
+    let repo = make_repo(repo).await;
+    let components = vec![(Component::Run, empty_layer_digest())]
+        .into_iter()
+        .collect();
+    for name in ["my-pkg", "new-pkg"] {
+        let recipe = recipe!({ "pkg": format!("{name}/1.0.0") });
+        repo.publish_recipe(&recipe).await.unwrap();
+        let spec = spec!({ "pkg": format!("{name}/1.0.0/3I42H3S6") });
+        repo.publish_package(&spec, &components).await.unwrap();
+    }
+
+    match repo
+        .rename_package(pkg_name!("my-pkg"), pkg_name!("new-pkg"))
+        .await
+    {
+        Err(Error::PackageExists(name)) => assert_eq!(&*name, pkg_name!("new-pkg")),
+        res => panic!("expected PackageExists error, got {res:?}"),
+    }
+    repo.read_package(&parse_build_ident("my-pkg/1.0.0/3I42H3S6").unwrap())
+        .await
+        .expect("the package should be left where it was");
+}
+
+#[rstest]
+#[case::mem(RepoKind::Mem)]
+#[case::spfs(RepoKind::Spfs)]
+#[case::indexed(RepoKind::IndexedMem)]
+#[tokio::test]
+async fn test_repo_stream_package_builds(#[case] repo: RepoKind) {
+    let repo = make_repo(repo).await;
+    // this also publishes an embedded stub for my-embedded-pkg
+    let (recipe, _) = create_repo_for_embed_stubs_test(&repo).await;
+    for build in ["src", "7CI5R7Y4", "GQHNU5SK"] {
+        let spec = spec!({ "pkg": format!("my-pkg/1.0.0/{build}") });
+        repo.publish_package(
+            &spec,
+            &vec![(Component::Run, empty_layer_digest())]
+                .into_iter()
+                .collect(),
+        )
+        .await
+        .unwrap();
+    }
+
+    for pkg in [recipe.ident(), &version_ident!("my-embedded-pkg/1.0.0")] {
+        let expected = repo
+            .list_package_builds(pkg)
+            .await
+            .unwrap()
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let streamed = repo
+            .stream_package_builds(pkg)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            streamed.len(),
+            expected.len(),
+            "no build should be streamed twice"
+        );
+        assert_eq!(streamed.into_iter().collect::<HashSet<_>>(), expected);
+    }
+}
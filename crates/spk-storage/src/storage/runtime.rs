@@ -23,6 +23,10 @@ use super::Repository;
 use super::repository::{PublishPolicy, Storage};
 use crate::{Error, InvalidPackageSpec, Result};
 
+#[cfg(test)]
+#[path = "./runtime_test.rs"]
+mod runtime_test;
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct RuntimeRepository {
     address: url::Url,
@@ -370,6 +374,9 @@ impl Storage for RuntimeRepository {
         reader
             .read_to_string(&mut yaml)
             .map_err(|err| Error::FileReadError(path.to_owned(), err))?;
+        if yaml.trim().is_empty() {
+            return Err(Error::EmptyPackageSpec(path));
+        }
         <Self::Recipe as spk_schema::Recipe>::Output::from_yaml(yaml)
             .map(Arc::new)
             .map_err(|err| {
@@ -498,9 +505,7 @@ async fn get_all_filenames<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<Str
 
 async fn find_layer_by_filename<S: AsRef<str>>(path: S) -> Result<spfs::encoding::Digest> {
     let runtime = spfs::active_runtime().await?;
-    let repo = spfs::get_runtime_backing_repo(&runtime).await?;
-
-    let layers = spfs::resolve_stack_to_layers(&runtime.status.stack, Some(&repo)).await?;
+    let (repo, _, layers) = spfs::resolve_runtime_layers(&runtime).await?;
     for layer in layers.iter().rev() {
         if let Some(manifest_digest) = layer.manifest() {
             let manifest = repo
@@ -530,14 +535,13 @@ async fn find_layers_by_filenames<S: AsRef<str>>(
     }
 
     let runtime = spfs::active_runtime().await?;
-    let repo = spfs::get_runtime_backing_repo(&runtime).await?;
+    let (repo, _, layers) = spfs::resolve_runtime_layers(&runtime).await?;
 
     let mut paths = paths.iter().map(Some).enumerate().collect::<Vec<_>>();
 
     let mut results = Vec::new();
     results.resize_with(paths.len(), Default::default);
 
-    let layers = spfs::resolve_stack_to_layers(&runtime.status.stack, Some(&repo)).await?;
     for layer in layers.iter().rev() {
         let manifest_digest = match layer.manifest() {
             None => continue,
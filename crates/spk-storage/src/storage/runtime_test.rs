@@ -0,0 +1,46 @@
+// Copyright (c) Contributors to the SPK project.
+// SPDX-License-Identifier: Apache-2.0
+// https://github.com/spkenv/spk
+
+use rstest::rstest;
+use spk_schema::foundation::build_ident;
+use spk_schema::foundation::fixtures::*;
+
+use super::RuntimeRepository;
+use crate::Error;
+use crate::storage::Repository;
+
+#[rstest]
+#[tokio::test]
+async fn test_read_empty_package_spec(tmpdir: tempfile::TempDir) {
+    let repo = RuntimeRepository::new(tmpdir.path().to_path_buf());
+    let pkg = build_ident!("my-pkg/1.0.0/3I42H3S6");
+    let spec_dir = tmpdir.path().join(pkg.to_string());
+    std::fs::create_dir_all(&spec_dir).unwrap();
+    std::fs::write(spec_dir.join("spec.yaml"), "").unwrap();
+
+    let err = repo
+        .read_package(&pkg)
+        .await
+        .expect_err("an empty spec should not be readable");
+    assert!(
+        matches!(err, Error::EmptyPackageSpec(ref path) if *path == spec_dir.join("spec.yaml")),
+        "expected an empty package spec error, got: {err:?}"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_read_missing_package_spec(tmpdir: tempfile::TempDir) {
+    let repo = RuntimeRepository::new(tmpdir.path().to_path_buf());
+    let pkg = build_ident!("my-pkg/1.0.0/3I42H3S6");
+
+    let err = repo
+        .read_package(&pkg)
+        .await
+        .expect_err("a missing spec should not be readable");
+    assert!(
+        matches!(err, Error::PackageNotFound(_)),
+        "expected a package not found error, got: {err:?}"
+    );
+}
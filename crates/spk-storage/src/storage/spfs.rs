@@ -5,12 +5,14 @@
 use std::collections::{HashMap, HashSet, hash_map};
 use std::convert::{TryFrom, TryInto};
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
+use async_stream::try_stream;
 use dashmap::DashMap;
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use relative_path::RelativePathBuf;
@@ -759,6 +761,47 @@ impl crate::Repository for SpfsRepository {
         r
     }
 
+    fn stream_package_builds<'a>(
+        &'a self,
+        pkg: &'a VersionIdent,
+    ) -> Pin<Box<dyn Stream<Item = Result<BuildIdent>> + Send + 'a>> {
+        Box::pin(try_stream! {
+            // Like get_concrete_package_builds, this includes builds
+            // from both the `spk/spec/` and `spk/pkg/` tag trees, but
+            // each build is yielded as soon as its tag is read.
+            let mut seen = HashSet::new();
+            for base in [Self::build_spec_tag(&pkg), Self::build_package_tag(&pkg)] {
+                let mut entries = self.stream_tags(&base);
+                while let Some(entry) = entries.next().await {
+                    let name = match entry {
+                        Ok(EntryType::Tag(name))
+                            if !name.starts_with(EmbeddedSourcePackage::EMBEDDED_BY_PREFIX) =>
+                        {
+                            name
+                        }
+                        Ok(EntryType::Folder(name)) => name,
+                        Ok(EntryType::Tag(_)) | Ok(EntryType::Namespace { .. }) | Err(_) => {
+                            continue;
+                        }
+                    };
+                    let build = match parse_build(&name) {
+                        Ok(build) => pkg.to_build_ident(build),
+                        Err(_) => {
+                            tracing::warn!("Invalid build found in spfs tags: {}", name);
+                            continue;
+                        }
+                    };
+                    if seen.insert(build.clone()) {
+                        yield build;
+                    }
+                }
+            }
+            for build in self.get_embedded_package_builds(pkg).await? {
+                yield build;
+            }
+        })
+    }
+
     async fn list_build_components(&self, pkg: &BuildIdent) -> Result<Vec<Component>> {
         if self.cached_result_permitted()
             && let Some(v) = self.caches.list_build_components.get(pkg)
@@ -1004,6 +1047,28 @@ impl SpfsRepository {
         self.caches.list_build_components.clear();
     }
 
+    /// List the tags under `path` as they are read, or from the cached
+    /// listing if there is one.
+    ///
+    /// Unlike [`Self::ls_tags`], this does not populate the cache, since
+    /// the stream may not be read to the end.
+    fn stream_tags(
+        &self,
+        path: &relative_path::RelativePath,
+    ) -> Pin<Box<dyn Stream<Item = Result<EntryType>> + Send>> {
+        if self.cached_result_permitted()
+            && let Some(v) = self.caches.ls_tags.get(path)
+        {
+            let entries = v.value().clone();
+            return Box::pin(futures::stream::iter(entries.into_iter().map(Ok)));
+        }
+        Box::pin(
+            self.inner
+                .ls_tags(path)
+                .map(|el| el.map_err(|err| err.into())),
+        )
+    }
+
     async fn ls_tags(&self, path: &relative_path::RelativePath) -> Vec<Result<EntryType>> {
         if self.cached_result_permitted()
             && let Some(v) = self.caches.ls_tags.get(path)
@@ -1212,6 +1277,18 @@ pub async fn local_repository() -> Result<SpfsRepository> {
     })
 }
 
+/// Return the configured address of the named repository.
+///
+/// The "local" repository is the local spfs storage, and all other
+/// names must be configured as spfs remotes.
+pub fn repository_address(name: &RepositoryName) -> Result<url::Url> {
+    let config = spfs::get_config()?;
+    if name.is_local() {
+        return Ok(config.storage.address());
+    }
+    Ok(config.get_remote_address(name.as_str())?)
+}
+
 /// Return the remote repository of the given name.
 ///
 /// If not name is specified, return the default spfs repository.
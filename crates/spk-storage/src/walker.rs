@@ -259,6 +259,8 @@ pub struct RepoWalker<'a> {
     report_src: bool,
     report_deprecated: bool,
     report_embedded: bool,
+    /// When set, only builds with this deprecation status are walked
+    deprecation_filter: Option<bool>,
     /// The objects inside a build
     report_on_components: bool,
     report_on_files: bool,
@@ -283,6 +285,7 @@ impl std::fmt::Debug for RepoWalker<'_> {
             .field("report on builds", &self.report_on_builds)
             .field("include /src builds", &self.report_src)
             .field("include deprecated builds", &self.report_deprecated)
+            .field("deprecation filter", &self.deprecation_filter)
             .field("include embedded builds", &self.report_embedded)
             .field("report on components", &self.report_on_components)
             .field("report on files", &self.report_on_files)
@@ -519,9 +522,17 @@ impl RepoWalker<'_> {
         };
 
         // Check the build spec against the spec level filters, if configured
-        if !self.report_deprecated && spec.is_deprecated() {
-            // Filter out deprecated builds
-            return Ok(None);
+        match self.deprecation_filter {
+            Some(deprecated) if spec.is_deprecated() != deprecated => {
+                // Filter out builds that don't have the wanted status
+                return Ok(None);
+            }
+            Some(_) => {}
+            None if !self.report_deprecated && spec.is_deprecated() => {
+                // Filter out deprecated builds
+                return Ok(None);
+            }
+            None => {}
         }
 
         let b = WalkedBuild {
@@ -884,6 +895,7 @@ pub struct RepoWalkerBuilder<'a> {
     report_src: bool,
     report_deprecated: bool,
     report_embedded: bool,
+    deprecation_filter: Option<bool>,
     /// Below sub-build object reporting controls
     report_on_components: bool,
     report_on_files: bool,
@@ -931,6 +943,8 @@ impl<'a> RepoWalkerBuilder<'a> {
             report_deprecated: false,
             // Show embedded builds by default
             report_embedded: true,
+            // Leave deprecated builds to report_deprecated by default
+            deprecation_filter: None,
             // Don't consider, or report on, things below builds by default
             report_on_components: false,
             report_on_files: false,
@@ -1210,6 +1224,16 @@ impl<'a> RepoWalkerBuilder<'a> {
         self
     }
 
+    /// Have the walk include only deprecated builds, when given
+    /// `Some(true)`, or only builds that are not deprecated, when
+    /// given `Some(false)`. When set, this takes the place of
+    /// [Self::with_report_deprecated_builds]. It is not set by
+    /// default.
+    pub fn with_deprecation_filter(&mut self, deprecated: Option<bool>) -> &mut Self {
+        self.deprecation_filter = deprecated;
+        self
+    }
+
     /// Have the walk include embedded builds. This is a global
     /// control that is applied in addition to any build filter
     /// function is configured. It is enabled by default and embedded
@@ -1315,6 +1339,7 @@ impl<'a> RepoWalkerBuilder<'a> {
             report_src: self.report_src,
             report_deprecated: self.report_deprecated,
             report_embedded: self.report_embedded,
+            deprecation_filter: self.deprecation_filter,
             report_on_components: self.report_on_components,
             report_on_files: self.report_on_files,
             emit_end_of_markers: self.end_of_markers,
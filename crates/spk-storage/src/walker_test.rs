@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use futures::TryStreamExt;
 use itertools::zip_eq;
+use rstest::rstest;
 use spfs::RemoteAddress;
 use spfs::config::Remote;
 use spfstest::spfstest;
@@ -12,7 +13,7 @@ use spk_schema::foundation::ident_component::Component;
 use spk_schema::ident::parse_version_ident;
 use spk_schema::ident_build::Build;
 use spk_schema::name::PkgNameBuf;
-use spk_schema::{DeprecateMut, Spec, recipe, spec};
+use spk_schema::{Deprecate, DeprecateMut, Spec, recipe, spec};
 
 use super::RepoWalkerBuilder;
 use crate::fixtures::{empty_layer_digest, spfs_runtime, spfsrepo};
@@ -64,6 +65,7 @@ async fn test_walker_builder_with_calls() {
         .with_report_on_builds(true)
         .with_report_src_builds(true)
         .with_report_deprecated_builds(true)
+        .with_deprecation_filter(None)
         .with_report_embedded_builds(true)
         .with_build_options_matching(None)
         .with_report_on_components(true)
@@ -348,3 +350,81 @@ async fn test_walker_builder_walker_walk_with_calc_deprecated_versions() {
         );
     }
 }
+
+#[spfstest]
+#[rstest]
+#[case::unfiltered(None, 3)]
+#[case::only_deprecated(Some(true), 2)]
+#[case::only_active(Some(false), 3)]
+#[tokio::test]
+async fn test_walker_with_deprecation_filter(
+    #[case] deprecation_filter: Option<bool>,
+    #[case] expected_builds: usize,
+) {
+    // Set up a test repo in the runtime
+    let mut rt = spfs_runtime().await;
+    let remote_repo = spfsrepo().await;
+    rt.add_remote_repo(
+        "origin",
+        Remote::Address(RemoteAddress {
+            address: remote_repo.address().clone(),
+        }),
+    )
+    .unwrap();
+
+    // Three active builds and two deprecated ones
+    for version in ["my-pkg/1.0.0", "my-pkg/2.0.0", "my-pkg/3.0.0"] {
+        let recipe = recipe!({ "pkg": version });
+        remote_repo.publish_recipe(&recipe).await.unwrap();
+    }
+    for (ident, deprecated) in [
+        ("my-pkg/1.0.0/BGSHW3CN", true),
+        ("my-pkg/1.0.0/DWHSVGP2", false),
+        ("my-pkg/2.0.0/BGSHW3CN", true),
+        ("my-pkg/3.0.0/BGSHW3CN", false),
+        ("my-pkg/3.0.0/DWHSVGP2", false),
+    ] {
+        let mut spec = spec!({ "pkg": ident });
+        if deprecated {
+            spec.deprecate().unwrap();
+        }
+        remote_repo
+            .publish_package(
+                &spec,
+                &vec![(Component::Run, empty_layer_digest())]
+                    .into_iter()
+                    .collect(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Setup the list of repos to walk
+    let repo_name: &str = "origin";
+    let repo = remote_repository(repo_name).await.unwrap();
+    let repos = vec![(repo_name.to_string(), RepositoryHandle::SPFS(repo))];
+
+    // The filter applies whether or not deprecated builds are
+    // reported, and is left to report_deprecated when not set
+    let mut builder = RepoWalkerBuilder::new(&repos);
+    let walker = builder
+        .with_report_deprecated_builds(false)
+        .with_deprecation_filter(deprecation_filter)
+        .build();
+    let mut traversal = walker.walk();
+
+    let mut builds = Vec::new();
+    while let Some(item) = traversal.try_next().await.unwrap() {
+        if let RepoWalkerItem::Build(build) = item {
+            builds.push(build);
+        }
+    }
+
+    assert_eq!(builds.len(), expected_builds);
+    if let Some(deprecated) = deprecation_filter {
+        assert!(
+            builds.iter().all(|b| b.spec.is_deprecated() == deprecated),
+            "only builds with the filtered deprecation status should be walked"
+        );
+    }
+}
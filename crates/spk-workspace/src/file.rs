@@ -51,18 +51,7 @@ impl WorkspaceFile {
     /// as necessary to find the workspace root. Returns the workspace root directory
     /// that was found, if any.
     pub fn discover<P: AsRef<Path>>(cwd: P) -> Result<(Self, PathBuf), LoadWorkspaceFileError> {
-        let cwd = if cwd.as_ref().is_absolute() {
-            cwd.as_ref().to_owned()
-        } else {
-            // prefer PWD if available, since it may be more representative of
-            // how the user arrived at the current dir and avoids dereferencing
-            // symlinks that could otherwise make error messages harder to understand
-            match std::env::var("PWD").ok() {
-                Some(pwd) => Path::new(&pwd).join(cwd),
-                None => std::env::current_dir().unwrap_or_default().join(cwd),
-            }
-        };
-
+        let cwd = absolute_cwd(cwd.as_ref());
         let mut candidate: std::path::PathBuf = cwd.clone();
         loop {
             if candidate.join(WorkspaceFile::FILE_NAME).is_file() {
@@ -74,6 +63,49 @@ impl WorkspaceFile {
         }
         Err(LoadWorkspaceFileError::WorkspaceNotFound(cwd))
     }
+
+    /// Find the nearest directory that contains package spec files,
+    /// starting at the given dir and looking at parent directories
+    /// as necessary.
+    ///
+    /// The search stops at the filesystem root, or at any directory
+    /// with a workspace file, in which case that workspace should be
+    /// used instead and nothing is returned.
+    pub fn discover_recipes_dir<P: AsRef<Path>>(cwd: P) -> Option<PathBuf> {
+        let mut candidate = absolute_cwd(cwd.as_ref());
+        loop {
+            if candidate.join(WorkspaceFile::FILE_NAME).is_file() {
+                return None;
+            }
+            let has_recipes = std::fs::read_dir(&candidate)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .any(|entry| {
+                    entry.file_name().to_string_lossy().ends_with(".spk.yaml")
+                        && entry.path().is_file()
+                });
+            if has_recipes {
+                return Some(candidate);
+            }
+            if !candidate.pop() {
+                return None;
+            }
+        }
+    }
+}
+
+fn absolute_cwd(cwd: &Path) -> PathBuf {
+    if cwd.is_absolute() {
+        return cwd.to_owned();
+    }
+    // prefer PWD if available, since it may be more representative of
+    // how the user arrived at the current dir and avoids dereferencing
+    // symlinks that could otherwise make error messages harder to understand
+    match std::env::var("PWD").ok() {
+        Some(pwd) => Path::new(&pwd).join(cwd),
+        None => std::env::current_dir().unwrap_or_default().join(cwd),
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
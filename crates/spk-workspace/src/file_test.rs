@@ -73,3 +73,38 @@ fn test_workspace_discovery(
 
     WorkspaceFile::discover(&cwd).expect("failed to load workspace");
 }
+
+#[rstest]
+#[case("", "", Some(""))]
+#[case("my-package", "my-package/src/lib", Some("my-package"))]
+#[case("my-package", "other-dir", None)]
+fn test_recipes_dir_discovery(
+    tmpdir: tempfile::TempDir,
+    #[case] recipe_dir: &str,
+    #[case] discovery_start: &str,
+    #[case] expected: Option<&str>,
+) {
+    let dir = tmpdir.path();
+    let recipes = dir.join(recipe_dir);
+    let cwd = dir.join(discovery_start);
+
+    std::fs::create_dir_all(&cwd).unwrap();
+    std::fs::create_dir_all(&recipes).unwrap();
+    std::fs::write(recipes.join("my-package.spk.yaml"), "pkg: my-package/1.0.0").unwrap();
+
+    let found = WorkspaceFile::discover_recipes_dir(&cwd);
+    assert_eq!(found, expected.map(|e| dir.join(e)));
+}
+
+#[rstest]
+fn test_recipes_dir_discovery_stops_at_workspace(tmpdir: tempfile::TempDir) {
+    let dir = tmpdir.path();
+    let workspace = dir.join("workspace");
+    let cwd = workspace.join("packages");
+
+    std::fs::create_dir_all(&cwd).unwrap();
+    std::fs::write(dir.join("my-package.spk.yaml"), "pkg: my-package/1.0.0").unwrap();
+    std::fs::write(workspace.join(WorkspaceFile::FILE_NAME), EMPTY_WORKSPACE).unwrap();
+
+    assert_eq!(WorkspaceFile::discover_recipes_dir(&cwd), None);
+}
@@ -14,8 +14,8 @@ use spk_cli_common::configure_sentry;
 use spk_cli_common::{CommandArgs, Error, Run, configure_logging};
 use spk_cli_group1::{cmd_bake, cmd_completion, cmd_deprecate, cmd_undeprecate};
 use spk_cli_group2::{cmd_ls, cmd_new, cmd_num_variants, cmd_publish, cmd_remove, cmd_stats};
-use spk_cli_group3::{cmd_export, cmd_import};
-use spk_cli_group4::{cmd_lint, cmd_search, cmd_version, cmd_view};
+use spk_cli_group3::{cmd_convert_archive, cmd_export, cmd_export_env, cmd_import, cmd_import_env};
+use spk_cli_group4::{cmd_build_diff, cmd_lint, cmd_search, cmd_version, cmd_view};
 use spk_cmd_build::cmd_build;
 use spk_cmd_convert::cmd_convert;
 use spk_cmd_debug::cmd_debug;
@@ -159,15 +159,19 @@ impl Opt {
 pub enum Command {
     Bake(cmd_bake::Bake),
     Build(cmd_build::Build),
+    BuildDiff(cmd_build_diff::BuildDiff),
     Completion(cmd_completion::Completion),
     Convert(cmd_convert::Convert),
+    ConvertArchive(cmd_convert_archive::ConvertArchive),
     Debug(cmd_debug::Debug),
     Deprecate(cmd_deprecate::DeprecateCmd),
     Du(cmd_du::Du),
     Env(cmd_env::Env),
     Explain(cmd_explain::Explain),
     Export(cmd_export::Export),
+    ExportEnv(cmd_export_env::ExportEnv),
     Import(cmd_import::Import),
+    ImportEnv(cmd_import_env::ImportEnv),
     Install(cmd_install::Install),
     Lint(cmd_lint::Lint),
     Ls(cmd_ls::Ls),
@@ -200,15 +204,19 @@ impl Run for Command {
         match self {
             Command::Bake(cmd) => cmd.run().await,
             Command::Build(cmd) => cmd.run().await.map(Into::into),
+            Command::BuildDiff(cmd) => cmd.run().await,
             Command::Completion(cmd) => cmd.run(Opt::command()),
             Command::Convert(cmd) => cmd.run().await,
+            Command::ConvertArchive(cmd) => cmd.run().await,
             Command::Debug(cmd) => cmd.run().await,
             Command::Deprecate(cmd) => cmd.run().await,
             Command::Du(cmd) => cmd.run().await,
             Command::Env(cmd) => cmd.run().await,
             Command::Explain(cmd) => cmd.run().await,
             Command::Export(cmd) => cmd.run().await,
+            Command::ExportEnv(cmd) => cmd.run().await,
             Command::Import(cmd) => cmd.run().await,
+            Command::ImportEnv(cmd) => cmd.run().await,
             Command::Install(cmd) => cmd.run().await,
             Command::Lint(cmd) => cmd.run().await,
             Command::Ls(cmd) => cmd.run().await,
@@ -236,7 +244,9 @@ impl CommandArgs for Command {
         match self {
             Command::Bake(cmd) => cmd.get_positional_args(),
             Command::Build(cmd) => cmd.get_positional_args(),
+            Command::BuildDiff(cmd) => cmd.get_positional_args(),
             Command::Convert(cmd) => cmd.get_positional_args(),
+            Command::ConvertArchive(cmd) => cmd.get_positional_args(),
             Command::Completion(cmd) => cmd.get_positional_args(),
             Command::Debug(cmd) => cmd.get_positional_args(),
             Command::Deprecate(cmd) => cmd.get_positional_args(),
@@ -244,7 +254,9 @@ impl CommandArgs for Command {
             Command::Env(cmd) => cmd.get_positional_args(),
             Command::Explain(cmd) => cmd.get_positional_args(),
             Command::Export(cmd) => cmd.get_positional_args(),
+            Command::ExportEnv(cmd) => cmd.get_positional_args(),
             Command::Import(cmd) => cmd.get_positional_args(),
+            Command::ImportEnv(cmd) => cmd.get_positional_args(),
             Command::Install(cmd) => cmd.get_positional_args(),
             Command::Lint(cmd) => cmd.get_positional_args(),
             Command::Ls(cmd) => cmd.get_positional_args(),